@@ -31,6 +31,7 @@ mod iox_authorizer;
 pub use iox_authorizer::{Error, IoxAuthorizer};
 mod instrumentation;
 pub use instrumentation::AuthorizerInstrumentation;
+pub mod mock;
 mod permission;
 pub use permission::{Action, Permission, Resource};
 
@@ -0,0 +1,128 @@
+//! Mock [`Authorizer`] for testing.
+
+use std::{collections::HashMap, sync::Mutex};
+
+use async_trait::async_trait;
+
+use super::{Authorizer, Error, Permission};
+
+/// A call to [`MockAuthorizer::permissions`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockAuthorizerCall {
+    /// The token of the request.
+    pub token: Option<Vec<u8>>,
+    /// The requested permissions.
+    pub perms: Vec<Permission>,
+}
+
+/// [`Authorizer`] that answers based on a fixed set of tokens and records all calls.
+///
+/// * Allowed tokens are granted all requested permissions.
+/// * Denied tokens result in [`Error::Forbidden`].
+/// * Unknown tokens result in [`Error::InvalidToken`].
+/// * A missing token results in [`Error::NoToken`].
+#[derive(Debug, Default)]
+pub struct MockAuthorizer {
+    tokens: HashMap<Vec<u8>, bool>,
+    calls: Mutex<Vec<MockAuthorizerCall>>,
+}
+
+impl MockAuthorizer {
+    /// Create a new authorizer that does not know any token.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Grant all permissions to `token`.
+    pub fn with_allowed_token(mut self, token: impl Into<Vec<u8>>) -> Self {
+        self.tokens.insert(token.into(), true);
+        self
+    }
+
+    /// Deny all permissions to `token`.
+    pub fn with_denied_token(mut self, token: impl Into<Vec<u8>>) -> Self {
+        self.tokens.insert(token.into(), false);
+        self
+    }
+
+    /// Calls made to this authorizer so far.
+    pub fn calls(&self) -> Vec<MockAuthorizerCall> {
+        self.calls.lock().expect("not poisoned").clone()
+    }
+}
+
+#[async_trait]
+impl Authorizer for MockAuthorizer {
+    async fn permissions(
+        &self,
+        token: Option<Vec<u8>>,
+        perms: &[Permission],
+    ) -> Result<Vec<Permission>, Error> {
+        self.calls
+            .lock()
+            .expect("not poisoned")
+            .push(MockAuthorizerCall {
+                token: token.clone(),
+                perms: perms.to_vec(),
+            });
+
+        match self.tokens.get(&token.ok_or(Error::NoToken)?) {
+            Some(true) => Ok(perms.to_vec()),
+            Some(false) => Err(Error::Forbidden),
+            None => Err(Error::InvalidToken),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+    use crate::{Action, Resource};
+
+    #[tokio::test]
+    async fn test_permissions() {
+        let authz = MockAuthorizer::new()
+            .with_allowed_token("GOOD")
+            .with_denied_token("BAD");
+        let perms = [Permission::ResourceAction(
+            Resource::Database("bananas".to_string()),
+            Action::Read,
+        )];
+
+        assert_eq!(
+            authz
+                .permissions(Some(b"GOOD".to_vec()), &perms)
+                .await
+                .unwrap(),
+            perms
+        );
+        assert_matches!(
+            authz.permissions(Some(b"BAD".to_vec()), &perms).await,
+            Err(Error::Forbidden)
+        );
+        assert_matches!(
+            authz.permissions(Some(b"UGLY".to_vec()), &perms).await,
+            Err(Error::InvalidToken)
+        );
+        assert_matches!(authz.permissions(None, &perms).await, Err(Error::NoToken));
+        authz.probe().await.unwrap();
+
+        let tokens = authz
+            .calls()
+            .into_iter()
+            .map(|call| call.token)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tokens,
+            [
+                Some(b"GOOD".to_vec()),
+                Some(b"BAD".to_vec()),
+                Some(b"UGLY".to_vec()),
+                None,
+                Some(b"".to_vec()),
+            ]
+        );
+    }
+}
@@ -1,6 +1,12 @@
 //! CLI config for compactor-related commands
 
-use std::num::NonZeroUsize;
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    fmt::Display,
+    num::NonZeroUsize,
+    path::PathBuf,
+    str::FromStr,
+};
 
 use super::compactor_scheduler::CompactorSchedulerConfig;
 
@@ -46,6 +52,16 @@ pub struct CompactorConfig {
     )]
     pub compaction_partition_scratchpad_concurrency: NonZeroUsize,
 
+    /// Number of compacted output files PER BRANCH that are uploaded
+    /// concurrently from the scratchpad to the object store.
+    #[clap(
+        long = "compaction-upload-concurrency",
+        env = "INFLUXDB_IOX_COMPACTION_UPLOAD_CONCURRENCY",
+        default_value = "10",
+        action
+    )]
+    pub compaction_upload_concurrency: NonZeroUsize,
+
     /// Number of threads to use for the compactor query execution,
     /// compaction and persistence.
     /// If not specified, defaults to one less than the number of cores on the system
@@ -149,6 +165,21 @@ pub struct CompactorConfig {
     )]
     pub partition_timeout_secs: u64,
 
+    /// Per-partition overrides of `--compaction-partition-timeout-secs`.
+    ///
+    /// Comma-separated list of `<partition_id>=<secs>` pairs, e.g. to grant
+    /// known-large partitions more time without raising the global limit.
+    /// Every partition may only be listed once and timeouts must be
+    /// greater than zero.
+    #[clap(
+        long = "compaction-partition-timeout-overrides",
+        env = "INFLUXDB_IOX_COMPACTION_PARTITION_TIMEOUT_OVERRIDES",
+        default_value = "",
+        value_parser = parse_partition_timeout_overrides,
+        action
+    )]
+    pub partition_timeout_overrides: HashMap<i64, u64>,
+
     /// Shadow mode.
     ///
     /// This will NOT write / commit any output to the object store or catalog.
@@ -161,6 +192,19 @@ pub struct CompactorConfig {
     )]
     pub shadow_mode: bool,
 
+    /// Abort a compaction round if another process modified the partition's
+    /// files while the compactor was working on them. The partition is
+    /// retried in a later round.
+    ///
+    /// By default such concurrent modifications are only logged and the
+    /// compactor commits its results anyway.
+    #[clap(
+        long = "compaction-fail-on-concurrent-modification",
+        env = "INFLUXDB_IOX_COMPACTION_FAIL_ON_CONCURRENT_MODIFICATION",
+        action
+    )]
+    pub fail_on_concurrent_modification: bool,
+
     /// Enable scratchpad.
     ///
     /// This allows disabling the scratchpad in production.
@@ -182,13 +226,17 @@ pub struct CompactorConfig {
     /// compactor. Increasing this setting also increases the peak
     /// memory used for each compaction plan, and thus if it is set
     /// too high, the compactor plans may exceed available memory.
+    ///
+    /// This also caps the number of files in each branch of a
+    /// compaction round, so lowering it reduces memory usage on
+    /// memory-constrained nodes. Must be positive.
     #[clap(
         long = "compaction-max-num-files-per-plan",
         env = "INFLUXDB_IOX_COMPACTION_MAX_NUM_FILES_PER_PLAN",
         default_value = "20",
         action
     )]
-    pub max_num_files_per_plan: usize,
+    pub max_num_files_per_plan: NonZeroUsize,
 
     /// Minimum number of L1 files to compact to L2.
     ///
@@ -242,4 +290,378 @@ pub struct CompactorConfig {
         action
     )]
     pub max_partition_fetch_queries_per_second: Option<usize>,
+
+    /// Path of a local file that the outcome of every compaction job is
+    /// appended to, as one JSON object per line.
+    ///
+    /// The file is rotated once it grows beyond 100MB. If not set, no
+    /// audit log is written.
+    #[clap(
+        long = "compaction-audit-log-path",
+        env = "INFLUXDB_IOX_COMPACTION_AUDIT_LOG_PATH",
+        action
+    )]
+    pub audit_log_path: Option<PathBuf>,
+
+    /// Number of consecutive failed compaction jobs after which a
+    /// partition is marked as skipped, regardless of the error kind.
+    ///
+    /// Skipped partitions are not compacted again until they are
+    /// manually cleared. If not set, partitions are only skipped based
+    /// on the kind of error.
+    #[clap(
+        long = "compaction-max-consecutive-failures",
+        env = "INFLUXDB_IOX_COMPACTION_MAX_CONSECUTIVE_FAILURES",
+        action
+    )]
+    pub max_consecutive_failures: Option<NonZeroUsize>,
+
+    /// Maximum number of partitions that are pulled from the
+    /// partition source at once.
+    ///
+    /// A batch is fully compacted before the next one is pulled. If
+    /// not set, partitions are pulled continuously.
+    #[clap(
+        long = "compaction-fetch-batch-size",
+        env = "INFLUXDB_IOX_COMPACTION_FETCH_BATCH_SIZE",
+        action
+    )]
+    pub fetch_batch_size: Option<NonZeroUsize>,
+
+    /// Maximum number of compaction rounds for a single partition in
+    /// one compaction job.
+    ///
+    /// If a partition still needs compaction after this many rounds,
+    /// the job fails with a "max rounds exceeded" error. If not set,
+    /// the number of rounds is unbounded.
+    #[clap(
+        long = "compaction-max-rounds",
+        env = "INFLUXDB_IOX_COMPACTION_MAX_ROUNDS",
+        action
+    )]
+    pub max_rounds: Option<NonZeroUsize>,
+
+    /// URL that is notified via a JSON POST request whenever a
+    /// compaction job fails with an error that marks the partition as
+    /// skipped.
+    ///
+    /// The payload contains the partition ID, the error kind, the error
+    /// message and a timestamp. Failed requests are retried a few
+    /// times. If not set, no webhook is notified.
+    #[clap(
+        long = "compaction-webhook-url",
+        env = "INFLUXDB_IOX_COMPACTION_WEBHOOK_URL",
+        action
+    )]
+    pub webhook_url: Option<String>,
+
+    /// Hard limit on the number of input files a single compaction
+    /// plan may open.
+    ///
+    /// Unlike `--compaction-max-num-files-per-plan`, which guides the
+    /// file selection, this is a safety check: partitions whose
+    /// classified compaction would exceed it are skipped with a
+    /// descriptive reason instead of being compacted. If not set, no
+    /// such check is performed.
+    #[clap(
+        long = "compaction-max-input-files-per-plan",
+        env = "INFLUXDB_IOX_COMPACTION_MAX_INPUT_FILES_PER_PLAN",
+        action
+    )]
+    pub max_input_files_per_plan: Option<NonZeroUsize>,
+
+    /// Compression codec of the parquet files written by the compactor.
+    ///
+    /// One of `none`, `snappy`, `zstd` or `zstd(<level>)` with a level
+    /// between 1 and 22. Higher ZSTD levels result in smaller files at
+    /// the cost of more CPU time spent on compaction.
+    #[clap(
+        long = "compaction-parquet-compression",
+        env = "INFLUXDB_IOX_COMPACTION_PARQUET_COMPRESSION",
+        default_value = "zstd",
+        action
+    )]
+    pub compaction_parquet_compression: ParquetCompression,
+
+    /// Compact the partitions with the most L0 files first.
+    ///
+    /// Ties are broken by the total size of the files of a partition.
+    /// By default partitions are compacted in random order. This
+    /// costs one additional aggregate catalog query per round and is
+    /// mostly useful to catch up on a backlog of un-compacted data.
+    #[clap(
+        long = "compaction-prioritize-by-l0-count",
+        env = "INFLUXDB_IOX_COMPACTION_PRIORITIZE_BY_L0_COUNT",
+        action
+    )]
+    pub prioritize_by_l0_count: bool,
+
+    /// Verify every compacted file after uploading it to the object
+    /// store.
+    ///
+    /// The size, the parquet footer and the parquet metadata of each
+    /// uploaded file are read back and checked against what was
+    /// written. On a mismatch the partition is skipped and nothing is
+    /// committed to the catalog. Costs three additional object store
+    /// requests per file.
+    #[clap(
+        long = "compaction-verify-uploads",
+        env = "INFLUXDB_IOX_COMPACTION_VERIFY_UPLOADS",
+        action
+    )]
+    pub verify_uploads: bool,
+
+    /// Reuse the partition files fetched at the start of a compaction
+    /// job instead of querying the catalog again before the first
+    /// commit.
+    ///
+    /// This reduces catalog load, but modifications of the partition
+    /// by other processes go unnoticed until the first commit of a
+    /// job. Ignored if `--compaction-fail-on-concurrent-modification`
+    /// is set.
+    #[clap(
+        long = "compaction-cache-partition-files",
+        env = "INFLUXDB_IOX_COMPACTION_CACHE_PARTITION_FILES",
+        action
+    )]
+    pub cache_partition_files: bool,
+
+    /// Number of compaction jobs that may fail with an object store
+    /// error before the compactor stops starting new jobs and backs
+    /// off for `--compaction-object-store-error-backoff-secs`.
+    ///
+    /// Failures are counted across all partitions. The count starts
+    /// over after every back off. If not set, the compactor never
+    /// backs off because of object store errors.
+    #[clap(
+        long = "compaction-object-store-error-budget",
+        env = "INFLUXDB_IOX_COMPACTION_OBJECT_STORE_ERROR_BUDGET",
+        action
+    )]
+    pub object_store_error_budget: Option<NonZeroUsize>,
+
+    /// Duration in seconds to back off once
+    /// `--compaction-object-store-error-budget` is exceeded.
+    ///
+    /// The back off is shared: partitions that are about to be
+    /// compacted wait until the same point in time.
+    #[clap(
+        long = "compaction-object-store-error-backoff-secs",
+        env = "INFLUXDB_IOX_COMPACTION_OBJECT_STORE_ERROR_BACKOFF_SECS",
+        default_value = "60",
+        action
+    )]
+    pub object_store_error_backoff_secs: u64,
+}
+
+/// Compression codec of parquet files written by the compactor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParquetCompression {
+    /// No compression.
+    Uncompressed,
+
+    /// Snappy compression.
+    Snappy,
+
+    /// ZSTD compression with the given level.
+    Zstd(i32),
+}
+
+impl ParquetCompression {
+    /// ZSTD level used if none is specified.
+    pub const DEFAULT_ZSTD_LEVEL: i32 = 1;
+
+    /// Range of valid ZSTD levels.
+    const ZSTD_LEVELS: std::ops::RangeInclusive<i32> = 1..=22;
+}
+
+impl FromStr for ParquetCompression {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let codec = s.trim().to_ascii_lowercase();
+        match codec.as_str() {
+            "none" | "uncompressed" => return Ok(Self::Uncompressed),
+            "snappy" => return Ok(Self::Snappy),
+            "zstd" => return Ok(Self::Zstd(Self::DEFAULT_ZSTD_LEVEL)),
+            _ => {}
+        }
+
+        let level = codec
+            .strip_prefix("zstd(")
+            .and_then(|rest| rest.strip_suffix(')'))
+            .ok_or_else(|| {
+                format!("invalid compression '{s}', expected none, snappy, zstd or zstd(<level>)")
+            })?;
+        let level = level
+            .trim()
+            .parse()
+            .map_err(|e| format!("invalid ZSTD level '{level}': {e}"))?;
+        if !Self::ZSTD_LEVELS.contains(&level) {
+            return Err(format!(
+                "invalid ZSTD level {level}, expected a level between {} and {}",
+                Self::ZSTD_LEVELS.start(),
+                Self::ZSTD_LEVELS.end(),
+            ));
+        }
+        Ok(Self::Zstd(level))
+    }
+}
+
+impl Display for ParquetCompression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Uncompressed => write!(f, "none"),
+            Self::Snappy => write!(f, "snappy"),
+            Self::Zstd(level) => write!(f, "zstd({level})"),
+        }
+    }
+}
+
+fn parse_partition_timeout_overrides(s: &str) -> Result<HashMap<i64, u64>, String> {
+    let mut overrides = HashMap::new();
+
+    for pair in s.split(',').filter(|pair| !pair.trim().is_empty()) {
+        let (partition_id, secs) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("expected <partition_id>=<secs> but got '{pair}'"))?;
+        let partition_id: i64 = partition_id
+            .trim()
+            .parse()
+            .map_err(|e| format!("invalid partition ID '{partition_id}': {e}"))?;
+        let secs: u64 = secs
+            .trim()
+            .parse()
+            .map_err(|e| format!("invalid timeout '{secs}': {e}"))?;
+        if secs == 0 {
+            return Err(format!(
+                "invalid timeout for partition {partition_id}: must be greater than zero"
+            ));
+        }
+
+        match overrides.entry(partition_id) {
+            Entry::Occupied(_) => {
+                return Err(format!("partition {partition_id} is listed more than once"));
+            }
+            Entry::Vacant(v) => {
+                v.insert(secs);
+            }
+        }
+    }
+
+    Ok(overrides)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use test_helpers::assert_contains;
+
+    #[test]
+    fn test_max_num_files_per_plan() {
+        let actual = CompactorConfig::try_parse_from(["my_binary"]).unwrap();
+        assert_eq!(actual.max_num_files_per_plan.get(), 20);
+
+        let actual = CompactorConfig::try_parse_from([
+            "my_binary",
+            "--compaction-max-num-files-per-plan",
+            "50",
+        ])
+        .unwrap();
+        assert_eq!(actual.max_num_files_per_plan.get(), 50);
+
+        let err = CompactorConfig::try_parse_from([
+            "my_binary",
+            "--compaction-max-num-files-per-plan",
+            "0",
+        ])
+        .unwrap_err();
+        assert_contains!(
+            err.to_string(),
+            "invalid value '0' for '--compaction-max-num-files-per-plan"
+        );
+    }
+
+    #[test]
+    fn test_partition_timeout_overrides() {
+        let actual = CompactorConfig::try_parse_from(["my_binary"]).unwrap();
+        assert!(actual.partition_timeout_overrides.is_empty());
+
+        let actual = CompactorConfig::try_parse_from([
+            "my_binary",
+            "--compaction-partition-timeout-overrides",
+            "1=60, 42 = 3600",
+        ])
+        .unwrap();
+        assert_eq!(
+            actual.partition_timeout_overrides,
+            HashMap::from([(1, 60), (42, 3600)])
+        );
+
+        for (s, expected) in [
+            ("1", "expected <partition_id>=<secs> but got '1'"),
+            ("1=60,2", "expected <partition_id>=<secs> but got '2'"),
+            ("x=60", "invalid partition ID 'x'"),
+            ("1=", "invalid timeout ''"),
+            ("1=-5", "invalid timeout '-5'"),
+            (
+                "1=0",
+                "invalid timeout for partition 1: must be greater than zero",
+            ),
+            ("1=60,2=60,1=120", "partition 1 is listed more than once"),
+        ] {
+            let err = CompactorConfig::try_parse_from([
+                "my_binary",
+                "--compaction-partition-timeout-overrides",
+                s,
+            ])
+            .unwrap_err();
+            assert_contains!(err.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_parquet_compression() {
+        let actual = CompactorConfig::try_parse_from(["my_binary"]).unwrap();
+        assert_eq!(
+            actual.compaction_parquet_compression,
+            ParquetCompression::Zstd(ParquetCompression::DEFAULT_ZSTD_LEVEL)
+        );
+
+        for (s, expected) in [
+            ("none", ParquetCompression::Uncompressed),
+            ("snappy", ParquetCompression::Snappy),
+            ("SNAPPY", ParquetCompression::Snappy),
+            ("zstd(3)", ParquetCompression::Zstd(3)),
+        ] {
+            let actual = CompactorConfig::try_parse_from([
+                "my_binary",
+                "--compaction-parquet-compression",
+                s,
+            ])
+            .unwrap();
+            assert_eq!(actual.compaction_parquet_compression, expected);
+            assert_eq!(
+                expected.to_string().parse::<ParquetCompression>().unwrap(),
+                expected
+            );
+        }
+
+        for (s, expected) in [
+            ("gzip", "invalid compression 'gzip'"),
+            ("zstd(", "invalid compression 'zstd('"),
+            ("zstd(x)", "invalid ZSTD level 'x'"),
+            ("zstd(0)", "invalid ZSTD level 0"),
+            ("zstd(23)", "invalid ZSTD level 23"),
+        ] {
+            let err = CompactorConfig::try_parse_from([
+                "my_binary",
+                "--compaction-parquet-compression",
+                s,
+            ])
+            .unwrap_err();
+            assert_contains!(err.to_string(), expected);
+        }
+    }
 }
@@ -75,4 +75,56 @@ pub struct IngesterConfig {
         action
     )]
     pub persist_hot_partition_cost: usize,
+
+    /// The number of milliseconds hot partitions are accumulated for before
+    /// being queued for persistence together.
+    ///
+    /// Partitions whose persist cost exceeds the limit within this window are
+    /// queued as a single batch. A value of 0 queues each partition
+    /// immediately.
+    #[clap(
+        long = "persist-hot-partition-batch-window-millis",
+        env = "INFLUXDB_IOX_PERSIST_HOT_PARTITION_BATCH_WINDOW_MILLIS",
+        default_value = "0",
+        action
+    )]
+    pub persist_hot_partition_batch_window_millis: u64,
+
+    /// The persist latency, in seconds, above which the hot partition persist
+    /// cost limit is lowered.
+    ///
+    /// While persist operations take longer than this to complete, the limit
+    /// is halved for each slow persist (down to
+    /// `--persist-hot-partition-min-cost`), and doubled again for each fast
+    /// one (up to `--persist-hot-partition-cost`). A value of 0 disables the
+    /// adaptation.
+    #[clap(
+        long = "persist-hot-partition-target-latency-seconds",
+        env = "INFLUXDB_IOX_PERSIST_HOT_PARTITION_TARGET_LATENCY_SECONDS",
+        default_value = "0",
+        action
+    )]
+    pub persist_hot_partition_target_latency_seconds: u64,
+
+    /// The lowest limit the hot partition persist cost limit is lowered to
+    /// while persist operations are slow.
+    #[clap(
+        long = "persist-hot-partition-min-cost",
+        env = "INFLUXDB_IOX_PERSIST_HOT_PARTITION_MIN_COST",
+        default_value = "2000000", // 2,000,000
+        action
+    )]
+    pub persist_hot_partition_min_cost: usize,
+
+    /// Sort the data of each partition returned by a query by the partition's
+    /// sort key.
+    ///
+    /// Pre-sorted data is cheaper for the querier to merge, at the cost of
+    /// sorting in the ingester. By default data is returned in buffer order.
+    #[clap(
+        long = "sort-query-output",
+        env = "INFLUXDB_IOX_SORT_QUERY_OUTPUT",
+        action
+    )]
+    pub sort_query_output: bool,
 }
@@ -8,7 +8,12 @@ use object_store::{throttle::ThrottleConfig, DynObjectStore};
 use observability_deps::tracing::{info, warn};
 use snafu::{ResultExt, Snafu};
 use std::sync::Arc;
-use std::{fs, num::NonZeroUsize, path::PathBuf, time::Duration};
+use std::{
+    fs,
+    num::NonZeroUsize,
+    path::{Path as FsPath, PathBuf},
+    time::Duration,
+};
 use uuid::Uuid;
 
 #[derive(Debug, Snafu)]
@@ -391,15 +396,7 @@ pub fn make_object_store(config: &ObjectStoreConfig) -> Result<Arc<DynObjectStor
         Some(ObjectStoreType::S3) => new_s3(config),
         Some(ObjectStoreType::Azure) => new_azure(config),
         Some(ObjectStoreType::File) => match config.database_directory.as_ref() {
-            Some(db_dir) => {
-                info!(?db_dir, object_store_type = "Directory", "Object Store");
-                fs::create_dir_all(db_dir)
-                    .context(CreatingDatabaseDirectorySnafu { path: db_dir })?;
-
-                let store = object_store::local::LocalFileSystem::new_with_prefix(db_dir)
-                    .context(CreateLocalFileSystemSnafu { path: db_dir })?;
-                Ok(Arc::new(store))
-            }
+            Some(db_dir) => make_local_object_store(db_dir),
             None => MissingObjectStoreConfigSnafu {
                 object_store: ObjectStoreType::File,
                 missing: "data-dir",
@@ -409,6 +406,16 @@ pub fn make_object_store(config: &ObjectStoreConfig) -> Result<Arc<DynObjectStor
     }
 }
 
+/// Create an object store in the local directory `db_dir`, creating the directory if it does not exist yet.
+pub fn make_local_object_store(db_dir: &FsPath) -> Result<Arc<DynObjectStore>, ParseError> {
+    info!(?db_dir, object_store_type = "Directory", "Object Store");
+    fs::create_dir_all(db_dir).context(CreatingDatabaseDirectorySnafu { path: db_dir })?;
+
+    let store = object_store::local::LocalFileSystem::new_with_prefix(db_dir)
+        .context(CreateLocalFileSystemSnafu { path: db_dir })?;
+    Ok(Arc::new(store))
+}
+
 #[derive(Debug, Snafu)]
 #[allow(missing_docs)]
 pub enum CheckError {
@@ -2,9 +2,11 @@
 
 use crate::{
     ingester_address::IngesterAddress,
+    object_store::{make_local_object_store, ParseError},
     single_tenant::{CONFIG_AUTHZ_ENV_NAME, CONFIG_AUTHZ_FLAG},
 };
-use std::{collections::HashMap, num::NonZeroUsize};
+use object_store::DynObjectStore;
+use std::{collections::HashMap, num::NonZeroUsize, path::PathBuf, sync::Arc, time::Duration};
 
 /// CLI config for querier configuration
 #[derive(Debug, Clone, PartialEq, Eq, clap::Parser)]
@@ -81,6 +83,19 @@ pub struct QuerierConfig {
     )]
     pub max_concurrent_queries: usize,
 
+    /// Limit the size of a single query result, in bytes.
+    ///
+    /// Queries producing more data are aborted with an error. This protects the querier from
+    /// running out of memory due to pathological queries.
+    ///
+    /// If not set, query results are not limited.
+    #[clap(
+        long = "max-query-result-bytes",
+        env = "INFLUXDB_IOX_MAX_QUERY_RESULT_BYTES",
+        action
+    )]
+    pub max_query_result_bytes: Option<NonZeroUsize>,
+
     /// After how many ingester query errors should the querier enter circuit breaker mode?
     ///
     /// The querier normally contacts the ingester for any unpersisted data during query planning.
@@ -104,7 +119,37 @@ pub struct QuerierConfig {
     )]
     pub ingester_circuit_breaker_threshold: u64,
 
+    /// Number of gRPC connections the querier keeps open to every ingester.
+    ///
+    /// Queries are spread over these connections in a round-robin fashion.
+    /// Use more than one connection if a single HTTP/2 connection becomes a
+    /// bottleneck under high query concurrency.
+    #[clap(
+        long = "ingester-connection-pool-size",
+        env = "INFLUXDB_IOX_INGESTER_CONNECTION_POOL_SIZE",
+        default_value = "1",
+        action
+    )]
+    pub ingester_connection_pool_size: NonZeroUsize,
+
+    /// Never query the ingesters, even if `--ingester-addresses` are set.
+    ///
+    /// Queries are answered from persisted Parquet files only and do not include
+    /// unpersisted data. This is useful for queriers that only serve historical
+    /// ("cold") data.
+    #[clap(
+        long = "disable-ingester-queries",
+        env = "INFLUXDB_IOX_DISABLE_INGESTER_QUERIES",
+        action
+    )]
+    pub disable_ingester_queries: bool,
+
     /// DataFusion config.
+    ///
+    /// Comma-separated `KEY:VALUE` pairs that override individual
+    /// DataFusion (`datafusion.*`) or IOx (`iox.*`) query settings,
+    /// e.g. `datafusion.execution.batch_size:1024`. Unknown keys and
+    /// invalid values are rejected when the querier starts.
     #[clap(
         long = "datafusion-config",
         env = "INFLUXDB_IOX_DATAFUSION_CONFIG",
@@ -113,6 +158,79 @@ pub struct QuerierConfig {
         action
     )]
     pub datafusion_config: HashMap<String, String>,
+
+    /// Log a warning for every query that takes longer than this to complete.
+    ///
+    /// The warning contains the query text, the namespace, the elapsed time
+    /// and the number of returned rows (if known). This helps to find slow
+    /// queries without enabling full tracing.
+    ///
+    /// If not set, slow queries are not logged.
+    #[clap(
+        long = "slow-query-threshold",
+        env = "INFLUXDB_IOX_SLOW_QUERY_THRESHOLD",
+        value_parser = humantime::parse_duration,
+        action
+    )]
+    pub slow_query_threshold: Option<Duration>,
+
+    /// Only serve the given namespaces (comma-separated).
+    ///
+    /// Queries against other namespaces fail with a "not served by this
+    /// querier" permission-denied error. Can not be combined with
+    /// `--namespace-denylist`.
+    ///
+    /// If not set, all namespaces are served.
+    #[clap(
+        long = "namespace-allowlist",
+        env = "INFLUXDB_IOX_NAMESPACE_ALLOWLIST",
+        value_delimiter = ',',
+        conflicts_with = "namespace_denylist",
+        action
+    )]
+    pub namespace_allowlist: Vec<String>,
+
+    /// Serve all but the given namespaces (comma-separated).
+    ///
+    /// Queries against these namespaces fail with a "not served by this
+    /// querier" permission-denied error. Can not be combined with
+    /// `--namespace-allowlist`.
+    #[clap(
+        long = "namespace-denylist",
+        env = "INFLUXDB_IOX_NAMESPACE_DENYLIST",
+        value_delimiter = ',',
+        action
+    )]
+    pub namespace_denylist: Vec<String>,
+
+    /// How long the results of FlightSQL catalog and db-schema metadata
+    /// requests are cached per namespace.
+    ///
+    /// These are sent by every (re)connecting client but rarely change. Newly
+    /// created namespaces may be missing from the results for up to this long.
+    #[clap(
+        long = "flightsql-metadata-cache-ttl",
+        env = "INFLUXDB_IOX_FLIGHTSQL_METADATA_CACHE_TTL",
+        default_value = "10s",
+        value_parser = humantime::parse_duration,
+        action
+    )]
+    pub flightsql_metadata_cache_ttl: Duration,
+
+    /// Additional parquet stores that are registered with the query runtime
+    /// next to the main object store, e.g. for a cold storage tier.
+    ///
+    /// Comma-separated list of `ID:DIRECTORY` pairs, each store is backed by
+    /// the given local directory. Stores whose ID is already taken are
+    /// ignored with a warning.
+    #[clap(
+        long = "parquet-store-dirs",
+        env = "INFLUXDB_IOX_PARQUET_STORE_DIRS",
+        value_delimiter = ',',
+        value_parser = parse_parquet_store_dir,
+        action
+    )]
+    pub parquet_store_dirs: Vec<(&'static str, PathBuf)>,
 }
 
 impl QuerierConfig {
@@ -136,6 +254,46 @@ impl QuerierConfig {
     pub fn max_concurrent_queries(&self) -> usize {
         self.max_concurrent_queries
     }
+
+    /// Maximum size of a single query result in bytes, if limited
+    pub fn max_query_result_bytes(&self) -> Option<NonZeroUsize> {
+        self.max_query_result_bytes
+    }
+
+    /// Number of connections to every ingester
+    pub fn ingester_connection_pool_size(&self) -> NonZeroUsize {
+        self.ingester_connection_pool_size
+    }
+
+    /// Duration after which a query is logged as slow, if enabled
+    pub fn slow_query_threshold(&self) -> Option<Duration> {
+        self.slow_query_threshold
+    }
+
+    /// Create the [additional parquet stores](Self::parquet_store_dirs) as
+    /// `(id, store)` pairs.
+    pub fn parquet_stores(&self) -> Result<Vec<(&'static str, Arc<DynObjectStore>)>, ParseError> {
+        self.parquet_store_dirs
+            .iter()
+            .map(|(id, dir)| Ok((*id, make_local_object_store(dir)?)))
+            .collect()
+    }
+}
+
+/// Parse an `ID:DIRECTORY` pair of `--parquet-store-dirs`.
+///
+/// The ID is leaked, as stores are identified by static strings and only
+/// configured once per process.
+pub fn parse_parquet_store_dir(
+    s: &str,
+) -> Result<(&'static str, PathBuf), Box<dyn std::error::Error + Send + Sync + 'static>> {
+    match s.trim().split_once(':') {
+        Some((id, dir)) if !id.trim().is_empty() && !dir.trim().is_empty() => Ok((
+            Box::leak(id.trim().to_owned().into_boxed_str()),
+            PathBuf::from(dir.trim()),
+        )),
+        _ => Err(format!("Invalid parquet store - expected 'ID:DIRECTORY' got '{s}'").into()),
+    }
 }
 
 fn parse_datafusion_config(
@@ -181,7 +339,60 @@ mod tests {
 
         assert_eq!(actual.num_query_threads(), None);
         assert!(actual.ingester_addresses.is_empty());
+        assert!(!actual.disable_ingester_queries);
         assert!(actual.datafusion_config.is_empty());
+        assert_eq!(actual.slow_query_threshold(), None);
+        assert_eq!(actual.flightsql_metadata_cache_ttl, Duration::from_secs(10));
+        assert_eq!(
+            actual.ingester_connection_pool_size(),
+            NonZeroUsize::new(1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_namespace_lists() {
+        let actual =
+            QuerierConfig::try_parse_from(["my_binary", "--namespace-allowlist", "ns1,ns2"])
+                .unwrap();
+        assert_eq!(actual.namespace_allowlist, ["ns1", "ns2"]);
+        assert!(actual.namespace_denylist.is_empty());
+
+        let actual =
+            QuerierConfig::try_parse_from(["my_binary", "--namespace-denylist", "ns1"]).unwrap();
+        assert!(actual.namespace_allowlist.is_empty());
+        assert_eq!(actual.namespace_denylist, ["ns1"]);
+
+        let actual = QuerierConfig::try_parse_from([
+            "my_binary",
+            "--namespace-allowlist",
+            "ns1",
+            "--namespace-denylist",
+            "ns2",
+        ])
+        .unwrap_err()
+        .to_string();
+        assert_contains!(actual, "cannot be used with");
+    }
+
+    #[test]
+    fn test_slow_query_threshold() {
+        let actual =
+            QuerierConfig::try_parse_from(["my_binary", "--slow-query-threshold", "1s 500ms"])
+                .unwrap();
+
+        assert_eq!(
+            actual.slow_query_threshold(),
+            Some(Duration::from_millis(1500))
+        );
+    }
+
+    #[test]
+    fn test_flightsql_metadata_cache_ttl() {
+        let actual =
+            QuerierConfig::try_parse_from(["my_binary", "--flightsql-metadata-cache-ttl", "1m"])
+                .unwrap();
+
+        assert_eq!(actual.flightsql_metadata_cache_ttl, Duration::from_secs(60));
     }
 
     #[test]
@@ -250,6 +461,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parquet_store_dirs() {
+        let actual = QuerierConfig::try_parse_from(["my_binary"]).unwrap();
+        assert!(actual.parquet_store_dirs.is_empty());
+
+        let dir = test_helpers::tmp_dir().unwrap();
+        let cold = dir.path().join("cold");
+        let actual = QuerierConfig::try_parse_from([
+            "my_binary",
+            "--parquet-store-dirs",
+            &format!(
+                "cold:{}, archive : {}",
+                cold.display(),
+                dir.path().display()
+            ),
+        ])
+        .unwrap();
+        assert_eq!(
+            actual.parquet_store_dirs,
+            [("cold", cold.clone()), ("archive", dir.path().to_owned())]
+        );
+
+        let stores = actual.parquet_stores().unwrap();
+        let ids = stores.iter().map(|(id, _)| *id).collect::<Vec<_>>();
+        assert_eq!(ids, ["cold", "archive"]);
+        assert!(cold.is_dir());
+
+        let actual = QuerierConfig::try_parse_from(["my_binary", "--parquet-store-dirs", "cold"])
+            .unwrap_err()
+            .to_string();
+        assert_contains!(
+            actual,
+            "Invalid parquet store - expected 'ID:DIRECTORY' got 'cold'"
+        );
+    }
+
     #[test]
     fn bad_datafusion_config() {
         let actual = QuerierConfig::try_parse_from(["my_binary", "--datafusion-config=foo"])
@@ -59,9 +59,14 @@ impl Compactor {
                     compact(
                         config.trace_collector,
                         config.partition_concurrency,
+                        config.fetch_batch_size,
                         config.partition_timeout,
+                        &config.partition_timeout_overrides,
+                        config.max_rounds,
+                        config.upload_concurrency,
                         Arc::clone(&df_semaphore),
-                        &components
+                        &components,
+                        None,
                     ).await;
 
                     info!("compactor done");
@@ -0,0 +1,76 @@
+use std::fmt::Display;
+
+use async_trait::async_trait;
+
+use super::{ChangedFilesFilter, SavedParquetFileState};
+
+/// Runs the inner filter (e.g. for logging) but never reports a modification, so the compactor commits its results
+/// regardless of concurrent changes.
+#[derive(Debug)]
+pub struct IgnoreChangedFilesWrapper<T>
+where
+    T: ChangedFilesFilter,
+{
+    inner: T,
+}
+
+impl<T> IgnoreChangedFilesWrapper<T>
+where
+    T: ChangedFilesFilter,
+{
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+}
+
+impl<T> Display for IgnoreChangedFilesWrapper<T>
+where
+    T: ChangedFilesFilter,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ignore({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl<T> ChangedFilesFilter for IgnoreChangedFilesWrapper<T>
+where
+    T: ChangedFilesFilter,
+{
+    async fn apply(&self, old: &SavedParquetFileState, new: &SavedParquetFileState) -> bool {
+        let _modified = self.inner.apply(old, new).await;
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data_types::CompactionLevel;
+    use iox_tests::ParquetFileBuilder;
+
+    use crate::components::changed_files_filter::logging::LoggingChangedFiles;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let filter = IgnoreChangedFilesWrapper::new(LoggingChangedFiles::new());
+        assert_eq!(filter.to_string(), "ignore(logging_changed_files)");
+    }
+
+    #[tokio::test]
+    async fn test_apply() {
+        let pf_id1_level_0 = ParquetFileBuilder::new(1)
+            .with_compaction_level(CompactionLevel::Initial)
+            .build();
+        let old = SavedParquetFileState::from([&pf_id1_level_0]);
+        let new = SavedParquetFileState::from([]);
+
+        let inner = LoggingChangedFiles::new();
+        assert!(inner.apply(&old, &new).await);
+
+        let filter = IgnoreChangedFilesWrapper::new(inner);
+        assert!(!filter.apply(&old, &new).await);
+        assert!(!filter.apply(&old, &old).await);
+    }
+}
@@ -3,7 +3,7 @@ use std::fmt::Display;
 use super::{ChangedFilesFilter, SavedParquetFileState};
 
 use async_trait::async_trait;
-use observability_deps::tracing::info;
+use observability_deps::tracing::warn;
 
 #[derive(Debug, Default, Copy, Clone)]
 pub struct LoggingChangedFiles {}
@@ -23,11 +23,18 @@ impl Display for LoggingChangedFiles {
 #[async_trait]
 impl ChangedFilesFilter for LoggingChangedFiles {
     async fn apply(&self, old: &SavedParquetFileState, new: &SavedParquetFileState) -> bool {
-        if old.existing_files_modified(new) {
-            let modified_ids_and_levels = old.modified_ids_and_levels(new);
-            info!(?modified_ids_and_levels, "Concurrent modification detected");
+        let diff = old.diff(new);
+
+        // new files alone (e.g. from ingestion) are not a conflict
+        let modified = !diff.removed.is_empty();
+        if modified {
+            warn!(
+                removed = ?diff.removed,
+                added = ?diff.added,
+                "Concurrent modification detected",
+            );
         }
 
-        false // we're ignoring the return value anyway for the moment
+        modified
     }
 }
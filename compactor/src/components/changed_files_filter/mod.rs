@@ -6,6 +6,7 @@ use std::{
 use async_trait::async_trait;
 use data_types::{CompactionLevel, ParquetFile, ParquetFileId};
 
+pub mod ignore;
 pub mod logging;
 
 /// Returns `true` if the files in the saved state have been changed according to the current state.
@@ -61,6 +62,36 @@ impl SavedParquetFileState {
     pub fn modified_ids_and_levels(&self, new: &Self) -> Vec<(ParquetFileId, CompactionLevel)> {
         self.missing(new).cloned().collect()
     }
+
+    /// Files that differ between `self` and the `new` state, sorted by ID.
+    ///
+    /// A file whose compaction level changed shows up as both removed (with its old level) and added (with its new
+    /// level).
+    pub fn diff(&self, new: &Self) -> ParquetFileStateDiff {
+        let mut removed = self.missing(new).cloned().collect::<Vec<_>>();
+        removed.sort();
+        let mut added = new.missing(self).cloned().collect::<Vec<_>>();
+        added.sort();
+
+        ParquetFileStateDiff { removed, added }
+    }
+}
+
+/// Difference between two [`SavedParquetFileState`]s, see [`SavedParquetFileState::diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParquetFileStateDiff {
+    /// Files of the old state that are missing from the new state, i.e. that were modified by some other process.
+    pub removed: Vec<(ParquetFileId, CompactionLevel)>,
+
+    /// Files of the new state that were not part of the old state, e.g. newly ingested files.
+    pub added: Vec<(ParquetFileId, CompactionLevel)>,
+}
+
+impl ParquetFileStateDiff {
+    /// Returns `true` if both states contain the same files.
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty() && self.added.is_empty()
+    }
 }
 
 #[cfg(test)]
@@ -189,6 +220,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn diff_lists_added_and_removed_files() {
+        let pf_id1_level_0 = ParquetFileBuilder::new(1)
+            .with_compaction_level(CompactionLevel::Initial)
+            .build();
+        let pf_id1_level_1 = ParquetFileBuilder::new(1)
+            .with_compaction_level(CompactionLevel::FileNonOverlapped)
+            .build();
+        let pf_id2_level_0 = ParquetFileBuilder::new(2)
+            .with_compaction_level(CompactionLevel::Initial)
+            .build();
+        let pf_id3_level_2 = ParquetFileBuilder::new(3)
+            .with_compaction_level(CompactionLevel::Final)
+            .build();
+        let pf_id4_level_0 = ParquetFileBuilder::new(4)
+            .with_compaction_level(CompactionLevel::Initial)
+            .build();
+
+        let saved_state_1 =
+            SavedParquetFileState::from([&pf_id1_level_0, &pf_id2_level_0, &pf_id3_level_2]);
+        let saved_state_2 =
+            SavedParquetFileState::from([&pf_id4_level_0, &pf_id1_level_1, &pf_id3_level_2]);
+
+        assert_eq!(
+            saved_state_1.diff(&saved_state_2),
+            ParquetFileStateDiff {
+                removed: vec![
+                    (ParquetFileId::new(1), CompactionLevel::Initial),
+                    (ParquetFileId::new(2), CompactionLevel::Initial),
+                ],
+                added: vec![
+                    (ParquetFileId::new(1), CompactionLevel::FileNonOverlapped),
+                    (ParquetFileId::new(4), CompactionLevel::Initial),
+                ],
+            }
+        );
+
+        assert!(saved_state_1.diff(&saved_state_1).is_empty());
+    }
+
     #[test]
     fn same_number_of_files_different_ids_indicates_modification() {
         let pf_id1_level_0 = ParquetFileBuilder::new(1)
@@ -0,0 +1,306 @@
+use std::{
+    fmt::{Debug, Display},
+    fs::{File, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use compactor_scheduler::CompactionJob;
+use iox_time::TimeProvider;
+use observability_deps::tracing::warn;
+use serde_json::json;
+
+use crate::error::{DynError, ErrorKindExt};
+
+use super::CompactionJobDoneSink;
+
+/// Destination for audit log lines.
+///
+/// Appending may block, so it is run on a blocking thread by [`AuditCompactionJobDoneSinkWrapper`].
+pub trait AuditLogTarget: Debug + Display + Send + Sync + 'static {
+    /// Append a single line (without trailing newline) and flush it.
+    fn append(&self, line: &str) -> std::io::Result<()>;
+}
+
+/// Appends audit lines to a local file.
+///
+/// Once the file grows beyond `max_bytes`, it is moved to `<path>.1` (replacing any previous rotated file) and a
+/// new file is started.
+#[derive(Debug)]
+pub struct FileAuditLogTarget {
+    path: PathBuf,
+    max_bytes: u64,
+    file: Mutex<Option<File>>,
+}
+
+impl FileAuditLogTarget {
+    pub fn new(path: impl Into<PathBuf>, max_bytes: u64) -> Self {
+        Self {
+            path: path.into(),
+            max_bytes,
+            file: Mutex::new(None),
+        }
+    }
+
+    fn open(&self) -> std::io::Result<File> {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+    }
+
+    fn rotated_path(&self) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(".1");
+        path.into()
+    }
+}
+
+impl Display for FileAuditLogTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "file({})", self.path.display())
+    }
+}
+
+impl AuditLogTarget for FileAuditLogTarget {
+    fn append(&self, line: &str) -> std::io::Result<()> {
+        let mut guard = self.file.lock().expect("not poisoned");
+
+        let mut file = match guard.take() {
+            Some(file) => file,
+            None => self.open()?,
+        };
+
+        let len = file.metadata()?.len();
+        if len > 0 && len + line.len() as u64 + 1 > self.max_bytes {
+            drop(file);
+            std::fs::rename(&self.path, self.rotated_path())?;
+            file = self.open()?;
+        }
+
+        writeln!(file, "{line}")?;
+        file.flush()?;
+
+        *guard = Some(file);
+        Ok(())
+    }
+}
+
+/// Keeps audit lines in memory, mostly useful for testing.
+#[derive(Debug, Default)]
+pub struct MemoryAuditLogTarget {
+    lines: Mutex<Vec<String>>,
+}
+
+impl MemoryAuditLogTarget {
+    #[allow(dead_code)] // used for testing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the recorded lines.
+    #[allow(dead_code)] // used for testing
+    pub fn lines(&self) -> Vec<String> {
+        self.lines.lock().expect("not poisoned").clone()
+    }
+}
+
+impl Display for MemoryAuditLogTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "memory")
+    }
+}
+
+impl AuditLogTarget for MemoryAuditLogTarget {
+    fn append(&self, line: &str) -> std::io::Result<()> {
+        self.lines
+            .lock()
+            .expect("not poisoned")
+            .push(line.to_owned());
+        Ok(())
+    }
+}
+
+/// Writes one JSON line per compaction job outcome to an [`AuditLogTarget`] before passing the result on to the
+/// inner sink.
+///
+/// The line is written on a blocking thread (see [`spawn_blocking`](tokio::task::spawn_blocking)) so that file I/O does
+/// not stall the runtime. Failing to write the audit log is logged but does NOT fail the job.
+#[derive(Debug)]
+pub struct AuditCompactionJobDoneSinkWrapper<T>
+where
+    T: CompactionJobDoneSink,
+{
+    target: Arc<dyn AuditLogTarget>,
+    time_provider: Arc<dyn TimeProvider>,
+    inner: T,
+}
+
+impl<T> AuditCompactionJobDoneSinkWrapper<T>
+where
+    T: CompactionJobDoneSink,
+{
+    pub fn new(
+        inner: T,
+        target: Arc<dyn AuditLogTarget>,
+        time_provider: Arc<dyn TimeProvider>,
+    ) -> Self {
+        Self {
+            target,
+            time_provider,
+            inner,
+        }
+    }
+}
+
+impl<T> Display for AuditCompactionJobDoneSinkWrapper<T>
+where
+    T: CompactionJobDoneSink,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "audit({}, {})", self.target, self.inner)
+    }
+}
+
+#[async_trait]
+impl<T> CompactionJobDoneSink for AuditCompactionJobDoneSinkWrapper<T>
+where
+    T: CompactionJobDoneSink,
+{
+    async fn record(&self, job: CompactionJob, res: Result<(), DynError>) -> Result<(), DynError> {
+        let line = match &res {
+            Ok(()) => json!({
+                "partition_id": job.partition_id.get(),
+                "result": "ok",
+                "timestamp": self.time_provider.now().to_rfc3339(),
+            }),
+            Err(e) => json!({
+                "partition_id": job.partition_id.get(),
+                "result": "error",
+                "kind": e.classify().name(),
+                "error": e.to_string(),
+                "timestamp": self.time_provider.now().to_rfc3339(),
+            }),
+        };
+
+        let target = Arc::clone(&self.target);
+        let res_append = tokio::task::spawn_blocking(move || target.append(&line.to_string()))
+            .await
+            .unwrap_or_else(|e| Err(std::io::Error::new(std::io::ErrorKind::Other, e)));
+        if let Err(e) = res_append {
+            warn!(
+                %e,
+                target=%self.target,
+                partition_id = job.partition_id.get(),
+                "Cannot write compaction audit log",
+            );
+        }
+
+        self.inner.record(job, res).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use data_types::PartitionId;
+    use iox_time::{MockProvider, Time};
+    use object_store::Error as ObjectStoreError;
+
+    use super::{super::mock::MockCompactionJobDoneSink, *};
+
+    #[test]
+    fn test_display() {
+        let sink = AuditCompactionJobDoneSinkWrapper::new(
+            MockCompactionJobDoneSink::new(),
+            Arc::new(MemoryAuditLogTarget::new()),
+            Arc::new(MockProvider::new(Time::MIN)),
+        );
+        assert_eq!(sink.to_string(), "audit(memory, mock)");
+
+        let sink = AuditCompactionJobDoneSinkWrapper::new(
+            MockCompactionJobDoneSink::new(),
+            Arc::new(FileAuditLogTarget::new("/tmp/audit.log", 1024)),
+            Arc::new(MockProvider::new(Time::MIN)),
+        );
+        assert_eq!(sink.to_string(), "audit(file(/tmp/audit.log), mock)");
+    }
+
+    #[test]
+    fn test_file_rotation() {
+        let dir = test_helpers::tmp_dir().unwrap();
+        let path = dir.path().join("audit.log");
+        let rotated = dir.path().join("audit.log.1");
+
+        // room for two lines of 9 bytes (incl. newline)
+        let target = FileAuditLogTarget::new(&path, 20);
+
+        target.append("line 001").unwrap();
+        target.append("line 002").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "line 001\nline 002\n"
+        );
+        assert!(!rotated.exists());
+
+        // the third line does not fit anymore
+        target.append("line 003").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&rotated).unwrap(),
+            "line 001\nline 002\n"
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "line 003\n");
+
+        // rotating again replaces the previously rotated file
+        target.append("line 004").unwrap();
+        target.append("line 005").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&rotated).unwrap(),
+            "line 003\nline 004\n"
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "line 005\n");
+    }
+
+    #[tokio::test]
+    async fn test_record() {
+        let inner = Arc::new(MockCompactionJobDoneSink::new());
+        let target = Arc::new(MemoryAuditLogTarget::new());
+        let sink = AuditCompactionJobDoneSinkWrapper::new(
+            Arc::clone(&inner),
+            Arc::clone(&target) as _,
+            Arc::new(MockProvider::new(Time::from_timestamp_nanos(0))),
+        );
+
+        let cj_1 = CompactionJob::new(PartitionId::new(1));
+        let cj_2 = CompactionJob::new(PartitionId::new(2));
+
+        sink.record(cj_1.clone(), Ok(()))
+            .await
+            .expect("record failed");
+        sink.record(
+            cj_2.clone(),
+            Err(Box::new(ObjectStoreError::NotImplemented)),
+        )
+        .await
+        .expect("record failed");
+
+        assert_eq!(
+            target.lines(),
+            vec![
+                r#"{"partition_id":1,"result":"ok","timestamp":"1970-01-01T00:00:00+00:00"}"#,
+                r#"{"error":"Operation not yet implemented.","kind":"object_store","partition_id":2,"result":"error","timestamp":"1970-01-01T00:00:00+00:00"}"#,
+            ],
+        );
+
+        assert_eq!(
+            inner.results(),
+            HashMap::from([
+                (cj_1, Ok(())),
+                (cj_2, Err(String::from("Operation not yet implemented."))),
+            ]),
+        );
+    }
+}
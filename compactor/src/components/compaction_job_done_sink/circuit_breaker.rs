@@ -0,0 +1,202 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use compactor_scheduler::{
+    CompactionJob, CompactionJobEnd, CompactionJobEndVariant, Scheduler, SkipReason,
+};
+use data_types::PartitionId;
+use observability_deps::tracing::warn;
+
+use crate::error::DynError;
+
+use super::CompactionJobDoneSink;
+
+/// Tracks consecutive failures per partition and requests the partition to be skipped once `threshold` failures in
+/// a row have been observed, regardless of the error kind.
+///
+/// Results are passed to the inner sink until the breaker trips. The tripping failure is NOT passed on, so the
+/// partition is only recorded as skipped once with the circuit breaker reason. A successful job resets the count.
+#[derive(Debug)]
+pub struct CircuitBreakerCompactionJobDoneSinkWrapper<T>
+where
+    T: CompactionJobDoneSink,
+{
+    threshold: NonZeroUsize,
+    failures: Mutex<HashMap<PartitionId, usize>>,
+    scheduler: Arc<dyn Scheduler>,
+    inner: T,
+}
+
+impl<T> CircuitBreakerCompactionJobDoneSinkWrapper<T>
+where
+    T: CompactionJobDoneSink,
+{
+    pub fn new(inner: T, threshold: NonZeroUsize, scheduler: Arc<dyn Scheduler>) -> Self {
+        Self {
+            threshold,
+            failures: Mutex::new(HashMap::new()),
+            scheduler,
+            inner,
+        }
+    }
+}
+
+impl<T> Display for CircuitBreakerCompactionJobDoneSinkWrapper<T>
+where
+    T: CompactionJobDoneSink,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "circuit_breaker({}, {})", self.threshold, self.inner)
+    }
+}
+
+#[async_trait]
+impl<T> CompactionJobDoneSink for CircuitBreakerCompactionJobDoneSinkWrapper<T>
+where
+    T: CompactionJobDoneSink,
+{
+    async fn record(&self, job: CompactionJob, res: Result<(), DynError>) -> Result<(), DynError> {
+        let e = match res {
+            Ok(()) => {
+                self.failures
+                    .lock()
+                    .expect("not poisoned")
+                    .remove(&job.partition_id);
+                return self.inner.record(job, Ok(())).await;
+            }
+            Err(e) => e,
+        };
+
+        let failures = {
+            let mut guard = self.failures.lock().expect("not poisoned");
+            let failures = guard.entry(job.partition_id).or_default();
+            *failures += 1;
+            let n = *failures;
+            if n >= self.threshold.get() {
+                guard.remove(&job.partition_id);
+            }
+            n
+        };
+
+        if failures < self.threshold.get() {
+            return self.inner.record(job, Err(e)).await;
+        }
+
+        warn!(
+            %e,
+            partition_id = job.partition_id.get(),
+            failures,
+            "Circuit breaker tripped, skipping partition",
+        );
+
+        self.scheduler
+            .end_job(CompactionJobEnd {
+                job,
+                end_action: CompactionJobEndVariant::RequestToSkip(SkipReason(format!(
+                    "circuit breaker: {failures} consecutive failures, last error: {e}"
+                ))),
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use compactor_scheduler::create_test_scheduler;
+    use iox_tests::TestCatalog;
+    use iox_time::{MockProvider, Time};
+
+    use super::{super::mock::MockCompactionJobDoneSink, *};
+
+    #[test]
+    fn test_display() {
+        let sink = CircuitBreakerCompactionJobDoneSinkWrapper::new(
+            MockCompactionJobDoneSink::new(),
+            NonZeroUsize::new(3).unwrap(),
+            create_test_scheduler(
+                TestCatalog::new().catalog(),
+                Arc::new(MockProvider::new(Time::MIN)),
+                None,
+            ),
+        );
+        assert_eq!(sink.to_string(), "circuit_breaker(3, mock)");
+    }
+
+    #[tokio::test]
+    async fn test_record() {
+        let catalog = TestCatalog::new();
+        let inner = Arc::new(MockCompactionJobDoneSink::new());
+        let sink = CircuitBreakerCompactionJobDoneSinkWrapper::new(
+            Arc::clone(&inner),
+            NonZeroUsize::new(3).unwrap(),
+            create_test_scheduler(
+                catalog.catalog(),
+                Arc::new(MockProvider::new(Time::MIN)),
+                None,
+            ),
+        );
+
+        let cj_1 = CompactionJob::new(PartitionId::new(1));
+        let cj_2 = CompactionJob::new(PartitionId::new(1));
+        let cj_3 = CompactionJob::new(PartitionId::new(1));
+        let cj_4 = CompactionJob::new(PartitionId::new(2));
+        let cj_5 = CompactionJob::new(PartitionId::new(2));
+        let cj_6 = CompactionJob::new(PartitionId::new(2));
+
+        // partition 1 fails three times in a row
+        sink.record(cj_1.clone(), Err("msg 1".into()))
+            .await
+            .expect("record failed");
+        sink.record(cj_2.clone(), Err("msg 2".into()))
+            .await
+            .expect("record failed");
+        sink.record(cj_3, Err("msg 3".into()))
+            .await
+            .expect("record failed");
+
+        // partition 2 is reset by a successful job in between
+        sink.record(cj_4.clone(), Err("msg 4".into()))
+            .await
+            .expect("record failed");
+        sink.record(cj_5.clone(), Ok(()))
+            .await
+            .expect("record failed");
+        sink.record(cj_6.clone(), Err("msg 6".into()))
+            .await
+            .expect("record failed");
+
+        // the tripping failure is not passed to the inner sink
+        assert_eq!(
+            inner.results(),
+            HashMap::from([
+                (cj_1, Err(String::from("msg 1"))),
+                (cj_2, Err(String::from("msg 2"))),
+                (cj_4, Err(String::from("msg 4"))),
+                (cj_5, Ok(())),
+                (cj_6, Err(String::from("msg 6"))),
+            ]),
+        );
+
+        let skipped = catalog
+            .catalog()
+            .repositories()
+            .await
+            .partitions()
+            .list_skipped_compactions()
+            .await
+            .unwrap();
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].partition_id, PartitionId::new(1));
+        assert_eq!(
+            skipped[0].reason,
+            "circuit breaker: 3 consecutive failures, last error: msg 3"
+        );
+    }
+}
@@ -56,6 +56,7 @@ where
                 let scheduler_error = match SchedulerErrorKind::from(e.classify()) {
                     SchedulerErrorKind::OutOfMemory => SchedulerErrorKind::OutOfMemory,
                     SchedulerErrorKind::ObjectStore => SchedulerErrorKind::ObjectStore,
+                    SchedulerErrorKind::Catalog => SchedulerErrorKind::Catalog,
                     SchedulerErrorKind::Timeout => SchedulerErrorKind::Timeout,
                     SchedulerErrorKind::Unknown(_) => SchedulerErrorKind::Unknown(e.to_string()),
                 };
@@ -8,11 +8,15 @@ use compactor_scheduler::CompactionJob;
 
 use crate::DynError;
 
+pub mod audit;
+pub mod circuit_breaker;
 pub mod error_kind;
 pub mod logging;
 pub mod metrics;
 pub mod mock;
 pub mod outcome;
+pub mod retry_budget;
+pub mod webhook;
 
 /// Records "compaction job is done" status for given partition.
 #[async_trait]
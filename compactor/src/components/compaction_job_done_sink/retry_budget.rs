@@ -0,0 +1,220 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use compactor_scheduler::CompactionJob;
+use observability_deps::tracing::warn;
+
+use crate::error::{DynError, ErrorKind, ErrorKindExt};
+
+use super::CompactionJobDoneSink;
+
+/// Number of failed compaction jobs per [`ErrorKind`] that are tolerated before the compactor should back off
+/// globally, e.g. because the object store is down and every partition would fail anyway.
+///
+/// Kinds without a budget are not limited. The budget is shared between the
+/// [sink](RetryBudgetCompactionJobDoneSinkWrapper) that counts the failures and whatever reacts to it being exhausted.
+#[derive(Debug)]
+pub struct RetryBudget {
+    budgets: HashMap<ErrorKind, NonZeroUsize>,
+    failures: Mutex<HashMap<ErrorKind, usize>>,
+}
+
+impl RetryBudget {
+    pub fn new(budgets: HashMap<ErrorKind, NonZeroUsize>) -> Self {
+        Self {
+            budgets,
+            failures: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Count a failure of the given kind.
+    ///
+    /// Returns the number of failures of this kind if that exceeds the budget.
+    fn record_failure(&self, kind: ErrorKind) -> Option<usize> {
+        let budget = self.budgets.get(&kind)?;
+
+        let mut failures = self.failures.lock().expect("not poisoned");
+        let n = failures.entry(kind).or_default();
+        *n += 1;
+        (*n > budget.get()).then_some(*n)
+    }
+
+    /// Returns an error kind that exceeded its budget, if any.
+    pub fn exhausted(&self) -> Option<ErrorKind> {
+        let failures = self.failures.lock().expect("not poisoned");
+        self.budgets
+            .iter()
+            .filter(|(kind, budget)| failures.get(*kind).is_some_and(|n| *n > budget.get()))
+            .map(|(kind, _budget)| *kind)
+            .min()
+    }
+
+    /// Forget all failures, e.g. after backing off.
+    pub fn reset(&self) {
+        self.failures.lock().expect("not poisoned").clear();
+    }
+}
+
+impl Display for RetryBudget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut budgets = self.budgets.iter().collect::<Vec<_>>();
+        budgets.sort();
+        for (i, (kind, budget)) in budgets.into_iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{kind}={budget}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Counts failed compaction jobs against a [`RetryBudget`].
+///
+/// All results are passed to the inner sink, this wrapper only observes them.
+#[derive(Debug)]
+pub struct RetryBudgetCompactionJobDoneSinkWrapper<T>
+where
+    T: CompactionJobDoneSink,
+{
+    budget: Arc<RetryBudget>,
+    inner: T,
+}
+
+impl<T> RetryBudgetCompactionJobDoneSinkWrapper<T>
+where
+    T: CompactionJobDoneSink,
+{
+    pub fn new(inner: T, budget: Arc<RetryBudget>) -> Self {
+        Self { budget, inner }
+    }
+}
+
+impl<T> Display for RetryBudgetCompactionJobDoneSinkWrapper<T>
+where
+    T: CompactionJobDoneSink,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "retry_budget([{}], {})", self.budget, self.inner)
+    }
+}
+
+#[async_trait]
+impl<T> CompactionJobDoneSink for RetryBudgetCompactionJobDoneSinkWrapper<T>
+where
+    T: CompactionJobDoneSink,
+{
+    async fn record(&self, job: CompactionJob, res: Result<(), DynError>) -> Result<(), DynError> {
+        if let Err(e) = &res {
+            let kind = e.classify();
+            if let Some(failures) = self.budget.record_failure(kind) {
+                warn!(
+                    %e,
+                    %kind,
+                    failures,
+                    partition_id = job.partition_id.get(),
+                    "Retry budget exhausted",
+                );
+            }
+        }
+
+        self.inner.record(job, res).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data_types::PartitionId;
+
+    use crate::error::SimpleError;
+
+    use super::{super::mock::MockCompactionJobDoneSink, *};
+
+    fn budget() -> Arc<RetryBudget> {
+        Arc::new(RetryBudget::new(HashMap::from([
+            (ErrorKind::ObjectStore, NonZeroUsize::new(2).unwrap()),
+            (ErrorKind::Catalog, NonZeroUsize::new(5).unwrap()),
+        ])))
+    }
+
+    fn err(kind: ErrorKind) -> Result<(), DynError> {
+        Err(Box::new(SimpleError::new(kind, format!("{kind} failed"))))
+    }
+
+    #[test]
+    fn test_display() {
+        let sink = RetryBudgetCompactionJobDoneSinkWrapper::new(
+            MockCompactionJobDoneSink::new(),
+            budget(),
+        );
+        assert_eq!(
+            sink.to_string(),
+            "retry_budget([object_store=2, catalog=5], mock)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_object_store_budget_exhausted() {
+        let inner = Arc::new(MockCompactionJobDoneSink::new());
+        let budget = budget();
+        let sink =
+            RetryBudgetCompactionJobDoneSinkWrapper::new(Arc::clone(&inner), Arc::clone(&budget));
+
+        let cj_1 = CompactionJob::new(PartitionId::new(1));
+        let cj_2 = CompactionJob::new(PartitionId::new(2));
+        let cj_3 = CompactionJob::new(PartitionId::new(3));
+        let cj_4 = CompactionJob::new(PartitionId::new(4));
+        let cj_5 = CompactionJob::new(PartitionId::new(5));
+
+        // successes and kinds without a budget are not counted
+        sink.record(cj_1.clone(), Ok(()))
+            .await
+            .expect("record failed");
+        sink.record(cj_2.clone(), err(ErrorKind::Unknown))
+            .await
+            .expect("record failed");
+        assert_eq!(budget.exhausted(), None);
+
+        // the budget allows two object store failures
+        sink.record(cj_3.clone(), err(ErrorKind::ObjectStore))
+            .await
+            .expect("record failed");
+        sink.record(cj_4.clone(), err(ErrorKind::ObjectStore))
+            .await
+            .expect("record failed");
+        assert_eq!(budget.exhausted(), None);
+
+        sink.record(cj_5.clone(), err(ErrorKind::ObjectStore))
+            .await
+            .expect("record failed");
+        assert_eq!(budget.exhausted(), Some(ErrorKind::ObjectStore));
+
+        // all results are passed on
+        assert_eq!(
+            inner.results(),
+            HashMap::from([
+                (cj_1, Ok(())),
+                (cj_2, Err(String::from("unknown failed"))),
+                (cj_3, Err(String::from("object_store failed"))),
+                (cj_4, Err(String::from("object_store failed"))),
+                (cj_5, Err(String::from("object_store failed"))),
+            ]),
+        );
+
+        // backing off starts a new budget
+        budget.reset();
+        assert_eq!(budget.exhausted(), None);
+        sink.record(
+            CompactionJob::new(PartitionId::new(6)),
+            err(ErrorKind::ObjectStore),
+        )
+        .await
+        .expect("record failed");
+        assert_eq!(budget.exhausted(), None);
+    }
+}
@@ -0,0 +1,309 @@
+use std::{collections::HashSet, fmt::Display, num::NonZeroUsize, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use compactor_scheduler::CompactionJob;
+use iox_time::TimeProvider;
+use observability_deps::tracing::warn;
+use serde_json::{json, Value};
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+use crate::error::{DynError, ErrorKind, ErrorKindExt};
+
+use super::CompactionJobDoneSink;
+
+/// Delay before the first retry of a failed webhook request. Doubled for every further retry.
+const INITIAL_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// POSTs a JSON payload to a webhook for every compaction job that failed with one of the given error kinds, e.g. to
+/// alert on partitions that are about to be skipped.
+///
+/// Notifications are sent by a background task so that a slow webhook never delays the job. Up to `queue_size`
+/// notifications wait to be sent, further ones are dropped with a warning. Failed requests are retried up to
+/// `max_attempts` times in total. Failing to notify the webhook is logged but does NOT fail the job. Successful jobs
+/// and errors of other kinds are passed on silently.
+#[derive(Debug)]
+pub struct WebhookCompactionJobDoneSinkWrapper<T>
+where
+    T: CompactionJobDoneSink,
+{
+    url: Arc<str>,
+    kind: HashSet<ErrorKind>,
+    notifications: mpsc::Sender<(CompactionJob, Value)>,
+    time_provider: Arc<dyn TimeProvider>,
+    inner: T,
+}
+
+impl<T> WebhookCompactionJobDoneSinkWrapper<T>
+where
+    T: CompactionJobDoneSink,
+{
+    /// Create a new wrapper and spawn the task that sends the notifications.
+    ///
+    /// The task stops once the wrapper is dropped and all queued notifications are sent.
+    pub fn new(
+        inner: T,
+        url: impl Into<String>,
+        kind: HashSet<ErrorKind>,
+        timeout: Duration,
+        max_attempts: NonZeroUsize,
+        queue_size: NonZeroUsize,
+        time_provider: Arc<dyn TimeProvider>,
+    ) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .expect("webhook client config is valid");
+        let url: Arc<str> = Arc::from(url.into());
+
+        let (notifications, mut rx) = mpsc::channel::<(CompactionJob, Value)>(queue_size.get());
+        let notifier = Notifier {
+            url: Arc::clone(&url),
+            client,
+            max_attempts,
+        };
+        tokio::spawn(async move {
+            while let Some((job, payload)) = rx.recv().await {
+                if let Err(e) = notifier.notify(&payload).await {
+                    warn!(
+                        %e,
+                        url=%notifier.url,
+                        partition_id = job.partition_id.get(),
+                        "Cannot notify compaction webhook",
+                    );
+                }
+            }
+        });
+
+        Self {
+            url,
+            kind,
+            notifications,
+            time_provider,
+            inner,
+        }
+    }
+}
+
+/// Sends the webhook requests, see [`WebhookCompactionJobDoneSinkWrapper`].
+#[derive(Debug)]
+struct Notifier {
+    url: Arc<str>,
+    client: reqwest::Client,
+    max_attempts: NonZeroUsize,
+}
+
+impl Notifier {
+    async fn notify(&self, payload: &Value) -> Result<(), reqwest::Error> {
+        let mut delay = INITIAL_RETRY_DELAY;
+        let mut attempt = 1;
+
+        loop {
+            let res = self
+                .client
+                .post(self.url.as_ref())
+                .json(payload)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+
+            match res {
+                Ok(_) => return Ok(()),
+                Err(e) if attempt >= self.max_attempts.get() => return Err(e),
+                Err(e) => {
+                    warn!(
+                        %e,
+                        url=%self.url,
+                        attempt,
+                        "Cannot notify compaction webhook, retrying",
+                    );
+                }
+            }
+
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+            attempt += 1;
+        }
+    }
+}
+
+impl<T> Display for WebhookCompactionJobDoneSinkWrapper<T>
+where
+    T: CompactionJobDoneSink,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "webhook({}, {})", self.url, self.inner)
+    }
+}
+
+#[async_trait]
+impl<T> CompactionJobDoneSink for WebhookCompactionJobDoneSinkWrapper<T>
+where
+    T: CompactionJobDoneSink,
+{
+    async fn record(&self, job: CompactionJob, res: Result<(), DynError>) -> Result<(), DynError> {
+        if let Err(e) = &res {
+            let kind = e.classify();
+            if self.kind.contains(&kind) {
+                let payload = json!({
+                    "partition_id": job.partition_id.get(),
+                    "kind": kind.name(),
+                    "error": e.to_string(),
+                    "timestamp": self.time_provider.now().to_rfc3339(),
+                });
+
+                match self.notifications.try_send((job.clone(), payload)) {
+                    Ok(()) => {}
+                    Err(TrySendError::Full(_)) => {
+                        warn!(
+                            url=%self.url,
+                            partition_id = job.partition_id.get(),
+                            "Compaction webhook queue is full, dropping notification",
+                        );
+                    }
+                    Err(TrySendError::Closed(_)) => {
+                        warn!(
+                            url=%self.url,
+                            partition_id = job.partition_id.get(),
+                            "Compaction webhook task is gone, dropping notification",
+                        );
+                    }
+                }
+            }
+        }
+
+        self.inner.record(job, res).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use data_types::PartitionId;
+    use datafusion::error::DataFusionError;
+    use iox_time::{MockProvider, Time};
+    use mockito::{Matcher, Mock, Server};
+
+    use super::{super::mock::MockCompactionJobDoneSink, *};
+
+    fn sink<T>(inner: T, url: String) -> WebhookCompactionJobDoneSinkWrapper<T>
+    where
+        T: CompactionJobDoneSink,
+    {
+        WebhookCompactionJobDoneSinkWrapper::new(
+            inner,
+            url,
+            HashSet::from([ErrorKind::Unknown]),
+            Duration::from_secs(5),
+            NonZeroUsize::new(3).unwrap(),
+            NonZeroUsize::new(10).unwrap(),
+            Arc::new(MockProvider::new(Time::from_timestamp_nanos(0))),
+        )
+    }
+
+    /// Wait until the background task sent all requests that `mock` expects.
+    async fn wait_until_matched(mock: &Mock) {
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while !mock.matched_async().await {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("webhook was not notified in time");
+    }
+
+    #[tokio::test]
+    async fn test_display() {
+        let sink = sink(
+            MockCompactionJobDoneSink::new(),
+            String::from("http://localhost:1234/hook"),
+        );
+        assert_eq!(
+            sink.to_string(),
+            "webhook(http://localhost:1234/hook, mock)"
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_record() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/hook")
+            .match_header("content-type", "application/json")
+            .match_body(Matcher::Json(json!({
+                "partition_id": 1,
+                "kind": "unknown",
+                "error": "msg 1",
+                "timestamp": "1970-01-01T00:00:00+00:00",
+            })))
+            .with_status(200)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let inner = Arc::new(MockCompactionJobDoneSink::new());
+        let sink = sink(Arc::clone(&inner), format!("{}/hook", server.url()));
+
+        let cj_1 = CompactionJob::new(PartitionId::new(1));
+        let cj_2 = CompactionJob::new(PartitionId::new(2));
+        let cj_3 = CompactionJob::new(PartitionId::new(3));
+
+        sink.record(cj_1.clone(), Err("msg 1".into()))
+            .await
+            .expect("record failed");
+        // other error kinds and successes do not notify the webhook
+        sink.record(
+            cj_2.clone(),
+            Err(Box::new(DataFusionError::ResourcesExhausted(String::from(
+                "foo",
+            )))),
+        )
+        .await
+        .expect("record failed");
+        sink.record(cj_3.clone(), Ok(()))
+            .await
+            .expect("record failed");
+
+        wait_until_matched(&mock).await;
+        mock.assert_async().await;
+
+        assert_eq!(
+            inner.results(),
+            HashMap::from([
+                (cj_1, Err(String::from("msg 1"))),
+                (cj_2, Err(String::from("Resources exhausted: foo"))),
+                (cj_3, Ok(())),
+            ]),
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_record_retries() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/hook")
+            .with_status(500)
+            .expect(3)
+            .create_async()
+            .await;
+
+        let inner = Arc::new(MockCompactionJobDoneSink::new());
+        let sink = sink(Arc::clone(&inner), format!("{}/hook", server.url()));
+
+        let cj = CompactionJob::new(PartitionId::new(1));
+
+        // a failing webhook does not fail the job, and the job does not wait for the retries
+        sink.record(cj.clone(), Err("msg".into()))
+            .await
+            .expect("record failed");
+        assert!(!mock.matched_async().await);
+
+        wait_until_matched(&mock).await;
+        mock.assert_async().await;
+
+        assert_eq!(
+            inner.results(),
+            HashMap::from([(cj, Err(String::from("msg")))]),
+        );
+    }
+}
@@ -5,6 +5,7 @@ pub mod logging;
 pub mod metrics;
 pub mod mock;
 pub mod not_empty;
+pub mod priority;
 pub mod randomize_order;
 pub mod scheduled;
 
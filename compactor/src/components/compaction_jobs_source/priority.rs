@@ -0,0 +1,302 @@
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use backoff::{Backoff, BackoffConfig};
+use compactor_scheduler::CompactionJob;
+use data_types::PartitionId;
+use iox_catalog::interface::Catalog;
+
+use super::CompactionJobsSource;
+
+/// Maximum number of partitions whose priority is requested from the catalog in a single query.
+const PRIORITY_BATCH_SIZE: usize = 1_000;
+
+/// Priority of a partition. Partitions with a higher priority are compacted first.
+///
+/// Priorities are compared by the number of L0 files first and the total size of all files second.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PartitionPriority {
+    /// Number of L0 files.
+    pub num_l0_files: usize,
+
+    /// Total size of all files, in bytes.
+    pub total_file_size_bytes: i64,
+}
+
+/// Determines the [`PartitionPriority`] of partitions.
+#[async_trait]
+pub trait PartitionPrioritySource: Debug + Display + Send + Sync {
+    /// Get priorities of the given partitions.
+    ///
+    /// Partitions that are missing from the result have the default (lowest) priority.
+    ///
+    /// This method performs retries.
+    ///
+    /// This MUST only use catalog metadata. It MUST NOT inspect the content of individual parquet files.
+    async fn fetch(&self, partition_ids: &[PartitionId])
+        -> HashMap<PartitionId, PartitionPriority>;
+}
+
+/// Fetches the [`PartitionPriority`] from aggregated catalog file statistics, one query per batch of partitions.
+#[derive(Debug)]
+pub struct CatalogPartitionPrioritySource {
+    backoff_config: BackoffConfig,
+    catalog: Arc<dyn Catalog>,
+}
+
+impl CatalogPartitionPrioritySource {
+    pub fn new(backoff_config: BackoffConfig, catalog: Arc<dyn Catalog>) -> Self {
+        Self {
+            backoff_config,
+            catalog,
+        }
+    }
+}
+
+impl Display for CatalogPartitionPrioritySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "catalog")
+    }
+}
+
+#[async_trait]
+impl PartitionPrioritySource for CatalogPartitionPrioritySource {
+    async fn fetch(
+        &self,
+        partition_ids: &[PartitionId],
+    ) -> HashMap<PartitionId, PartitionPriority> {
+        let mut priorities = HashMap::with_capacity(partition_ids.len());
+
+        for chunk in partition_ids.chunks(PRIORITY_BATCH_SIZE) {
+            let stats = Backoff::new(&self.backoff_config)
+                .retry_all_errors("partition_file_stats", || async {
+                    self.catalog
+                        .repositories()
+                        .await
+                        .parquet_files()
+                        .partition_file_stats(chunk)
+                        .await
+                })
+                .await
+                .expect("retry forever");
+
+            priorities.extend(stats.into_iter().map(|s| {
+                (
+                    s.partition_id,
+                    PartitionPriority {
+                        num_l0_files: s.num_l0_files as usize,
+                        total_file_size_bytes: s.total_file_size_bytes,
+                    },
+                )
+            }));
+        }
+
+        priorities
+    }
+}
+
+/// Orders compaction jobs by the [`PartitionPriority`] of their partitions, highest first.
+///
+/// Jobs with the same priority keep the order of the inner source.
+#[derive(Debug)]
+pub struct PriorityCompactionJobsSourceWrapper<T, P>
+where
+    T: CompactionJobsSource,
+    P: PartitionPrioritySource,
+{
+    inner: T,
+    priority_source: P,
+}
+
+impl<T, P> PriorityCompactionJobsSourceWrapper<T, P>
+where
+    T: CompactionJobsSource,
+    P: PartitionPrioritySource,
+{
+    pub fn new(inner: T, priority_source: P) -> Self {
+        Self {
+            inner,
+            priority_source,
+        }
+    }
+}
+
+impl<T, P> Display for PriorityCompactionJobsSourceWrapper<T, P>
+where
+    T: CompactionJobsSource,
+    P: PartitionPrioritySource,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "priority({}, {})", self.priority_source, self.inner)
+    }
+}
+
+#[async_trait]
+impl<T, P> CompactionJobsSource for PriorityCompactionJobsSourceWrapper<T, P>
+where
+    T: CompactionJobsSource,
+    P: PartitionPrioritySource,
+{
+    async fn fetch(&self) -> Vec<CompactionJob> {
+        let mut compaction_jobs = self.inner.fetch().await;
+        if compaction_jobs.is_empty() {
+            return compaction_jobs;
+        }
+
+        let partition_ids = compaction_jobs
+            .iter()
+            .map(|job| job.partition_id)
+            .collect::<Vec<_>>();
+        let priorities = self.priority_source.fetch(&partition_ids).await;
+
+        // stable sort, so jobs with the same priority keep their order
+        compaction_jobs.sort_by_cached_key(|job| {
+            std::cmp::Reverse(
+                priorities
+                    .get(&job.partition_id)
+                    .copied()
+                    .unwrap_or_default(),
+            )
+        });
+        compaction_jobs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use data_types::CompactionLevel;
+    use iox_tests::{TestCatalog, TestParquetFileBuilder};
+
+    use super::{super::mock::MockCompactionJobsSource, *};
+
+    #[derive(Debug)]
+    struct MockPartitionPrioritySource {
+        priorities: HashMap<PartitionId, PartitionPriority>,
+    }
+
+    impl Display for MockPartitionPrioritySource {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "mock")
+        }
+    }
+
+    #[async_trait]
+    impl PartitionPrioritySource for MockPartitionPrioritySource {
+        async fn fetch(
+            &self,
+            partition_ids: &[PartitionId],
+        ) -> HashMap<PartitionId, PartitionPriority> {
+            partition_ids
+                .iter()
+                .filter_map(|id| self.priorities.get(id).map(|p| (*id, *p)))
+                .collect()
+        }
+    }
+
+    fn priority(num_l0_files: usize, total_file_size_bytes: i64) -> PartitionPriority {
+        PartitionPriority {
+            num_l0_files,
+            total_file_size_bytes,
+        }
+    }
+
+    fn source(
+        compaction_jobs: Vec<CompactionJob>,
+        priorities: impl IntoIterator<Item = (i64, PartitionPriority)>,
+    ) -> PriorityCompactionJobsSourceWrapper<MockCompactionJobsSource, MockPartitionPrioritySource>
+    {
+        PriorityCompactionJobsSourceWrapper::new(
+            MockCompactionJobsSource::new(compaction_jobs),
+            MockPartitionPrioritySource {
+                priorities: priorities
+                    .into_iter()
+                    .map(|(id, p)| (PartitionId::new(id), p))
+                    .collect(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(source(vec![], []).to_string(), "priority(mock, mock)");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_empty() {
+        assert_eq!(source(vec![], []).fetch().await, vec![]);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_some() {
+        let cj_1 = CompactionJob::new(PartitionId::new(1));
+        let cj_2 = CompactionJob::new(PartitionId::new(2));
+        let cj_3 = CompactionJob::new(PartitionId::new(3));
+        let cj_4 = CompactionJob::new(PartitionId::new(4));
+        let cj_5 = CompactionJob::new(PartitionId::new(5));
+
+        let source = source(
+            vec![
+                cj_1.clone(),
+                cj_2.clone(),
+                cj_3.clone(),
+                cj_4.clone(),
+                cj_5.clone(),
+            ],
+            [
+                (1, priority(2, 1_000)),
+                (2, priority(10, 1)),
+                (3, priority(2, 5_000)),
+                (4, priority(2, 1_000)),
+                // 5 has no files
+            ],
+        );
+
+        // highest L0 count first, then the largest partition, ties keep their order
+        assert_eq!(source.fetch().await, vec![cj_2, cj_3, cj_1, cj_4, cj_5]);
+    }
+
+    #[tokio::test]
+    async fn test_catalog_priority_source() {
+        let catalog = TestCatalog::new();
+        let table = catalog
+            .create_namespace_1hr_retention("ns")
+            .await
+            .create_table("table")
+            .await;
+        let partition = table.create_partition("k1").await;
+        let empty_partition = table.create_partition("k2").await;
+
+        for (level, size, to_delete) in [
+            (CompactionLevel::Initial, 10, false),
+            (CompactionLevel::Initial, 20, false),
+            (CompactionLevel::FileNonOverlapped, 100, false),
+            (CompactionLevel::Initial, 1_000, true),
+        ] {
+            partition
+                .create_parquet_file_catalog_record(
+                    TestParquetFileBuilder::default()
+                        .with_compaction_level(level)
+                        .with_file_size_bytes(size)
+                        .with_to_delete(to_delete),
+                )
+                .await;
+        }
+
+        let source =
+            CatalogPartitionPrioritySource::new(BackoffConfig::default(), catalog.catalog());
+        assert_eq!(source.to_string(), "catalog");
+
+        let partition_id = partition.partition.id;
+        assert_eq!(
+            source
+                .fetch(&[partition_id, empty_partition.partition.id])
+                .await,
+            HashMap::from([(partition_id, priority(2, 130))]),
+        );
+        assert_eq!(source.fetch(&[]).await, HashMap::new());
+    }
+}
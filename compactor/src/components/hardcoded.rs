@@ -2,7 +2,7 @@
 //!
 //! TODO: Make this a runtime-config.
 
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, num::NonZeroUsize, sync::Arc, time::Duration};
 
 use compactor_scheduler::{create_scheduler, Scheduler};
 use data_types::CompactionLevel;
@@ -11,21 +11,32 @@ use object_store::memory::InMemory;
 use crate::{config::Config, error::ErrorKind, object_store::ignore_writes::IgnoreWrites};
 
 use super::{
-    changed_files_filter::logging::LoggingChangedFiles,
+    changed_files_filter::{
+        ignore::IgnoreChangedFilesWrapper, logging::LoggingChangedFiles, ChangedFilesFilter,
+    },
     commit::CommitToScheduler,
     compaction_job_done_sink::{
+        audit::{AuditCompactionJobDoneSinkWrapper, FileAuditLogTarget},
+        circuit_breaker::CircuitBreakerCompactionJobDoneSinkWrapper,
         error_kind::ErrorKindCompactionJobDoneSinkWrapper,
-        logging::LoggingCompactionJobDoneSinkWrapper, metrics::MetricsCompactionJobDoneSinkWrapper,
-        outcome::CompactionJobDoneSinkToScheduler, CompactionJobDoneSink,
+        logging::LoggingCompactionJobDoneSinkWrapper,
+        metrics::MetricsCompactionJobDoneSinkWrapper,
+        outcome::CompactionJobDoneSinkToScheduler,
+        retry_budget::{RetryBudget, RetryBudgetCompactionJobDoneSinkWrapper},
+        webhook::WebhookCompactionJobDoneSinkWrapper,
+        CompactionJobDoneSink,
     },
     compaction_job_stream::{
         endless::EndlessCompactionJobStream, once::OnceCompactionJobStream, CompactionJobStream,
     },
     compaction_jobs_source::{
-        logging::LoggingCompactionJobsWrapper, metrics::MetricsCompactionJobsSourceWrapper,
+        logging::LoggingCompactionJobsWrapper,
+        metrics::MetricsCompactionJobsSourceWrapper,
         not_empty::NotEmptyCompactionJobsSourceWrapper,
+        priority::{CatalogPartitionPrioritySource, PriorityCompactionJobsSourceWrapper},
         randomize_order::RandomizeOrderCompactionJobsSourcesWrapper,
-        scheduled::ScheduledCompactionJobsSource, CompactionJobsSource,
+        scheduled::ScheduledCompactionJobsSource,
+        CompactionJobsSource,
     },
     df_plan_exec::{
         dedicated::DedicatedDataFusionPlanExec, noop::NoopDataFusionPlanExec, DataFusionPlanExec,
@@ -49,6 +60,7 @@ use super::{
     },
     parquet_files_sink::{dispatch::DispatchParquetFilesSink, ParquetFilesSink},
     partition_files_source::{
+        cache::CachingPartitionFilesSource,
         catalog::{CatalogPartitionFilesSource, QueryRateLimiter},
         rate_limit::RateLimit,
         PartitionFilesSource,
@@ -65,14 +77,19 @@ use super::{
         catalog::CatalogPartitionSource, logging::LoggingPartitionSourceWrapper,
         metrics::MetricsPartitionSourceWrapper,
     },
+    pause_gate::{noop::NoopPauseGate, retry_budget::RetryBudgetPauseGateWrapper, PauseGate},
+    phase_metrics::PhaseMetrics,
     post_classification_partition_filter::{
-        logging::LoggingPostClassificationFilterWrapper,
-        metrics::MetricsPostClassificationFilterWrapper, possible_progress::PossibleProgressFilter,
-        PostClassificationPartitionFilter,
+        and::AndPostClassificationPartitionFilter, logging::LoggingPostClassificationFilterWrapper,
+        max_input_files::MaxInputFilesFilter, metrics::MetricsPostClassificationFilterWrapper,
+        possible_progress::PossibleProgressFilter, PostClassificationPartitionFilter,
     },
     round_info_source::{LevelBasedRoundInfo, LoggingRoundInfoWrapper, RoundInfoSource},
     round_split::many_files::ManyFilesRoundSplit,
-    scratchpad::{noop::NoopScratchpadGen, prod::ProdScratchpadGen, ScratchpadGen},
+    scratchpad::{
+        metrics::MetricsScratchpadGenWrapper, noop::NoopScratchpadGen, prod::ProdScratchpadGen,
+        ScratchpadGen,
+    },
     split_or_compact::{
         logging::LoggingSplitOrCompactWrapper, metrics::MetricsSplitOrCompactWrapper,
         split_compact::SplitCompact,
@@ -81,6 +98,18 @@ use super::{
     Components,
 };
 
+/// Size at which the compaction audit log file is rotated.
+const AUDIT_LOG_MAX_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Timeout of a single compaction webhook request.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Number of attempts to notify the compaction webhook.
+const WEBHOOK_MAX_ATTEMPTS: usize = 3;
+
+/// Number of compaction webhook notifications that may wait to be sent before further ones are dropped.
+const WEBHOOK_QUEUE_SIZE: usize = 1_000;
+
 /// Get hardcoded components.
 pub fn hardcoded_components(config: &Config) -> Arc<Components> {
     let scheduler = create_scheduler(
@@ -90,8 +119,9 @@ pub fn hardcoded_components(config: &Config) -> Arc<Components> {
         Arc::clone(&config.metric_registry),
         config.shadow_mode,
     );
+    let retry_budget = make_retry_budget(config);
     let (compaction_jobs_source, commit, compaction_job_done_sink) =
-        make_jobs_source_commit_jobs_sink(config, Arc::clone(&scheduler));
+        make_jobs_source_commit_jobs_sink(config, Arc::clone(&scheduler), retry_budget.clone());
 
     Arc::new(Components {
         compaction_job_stream: make_compaction_job_stream(config, compaction_jobs_source),
@@ -110,13 +140,19 @@ pub fn hardcoded_components(config: &Config) -> Arc<Components> {
         scratchpad_gen: make_scratchpad_gen(config),
         file_classifier: make_file_classifier(config),
         post_classification_partition_filter: make_post_classification_partition_filter(config),
-        changed_files_filter: Arc::new(LoggingChangedFiles::new()),
+        changed_files_filter: make_changed_files_filter(config),
+        pause_gate: make_pause_gate(config, retry_budget),
+        phase_metrics: Arc::new(PhaseMetrics::new(
+            &config.metric_registry,
+            Arc::clone(&config.time_provider),
+        )),
     })
 }
 
 fn make_jobs_source_commit_jobs_sink(
     config: &Config,
     scheduler: Arc<dyn Scheduler>,
+    retry_budget: Option<Arc<RetryBudget>>,
 ) -> (
     Arc<dyn CompactionJobsSource>,
     Arc<CommitToScheduler>,
@@ -137,29 +173,81 @@ fn make_jobs_source_commit_jobs_sink(
             compaction_job_done_sink,
             ErrorKind::variants()
                 .iter()
-                .filter(|kind| {
-                    // use explicit match statement so we never forget to add new variants
-                    match kind {
-                        ErrorKind::OutOfMemory | ErrorKind::Timeout | ErrorKind::Unknown => true,
-                        ErrorKind::ObjectStore => false,
-                    }
-                })
+                // retryable errors are NOT passed on, so the partition is not marked as skipped and will be retried
+                .filter(|kind| !kind.is_retryable())
                 .copied()
                 .collect(),
-            scheduler,
+            Arc::clone(&scheduler),
         ))
     };
-    let compaction_job_done_sink = Arc::new(LoggingCompactionJobDoneSinkWrapper::new(
+    let compaction_job_done_sink: Arc<dyn CompactionJobDoneSink> =
+        match config.max_consecutive_failures {
+            Some(threshold) => Arc::new(CircuitBreakerCompactionJobDoneSinkWrapper::new(
+                compaction_job_done_sink,
+                threshold,
+                scheduler,
+            )),
+            None => compaction_job_done_sink,
+        };
+    let compaction_job_done_sink: Arc<dyn CompactionJobDoneSink> = match retry_budget {
+        Some(retry_budget) => Arc::new(RetryBudgetCompactionJobDoneSinkWrapper::new(
+            compaction_job_done_sink,
+            retry_budget,
+        )),
+        None => compaction_job_done_sink,
+    };
+    let compaction_job_done_sink: Arc<dyn CompactionJobDoneSink> = Arc::new(
         MetricsCompactionJobDoneSinkWrapper::new(compaction_job_done_sink, &config.metric_registry),
+    );
+    let compaction_job_done_sink: Arc<dyn CompactionJobDoneSink> = match &config.audit_log_path {
+        Some(path) => Arc::new(AuditCompactionJobDoneSinkWrapper::new(
+            compaction_job_done_sink,
+            Arc::new(FileAuditLogTarget::new(path.clone(), AUDIT_LOG_MAX_BYTES)),
+            Arc::clone(&config.time_provider),
+        )),
+        None => compaction_job_done_sink,
+    };
+    let compaction_job_done_sink: Arc<dyn CompactionJobDoneSink> = match &config.webhook_url {
+        Some(url) => Arc::new(WebhookCompactionJobDoneSinkWrapper::new(
+            compaction_job_done_sink,
+            url.clone(),
+            // only notify about errors that mark the partition as skipped
+            ErrorKind::variants()
+                .iter()
+                .filter(|kind| !kind.is_retryable())
+                .copied()
+                .collect(),
+            WEBHOOK_TIMEOUT,
+            NonZeroUsize::new(WEBHOOK_MAX_ATTEMPTS).expect("not zero"),
+            NonZeroUsize::new(WEBHOOK_QUEUE_SIZE).expect("not zero"),
+            Arc::clone(&config.time_provider),
+        )),
+        None => compaction_job_done_sink,
+    };
+    let compaction_job_done_sink = Arc::new(LoggingCompactionJobDoneSinkWrapper::new(
+        compaction_job_done_sink,
     ));
 
+    let compaction_jobs_source =
+        RandomizeOrderCompactionJobsSourcesWrapper::new(compaction_jobs_source, 1234);
+    let compaction_jobs_source: Arc<dyn CompactionJobsSource> = if config.prioritize_by_l0_count {
+        // shuffle first so that partitions with the same priority are still processed in random order
+        Arc::new(PriorityCompactionJobsSourceWrapper::new(
+            compaction_jobs_source,
+            CatalogPartitionPrioritySource::new(
+                config.backoff_config.clone(),
+                Arc::clone(&config.catalog),
+            ),
+        ))
+    } else {
+        Arc::new(compaction_jobs_source)
+    };
+
     // Note: Place "not empty" wrapper at the very last so that the logging and metric wrapper work
     // even when there is not data.
-    let compaction_jobs_source =
-        LoggingCompactionJobsWrapper::new(MetricsCompactionJobsSourceWrapper::new(
-            RandomizeOrderCompactionJobsSourcesWrapper::new(compaction_jobs_source, 1234),
-            &config.metric_registry,
-        ));
+    let compaction_jobs_source = LoggingCompactionJobsWrapper::new(
+        MetricsCompactionJobsSourceWrapper::new(compaction_jobs_source, &config.metric_registry),
+    );
     let compaction_jobs_source: Arc<dyn CompactionJobsSource> = if config.process_once {
         // do not wrap into the "not empty" filter because we do NOT wanna throttle in this case
         // but just exit early
@@ -202,6 +290,17 @@ fn make_partition_info_source(config: &Config) -> Arc<dyn PartitionInfoSource> {
 }
 
 fn make_partition_files_source(config: &Config) -> Arc<dyn PartitionFilesSource> {
+    let source = make_catalog_partition_files_source(config);
+
+    // a cache hit would hide concurrent modifications from the changed files filter
+    if config.cache_partition_files && !config.fail_on_concurrent_modification {
+        Arc::new(CachingPartitionFilesSource::new(source))
+    } else {
+        source
+    }
+}
+
+fn make_catalog_partition_files_source(config: &Config) -> Arc<dyn PartitionFilesSource> {
     match config.max_partition_fetch_queries_per_second {
         Some(rps) => Arc::new(CatalogPartitionFilesSource::new(
             config.backoff_config.clone(),
@@ -306,7 +405,10 @@ fn make_parquet_files_sink(config: &Config) -> Arc<dyn ParquetFilesSink> {
             DedicatedExecParquetFileSinkWrapper::new(
                 ObjectStoreParquetFileSink::new(
                     config.exec.pool(),
-                    config.parquet_store_scratchpad.clone(),
+                    config
+                        .parquet_store_scratchpad
+                        .clone()
+                        .with_compression(config.parquet_compression),
                     Arc::clone(&config.time_provider),
                 ),
                 Arc::clone(&config.exec),
@@ -326,13 +428,22 @@ fn make_scratchpad_gen(config: &Config) -> Arc<dyn ScratchpadGen> {
             Arc::clone(config.parquet_store_real.object_store())
         };
 
-        Arc::new(ProdScratchpadGen::new(
+        let mut scratchpad_gen = ProdScratchpadGen::new(
             config.shadow_mode,
             config.partition_scratchpad_concurrency,
             config.backoff_config.clone(),
             Arc::clone(config.parquet_store_real.object_store()),
             Arc::clone(config.parquet_store_scratchpad.object_store()),
             scratchpad_store_output,
+        );
+        if config.verify_uploads {
+            scratchpad_gen = scratchpad_gen.with_verify_uploads();
+        }
+
+        Arc::new(MetricsScratchpadGenWrapper::new(
+            scratchpad_gen,
+            &config.metric_registry,
+            Arc::clone(&config.time_provider),
         ))
     }
 }
@@ -360,12 +471,50 @@ fn make_post_classification_partition_filter(
 ) -> Arc<dyn PostClassificationPartitionFilter> {
     let partition_resource_limit_conditions = "resource_limit_conditions";
 
+    let mut filters: Vec<Arc<dyn PostClassificationPartitionFilter>> = vec![Arc::new(
+        PossibleProgressFilter::new(config.max_compact_size_bytes()),
+    )];
+    if let Some(max_input_files) = config.max_input_files_per_plan {
+        filters.push(Arc::new(MaxInputFilesFilter::new(max_input_files)));
+    }
+
     Arc::new(LoggingPostClassificationFilterWrapper::new(
         MetricsPostClassificationFilterWrapper::new(
-            PossibleProgressFilter::new(config.max_compact_size_bytes()),
+            AndPostClassificationPartitionFilter::new(filters),
             &config.metric_registry,
             partition_resource_limit_conditions,
         ),
         partition_resource_limit_conditions,
     ))
 }
+
+fn make_changed_files_filter(config: &Config) -> Arc<dyn ChangedFilesFilter> {
+    if config.fail_on_concurrent_modification {
+        Arc::new(LoggingChangedFiles::new())
+    } else {
+        // only log concurrent modifications but commit anyway
+        Arc::new(IgnoreChangedFilesWrapper::new(LoggingChangedFiles::new()))
+    }
+}
+
+fn make_retry_budget(config: &Config) -> Option<Arc<RetryBudget>> {
+    config.object_store_error_budget.map(|budget| {
+        Arc::new(RetryBudget::new(HashMap::from([(
+            ErrorKind::ObjectStore,
+            budget,
+        )])))
+    })
+}
+
+fn make_pause_gate(config: &Config, retry_budget: Option<Arc<RetryBudget>>) -> Arc<dyn PauseGate> {
+    let pause_gate: Arc<dyn PauseGate> = Arc::new(NoopPauseGate::new());
+
+    match retry_budget {
+        Some(retry_budget) => Arc::new(RetryBudgetPauseGateWrapper::new(
+            pause_gate,
+            retry_budget,
+            config.object_store_error_backoff,
+        )),
+        None => pause_gate,
+    }
+}
@@ -133,4 +133,8 @@ where
 
         plan
     }
+
+    fn estimate_output_sizes(&self, partition: &PartitionInfo, files: &[ParquetFile]) -> Vec<u64> {
+        self.inner.estimate_output_sizes(partition, files)
+    }
 }
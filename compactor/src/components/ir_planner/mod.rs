@@ -49,4 +49,7 @@ pub trait IRPlanner: Debug + Display + Send + Sync {
         partition: Arc<PartitionInfo>,
         target_level: CompactionLevel,
     ) -> PlanIR;
+
+    /// Estimate the sizes of the output files of compacting the given files into one target level, in time order
+    fn estimate_output_sizes(&self, partition: &PartitionInfo, files: &[ParquetFile]) -> Vec<u64>;
 }
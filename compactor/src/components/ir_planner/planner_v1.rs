@@ -104,6 +104,55 @@ impl V1IRPlanner {
         split_times
     }
 
+    /// Times at which the output of compacting `files` is split, assuming the compaction is NOT forced into a single
+    /// file (see [`CompactReason::ManySmallFiles`]).
+    ///
+    /// Returns an empty list if the output is written to a single file.
+    fn output_split_times(&self, files: &[ParquetFile]) -> Vec<i64> {
+        // total file size is the sum of the file sizes of the files to compact
+        let total_size = files.iter().map(|f| f.file_size_bytes).sum::<i64>() as u64;
+        let (Some(min_time), Some(max_time)) = (
+            files.iter().map(|f| f.min_time.get()).min(),
+            files.iter().map(|f| f.max_time.get()).max(),
+        ) else {
+            return vec![];
+        };
+
+        let (small_cutoff_bytes, large_cutoff_bytes) = Self::cutoff_bytes(
+            self.max_desired_file_size_bytes,
+            self.percentage_max_file_size,
+        );
+        if total_size <= small_cutoff_bytes {
+            return vec![];
+        }
+
+        let split_times = if total_size <= large_cutoff_bytes {
+            // Split compaction into two files, the earlier of split_percentage amount of
+            // max_desired_file_size_bytes, the later of the rest
+            vec![min_time + ((max_time - min_time) * self.split_percentage as i64) / 100]
+        } else {
+            // Split compaction into multiple files
+            let chunk_times = files
+                .iter()
+                .map(|f| TimestampMinMax::new(f.min_time.get(), f.max_time.get()))
+                .collect::<Vec<_>>();
+            Self::compute_split_time(
+                chunk_times,
+                min_time,
+                max_time,
+                total_size,
+                self.max_desired_file_size_bytes,
+            )
+        };
+
+        if split_times.len() == 1 && split_times[0] == max_time {
+            // The split times might not have actually split anything, so in this case, compact
+            // everything into one file
+            return vec![];
+        }
+        split_times
+    }
+
     // time_range_present returns true if the given time range is included in any of the chunks.
     fn time_range_present(chunk_times: &[TimestampMinMax], min_time: i64, max_time: i64) -> bool {
         chunk_times
@@ -174,28 +223,11 @@ impl IRPlanner for V1IRPlanner {
         _partition: Arc<PartitionInfo>,
         target_level: CompactionLevel,
     ) -> PlanIR {
-        // gather data
-        // total file size is the sum of the file sizes of the files to compact
-        let total_size = files.iter().map(|f| f.file_size_bytes).sum::<i64>() as u64;
-        let chunk_times = files
-            .iter()
-            .map(|f| TimestampMinMax::new(f.min_time.get(), f.max_time.get()))
-            .collect::<Vec<_>>();
-        let min_time = files
-            .iter()
-            .map(|f| f.min_time.get())
-            .min()
-            .expect("at least one file");
-        let max_time = files
-            .iter()
-            .map(|f| f.max_time.get())
-            .max()
-            .expect("at least one file");
-
-        let (small_cutoff_bytes, large_cutoff_bytes) = Self::cutoff_bytes(
-            self.max_desired_file_size_bytes,
-            self.percentage_max_file_size,
-        );
+        let split_times = if reason == CompactReason::ManySmallFiles {
+            vec![]
+        } else {
+            self.output_split_times(&files)
+        };
 
         let files = files
             .into_iter()
@@ -215,45 +247,19 @@ impl IRPlanner for V1IRPlanner {
             .collect::<Vec<_>>();
 
         // Build logical compact plan
-        if total_size <= small_cutoff_bytes || reason == CompactReason::ManySmallFiles {
+        if split_times.is_empty() {
             PlanIR::Compact {
                 files,
                 target_level,
                 reason,
             }
         } else {
-            let split_times = if small_cutoff_bytes < total_size && total_size <= large_cutoff_bytes
-            {
-                // Split compaction into two files, the earlier of split_percentage amount of
-                // max_desired_file_size_bytes, the later of the rest
-                vec![min_time + ((max_time - min_time) * self.split_percentage as i64) / 100]
-            } else {
-                // Split compaction into multiple files
-                Self::compute_split_time(
-                    chunk_times,
-                    min_time,
-                    max_time,
-                    total_size,
-                    self.max_desired_file_size_bytes,
-                )
-            };
-
-            if split_times.is_empty() || (split_times.len() == 1 && split_times[0] == max_time) {
-                // The split times might not have actually split anything, so in this case, compact
-                // everything into one file
-                PlanIR::Compact {
-                    files,
-                    target_level,
-                    reason,
-                }
-            } else {
-                // split compact query plan to split the result into multiple files
-                PlanIR::Split {
-                    files,
-                    split_times,
-                    target_level,
-                    reason: SplitReason::CompactAndSplitOutput(reason),
-                }
+            // split compact query plan to split the result into multiple files
+            PlanIR::Split {
+                files,
+                split_times,
+                target_level,
+                reason: SplitReason::CompactAndSplitOutput(reason),
             }
         }
     }
@@ -287,6 +293,37 @@ impl IRPlanner for V1IRPlanner {
             reason,
         }
     }
+
+    /// Estimate the output file sizes, assuming the data is evenly distributed over the time range of the input
+    fn estimate_output_sizes(&self, _partition: &PartitionInfo, files: &[ParquetFile]) -> Vec<u64> {
+        let total_size = files.iter().map(|f| f.file_size_bytes).sum::<i64>() as u64;
+        let (Some(min_time), Some(max_time)) = (
+            files.iter().map(|f| f.min_time.get()).min(),
+            files.iter().map(|f| f.max_time.get()).max(),
+        ) else {
+            return vec![];
+        };
+
+        let split_times = self.output_split_times(files);
+        if split_times.is_empty() {
+            return vec![total_size];
+        }
+
+        // each output file covers the time range up to (and including) its split time, the last one up to max_time
+        let span = (max_time - min_time) as u128;
+        let mut sizes = Vec::with_capacity(split_times.len() + 1);
+        let mut start = min_time;
+        for end in split_times.into_iter().chain(std::iter::once(max_time)) {
+            sizes.push((total_size as u128 * (end - start) as u128 / span) as u64);
+            start = end;
+        }
+
+        // attribute rounding errors to the last file so the estimates add up to the input size
+        let estimated = sizes.iter().sum::<u64>();
+        *sizes.last_mut().expect("at least one output file") += total_size - estimated;
+
+        sizes
+    }
 }
 
 // Order of the chunk so they can be deduplicated correctly
@@ -335,6 +372,9 @@ mod tests {
     use super::*;
 
     use data_types::TimestampMinMax;
+    use iox_tests::ParquetFileBuilder;
+
+    use crate::test_utils::PartitionInfoBuilder;
 
     #[test]
     fn test_cutoff_bytes() {
@@ -490,4 +530,56 @@ mod tests {
         assert_eq!(result.len(), 1);
         assert_eq!(result[0], 34);
     }
+
+    #[test]
+    fn test_estimate_output_sizes() {
+        let planner = V1IRPlanner::new(100, 30, 80);
+        let partition = Arc::new(PartitionInfoBuilder::new().build());
+
+        let file = |id, size, min_time, max_time| {
+            ParquetFileBuilder::new(id)
+                .with_file_size_bytes(size)
+                .with_time_range(min_time, max_time)
+                .build()
+        };
+        let split_times = |files: &[ParquetFile]| {
+            let plan = planner.compact_plan(
+                files.to_vec(),
+                files.iter().map(ParquetFilePath::from).collect(),
+                files.iter().map(|f| f.object_store_id).collect(),
+                CompactReason::TotalSizeLessThanMaxCompactSize,
+                Arc::clone(&partition),
+                CompactionLevel::FileNonOverlapped,
+            );
+            match plan {
+                PlanIR::Compact { .. } => vec![],
+                PlanIR::Split { split_times, .. } => split_times,
+                PlanIR::None { .. } => panic!("unexpected plan: {plan:?}"),
+            }
+        };
+
+        // no input, no output
+        assert_eq!(planner.estimate_output_sizes(&partition, &[]), vec![]);
+
+        // too small to split
+        let files = vec![file(1, 10, 0, 50), file(2, 10, 40, 100)];
+        assert_eq!(split_times(&files), vec![]);
+        assert_eq!(planner.estimate_output_sizes(&partition, &files), vec![20]);
+
+        // split into two files at split_percentage of the time range
+        let files = vec![file(1, 40, 0, 50), file(2, 80, 40, 100)];
+        assert_eq!(split_times(&files), vec![80]);
+        assert_eq!(
+            planner.estimate_output_sizes(&partition, &files),
+            vec![96, 24]
+        );
+
+        // split into multiple files of at most max_desired_file_size_bytes
+        let files = vec![file(1, 100, 0, 50), file(2, 200, 40, 100)];
+        assert_eq!(split_times(&files), vec![34, 68]);
+        assert_eq!(
+            planner.estimate_output_sizes(&partition, &files),
+            vec![102, 102, 96]
+        );
+    }
 }
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{fmt::Display, sync::Arc};
 
 use self::{
     changed_files_filter::ChangedFilesFilter, commit::CommitToScheduler,
@@ -6,7 +6,7 @@ use self::{
     df_plan_exec::DataFusionPlanExec, df_planner::DataFusionPlanner, divide_initial::DivideInitial,
     file_classifier::FileClassifier, ir_planner::IRPlanner, parquet_files_sink::ParquetFilesSink,
     partition_files_source::PartitionFilesSource, partition_filter::PartitionFilter,
-    partition_info_source::PartitionInfoSource,
+    partition_info_source::PartitionInfoSource, pause_gate::PauseGate, phase_metrics::PhaseMetrics,
     post_classification_partition_filter::PostClassificationPartitionFilter,
     round_info_source::RoundInfoSource, round_split::RoundSplit, scratchpad::ScratchpadGen,
 };
@@ -31,6 +31,8 @@ pub mod partition_files_source;
 pub mod partition_filter;
 pub mod partition_info_source;
 pub mod partition_source;
+pub mod pause_gate;
+pub mod phase_metrics;
 pub mod post_classification_partition_filter;
 pub mod report;
 pub mod round_info_source;
@@ -79,4 +81,72 @@ pub struct Components {
     pub file_classifier: Arc<dyn FileClassifier>,
     /// Check for other processes modifying files.
     pub changed_files_filter: Arc<dyn ChangedFilesFilter>,
+    /// Pauses compaction before a partition is compacted.
+    pub pause_gate: Arc<dyn PauseGate>,
+    /// Records the time spent in each phase of compacting a branch.
+    pub phase_metrics: Arc<PhaseMetrics>,
+}
+
+impl Display for Components {
+    /// Renders the assembled pipeline, one component per line.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // use struct unpack so we don't forget any members
+        let Self {
+            compaction_job_stream,
+            partition_info_source,
+            partition_files_source,
+            round_info_source,
+            partition_filter,
+            post_classification_partition_filter,
+            compaction_job_done_sink,
+            commit,
+            ir_planner,
+            df_planner,
+            df_plan_exec,
+            parquet_files_sink,
+            round_split,
+            divide_initial,
+            scratchpad_gen,
+            file_classifier,
+            changed_files_filter,
+            pause_gate,
+            phase_metrics,
+        } = self;
+
+        let components: [(&str, &dyn Display); 19] = [
+            ("compaction_job_stream", compaction_job_stream),
+            ("partition_info_source", partition_info_source),
+            ("partition_files_source", partition_files_source),
+            ("round_info_source", round_info_source),
+            ("partition_filter", partition_filter),
+            (
+                "post_classification_partition_filter",
+                post_classification_partition_filter,
+            ),
+            ("compaction_job_done_sink", compaction_job_done_sink),
+            ("commit", commit),
+            ("ir_planner", ir_planner),
+            ("df_planner", df_planner),
+            ("df_plan_exec", df_plan_exec),
+            ("parquet_files_sink", parquet_files_sink),
+            ("round_split", round_split),
+            ("divide_initial", divide_initial),
+            ("scratchpad_gen", scratchpad_gen),
+            ("file_classifier", file_classifier),
+            ("changed_files_filter", changed_files_filter),
+            ("pause_gate", pause_gate),
+            ("phase_metrics", phase_metrics),
+        ];
+
+        write!(f, "components")?;
+        for (i, (name, component)) in components.iter().enumerate() {
+            let branch = if i + 1 == components.len() {
+                "└──"
+            } else {
+                "├──"
+            };
+            write!(f, "\n{branch} {name}: {component}")?;
+        }
+        Ok(())
+    }
 }
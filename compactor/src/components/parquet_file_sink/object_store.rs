@@ -104,3 +104,66 @@ impl ParquetFileSink for ObjectStoreParquetFileSink {
         Ok(Some(parquet_file))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use data_types::CompactionLevel;
+    use datafusion::{
+        arrow::{datatypes::DataType, record_batch::RecordBatch},
+        execution::memory_pool::UnboundedMemoryPool,
+        physical_plan::stream::RecordBatchStreamAdapter,
+    };
+    use iox_time::MockProvider;
+    use object_store::memory::InMemory;
+    use parquet_file::storage::StorageId;
+    use schema::SchemaBuilder;
+
+    use crate::test_utils::PartitionInfoBuilder;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_store_no_rows() {
+        let sink = ObjectStoreParquetFileSink::new(
+            Arc::new(UnboundedMemoryPool::default()),
+            ParquetStorage::new(Arc::new(InMemory::new()), StorageId::from("iox")),
+            Arc::new(MockProvider::new(Time::from_timestamp_nanos(0))),
+        );
+
+        let schema = SchemaBuilder::new()
+            .field("f", DataType::Int64)
+            .unwrap()
+            .timestamp()
+            .build()
+            .unwrap()
+            .as_arrow();
+        let partition = Arc::new(PartitionInfoBuilder::new().build());
+        let level = CompactionLevel::FileNonOverlapped;
+        let max_l0_created_at = Time::from_timestamp_nanos(1);
+
+        // a plan that produces no batches at all
+        let stream = Box::pin(RecordBatchStreamAdapter::new(
+            Arc::clone(&schema),
+            futures::stream::empty(),
+        ));
+        assert_eq!(
+            sink.store(stream, Arc::clone(&partition), level, max_l0_created_at)
+                .await
+                .unwrap(),
+            None,
+        );
+
+        // a plan that produces only empty batches, e.g. because all input rows were removed
+        let record_batch = RecordBatch::new_empty(Arc::clone(&schema));
+        let stream = Box::pin(RecordBatchStreamAdapter::new(
+            Arc::clone(&schema),
+            futures::stream::once(async move { Ok(record_batch) }),
+        ));
+        assert_eq!(
+            sink.store(stream, Arc::clone(&partition), level, max_l0_created_at)
+                .await
+                .unwrap(),
+            None,
+        );
+    }
+}
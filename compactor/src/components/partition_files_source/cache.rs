@@ -0,0 +1,127 @@
+use std::{collections::HashMap, fmt::Display, sync::Arc};
+
+use async_trait::async_trait;
+use data_types::{ParquetFile, PartitionId};
+use parking_lot::Mutex;
+
+use super::PartitionFilesSource;
+
+/// Caches the files of a partition until they are [invalidated](PartitionFilesSource::invalidate).
+///
+/// The driver invalidates a partition after every commit and at the end of every compaction job, so the cache only
+/// saves the catalog query when the files are re-read within a job before anything was committed. Changes made by
+/// OTHER processes in the meantime are NOT seen.
+#[derive(Debug)]
+pub struct CachingPartitionFilesSource {
+    inner: Arc<dyn PartitionFilesSource>,
+    cache: Mutex<HashMap<PartitionId, Vec<ParquetFile>>>,
+}
+
+impl CachingPartitionFilesSource {
+    pub fn new(inner: Arc<dyn PartitionFilesSource>) -> Self {
+        Self {
+            inner,
+            cache: Default::default(),
+        }
+    }
+}
+
+impl Display for CachingPartitionFilesSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cache({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl PartitionFilesSource for CachingPartitionFilesSource {
+    async fn fetch(&self, partition_id: PartitionId) -> Vec<ParquetFile> {
+        if let Some(files) = self.cache.lock().get(&partition_id) {
+            return files.clone();
+        }
+
+        let files = self.inner.fetch(partition_id).await;
+        self.cache.lock().insert(partition_id, files.clone());
+        files
+    }
+
+    async fn invalidate(&self, partition_id: PartitionId) {
+        self.cache.lock().remove(&partition_id);
+        self.inner.invalidate(partition_id).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use iox_tests::ParquetFileBuilder;
+
+    use super::*;
+
+    /// Returns one file per call, counting the calls.
+    #[derive(Debug, Default)]
+    struct CountingPartitionFilesSource {
+        calls: AtomicUsize,
+    }
+
+    impl Display for CountingPartitionFilesSource {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "counting")
+        }
+    }
+
+    #[async_trait]
+    impl PartitionFilesSource for CountingPartitionFilesSource {
+        async fn fetch(&self, _partition_id: PartitionId) -> Vec<ParquetFile> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            vec![ParquetFileBuilder::new(call as i64).build()]
+        }
+
+        async fn invalidate(&self, _partition_id: PartitionId) {}
+    }
+
+    #[test]
+    fn test_display() {
+        let source =
+            CachingPartitionFilesSource::new(Arc::new(CountingPartitionFilesSource::default()));
+        assert_eq!(source.to_string(), "cache(counting)");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_without_commit_queries_once() {
+        let inner = Arc::new(CountingPartitionFilesSource::default());
+        let source = CachingPartitionFilesSource::new(Arc::clone(&inner) as _);
+        let p_1 = PartitionId::new(1);
+
+        let files_1 = source.fetch(p_1).await;
+        let files_2 = source.fetch(p_1).await;
+        assert_eq!(files_1, files_2);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+
+        // partitions are cached separately
+        source.fetch(PartitionId::new(2)).await;
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_invalidate() {
+        let inner = Arc::new(CountingPartitionFilesSource::default());
+        let source = CachingPartitionFilesSource::new(Arc::clone(&inner) as _);
+        let p_1 = PartitionId::new(1);
+        let p_2 = PartitionId::new(2);
+
+        let files_p1_before = source.fetch(p_1).await;
+        let files_p2_before = source.fetch(p_2).await;
+
+        // e.g. after a commit
+        source.invalidate(p_1).await;
+
+        let files_p1_after = source.fetch(p_1).await;
+        assert_ne!(files_p1_before, files_p1_after);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+
+        // other partitions are untouched
+        assert_eq!(source.fetch(p_2).await, files_p2_before);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+    }
+}
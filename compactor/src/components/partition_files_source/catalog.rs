@@ -100,6 +100,10 @@ where
             .await
             .expect("retry forever")
     }
+
+    async fn invalidate(&self, _partition_id: PartitionId) {
+        // no cache
+    }
 }
 
 #[cfg(test)]
@@ -47,6 +47,10 @@ impl PartitionFilesSource for MockPartitionFilesSource {
             .and_then(|partition_hash_id| self.file_lookup.get(partition_hash_id).cloned())
             .unwrap_or_default()
     }
+
+    async fn invalidate(&self, _partition_id: PartitionId) {
+        // no cache
+    }
 }
 
 #[cfg(test)]
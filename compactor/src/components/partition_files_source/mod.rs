@@ -3,6 +3,7 @@ use std::fmt::{Debug, Display};
 use async_trait::async_trait;
 use data_types::{ParquetFile, PartitionId};
 
+pub mod cache;
 pub mod catalog;
 pub mod mock;
 pub mod rate_limit;
@@ -16,4 +17,7 @@ pub trait PartitionFilesSource: Debug + Display + Send + Sync {
     ///
     /// This method performs retries.
     async fn fetch(&self, partition: PartitionId) -> Vec<ParquetFile>;
+
+    /// Forget anything cached about the given partition, e.g. because its files were changed by a commit.
+    async fn invalidate(&self, partition: PartitionId);
 }
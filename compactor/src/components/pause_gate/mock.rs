@@ -0,0 +1,73 @@
+use std::fmt::Display;
+
+use async_trait::async_trait;
+use tokio::sync::watch;
+
+use super::PauseGate;
+
+/// A gate that is opened and closed by hand, for use in testing.
+#[derive(Debug)]
+pub struct MockPauseGate {
+    open: watch::Sender<bool>,
+}
+
+impl MockPauseGate {
+    pub fn new(open: bool) -> Self {
+        Self {
+            open: watch::channel(open).0,
+        }
+    }
+
+    /// Let compaction proceed.
+    pub fn open(&self) {
+        self.open.send_replace(true);
+    }
+
+    /// Pause compaction.
+    pub fn close(&self) {
+        self.open.send_replace(false);
+    }
+}
+
+impl Display for MockPauseGate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "mock")
+    }
+}
+
+#[async_trait]
+impl PauseGate for MockPauseGate {
+    async fn wait(&self) {
+        self.open
+            .subscribe()
+            .wait_for(|open| *open)
+            .await
+            .expect("sender is owned by self");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(MockPauseGate::new(true).to_string(), "mock");
+    }
+
+    #[tokio::test]
+    async fn test_open_close() {
+        let gate = MockPauseGate::new(true);
+        gate.wait().await;
+
+        gate.close();
+        tokio::time::timeout(Duration::from_millis(10), gate.wait())
+            .await
+            .unwrap_err();
+
+        gate.open();
+        gate.wait().await;
+    }
+}
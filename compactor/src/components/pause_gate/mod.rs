@@ -0,0 +1,19 @@
+use std::fmt::{Debug, Display};
+
+use async_trait::async_trait;
+
+pub mod mock;
+pub mod noop;
+pub mod retry_budget;
+
+/// Pauses compaction, e.g. to avoid competing with ingest for object store bandwidth.
+///
+/// The gate is consulted before a partition is compacted, so partitions that are already being compacted are NOT
+/// interrupted.
+#[async_trait]
+pub trait PauseGate: Debug + Display + Send + Sync {
+    /// Wait until compaction may proceed.
+    ///
+    /// Returns immediately if compaction is not paused.
+    async fn wait(&self);
+}
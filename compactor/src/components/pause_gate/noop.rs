@@ -0,0 +1,41 @@
+use std::fmt::Display;
+
+use async_trait::async_trait;
+
+use super::PauseGate;
+
+/// Never pauses compaction.
+#[derive(Debug, Default)]
+pub struct NoopPauseGate;
+
+impl NoopPauseGate {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Display for NoopPauseGate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "noop")
+    }
+}
+
+#[async_trait]
+impl PauseGate for NoopPauseGate {
+    async fn wait(&self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(NoopPauseGate::new().to_string(), "noop");
+    }
+
+    #[tokio::test]
+    async fn test_wait() {
+        NoopPauseGate::new().wait().await;
+    }
+}
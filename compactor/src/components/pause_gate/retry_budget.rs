@@ -0,0 +1,202 @@
+use std::{
+    fmt::Display,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use observability_deps::tracing::warn;
+use tokio::time::Instant;
+
+use crate::components::compaction_job_done_sink::retry_budget::RetryBudget;
+
+use super::PauseGate;
+
+/// Backs off for a fixed duration once a [`RetryBudget`] is exhausted, then starts a new budget.
+///
+/// The back off is shared by all callers: the first one that sees the exhausted budget sets a deadline, and all
+/// callers wait until that deadline instead of each waiting for the full duration.
+#[derive(Debug)]
+pub struct RetryBudgetPauseGateWrapper<T>
+where
+    T: PauseGate,
+{
+    budget: Arc<RetryBudget>,
+    backoff: Duration,
+    backoff_until: Mutex<Option<Instant>>,
+    inner: T,
+}
+
+impl<T> RetryBudgetPauseGateWrapper<T>
+where
+    T: PauseGate,
+{
+    pub fn new(inner: T, budget: Arc<RetryBudget>, backoff: Duration) -> Self {
+        Self {
+            budget,
+            backoff,
+            backoff_until: Mutex::new(None),
+            inner,
+        }
+    }
+}
+
+impl<T> Display for RetryBudgetPauseGateWrapper<T>
+where
+    T: PauseGate,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "retry_budget({:?}, {})", self.backoff, self.inner)
+    }
+}
+
+#[async_trait]
+impl<T> PauseGate for RetryBudgetPauseGateWrapper<T>
+where
+    T: PauseGate,
+{
+    async fn wait(&self) {
+        self.inner.wait().await;
+
+        let (until, started) = {
+            let mut backoff_until = self.backoff_until.lock().expect("not poisoned");
+            match *backoff_until {
+                Some(until) => (until, false),
+                None => {
+                    let Some(kind) = self.budget.exhausted() else {
+                        return;
+                    };
+                    warn!(
+                        %kind,
+                        backoff = ?self.backoff,
+                        "Retry budget exhausted, backing off",
+                    );
+                    let until = Instant::now() + self.backoff;
+                    *backoff_until = Some(until);
+                    (until, true)
+                }
+            }
+        };
+
+        tokio::time::sleep_until(until).await;
+
+        if started {
+            self.budget.reset();
+            *self.backoff_until.lock().expect("not poisoned") = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, num::NonZeroUsize};
+
+    use compactor_scheduler::CompactionJob;
+    use data_types::PartitionId;
+
+    use crate::{
+        components::{
+            compaction_job_done_sink::{
+                mock::MockCompactionJobDoneSink,
+                retry_budget::RetryBudgetCompactionJobDoneSinkWrapper, CompactionJobDoneSink,
+            },
+            pause_gate::noop::NoopPauseGate,
+        },
+        error::{ErrorKind, SimpleError},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let gate = RetryBudgetPauseGateWrapper::new(
+            NoopPauseGate::new(),
+            Arc::new(RetryBudget::new(HashMap::new())),
+            Duration::from_secs(60),
+        );
+        assert_eq!(gate.to_string(), "retry_budget(60s, noop)");
+    }
+
+    #[tokio::test]
+    async fn test_backoff() {
+        let budget = Arc::new(RetryBudget::new(HashMap::from([(
+            ErrorKind::ObjectStore,
+            NonZeroUsize::new(1).unwrap(),
+        )])));
+        let sink = RetryBudgetCompactionJobDoneSinkWrapper::new(
+            MockCompactionJobDoneSink::new(),
+            Arc::clone(&budget),
+        );
+        let backoff = Duration::from_millis(50);
+        let gate =
+            RetryBudgetPauseGateWrapper::new(NoopPauseGate::new(), Arc::clone(&budget), backoff);
+
+        // budget left
+        tokio::time::timeout(backoff / 2, gate.wait())
+            .await
+            .expect("no backoff");
+
+        for id in 1..=2 {
+            sink.record(
+                CompactionJob::new(PartitionId::new(id)),
+                Err(Box::new(SimpleError::new(ErrorKind::ObjectStore, "failed"))),
+            )
+            .await
+            .unwrap();
+        }
+        assert_eq!(budget.exhausted(), Some(ErrorKind::ObjectStore));
+
+        let start = tokio::time::Instant::now();
+        gate.wait().await;
+        assert!(start.elapsed() >= backoff);
+
+        // new budget
+        assert_eq!(budget.exhausted(), None);
+        tokio::time::timeout(backoff / 2, gate.wait())
+            .await
+            .expect("no backoff");
+    }
+
+    #[tokio::test]
+    async fn test_backoff_shared() {
+        let budget = Arc::new(RetryBudget::new(HashMap::from([(
+            ErrorKind::ObjectStore,
+            NonZeroUsize::new(1).unwrap(),
+        )])));
+        let sink = RetryBudgetCompactionJobDoneSinkWrapper::new(
+            MockCompactionJobDoneSink::new(),
+            Arc::clone(&budget),
+        );
+        let backoff = Duration::from_millis(200);
+        let gate = Arc::new(RetryBudgetPauseGateWrapper::new(
+            NoopPauseGate::new(),
+            Arc::clone(&budget),
+            backoff,
+        ));
+
+        for id in 1..=2 {
+            sink.record(
+                CompactionJob::new(PartitionId::new(id)),
+                Err(Box::new(SimpleError::new(ErrorKind::ObjectStore, "failed"))),
+            )
+            .await
+            .unwrap();
+        }
+
+        let start = Instant::now();
+        let first = tokio::spawn({
+            let gate = Arc::clone(&gate);
+            async move { gate.wait().await }
+        });
+        tokio::time::sleep(backoff / 2).await;
+
+        // a later caller only waits for the rest of the running back off
+        let second_start = Instant::now();
+        gate.wait().await;
+        assert!(start.elapsed() >= backoff);
+        assert!(second_start.elapsed() < backoff);
+
+        first.await.unwrap();
+        assert_eq!(budget.exhausted(), None);
+    }
+}
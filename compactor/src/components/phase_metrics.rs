@@ -0,0 +1,148 @@
+use std::{fmt::Display, sync::Arc, time::Duration};
+
+use iox_time::{Time, TimeProvider};
+use metric::{DurationHistogram, Registry};
+
+const METRIC_NAME_PHASE_DURATION: &str = "iox_compactor_phase_duration";
+
+/// Phase of compacting a single branch, see [`PhaseMetrics`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DriverPhase {
+    /// Classification of the branch files.
+    Classify,
+
+    /// Execution of the compaction / split plans.
+    RunPlans,
+
+    /// Upload of the created files to the object store.
+    Upload,
+
+    /// Catalog commit of the created, deleted and upgraded files.
+    Commit,
+}
+
+impl DriverPhase {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Classify => "classify",
+            Self::RunPlans => "run_plans",
+            Self::Upload => "upload",
+            Self::Commit => "commit",
+        }
+    }
+}
+
+/// Records how long the driver spent in each [`DriverPhase`] of a branch.
+///
+/// Phases that are repeated for every chunk of plans are summed up, so every successfully compacted branch adds one
+/// observation per phase. Phases that fail are not recorded.
+#[derive(Debug)]
+pub struct PhaseMetrics {
+    classify: DurationHistogram,
+    run_plans: DurationHistogram,
+    upload: DurationHistogram,
+    commit: DurationHistogram,
+    time_provider: Arc<dyn TimeProvider>,
+}
+
+impl PhaseMetrics {
+    pub fn new(registry: &Registry, time_provider: Arc<dyn TimeProvider>) -> Self {
+        let metric = registry.register_metric::<DurationHistogram>(
+            METRIC_NAME_PHASE_DURATION,
+            "Duration spent in each phase of compacting a branch",
+        );
+        let recorder = |phase: DriverPhase| metric.recorder(&[("phase", phase.name())]);
+
+        Self {
+            classify: recorder(DriverPhase::Classify),
+            run_plans: recorder(DriverPhase::RunPlans),
+            upload: recorder(DriverPhase::Upload),
+            commit: recorder(DriverPhase::Commit),
+            time_provider,
+        }
+    }
+
+    /// Start time of a phase, to be passed to [`elapsed`](Self::elapsed).
+    pub fn start(&self) -> Time {
+        self.time_provider.now()
+    }
+
+    /// Time passed since `start`.
+    pub fn elapsed(&self, start: Time) -> Duration {
+        self.time_provider
+            .now()
+            .checked_duration_since(start)
+            .unwrap_or_default()
+    }
+
+    /// Record the total `duration` of `phase` for one branch.
+    pub fn record(&self, phase: DriverPhase, duration: Duration) {
+        let histogram = match phase {
+            DriverPhase::Classify => &self.classify,
+            DriverPhase::RunPlans => &self.run_plans,
+            DriverPhase::Upload => &self.upload,
+            DriverPhase::Commit => &self.commit,
+        };
+        histogram.record(duration);
+    }
+}
+
+impl Display for PhaseMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "metrics")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iox_time::MockProvider;
+    use metric::{assert_histogram, Attributes};
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let metrics = PhaseMetrics::new(
+            &Registry::new(),
+            Arc::new(MockProvider::new(Time::from_timestamp_nanos(0))),
+        );
+        assert_eq!(metrics.to_string(), "metrics");
+    }
+
+    #[test]
+    fn test_record() {
+        let registry = Registry::new();
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_nanos(0)));
+        let metrics = PhaseMetrics::new(&registry, Arc::clone(&time_provider) as _);
+
+        let start = metrics.start();
+        time_provider.inc(Duration::from_secs(2));
+        metrics.record(DriverPhase::RunPlans, metrics.elapsed(start));
+        metrics.record(DriverPhase::RunPlans, Duration::from_secs(1));
+        metrics.record(DriverPhase::Commit, Duration::from_secs(5));
+
+        assert_histogram!(
+            registry,
+            DurationHistogram,
+            METRIC_NAME_PHASE_DURATION,
+            labels = Attributes::from(&[("phase", "run_plans")]),
+            samples = 2,
+            sum = Duration::from_secs(3),
+        );
+        assert_histogram!(
+            registry,
+            DurationHistogram,
+            METRIC_NAME_PHASE_DURATION,
+            labels = Attributes::from(&[("phase", "commit")]),
+            samples = 1,
+            sum = Duration::from_secs(5),
+        );
+        assert_histogram!(
+            registry,
+            DurationHistogram,
+            METRIC_NAME_PHASE_DURATION,
+            labels = Attributes::from(&[("phase", "classify")]),
+            samples = 0,
+        );
+    }
+}
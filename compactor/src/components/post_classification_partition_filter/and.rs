@@ -0,0 +1,100 @@
+use std::{fmt::Display, sync::Arc};
+
+use async_trait::async_trait;
+
+use crate::{error::DynError, file_classification::FilesForProgress, PartitionInfo};
+
+use super::PostClassificationPartitionFilter;
+
+#[derive(Debug)]
+pub struct AndPostClassificationPartitionFilter {
+    filters: Vec<Arc<dyn PostClassificationPartitionFilter>>,
+}
+
+impl AndPostClassificationPartitionFilter {
+    pub fn new(filters: Vec<Arc<dyn PostClassificationPartitionFilter>>) -> Self {
+        Self { filters }
+    }
+}
+
+impl Display for AndPostClassificationPartitionFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "and([")?;
+        for (i, sub) in self.filters.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{sub}")?;
+        }
+        write!(f, "])")
+    }
+}
+
+#[async_trait]
+impl PostClassificationPartitionFilter for AndPostClassificationPartitionFilter {
+    async fn apply(
+        &self,
+        partition_info: &PartitionInfo,
+        files_to_make_progress_on: &FilesForProgress,
+    ) -> Result<bool, DynError> {
+        for filter in &self.filters {
+            if !filter
+                .apply(partition_info, files_to_make_progress_on)
+                .await?
+            {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        components::post_classification_partition_filter::mock::MockPostClassificationPartitionFilter,
+        test_utils::PartitionInfoBuilder,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let filter = AndPostClassificationPartitionFilter::new(vec![
+            Arc::new(MockPostClassificationPartitionFilter::new(vec![])),
+            Arc::new(MockPostClassificationPartitionFilter::new(vec![])),
+        ]);
+
+        assert_eq!(format!("{filter}"), "and([mock, mock])");
+    }
+
+    #[tokio::test]
+    async fn test_apply() {
+        let p_info = Arc::new(PartitionInfoBuilder::new().build());
+        let files = FilesForProgress::empty();
+
+        let filter = AndPostClassificationPartitionFilter::new(vec![
+            Arc::new(MockPostClassificationPartitionFilter::new(vec![Ok(true)])),
+            Arc::new(MockPostClassificationPartitionFilter::new(vec![Ok(true)])),
+        ]);
+        assert!(filter.apply(&p_info, &files).await.unwrap());
+
+        // short-circuits on the first rejection
+        let filter = AndPostClassificationPartitionFilter::new(vec![
+            Arc::new(MockPostClassificationPartitionFilter::new(vec![Ok(false)])),
+            Arc::new(MockPostClassificationPartitionFilter::new(vec![])),
+        ]);
+        assert!(!filter.apply(&p_info, &files).await.unwrap());
+
+        let filter = AndPostClassificationPartitionFilter::new(vec![
+            Arc::new(MockPostClassificationPartitionFilter::new(vec![Ok(true)])),
+            Arc::new(MockPostClassificationPartitionFilter::new(vec![Err(
+                "problem".into(),
+            )])),
+        ]);
+        assert_eq!(
+            filter.apply(&p_info, &files).await.unwrap_err().to_string(),
+            "problem"
+        );
+    }
+}
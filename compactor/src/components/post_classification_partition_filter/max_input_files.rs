@@ -0,0 +1,142 @@
+use std::{fmt::Display, num::NonZeroUsize};
+
+use async_trait::async_trait;
+
+use crate::{
+    error::{DynError, ErrorKind, SimpleError},
+    file_classification::FilesForProgress,
+    PartitionInfo,
+};
+
+use super::PostClassificationPartitionFilter;
+
+/// Rejects classifications that would open more than `max_input_files` files in a single plan, e.g. to prevent
+/// running out of file descriptors.
+///
+/// Files to compact are read by a single plan while every file to split gets its own plan, so only the former is
+/// limited.
+#[derive(Debug)]
+pub struct MaxInputFilesFilter {
+    max_input_files: NonZeroUsize,
+}
+
+impl MaxInputFilesFilter {
+    pub fn new(max_input_files: NonZeroUsize) -> Self {
+        Self { max_input_files }
+    }
+}
+
+impl Display for MaxInputFilesFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "max_input_files({})", self.max_input_files)
+    }
+}
+
+#[async_trait]
+impl PostClassificationPartitionFilter for MaxInputFilesFilter {
+    async fn apply(
+        &self,
+        partition_info: &PartitionInfo,
+        files_to_make_progress_on: &FilesForProgress,
+    ) -> Result<bool, DynError> {
+        let num_files = files_to_make_progress_on
+            .split_or_compact
+            .num_files_to_compact();
+
+        if num_files <= self.max_input_files.get() {
+            Ok(true)
+        } else {
+            Err(SimpleError::new(
+                ErrorKind::Unknown,
+                format!(
+                    "partition {} would compact {} files in a single plan, which exceeds the limit of {} \
+                    input files per plan",
+                    partition_info.partition_id, num_files, self.max_input_files
+                ),
+            )
+            .into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        error::ErrorKindExt,
+        file_classification::{CompactReason, FileToSplit, FilesToSplitOrCompact, SplitReason},
+        test_utils::PartitionInfoBuilder,
+    };
+    use iox_tests::ParquetFileBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            MaxInputFilesFilter::new(NonZeroUsize::new(10).unwrap()).to_string(),
+            "max_input_files(10)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply() {
+        let filter = MaxInputFilesFilter::new(NonZeroUsize::new(2).unwrap());
+        let p_info = Arc::new(PartitionInfoBuilder::new().with_partition_id(1).build());
+        let f1 = ParquetFileBuilder::new(1).build();
+        let f2 = ParquetFileBuilder::new(2).build();
+        let f3 = ParquetFileBuilder::new(3).build();
+
+        // nothing to do
+        assert!(filter
+            .apply(&p_info, &FilesForProgress::empty())
+            .await
+            .unwrap());
+
+        // at the limit
+        let files_for_progress = FilesForProgress {
+            upgrade: vec![f3.clone()],
+            split_or_compact: FilesToSplitOrCompact::Compact(
+                vec![f1.clone(), f2.clone()],
+                CompactReason::ManySmallFiles,
+            ),
+        };
+        assert!(filter.apply(&p_info, &files_for_progress).await.unwrap());
+
+        // splits use one plan per file
+        let files_for_progress = FilesForProgress {
+            upgrade: vec![],
+            split_or_compact: FilesToSplitOrCompact::Split(
+                [&f1, &f2, &f3]
+                    .into_iter()
+                    .map(|f| FileToSplit {
+                        file: f.clone(),
+                        split_times: vec![5],
+                    })
+                    .collect(),
+                SplitReason::ReduceLargeFileSize,
+            ),
+        };
+        assert!(filter.apply(&p_info, &files_for_progress).await.unwrap());
+
+        // over the limit
+        let files_for_progress = FilesForProgress {
+            upgrade: vec![],
+            split_or_compact: FilesToSplitOrCompact::Compact(
+                vec![f1, f2, f3],
+                CompactReason::ManySmallFiles,
+            ),
+        };
+        let err = filter
+            .apply(&p_info, &files_for_progress)
+            .await
+            .unwrap_err();
+        assert_eq!(err.classify(), ErrorKind::Unknown);
+        assert_eq!(
+            err.to_string(),
+            "partition 1 would compact 3 files in a single plan, which exceeds the limit of 2 \
+            input files per plan"
+        );
+    }
+}
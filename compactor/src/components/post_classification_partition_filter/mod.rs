@@ -4,7 +4,9 @@ use async_trait::async_trait;
 
 use crate::{error::DynError, file_classification::FilesForProgress, PartitionInfo};
 
+pub mod and;
 pub mod logging;
+pub mod max_input_files;
 pub mod metrics;
 pub mod mock;
 pub mod possible_progress;
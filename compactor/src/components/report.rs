@@ -24,11 +24,14 @@ pub fn log_config(config: &Config) {
         partition_concurrency,
         df_concurrency,
         partition_scratchpad_concurrency,
+        upload_concurrency,
         max_desired_file_size_bytes,
         percentage_max_file_size,
         split_percentage,
         partition_timeout,
+        partition_timeout_overrides,
         shadow_mode,
+        fail_on_concurrent_modification,
         enable_scratchpad,
         min_num_l1_files_to_compact,
         process_once,
@@ -38,6 +41,18 @@ pub fn log_config(config: &Config) {
         max_num_columns_per_table,
         max_num_files_per_plan,
         max_partition_fetch_queries_per_second,
+        audit_log_path,
+        max_consecutive_failures,
+        fetch_batch_size,
+        max_rounds,
+        webhook_url,
+        max_input_files_per_plan,
+        parquet_compression,
+        prioritize_by_l0_count,
+        verify_uploads,
+        cache_partition_files,
+        object_store_error_budget,
+        object_store_error_backoff,
     } = &config;
 
     let parquet_files_sink_override = parquet_files_sink_override
@@ -56,11 +71,14 @@ pub fn log_config(config: &Config) {
         partition_concurrency=partition_concurrency.get(),
         df_concurrency=df_concurrency.get(),
         partition_scratchpad_concurrency=partition_scratchpad_concurrency.get(),
+        upload_concurrency=upload_concurrency.get(),
         max_desired_file_size_bytes,
         percentage_max_file_size,
         split_percentage,
         partition_timeout_secs=partition_timeout.as_secs_f32(),
+        ?partition_timeout_overrides,
         shadow_mode,
+        fail_on_concurrent_modification,
         enable_scratchpad,
         min_num_l1_files_to_compact,
         process_once,
@@ -70,51 +88,23 @@ pub fn log_config(config: &Config) {
         max_num_columns_per_table,
         max_num_files_per_plan,
         max_partition_fetch_queries_per_second,
+        ?audit_log_path,
+        ?max_consecutive_failures,
+        ?fetch_batch_size,
+        ?max_rounds,
+        ?webhook_url,
+        ?max_input_files_per_plan,
+        ?parquet_compression,
+        prioritize_by_l0_count,
+        verify_uploads,
+        cache_partition_files,
+        ?object_store_error_budget,
+        object_store_error_backoff_secs = object_store_error_backoff.as_secs_f32(),
         "config",
     );
 }
 
 /// Log component system at info level.
 pub fn log_components(components: &Components) {
-    // use struct unpack so we don't forget any members
-    let Components {
-        compaction_job_stream,
-        partition_info_source,
-        partition_files_source,
-        round_info_source,
-        partition_filter,
-        post_classification_partition_filter: partition_too_large_to_compact_filter,
-        compaction_job_done_sink,
-        commit,
-        ir_planner,
-        df_planner,
-        df_plan_exec,
-        parquet_files_sink,
-        round_split,
-        divide_initial,
-        scratchpad_gen,
-        file_classifier,
-        changed_files_filter,
-    } = components;
-
-    info!(
-        %compaction_job_stream,
-        %partition_info_source,
-        %partition_files_source,
-        %round_info_source,
-        %partition_filter,
-        %partition_too_large_to_compact_filter,
-        %compaction_job_done_sink,
-        %commit,
-        %ir_planner,
-        %df_planner,
-        %df_plan_exec,
-        %parquet_files_sink,
-        %round_split,
-        %divide_initial,
-        %scratchpad_gen,
-        %file_classifier,
-        %changed_files_filter,
-        "component setup",
-    );
+    info!(%components, "component setup");
 }
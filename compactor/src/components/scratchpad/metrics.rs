@@ -0,0 +1,271 @@
+use std::{
+    fmt::Display,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use async_trait::async_trait;
+use iox_time::{Time, TimeProvider};
+use metric::{DurationHistogram, Registry, U64Counter};
+use parquet_file::ParquetFilePath;
+use uuid::Uuid;
+
+use crate::error::DynError;
+
+use super::{Scratchpad, ScratchpadGen};
+
+const METRIC_NAME_SCRATCHPAD_STAGED_BYTES: &str = "iox_compactor_scratchpad_staged_bytes";
+const METRIC_NAME_SCRATCHPAD_RESIDENCY: &str = "iox_compactor_scratchpad_residency";
+
+/// Records the bytes staged into the scratchpads created by the inner [`ScratchpadGen`] and how long each
+/// scratchpad was held until it was cleaned.
+#[derive(Debug)]
+pub struct MetricsScratchpadGenWrapper<T>
+where
+    T: ScratchpadGen,
+{
+    staged_bytes_counter: U64Counter,
+    residency_histogram: DurationHistogram,
+    time_provider: Arc<dyn TimeProvider>,
+    inner: T,
+}
+
+impl<T> MetricsScratchpadGenWrapper<T>
+where
+    T: ScratchpadGen,
+{
+    pub fn new(inner: T, registry: &Registry, time_provider: Arc<dyn TimeProvider>) -> Self {
+        let staged_bytes_counter = registry
+            .register_metric::<U64Counter>(
+                METRIC_NAME_SCRATCHPAD_STAGED_BYTES,
+                "Number of bytes loaded into compactor scratchpads",
+            )
+            .recorder(&[]);
+        let residency_histogram = registry
+            .register_metric::<DurationHistogram>(
+                METRIC_NAME_SCRATCHPAD_RESIDENCY,
+                "Duration a compactor scratchpad was held from creation until it was cleaned",
+            )
+            .recorder(&[]);
+
+        Self {
+            staged_bytes_counter,
+            residency_histogram,
+            time_provider,
+            inner,
+        }
+    }
+}
+
+impl<T> Display for MetricsScratchpadGenWrapper<T>
+where
+    T: ScratchpadGen,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "metrics({})", self.inner)
+    }
+}
+
+impl<T> ScratchpadGen for MetricsScratchpadGenWrapper<T>
+where
+    T: ScratchpadGen,
+{
+    fn pad(&self) -> Arc<dyn Scratchpad> {
+        Arc::new(MetricsScratchpad {
+            staged_bytes_counter: self.staged_bytes_counter.clone(),
+            residency_histogram: self.residency_histogram.clone(),
+            time_provider: Arc::clone(&self.time_provider),
+            created_at: self.time_provider.now(),
+            staged_bytes_reported: AtomicU64::new(0),
+            inner: self.inner.pad(),
+        })
+    }
+}
+
+#[derive(Debug)]
+struct MetricsScratchpad {
+    staged_bytes_counter: U64Counter,
+    residency_histogram: DurationHistogram,
+    time_provider: Arc<dyn TimeProvider>,
+    created_at: Time,
+
+    /// Staged bytes of `inner` that were already added to `staged_bytes_counter`.
+    staged_bytes_reported: AtomicU64,
+    inner: Arc<dyn Scratchpad>,
+}
+
+#[async_trait]
+impl Scratchpad for MetricsScratchpad {
+    fn uuids(&self, files: &[ParquetFilePath]) -> Vec<Uuid> {
+        self.inner.uuids(files)
+    }
+
+    async fn load_to_scratchpad(&self, files: &[ParquetFilePath]) -> Vec<Uuid> {
+        let uuids = self.inner.load_to_scratchpad(files).await;
+
+        let staged_bytes = self.inner.staged_bytes();
+        let reported = self
+            .staged_bytes_reported
+            .fetch_max(staged_bytes, Ordering::Relaxed);
+        self.staged_bytes_counter
+            .inc(staged_bytes.saturating_sub(reported));
+
+        uuids
+    }
+
+    async fn make_public(&self, files: &[ParquetFilePath]) -> Vec<Uuid> {
+        self.inner.make_public(files).await
+    }
+
+    async fn verify_public(
+        &self,
+        file: &ParquetFilePath,
+        file_size_bytes: u64,
+    ) -> Result<(), DynError> {
+        self.inner.verify_public(file, file_size_bytes).await
+    }
+
+    async fn clean_from_scratchpad(&self, files: &[ParquetFilePath]) {
+        self.inner.clean_from_scratchpad(files).await
+    }
+
+    async fn clean_written_from_scratchpad(&self, files: &[ParquetFilePath]) {
+        self.inner.clean_written_from_scratchpad(files).await
+    }
+
+    async fn clean(&self) {
+        self.inner.clean().await;
+
+        if let Some(d) = self
+            .time_provider
+            .now()
+            .checked_duration_since(self.created_at)
+        {
+            self.residency_histogram.record(d);
+        }
+    }
+
+    fn staged_bytes(&self) -> u64 {
+        self.inner.staged_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Mutex, time::Duration};
+
+    use iox_time::MockProvider;
+    use metric::{assert_counter, assert_histogram};
+
+    use crate::components::scratchpad::test_util::file_path;
+
+    use super::*;
+
+    /// Scratchpad generator whose scratchpads stage 100 bytes per file.
+    #[derive(Debug, Default)]
+    struct MockScratchpadGen;
+
+    impl Display for MockScratchpadGen {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "mock")
+        }
+    }
+
+    impl ScratchpadGen for MockScratchpadGen {
+        fn pad(&self) -> Arc<dyn Scratchpad> {
+            Arc::new(MockScratchpad::default())
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct MockScratchpad {
+        staged: Mutex<u64>,
+    }
+
+    #[async_trait]
+    impl Scratchpad for MockScratchpad {
+        fn uuids(&self, files: &[ParquetFilePath]) -> Vec<Uuid> {
+            files.iter().map(|f| f.objest_store_id()).collect()
+        }
+
+        async fn load_to_scratchpad(&self, files: &[ParquetFilePath]) -> Vec<Uuid> {
+            *self.staged.lock().unwrap() += files.len() as u64 * 100;
+            self.uuids(files)
+        }
+
+        async fn make_public(&self, files: &[ParquetFilePath]) -> Vec<Uuid> {
+            self.uuids(files)
+        }
+
+        async fn verify_public(
+            &self,
+            _file: &ParquetFilePath,
+            _file_size_bytes: u64,
+        ) -> Result<(), DynError> {
+            Ok(())
+        }
+
+        async fn clean_from_scratchpad(&self, _files: &[ParquetFilePath]) {}
+
+        async fn clean_written_from_scratchpad(&self, _files: &[ParquetFilePath]) {}
+
+        async fn clean(&self) {}
+
+        fn staged_bytes(&self) -> u64 {
+            *self.staged.lock().unwrap()
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let gen = MetricsScratchpadGenWrapper::new(
+            MockScratchpadGen,
+            &Registry::new(),
+            Arc::new(MockProvider::new(Time::MIN)),
+        );
+        assert_eq!(gen.to_string(), "metrics(mock)");
+    }
+
+    #[tokio::test]
+    async fn test_metrics() {
+        let registry = Registry::new();
+        let time_provider = Arc::new(MockProvider::new(Time::MIN));
+        let gen = MetricsScratchpadGenWrapper::new(
+            MockScratchpadGen,
+            &registry,
+            Arc::clone(&time_provider) as _,
+        );
+
+        let pad_1 = gen.pad();
+        let pad_2 = gen.pad();
+
+        pad_1
+            .load_to_scratchpad(&[file_path(1), file_path(2)])
+            .await;
+        pad_1.load_to_scratchpad(&[file_path(3)]).await;
+        pad_2.load_to_scratchpad(&[file_path(4)]).await;
+        pad_1.make_public(&[file_path(1)]).await;
+
+        assert_counter!(
+            registry,
+            U64Counter,
+            METRIC_NAME_SCRATCHPAD_STAGED_BYTES,
+            value = 400,
+        );
+
+        time_provider.inc(Duration::from_secs(3));
+        pad_1.clean().await;
+        time_provider.inc(Duration::from_secs(2));
+        pad_2.clean().await;
+
+        assert_histogram!(
+            registry,
+            DurationHistogram,
+            METRIC_NAME_SCRATCHPAD_RESIDENCY,
+            samples = 2,
+            sum = Duration::from_secs(8),
+        );
+    }
+}
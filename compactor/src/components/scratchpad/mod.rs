@@ -5,6 +5,9 @@ use async_trait::async_trait;
 use parquet_file::ParquetFilePath;
 use uuid::Uuid;
 
+use crate::error::DynError;
+
+pub mod metrics;
 pub mod noop;
 pub mod prod;
 mod util;
@@ -52,7 +55,21 @@ pub trait Scratchpad: Debug + Send + Sync + 'static {
     fn uuids(&self, files: &[ParquetFilePath]) -> Vec<Uuid>;
     async fn load_to_scratchpad(&self, files: &[ParquetFilePath]) -> Vec<Uuid>;
     async fn make_public(&self, files: &[ParquetFilePath]) -> Vec<Uuid>;
+
+    /// Check that `file`, which was made public by [`make_public`](Self::make_public) under its new UUID, is present
+    /// in the output store with `file_size_bytes` bytes, a valid parquet footer and decodable parquet metadata.
+    ///
+    /// This is a no-op unless the scratchpad was set up to verify uploads.
+    async fn verify_public(
+        &self,
+        file: &ParquetFilePath,
+        file_size_bytes: u64,
+    ) -> Result<(), DynError>;
+
     async fn clean_from_scratchpad(&self, files: &[ParquetFilePath]);
     async fn clean_written_from_scratchpad(&self, files: &[ParquetFilePath]);
     async fn clean(&self);
+
+    /// Total number of bytes loaded into this scratchpad by [`load_to_scratchpad`](Self::load_to_scratchpad).
+    fn staged_bytes(&self) -> u64;
 }
@@ -4,6 +4,8 @@ use async_trait::async_trait;
 use parquet_file::ParquetFilePath;
 use uuid::Uuid;
 
+use crate::error::DynError;
+
 use super::{Scratchpad, ScratchpadGen};
 
 /// A scratchpad that ignores all inputs and outputs, for use in testing
@@ -45,9 +47,21 @@ impl Scratchpad for NoopScratchpad {
         files.iter().map(|f| f.objest_store_id()).collect()
     }
 
+    async fn verify_public(
+        &self,
+        _file: &ParquetFilePath,
+        _file_size_bytes: u64,
+    ) -> Result<(), DynError> {
+        Ok(())
+    }
+
     async fn clean_from_scratchpad(&self, _files: &[ParquetFilePath]) {}
 
     async fn clean_written_from_scratchpad(&self, _files: &[ParquetFilePath]) {}
 
     async fn clean(&self) {}
+
+    fn staged_bytes(&self) -> u64 {
+        0
+    }
 }
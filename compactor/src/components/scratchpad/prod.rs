@@ -2,16 +2,25 @@ use std::{
     collections::{hash_map::Entry, HashMap},
     fmt::Display,
     num::NonZeroUsize,
-    sync::{Arc, RwLock},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock,
+    },
 };
 
 use async_trait::async_trait;
 use backoff::BackoffConfig;
+use datafusion::parquet::file::{
+    footer::{decode_footer, decode_metadata},
+    FOOTER_SIZE,
+};
 use object_store::DynObjectStore;
 use observability_deps::tracing::warn;
 use parquet_file::ParquetFilePath;
 use uuid::Uuid;
 
+use crate::error::{DynError, ErrorKind, SimpleError};
+
 use super::{
     util::{copy_files, delete_files},
     Scratchpad, ScratchpadGen,
@@ -25,6 +34,7 @@ pub struct ProdScratchpadGen {
     store_input: Arc<DynObjectStore>,
     store_scratchpad: Arc<DynObjectStore>,
     store_output: Arc<DynObjectStore>,
+    verify_uploads: bool,
 }
 
 impl ProdScratchpadGen {
@@ -43,6 +53,17 @@ impl ProdScratchpadGen {
             store_input,
             store_scratchpad,
             store_output,
+            verify_uploads: false,
+        }
+    }
+
+    /// Re-read the size and parquet footer of every file made public and compare them against what was written.
+    ///
+    /// This is skipped in shadow mode, since nothing is written to the output store then.
+    pub fn with_verify_uploads(self) -> Self {
+        Self {
+            verify_uploads: true,
+            ..self
         }
     }
 }
@@ -63,8 +84,10 @@ impl ScratchpadGen for ProdScratchpadGen {
             store_input: Arc::clone(&self.store_input),
             store_scratchpad: Arc::clone(&self.store_scratchpad),
             store_output: Arc::clone(&self.store_output),
+            verify_uploads: self.verify_uploads,
             mask: Uuid::new_v4(),
             files_unmasked: RwLock::new(HashMap::default()),
+            staged_bytes: AtomicU64::new(0),
         })
     }
 }
@@ -76,6 +99,7 @@ struct ProdScratchpad {
     store_input: Arc<DynObjectStore>,
     store_scratchpad: Arc<DynObjectStore>,
     store_output: Arc<DynObjectStore>,
+    verify_uploads: bool,
     mask: Uuid,
 
     /// Set of known, unmasked file.
@@ -83,6 +107,9 @@ struct ProdScratchpad {
     /// If the file is part of this map, it is in the scratchpad. If the boolean key is set, it was already copied to
     /// the output store
     files_unmasked: RwLock<HashMap<ParquetFilePath, bool>>,
+
+    /// Number of bytes copied from the input store into the scratchpad.
+    staged_bytes: AtomicU64,
 }
 
 impl std::fmt::Debug for ProdScratchpad {
@@ -94,8 +121,10 @@ impl std::fmt::Debug for ProdScratchpad {
             .field("store_input", &self.store_input)
             .field("store_scratchpad", &self.store_scratchpad)
             .field("store_output", &self.store_output)
+            .field("verify_uploads", &self.verify_uploads)
             .field("mask", &self.mask)
             .field("files_unmasked", &ref_files_unmasked)
+            .field("staged_bytes", &self.staged_bytes)
             .finish()
     }
 }
@@ -186,7 +215,7 @@ impl Scratchpad for ProdScratchpad {
     async fn load_to_scratchpad(&self, files: &[ParquetFilePath]) -> Vec<Uuid> {
         let (files_to, uuids) = self.apply_mask(files);
         let (files_from, files_to) = self.check_known(files, &files_to, false);
-        copy_files(
+        let n_bytes = copy_files(
             &files_from,
             &files_to,
             Arc::clone(&self.store_input),
@@ -195,6 +224,7 @@ impl Scratchpad for ProdScratchpad {
             self.concurrency,
         )
         .await;
+        self.staged_bytes.fetch_add(n_bytes, Ordering::Relaxed);
         uuids
     }
 
@@ -216,6 +246,58 @@ impl Scratchpad for ProdScratchpad {
         uuids
     }
 
+    async fn verify_public(
+        &self,
+        file: &ParquetFilePath,
+        file_size_bytes: u64,
+    ) -> Result<(), DynError> {
+        if !self.verify_uploads || self.shadow_mode {
+            return Ok(());
+        }
+
+        let store = &self.store_output;
+        let path = file.object_store_path();
+
+        // A file that does not match what we wrote will not fix itself, so do not retry the partition.
+        let invalid = |msg: String| -> DynError {
+            Box::new(SimpleError::new(
+                ErrorKind::Unknown,
+                format!("uploaded file {path} {msg}"),
+            ))
+        };
+
+        let size = store.head(&path).await?.size;
+        if size as u64 != file_size_bytes {
+            return Err(invalid(format!(
+                "has {size} bytes, expected {file_size_bytes}"
+            )));
+        }
+        if size < FOOTER_SIZE {
+            return Err(invalid(String::from("is too small to be a parquet file")));
+        }
+
+        let footer = store.get_range(&path, size - FOOTER_SIZE..size).await?;
+        let footer: [u8; FOOTER_SIZE] = footer
+            .as_ref()
+            .try_into()
+            .map_err(|_| invalid(String::from("has a truncated parquet footer")))?;
+        let metadata_len = decode_footer(&footer)
+            .map_err(|e| invalid(format!("has an invalid parquet footer: {e}")))?;
+        let Some(metadata_start) = (size - FOOTER_SIZE).checked_sub(metadata_len) else {
+            return Err(invalid(format!(
+                "has parquet metadata of {metadata_len} bytes, which does not fit into the file"
+            )));
+        };
+
+        let metadata = store
+            .get_range(&path, metadata_start..size - FOOTER_SIZE)
+            .await?;
+        decode_metadata(&metadata)
+            .map_err(|e| invalid(format!("has invalid parquet metadata: {e}")))?;
+
+        Ok(())
+    }
+
     // clean_from_scratchpad selectively removes some files from the scratchpad.
     // This should be called after uploading files to objectstore.
     // Cleaning should be done regularly, so the scratchpad doesn't get too big.
@@ -267,6 +349,10 @@ impl Scratchpad for ProdScratchpad {
         // between this relock, clean_from_scratchpad will skip it.
         self.clean_from_scratchpad(&files).await;
     }
+
+    fn staged_bytes(&self) -> u64 {
+        self.staged_bytes.load(Ordering::Relaxed)
+    }
 }
 
 #[cfg(test)]
@@ -275,8 +361,18 @@ mod tests {
 
     use test_helpers::{maybe_start_logging, tracing::TracingCapture};
 
-    use crate::components::scratchpad::test_util::{assert_content, file_path, stores};
+    use crate::{
+        components::scratchpad::test_util::{assert_content, file_path, stores},
+        error::ErrorKindExt,
+    };
     use compactor_test_utils::list_object_store;
+    use datafusion::{
+        arrow::{
+            array::{ArrayRef, Int64Array},
+            record_batch::RecordBatch,
+        },
+        parquet::arrow::ArrowWriter,
+    };
 
     use super::*;
 
@@ -440,6 +536,136 @@ mod tests {
         assert_content(&store_output, [&f1, &f5, &f6]).await;
     }
 
+    #[tokio::test]
+    async fn test_verify_public() {
+        let (store_input, store_scratchpad, store_output) = stores();
+        let gen = ProdScratchpadGen::new(
+            false,
+            NonZeroUsize::new(1).unwrap(),
+            BackoffConfig::default(),
+            Arc::clone(&store_input),
+            Arc::clone(&store_scratchpad),
+            Arc::clone(&store_output),
+        );
+        let pad_unverified = gen.pad();
+        let pad = gen.with_verify_uploads().pad();
+
+        let data = parquet_bytes();
+        let f1_masked = file_path(1);
+        store_scratchpad
+            .put(&f1_masked.object_store_path(), data.clone())
+            .await
+            .unwrap();
+        let uuids = pad.make_public(&[f1_masked.clone()]).await;
+        let f1 = f1_masked.with_object_store_id(uuids[0]);
+        let f1_path = f1.object_store_path();
+
+        pad.verify_public(&f1, data.len() as u64).await.unwrap();
+
+        // the store returns a different size
+        let err = pad
+            .verify_public(&f1, data.len() as u64 + 1)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "uploaded file {f1_path} has {} bytes, expected {}",
+                data.len(),
+                data.len() + 1
+            ),
+        );
+        assert_eq!(err.classify(), ErrorKind::Unknown);
+
+        // not checked if verification is disabled
+        pad_unverified
+            .verify_public(&f1, data.len() as u64 + 1)
+            .await
+            .unwrap();
+
+        // the footer got lost
+        let truncated = data.slice(..data.len() - 4);
+        store_output.put(&f1_path, truncated.clone()).await.unwrap();
+        let err = pad
+            .verify_public(&f1, truncated.len() as u64)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().starts_with(&format!(
+                "uploaded file {f1_path} has an invalid parquet footer"
+            )),
+            "{err}",
+        );
+        assert_eq!(err.classify(), ErrorKind::Unknown);
+
+        // the footer claims more metadata than the file holds
+        let mut too_short = 100u32.to_le_bytes().to_vec();
+        too_short.extend_from_slice(b"PAR1");
+        store_output
+            .put(&f1_path, too_short.clone().into())
+            .await
+            .unwrap();
+        let err = pad
+            .verify_public(&f1, too_short.len() as u64)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "uploaded file {f1_path} has parquet metadata of 100 bytes, which does not fit into the file"
+            ),
+        );
+
+        // the metadata got corrupted
+        let mut corrupted = data.to_vec();
+        let metadata_end = corrupted.len() - FOOTER_SIZE;
+        let metadata_len = u32::from_le_bytes(
+            corrupted[metadata_end..metadata_end + 4]
+                .try_into()
+                .unwrap(),
+        ) as usize;
+        let metadata_start = metadata_end - metadata_len;
+        corrupted[metadata_start..metadata_start + 16].fill(0xff);
+        store_output
+            .put(&f1_path, corrupted.clone().into())
+            .await
+            .unwrap();
+        let err = pad
+            .verify_public(&f1, corrupted.len() as u64)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().starts_with(&format!(
+                "uploaded file {f1_path} has invalid parquet metadata"
+            )),
+            "{err}",
+        );
+        assert_eq!(err.classify(), ErrorKind::Unknown);
+
+        // the file does not exist at all
+        pad.verify_public(&file_path(2), data.len() as u64)
+            .await
+            .unwrap_err();
+
+        pad.clean().await;
+        pad_unverified.clean().await;
+    }
+
+    /// A small, valid parquet file.
+    fn parquet_bytes() -> bytes::Bytes {
+        let batch = RecordBatch::try_from_iter([(
+            "x",
+            Arc::new(Int64Array::from(vec![1, 2, 3])) as ArrayRef,
+        )])
+        .unwrap();
+
+        let mut data = vec![];
+        let mut writer = ArrowWriter::try_new(&mut data, batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        data.into()
+    }
+
     #[tokio::test]
     async fn test_collision() {
         let (store_input, store_scratchpad, store_output) = stores();
@@ -5,6 +5,7 @@ use futures::StreamExt;
 use object_store::DynObjectStore;
 use parquet_file::ParquetFilePath;
 
+/// Copy `files_in` from `from` to `files_out` in `to`, returning the total number of bytes copied.
 pub async fn copy_files(
     files_in: &[ParquetFilePath],
     files_out: &[ParquetFilePath],
@@ -12,7 +13,7 @@ pub async fn copy_files(
     to: Arc<DynObjectStore>,
     backoff_config: &BackoffConfig,
     concurrency: NonZeroUsize,
-) {
+) -> u64 {
     futures::stream::iter(files_in.iter().cloned().zip(files_out.to_vec()))
         .map(|(f_in, f_out)| {
             let backoff_config = backoff_config.clone();
@@ -25,16 +26,17 @@ pub async fn copy_files(
                 Backoff::new(&backoff_config)
                     .retry_all_errors("copy file", || async {
                         let bytes = from.get(&path_in).await?.bytes().await?;
+                        let n_bytes = bytes.len() as u64;
                         to.put(&path_out, bytes).await?;
-                        Ok::<_, object_store::Error>(())
+                        Ok::<_, object_store::Error>(n_bytes)
                     })
                     .await
                     .expect("retry forever")
             }
         })
         .buffer_unordered(concurrency.get())
-        .collect::<()>()
-        .await;
+        .fold(0, |acc, n_bytes| async move { acc + n_bytes })
+        .await
 }
 
 pub async fn delete_files(
@@ -1,8 +1,10 @@
 //! Config-related stuff.
-use std::{num::NonZeroUsize, sync::Arc, time::Duration};
+use std::{collections::HashMap, num::NonZeroUsize, path::PathBuf, sync::Arc, time::Duration};
 
 use backoff::BackoffConfig;
 use compactor_scheduler::SchedulerConfig;
+use data_types::PartitionId;
+use datafusion::parquet::basic::Compression;
 use iox_catalog::interface::Catalog;
 use iox_query::exec::Executor;
 use iox_time::TimeProvider;
@@ -61,6 +63,9 @@ pub struct Config {
     /// Number of jobs PER PARTITION that move files in and out of the scratchpad.
     pub partition_scratchpad_concurrency: NonZeroUsize,
 
+    /// Number of output files PER BRANCH that are uploaded concurrently from the scratchpad to the object store.
+    pub upload_concurrency: NonZeroUsize,
+
     /// Desired max size of compacted parquet files
     /// It is a target desired value than a guarantee
     pub max_desired_file_size_bytes: u64,
@@ -84,6 +89,9 @@ pub struct Config {
     /// Maximum duration of the per-partition compaction task.
     pub partition_timeout: Duration,
 
+    /// Partitions that get a different maximum duration than [`partition_timeout`](Self::partition_timeout).
+    pub partition_timeout_overrides: HashMap<PartitionId, Duration>,
+
     /// Shadow mode.
     ///
     /// This will NOT write / commit any output to the object store or catalog.
@@ -91,6 +99,12 @@ pub struct Config {
     /// This is mostly useful for debugging.
     pub shadow_mode: bool,
 
+    /// Do not commit the results of a compaction round if the partition's files were modified by another process
+    /// in the meantime, but fail the round instead. The partition is NOT skipped and will be retried later.
+    ///
+    /// If this is disabled, concurrent modifications are only logged.
+    pub fail_on_concurrent_modification: bool,
+
     /// Enable Scratchpad
     ///
     /// Enabled by default, if this is set to false, the compactor will not use the scratchpad
@@ -135,6 +149,70 @@ pub struct Config {
     ///
     /// Queries are smoothed over the full second.
     pub max_partition_fetch_queries_per_second: Option<usize>,
+
+    /// Local file that compaction job outcomes are appended to as JSON lines.
+    ///
+    /// No audit log is written if this is `None`.
+    pub audit_log_path: Option<PathBuf>,
+
+    /// Number of consecutive failures after which a partition is skipped, regardless of the error kind.
+    ///
+    /// Partitions are only skipped based on the error kind if this is `None`.
+    pub max_consecutive_failures: Option<NonZeroUsize>,
+
+    /// Maximum number of partitions that are pulled from the partition stream at once. A batch is fully compacted
+    /// before the next one is pulled.
+    ///
+    /// Partitions are pulled continuously if this is `None`.
+    pub fetch_batch_size: Option<NonZeroUsize>,
+
+    /// Maximum number of compaction rounds per partition and job. If a partition still needs compaction after this
+    /// many rounds, the job fails with a "max rounds exceeded" error.
+    ///
+    /// The number of rounds is unbounded if this is `None`.
+    pub max_rounds: Option<NonZeroUsize>,
+
+    /// URL that a JSON payload is POSTed to for every compaction job that fails with an error that marks the
+    /// partition as skipped.
+    ///
+    /// No webhook is notified if this is `None`.
+    pub webhook_url: Option<String>,
+
+    /// Hard limit on the number of input files a single compaction plan may open. Partitions whose classified
+    /// compaction would exceed it are skipped.
+    ///
+    /// No such check is performed if this is `None`.
+    pub max_input_files_per_plan: Option<NonZeroUsize>,
+
+    /// Compression codec of the parquet files written by the compactor.
+    pub parquet_compression: Compression,
+
+    /// Compact partitions with the most L0 files (and then the largest total file size) first instead of in random
+    /// order.
+    ///
+    /// This costs one aggregate catalog query per round and is mostly useful to catch up on a backlog.
+    pub prioritize_by_l0_count: bool,
+
+    /// Re-read the size, parquet footer and parquet metadata of every uploaded file and skip the partition (without
+    /// committing to the catalog) if they do not match what was written.
+    ///
+    /// Only applies when the scratchpad is enabled.
+    pub verify_uploads: bool,
+
+    /// Reuse the files of a partition fetched at the start of a compaction job when checking for concurrent
+    /// modifications before the first commit, instead of querying the catalog again.
+    ///
+    /// This reduces catalog load, but concurrent modifications by other processes go unnoticed until the first commit
+    /// of a job. Ignored if [`fail_on_concurrent_modification`](Self::fail_on_concurrent_modification) is set.
+    pub cache_partition_files: bool,
+
+    /// Number of compaction jobs that may fail with an object store error before the compactor backs off globally.
+    ///
+    /// Once exceeded, no new partitions are compacted for a while and the count starts over.
+    pub object_store_error_budget: Option<NonZeroUsize>,
+
+    /// Time to back off once [`object_store_error_budget`](Self::object_store_error_budget) is exceeded.
+    pub object_store_error_backoff: Duration,
 }
 
 impl Config {
@@ -1,13 +1,19 @@
-use std::{num::NonZeroUsize, sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    num::NonZeroUsize,
+    sync::Arc,
+    time::Duration,
+};
 
 use chrono::Utc;
 use compactor_scheduler::CompactionJob;
 use data_types::{CompactionLevel, ParquetFile, ParquetFileParams, PartitionId};
-use futures::{stream, StreamExt, TryStreamExt};
+use futures::{stream, Future, Stream, StreamExt, TryStreamExt};
 use iox_query::exec::query_tracing::send_metrics_to_tracing;
-use observability_deps::tracing::info;
+use observability_deps::tracing::{debug, info, warn};
+use parking_lot::Mutex;
 use parquet_file::ParquetFilePath;
-use tokio::sync::watch::Sender;
+use tokio::sync::watch::{self, Sender};
 use trace::span::Span;
 use trace::span::SpanRecorder;
 use tracker::InstrumentedAsyncSemaphore;
@@ -15,6 +21,7 @@ use tracker::InstrumentedAsyncSemaphore;
 use crate::{
     components::{
         changed_files_filter::SavedParquetFileState,
+        phase_metrics::DriverPhase,
         scratchpad::Scratchpad,
         timeout::{timeout_with_progress_checking, TimeoutWithProgress},
         Components,
@@ -25,20 +32,59 @@ use crate::{
     PlanIR, RoundInfo,
 };
 
+/// Overall progress of a [`compact`] run, e.g. for status reporting.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CompactionProgress {
+    /// Number of partitions for which compaction was started.
+    pub partitions_started: usize,
+
+    /// Number of partitions that finished successfully.
+    pub partitions_completed: usize,
+
+    /// Number of partitions that finished with an error.
+    pub partitions_failed: usize,
+}
+
 /// Tries to compact all eligible partitions, up to
 /// partition_concurrency at a time.
+///
+/// If `fetch_batch_size` is set, at most that many jobs are pulled from the job stream at once and the entire batch is
+/// processed before the next one is pulled.
+///
+/// Partitions listed in `partition_timeout_overrides` use their own timeout instead of `partition_timeout`.
+///
+/// If `max_rounds` is set, compacting a partition fails once it still needs compaction after that many rounds.
+///
+/// If `progress` is given, it is updated whenever a partition is started or finished.
+///
+/// A job for a partition that is still being compacted by an earlier job is skipped, so the same partition is never
+/// compacted concurrently within this process.
+#[allow(clippy::too_many_arguments)]
 pub async fn compact(
     trace_collector: Option<Arc<dyn trace::TraceCollector>>,
     partition_concurrency: NonZeroUsize,
+    fetch_batch_size: Option<NonZeroUsize>,
     partition_timeout: Duration,
+    partition_timeout_overrides: &HashMap<PartitionId, Duration>,
+    max_rounds: Option<NonZeroUsize>,
+    upload_concurrency: NonZeroUsize,
     df_semaphore: Arc<InstrumentedAsyncSemaphore>,
     components: &Arc<Components>,
+    progress: Option<watch::Sender<CompactionProgress>>,
 ) {
-    components
-        .compaction_job_stream
-        .stream()
-        .map(|job| {
+    let progress = progress.map(Arc::new);
+    let in_flight = Arc::new(Mutex::new(HashSet::new()));
+
+    process_in_batches(
+        components.compaction_job_stream.stream(),
+        fetch_batch_size,
+        partition_concurrency,
+        |job| {
+            // claim the partition as soon as the job is pulled, not when it is first polled
+            let in_flight_guard = InFlightGuard::try_new(Arc::clone(&in_flight), job.partition_id);
             let components = Arc::clone(components);
+            let progress = progress.as_ref().map(Arc::clone);
+            let df_semaphore = Arc::clone(&df_semaphore);
 
             // A root span is created for each compaction job (a.k.a. partition).
             // Later this can be linked to the
@@ -48,28 +94,191 @@ pub async fn compact(
                 .map(|collector| Span::root("compaction", Arc::clone(collector)));
             let span = SpanRecorder::new(root_span);
 
-            compact_partition(
-                span,
-                job,
-                partition_timeout,
-                Arc::clone(&df_semaphore),
-                components,
-            )
+            async move {
+                // The job source should never emit a partition that is still being compacted. Guard against buggy
+                // sources anyway, `SavedParquetFileState` only detects conflicts once the work is done.
+                let Some(_in_flight_guard) = in_flight_guard else {
+                    warn!(
+                        partition_id = job.partition_id.get(),
+                        "partition is already being compacted, skipping",
+                    );
+                    return;
+                };
+
+                compact_partition(
+                    span,
+                    job,
+                    partition_timeout,
+                    partition_timeout_overrides,
+                    max_rounds,
+                    upload_concurrency,
+                    df_semaphore,
+                    components,
+                    progress,
+                )
+                .await
+            }
+        },
+    )
+    .await;
+}
+
+/// Marks a partition as being compacted until it is dropped.
+#[derive(Debug)]
+struct InFlightGuard {
+    in_flight: Arc<Mutex<HashSet<PartitionId>>>,
+    partition_id: PartitionId,
+}
+
+impl InFlightGuard {
+    /// Returns `None` if the partition is already being compacted.
+    fn try_new(
+        in_flight: Arc<Mutex<HashSet<PartitionId>>>,
+        partition_id: PartitionId,
+    ) -> Option<Self> {
+        if !in_flight.lock().insert(partition_id) {
+            return None;
+        }
+
+        Some(Self {
+            in_flight,
+            partition_id,
         })
-        .buffer_unordered(partition_concurrency.get())
-        .collect::<()>()
-        .await;
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.in_flight.lock().remove(&self.partition_id);
+    }
 }
 
+/// Runs `f` for every job of `jobs`, up to `concurrency` at a time.
+///
+/// If `batch_size` is set, jobs are pulled from `jobs` in batches of (at most) this size and a batch must be fully
+/// processed before the next one is pulled.
+async fn process_in_batches<S, F, Fut>(
+    jobs: S,
+    batch_size: Option<NonZeroUsize>,
+    concurrency: NonZeroUsize,
+    mut f: F,
+) where
+    S: Stream<Item = CompactionJob>,
+    F: FnMut(CompactionJob) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let Some(batch_size) = batch_size else {
+        jobs.map(f)
+            .buffer_unordered(concurrency.get())
+            .collect::<()>()
+            .await;
+        return;
+    };
+
+    let mut batches = Box::pin(jobs.chunks(batch_size.get()));
+    while let Some(batch) = batches.next().await {
+        debug!(n_jobs = batch.len(), "process batch of compaction jobs");
+        stream::iter(batch)
+            .map(&mut f)
+            .buffer_unordered(concurrency.get())
+            .collect::<()>()
+            .await;
+    }
+}
+
+/// Result of [`dry_classify`].
+#[derive(Debug)]
+pub struct DryClassification {
+    /// The round the partition would be compacted in.
+    pub round_info: RoundInfo,
+
+    /// Classification of every branch of the round.
+    pub branches: Vec<FileClassification>,
+
+    /// Files that are not considered in this round.
+    pub files_later: Vec<ParquetFile>,
+}
+
+/// Classifies the files of the given partition for the first compaction round, i.e. what the compactor would do.
+///
+/// No plans are executed and neither the catalog nor the object store are modified. Returns `None` if the partition
+/// does not need compaction.
+pub async fn dry_classify(
+    components: &Arc<Components>,
+    partition_id: PartitionId,
+) -> Result<Option<DryClassification>, DynError> {
+    let files = components.partition_files_source.fetch(partition_id).await;
+    let partition_info = components.partition_info_source.fetch(partition_id).await?;
+
+    if files.is_empty()
+        || !components
+            .partition_filter
+            .apply(&partition_info, &files)
+            .await?
+    {
+        return Ok(None);
+    }
+
+    let (round_info, branches, files_later) = components
+        .round_info_source
+        .calculate(Arc::clone(components), &partition_info, files)
+        .await?;
+
+    let branches = branches
+        .into_iter()
+        .map(|branch| {
+            components
+                .file_classifier
+                .classify(&partition_info, &round_info, branch)
+        })
+        .collect();
+
+    Ok(Some(DryClassification {
+        round_info,
+        branches,
+        files_later,
+    }))
+}
+
+/// Estimates the sizes of the files the compactor writes when compacting the given files of a partition together, in
+/// time order.
+///
+/// This uses the same model the compaction plans use to decide whether and where to split the output, i.e. it assumes
+/// that the data is evenly distributed over time and that the output is about as large as the input. The actual file
+/// sizes may therefore deviate.
+pub fn estimate_output_sizes(
+    components: &Components,
+    partition_info: &PartitionInfo,
+    files: &[ParquetFile],
+) -> Vec<u64> {
+    components
+        .ir_planner
+        .estimate_output_sizes(partition_info, files)
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn compact_partition(
     mut span: SpanRecorder,
     job: CompactionJob,
     partition_timeout: Duration,
+    partition_timeout_overrides: &HashMap<PartitionId, Duration>,
+    max_rounds: Option<NonZeroUsize>,
+    upload_concurrency: NonZeroUsize,
     df_semaphore: Arc<InstrumentedAsyncSemaphore>,
     components: Arc<Components>,
+    progress: Option<Arc<watch::Sender<CompactionProgress>>>,
 ) {
     let partition_id = job.partition_id;
+
+    // wait before the timeout starts, time spent paused is not the fault of the partition
+    components.pause_gate.wait().await;
+
+    let partition_timeout =
+        timeout_for_partition(partition_id, partition_timeout, partition_timeout_overrides);
     info!(partition_id = partition_id.get(), timeout = ?partition_timeout, "compact partition",);
+    if let Some(progress) = &progress {
+        progress.send_modify(|p| p.partitions_started += 1);
+    }
     span.set_metadata("partition_id", partition_id.get().to_string());
     let scratchpad = components.scratchpad_gen.pad();
 
@@ -80,6 +289,8 @@ async fn compact_partition(
             try_compact_partition(
                 span,
                 job.clone(),
+                max_rounds,
+                upload_concurrency,
                 df_semaphore,
                 components,
                 scratchpad,
@@ -108,13 +319,40 @@ async fn compact_partition(
         TimeoutWithProgress::Completed(res) => res,
     };
 
+    if let Some(progress) = &progress {
+        let ok = res.is_ok();
+        progress.send_modify(|p| {
+            if ok {
+                p.partitions_completed += 1;
+            } else {
+                p.partitions_failed += 1;
+            }
+        });
+    }
+
     // TODO: how handle errors detected in the CompactionJob ending actions?
     let _ = components.compaction_job_done_sink.record(job, res).await;
 
     scratchpad.clean().await;
+    components
+        .partition_files_source
+        .invalidate(partition_id)
+        .await;
     info!(partition_id = partition_id.get(), "compaction job done",);
 }
 
+/// Timeout for compacting the given partition: its override, if there is one, or the default `partition_timeout`.
+fn timeout_for_partition(
+    partition_id: PartitionId,
+    partition_timeout: Duration,
+    partition_timeout_overrides: &HashMap<PartitionId, Duration>,
+) -> Duration {
+    partition_timeout_overrides
+        .get(&partition_id)
+        .copied()
+        .unwrap_or(partition_timeout)
+}
+
 /// Main function to compact files of a single partition.
 ///
 /// Input: any files in the partitions (L0s, L1s, L2s)
@@ -161,7 +399,7 @@ async fn compact_partition(
 ///
 /// The high level flow is:
 ///
-///   . Mutiple rounds, each round process mutltiple branches. Each branch includes at most 200 files
+///   . Mutiple rounds, each round process mutltiple branches. Each branch includes at most `max_num_files_per_plan` files
 ///   . Each branch will compact files lowest level (aka start-level) into its next level (aka target-level), either:
 ///      - Compact many L0s into fewer and larger L0s. Start-level = target-level = 0
 ///      - Compact many L1s into fewer and larger L1s. Start-level = target-level = 1
@@ -201,9 +439,13 @@ async fn compact_partition(
 /// Note:
 ///   . If there are no L0s files in the partition, the first round can just compact L1s and L2s to L2s
 ///   . Round 2 happens or not depends on the stop condition
+///   . If `max_rounds` is set and the stop condition is not met after that many rounds, an error is returned
+#[allow(clippy::too_many_arguments)]
 async fn try_compact_partition(
     span: SpanRecorder,
     job: CompactionJob,
+    max_rounds: Option<NonZeroUsize>,
+    upload_concurrency: NonZeroUsize,
     df_semaphore: Arc<InstrumentedAsyncSemaphore>,
     components: Arc<Components>,
     scratchpad_ctx: Arc<dyn Scratchpad>,
@@ -213,6 +455,7 @@ async fn try_compact_partition(
     let mut files = components.partition_files_source.fetch(partition_id).await;
     let partition_info = components.partition_info_source.fetch(partition_id).await?;
     let transmit_progress_signal = Arc::new(transmit_progress_signal);
+    let mut rounds = 0;
 
     // loop for each "Round", consider each file in the partition
     // for partitions with a lot of compaction work to do, keeping the work divided into multiple rounds,
@@ -240,6 +483,17 @@ async fn try_compact_partition(
             return Ok(());
         }
 
+        // Bound the work done for a single partition, e.g. if the stop condition is never met.
+        if let Some(max_rounds) = max_rounds {
+            if rounds >= max_rounds.get() {
+                return Err(Box::new(SimpleError::new(
+                    ErrorKind::Unknown,
+                    format!("max rounds exceeded: partition still needs compaction after {rounds} rounds"),
+                )));
+            }
+        }
+        rounds += 1;
+
         let (round_info, branches, files_later) = components
             .round_info_source
             .calculate(
@@ -274,6 +528,7 @@ async fn try_compact_partition(
                         branch_span,
                         job,
                         branch,
+                        upload_concurrency,
                         df_semaphore,
                         components,
                         scratchpad,
@@ -298,6 +553,7 @@ async fn execute_branch(
     span: SpanRecorder,
     job: CompactionJob,
     branch: Vec<ParquetFile>,
+    upload_concurrency: NonZeroUsize,
     df_semaphore: Arc<InstrumentedAsyncSemaphore>,
     components: Arc<Components>,
     scratchpad_ctx: Arc<dyn Scratchpad>,
@@ -312,9 +568,12 @@ async fn execute_branch(
     // throw away the compaction work we've done.
     let saved_parquet_file_state = SavedParquetFileState::from(&branch);
 
+    let phase_metrics = &components.phase_metrics;
+
     // Identify the target level and files that should be
     // compacted together, upgraded, and kept for next round of
     // compaction
+    let classify_start = phase_metrics.start();
     let FileClassification {
         target_level,
         files_to_make_progress_on,
@@ -322,6 +581,7 @@ async fn execute_branch(
     } = components
         .file_classifier
         .classify(&partition_info, &round_info, branch);
+    phase_metrics.record(DriverPhase::Classify, phase_metrics.elapsed(classify_start));
 
     // Evaluate whether there's work to do or not based on the files classified for
     // making progress on. If there's no work to do, return early.
@@ -354,6 +614,11 @@ async fn execute_branch(
 
     let mut files_next: Vec<ParquetFile> = Vec::new();
 
+    // time spent in the per-chunk phases, recorded once the whole branch is done
+    let mut run_plans_duration = Duration::ZERO;
+    let mut upload_duration = Duration::ZERO;
+    let mut commit_duration = Duration::ZERO;
+
     // The number of plans is often small (1), but can be thousands, especially in vertical splitting
     // scenarios when the partition is highly backlogged.  So we chunk the plans into groups to control
     // memory usage (all files for all plans in a chunk are loaded to the scratchpad at once), and to
@@ -374,6 +639,7 @@ async fn execute_branch(
             .collect();
 
         // Compact & Split
+        let run_plans_start = phase_metrics.start();
         let created_file_params = run_plans(
             span.child("run_plans"),
             chunk,
@@ -383,14 +649,18 @@ async fn execute_branch(
             Arc::<dyn Scratchpad>::clone(&scratchpad_ctx),
         )
         .await?;
+        run_plans_duration += phase_metrics.elapsed(run_plans_start);
 
         // upload files to real object store
         let upload_span = span.child("upload_objects");
+        let upload_start = phase_metrics.start();
         let created_file_params = upload_files_to_object_store(
             created_file_params,
             Arc::<dyn Scratchpad>::clone(&scratchpad_ctx),
+            upload_concurrency,
         )
-        .await;
+        .await?;
+        upload_duration += phase_metrics.elapsed(upload_start);
         drop(upload_span);
 
         for file_param in &created_file_params {
@@ -414,6 +684,7 @@ async fn execute_branch(
 
         // Update the catalog to reflect the newly created files, soft delete the compacted
         // files and update the upgraded files
+        let commit_start = phase_metrics.start();
         let (created_files, upgraded_files) = update_catalog(
             Arc::clone(&components),
             job.clone(),
@@ -424,6 +695,7 @@ async fn execute_branch(
             target_level,
         )
         .await?;
+        commit_duration += phase_metrics.elapsed(commit_start);
 
         // we only need to upgrade files on the first iteration, so empty the upgrade list for next loop.
         upgrade = Vec::new();
@@ -440,6 +712,10 @@ async fn execute_branch(
         files_next.extend(upgraded_files);
     }
 
+    phase_metrics.record(DriverPhase::RunPlans, run_plans_duration);
+    phase_metrics.record(DriverPhase::Upload, upload_duration);
+    phase_metrics.record(DriverPhase::Commit, commit_duration);
+
     files_next.extend(files_to_keep);
     Ok(files_next)
 }
@@ -593,23 +869,47 @@ async fn execute_plan(
     Ok(create)
 }
 
+/// Upload files to the real object store, at most `upload_concurrency` at a time.
+///
+/// Fails if an uploaded file cannot be [verified](Scratchpad::verify_public).
+///
+/// Returns the file params updated with the object store ID assigned by the scratchpad, in the same order as
+/// `created_file_params`.
 async fn upload_files_to_object_store(
     created_file_params: Vec<ParquetFileParams>,
     scratchpad_ctx: Arc<dyn Scratchpad>,
-) -> Vec<ParquetFileParams> {
-    // Upload files to real object store
-    let output_files: Vec<ParquetFilePath> = created_file_params.iter().map(|p| p.into()).collect();
-    let output_uuids = scratchpad_ctx.make_public(&output_files).await;
-
-    // Update file params with object_store_id
-    created_file_params
-        .into_iter()
-        .zip(output_uuids)
-        .map(|(f, uuid)| ParquetFileParams {
-            object_store_id: uuid,
-            ..f
+    upload_concurrency: NonZeroUsize,
+) -> Result<Vec<ParquetFileParams>, DynError> {
+    // `buffered` (rather than `buffer_unordered`) yields results in input order, so every file keeps the
+    // object_store_id of its own upload regardless of which upload finishes first.
+    stream::iter(created_file_params)
+        .map(|f| {
+            let scratchpad_ctx = Arc::clone(&scratchpad_ctx);
+            async move {
+                let output_file = ParquetFilePath::from(&f);
+                let uuid = scratchpad_ctx
+                    .make_public(&[output_file])
+                    .await
+                    .into_iter()
+                    .next()
+                    .expect("scratchpad returns one uuid per file");
+
+                // Update file params with object_store_id
+                let f = ParquetFileParams {
+                    object_store_id: uuid,
+                    ..f
+                };
+
+                scratchpad_ctx
+                    .verify_public(&ParquetFilePath::from(&f), f.file_size_bytes as u64)
+                    .await?;
+
+                Ok::<_, DynError>(f)
+            }
         })
-        .collect()
+        .buffered(upload_concurrency.get())
+        .try_collect()
+        .await
 }
 
 async fn fetch_and_save_parquet_file_state(
@@ -636,12 +936,19 @@ async fn update_catalog(
     let current_parquet_file_state =
         fetch_and_save_parquet_file_state(&components, partition_id).await;
 
-    // Right now this only logs; in the future we might decide not to commit these changes
-    let _ignore = components
+    if components
         .changed_files_filter
-        .apply(saved_parquet_file_state, &current_parquet_file_state);
+        .apply(saved_parquet_file_state, &current_parquet_file_state)
+        .await
+    {
+        // throw away our work, another process already compacted (some of) our input files
+        return Err(Box::new(SimpleError::new(
+            ErrorKind::ConcurrentModification,
+            "partition files were modified concurrently, not committing compaction results",
+        )));
+    }
 
-    let created_ids = components
+    let res = components
         .commit
         .commit(
             job,
@@ -650,7 +957,13 @@ async fn update_catalog(
             &file_params_to_create,
             target_level,
         )
-        .await?;
+        .await;
+    // the files of the partition changed (or might have if the commit failed)
+    components
+        .partition_files_source
+        .invalidate(partition_id)
+        .await;
+    let created_ids = res?;
 
     // Update created ids to their corresponding file params
     let created_file_params = file_params_to_create
@@ -702,8 +1015,179 @@ fn compute_permits(
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Mutex;
+
+    use async_trait::async_trait;
+    use iox_tests::ParquetFileBuilder;
+    use uuid::Uuid;
+
+    use crate::components::{
+        compaction_job_stream::{once::OnceCompactionJobStream, CompactionJobStream},
+        compaction_jobs_source::mock::MockCompactionJobsSource,
+    };
+
     use super::*;
 
+    /// Scratchpad that makes files public under a new UUID derived from the input UUID and finishes uploads of
+    /// files with a smaller UUID later, so uploads complete in reverse order.
+    #[derive(Debug)]
+    struct ReverseOrderScratchpad;
+
+    #[async_trait]
+    impl Scratchpad for ReverseOrderScratchpad {
+        fn uuids(&self, files: &[ParquetFilePath]) -> Vec<Uuid> {
+            files.iter().map(|f| f.objest_store_id()).collect()
+        }
+
+        async fn load_to_scratchpad(&self, files: &[ParquetFilePath]) -> Vec<Uuid> {
+            self.uuids(files)
+        }
+
+        async fn make_public(&self, files: &[ParquetFilePath]) -> Vec<Uuid> {
+            let mut uuids = Vec::with_capacity(files.len());
+            for f in files {
+                let id = f.objest_store_id().as_u128();
+                tokio::time::sleep(Duration::from_millis(50 - id as u64 * 10)).await;
+                uuids.push(Uuid::from_u128(id + 100));
+            }
+            uuids
+        }
+
+        async fn verify_public(
+            &self,
+            _file: &ParquetFilePath,
+            _file_size_bytes: u64,
+        ) -> Result<(), DynError> {
+            Ok(())
+        }
+
+        async fn clean_from_scratchpad(&self, _files: &[ParquetFilePath]) {}
+
+        async fn clean_written_from_scratchpad(&self, _files: &[ParquetFilePath]) {}
+
+        async fn clean(&self) {}
+
+        fn staged_bytes(&self) -> u64 {
+            0
+        }
+    }
+
+    #[tokio::test]
+    async fn upload_keeps_object_store_ids_paired() {
+        let created_file_params: Vec<ParquetFileParams> = (1..=4)
+            .map(|id| {
+                ParquetFileBuilder::new(id)
+                    .with_row_count(id * 10)
+                    .build()
+                    .into()
+            })
+            .collect();
+
+        let uploaded = upload_files_to_object_store(
+            created_file_params,
+            Arc::new(ReverseOrderScratchpad),
+            NonZeroUsize::new(4).unwrap(),
+        )
+        .await
+        .unwrap();
+
+        let got = uploaded
+            .iter()
+            .map(|f| (f.row_count, f.object_store_id))
+            .collect::<Vec<_>>();
+        let expected = (1..=4)
+            .map(|id| (id as i64 * 10, Uuid::from_u128(id + 100)))
+            .collect::<Vec<_>>();
+        assert_eq!(got, expected);
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum Event {
+        Fetched(i64),
+        Processed(i64),
+    }
+
+    #[tokio::test]
+    async fn process_jobs_in_batches() {
+        let jobs = (1..=10)
+            .map(|id| CompactionJob::new(PartitionId::new(id)))
+            .collect();
+        let stream = OnceCompactionJobStream::new(MockCompactionJobsSource::new(jobs));
+        let events = Mutex::new(vec![]);
+
+        process_in_batches(
+            stream.stream().inspect(|job| {
+                events
+                    .lock()
+                    .unwrap()
+                    .push(Event::Fetched(job.partition_id.get()))
+            }),
+            NonZeroUsize::new(4),
+            NonZeroUsize::new(2).unwrap(),
+            |job| {
+                events
+                    .lock()
+                    .unwrap()
+                    .push(Event::Processed(job.partition_id.get()));
+                async {}
+            },
+        )
+        .await;
+
+        // a batch is entirely processed before the next one is fetched
+        let batches = [1..=4, 5..=8, 9..=10];
+        let expected = batches
+            .into_iter()
+            .flat_map(|ids| {
+                ids.clone()
+                    .map(Event::Fetched)
+                    .chain(ids.map(Event::Processed))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(*events.lock().unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn process_jobs_unbatched() {
+        let jobs = (1..=10)
+            .map(|id| CompactionJob::new(PartitionId::new(id)))
+            .collect();
+        let stream = OnceCompactionJobStream::new(MockCompactionJobsSource::new(jobs));
+        let processed = Mutex::new(vec![]);
+
+        process_in_batches(
+            stream.stream(),
+            None,
+            NonZeroUsize::new(2).unwrap(),
+            |job| {
+                processed.lock().unwrap().push(job.partition_id.get());
+                async {}
+            },
+        )
+        .await;
+
+        assert_eq!(*processed.lock().unwrap(), (1..=10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn partition_timeout_overrides() {
+        let default = Duration::from_secs(60);
+        let overrides = HashMap::from([(PartitionId::new(1), Duration::from_secs(3_600))]);
+
+        assert_eq!(
+            timeout_for_partition(PartitionId::new(1), default, &overrides),
+            Duration::from_secs(3_600)
+        );
+        assert_eq!(
+            timeout_for_partition(PartitionId::new(2), default, &overrides),
+            default
+        );
+        assert_eq!(
+            timeout_for_partition(PartitionId::new(1), default, &HashMap::new()),
+            default
+        );
+    }
+
     #[test]
     fn concurrency_limits() {
         assert_eq!(compute_permits(100, 1), 1); // 1 column still takes 1 permit
@@ -2,7 +2,9 @@
 
 use compactor_scheduler::ErrorKind as SchedulerErrorKind;
 use datafusion::{arrow::error::ArrowError, error::DataFusionError, parquet::errors::ParquetError};
+use iox_catalog::interface::Error as CatalogError;
 use object_store::Error as ObjectStoreError;
+use sqlx::Error as SqlxError;
 use std::{error::Error, fmt::Display, sync::Arc};
 use tokio::time::error::Elapsed;
 
@@ -16,6 +18,11 @@ pub enum ErrorKind {
     /// See <https://github.com/influxdata/idpe/issues/16984>.
     ObjectStore,
 
+    /// Could not access the catalog, e.g. because the connection failed or a transaction could not be committed.
+    ///
+    /// This is transient and the partition will be retried in a later round.
+    Catalog,
+
     /// We ran out of memory (OOM).
     ///
     /// The compactor shall retry (if possible) with a smaller set of files.
@@ -24,6 +31,12 @@ pub enum ErrorKind {
     /// Partition took too long.
     Timeout,
 
+    /// The files of the partition were modified by another process while it was compacted, so the results were thrown
+    /// away instead of being committed (see `fail_on_concurrent_modification`).
+    ///
+    /// This is transient and the partition will be compacted again, based on its new files, in a later round.
+    ConcurrentModification,
+
     /// Unknown/unexpected error.
     ///
     /// This will likely mark the affected partition as "skipped" and the compactor will no longer touch it.
@@ -35,8 +48,10 @@ impl ErrorKind {
     pub fn variants() -> &'static [Self] {
         &[
             Self::ObjectStore,
+            Self::Catalog,
             Self::OutOfMemory,
             Self::Timeout,
+            Self::ConcurrentModification,
             Self::Unknown,
         ]
     }
@@ -45,19 +60,33 @@ impl ErrorKind {
     pub fn name(&self) -> &'static str {
         match self {
             Self::ObjectStore => "object_store",
+            Self::Catalog => "catalog",
             Self::OutOfMemory => "out_of_memory",
             Self::Timeout => "timeout",
+            Self::ConcurrentModification => "concurrent_modification",
             Self::Unknown => "unknown",
         }
     }
+
+    /// Returns true if the error is transient and the partition should be retried instead of being marked as
+    /// skipped.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::ObjectStore | Self::Catalog | Self::ConcurrentModification => true,
+            Self::OutOfMemory | Self::Timeout | Self::Unknown => false,
+        }
+    }
 }
 
 impl From<ErrorKind> for SchedulerErrorKind {
     fn from(e: ErrorKind) -> Self {
         match e {
             ErrorKind::ObjectStore => Self::ObjectStore,
+            ErrorKind::Catalog => Self::Catalog,
             ErrorKind::OutOfMemory => Self::OutOfMemory,
             ErrorKind::Timeout => Self::Timeout,
+            // the scheduler has no dedicated kind, the conflict is about the catalog state of the partition
+            ErrorKind::ConcurrentModification => Self::Catalog,
             ErrorKind::Unknown => Self::Unknown("".into()),
         }
     }
@@ -116,6 +145,40 @@ impl ErrorKindExt for ArrowError {
     }
 }
 
+impl ErrorKindExt for CatalogError {
+    fn classify(&self) -> ErrorKind {
+        // do NOT look at the source here: the underlying sqlx errors do not tell us anything that the variant doesn't
+        match self {
+            Self::SqlxError { .. }
+            | Self::StartTransaction { .. }
+            | Self::FailedToCommit { .. }
+            | Self::Setup { .. }
+            | Self::CouldNotRecordSkippedCompaction { .. }
+            | Self::CouldNotListSkippedCompactions { .. }
+            | Self::CouldNotDeleteSkippedCompactions { .. }
+            | Self::CouldNotDeleteNamespace { .. } => ErrorKind::Catalog,
+            Self::InvalidName { .. }
+            | Self::NameExists { .. }
+            | Self::TableNameExists { .. }
+            | Self::ForeignKeyViolation { .. }
+            | Self::ColumnTypeMismatch { .. }
+            | Self::UnknownColumnType { .. }
+            | Self::NamespaceNotFoundByName { .. }
+            | Self::NamespaceNotFoundById { .. }
+            | Self::TableNotFound { .. }
+            | Self::PartitionNotFound { .. }
+            | Self::ColumnCreateLimitError { .. }
+            | Self::TableCreateLimitError { .. }
+            | Self::FileExists { .. }
+            | Self::FileNotFound { .. }
+            | Self::ParquetRecordNotFound { .. }
+            | Self::InvalidColumn { .. }
+            | Self::NoTransaction
+            | Self::InvalidValue { .. } => ErrorKind::Unknown,
+        }
+    }
+}
+
 impl ErrorKindExt for DataFusionError {
     fn classify(&self) -> ErrorKind {
         match self.find_root() {
@@ -160,6 +223,21 @@ impl ErrorKindExt for ParquetError {
     }
 }
 
+impl ErrorKindExt for SqlxError {
+    fn classify(&self) -> ErrorKind {
+        // do NOT look at the source here: IO errors of the connection are transient, no matter what they say
+        match self {
+            Self::Io(_)
+            | Self::Tls(_)
+            | Self::Protocol(_)
+            | Self::PoolTimedOut
+            | Self::PoolClosed
+            | Self::WorkerCrashed => ErrorKind::Catalog,
+            _ => ErrorKind::Unknown,
+        }
+    }
+}
+
 impl ErrorKindExt for SimpleError {
     fn classify(&self) -> ErrorKind {
         self.kind
@@ -216,6 +294,10 @@ macro_rules! dispatch_body {
 
 impl ErrorKindExt for &(dyn std::error::Error + 'static) {
     fn classify(&self) -> ErrorKind {
+        if let Some(kind) = try_classify_catalog(*self) {
+            return kind;
+        }
+
         if let Some(source) = self.source() {
             return source.classify();
         }
@@ -226,6 +308,10 @@ impl ErrorKindExt for &(dyn std::error::Error + 'static) {
 
 impl ErrorKindExt for &(dyn std::error::Error + Send + Sync + 'static) {
     fn classify(&self) -> ErrorKind {
+        if let Some(kind) = try_classify_catalog(*self) {
+            return kind;
+        }
+
         if let Some(source) = self.source() {
             return source.classify();
         }
@@ -236,44 +322,48 @@ impl ErrorKindExt for &(dyn std::error::Error + Send + Sync + 'static) {
 
 impl ErrorKindExt for Arc<dyn std::error::Error> {
     fn classify(&self) -> ErrorKind {
-        if let Some(source) = self.source() {
-            return source.classify();
-        }
-
         self.as_ref().classify()
     }
 }
 
 impl ErrorKindExt for Arc<dyn std::error::Error + Send + Sync> {
     fn classify(&self) -> ErrorKind {
-        if let Some(source) = self.source() {
-            return source.classify();
-        }
-
         self.as_ref().classify()
     }
 }
 
 impl ErrorKindExt for Box<dyn std::error::Error> {
     fn classify(&self) -> ErrorKind {
-        if let Some(source) = self.source() {
-            return source.classify();
-        }
-
         self.as_ref().classify()
     }
 }
 
 impl ErrorKindExt for Box<dyn std::error::Error + Send + Sync> {
     fn classify(&self) -> ErrorKind {
-        if let Some(source) = self.source() {
-            return source.classify();
-        }
-
         self.as_ref().classify()
     }
 }
 
+/// Catalog and sqlx errors must be classified before looking at their source, since the underlying sqlx / IO error
+/// would otherwise be classified as [`ErrorKind::Unknown`].
+fn try_classify_catalog(e: &(dyn std::error::Error + 'static)) -> Option<ErrorKind> {
+    if let Some(e) = e.downcast_ref::<CatalogError>() {
+        Some(e.classify())
+    } else if let Some(e) = e.downcast_ref::<Arc<CatalogError>>() {
+        Some(e.as_ref().classify())
+    } else if let Some(e) = e.downcast_ref::<Box<CatalogError>>() {
+        Some(e.as_ref().classify())
+    } else if let Some(e) = e.downcast_ref::<SqlxError>() {
+        Some(e.classify())
+    } else if let Some(e) = e.downcast_ref::<Arc<SqlxError>>() {
+        Some(e.as_ref().classify())
+    } else if let Some(e) = e.downcast_ref::<Box<SqlxError>>() {
+        Some(e.as_ref().classify())
+    } else {
+        None
+    }
+}
+
 fn try_recover_unknown<E>(e: &E) -> ErrorKind
 where
     E: std::error::Error,
@@ -296,6 +386,8 @@ where
 mod tests {
     use std::time::Duration;
 
+    use data_types::ParquetFileId;
+
     use super::*;
 
     #[test]
@@ -409,6 +501,84 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_classify_catalog() {
+        // transient catalog errors
+        let transient = || CatalogError::SqlxError {
+            source: SqlxError::PoolTimedOut,
+        };
+        assert_eq!(transient().classify(), ErrorKind::Catalog);
+        assert_eq!(
+            CatalogError::FailedToCommit {
+                source: SqlxError::PoolClosed,
+            }
+            .classify(),
+            ErrorKind::Catalog,
+        );
+        assert_eq!(
+            (Box::new(transient()) as Box<dyn std::error::Error>).classify(),
+            ErrorKind::Catalog,
+        );
+        assert_eq!(
+            (Box::new(Arc::new(transient())) as Box<dyn std::error::Error>).classify(),
+            ErrorKind::Catalog,
+        );
+        assert_eq!(
+            DataFusionError::External(Box::new(transient())).classify(),
+            ErrorKind::Catalog,
+        );
+
+        // bare sqlx errors
+        assert_eq!(SqlxError::PoolTimedOut.classify(), ErrorKind::Catalog);
+        assert_eq!(
+            (Box::new(SqlxError::PoolClosed) as Box<dyn std::error::Error>).classify(),
+            ErrorKind::Catalog,
+        );
+        assert_eq!(
+            DataFusionError::External(Box::new(SqlxError::Io(std::io::Error::new(
+                std::io::ErrorKind::ConnectionReset,
+                "foo"
+            ))))
+            .classify(),
+            ErrorKind::Catalog,
+        );
+        assert_eq!(SqlxError::RowNotFound.classify(), ErrorKind::Unknown);
+
+        // data errors
+        let not_found = || CatalogError::ParquetRecordNotFound {
+            id: ParquetFileId::new(1),
+        };
+        assert_eq!(not_found().classify(), ErrorKind::Unknown);
+        assert_eq!(
+            (Box::new(not_found()) as Box<dyn std::error::Error>).classify(),
+            ErrorKind::Unknown,
+        );
+        assert_eq!(
+            CatalogError::ForeignKeyViolation {
+                source: SqlxError::RowNotFound,
+            }
+            .classify(),
+            ErrorKind::Unknown,
+        );
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        let retryable = ErrorKind::variants()
+            .iter()
+            .filter(|kind| kind.is_retryable())
+            .copied()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            retryable,
+            [
+                ErrorKind::ObjectStore,
+                ErrorKind::Catalog,
+                ErrorKind::ConcurrentModification
+            ]
+        );
+    }
+
     /// [`Elapsed`] has no public constructor, so we need to trigger it.
     fn elapsed() -> Elapsed {
         tokio::runtime::Runtime::new()
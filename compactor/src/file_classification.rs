@@ -50,12 +50,15 @@ impl FileClassification {
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct FilesForProgress {
+    /// Files that are moved to the target level without being rewritten.
     pub upgrade: Vec<ParquetFile>,
+
+    /// Files that are rewritten, either split or compacted.
     pub split_or_compact: FilesToSplitOrCompact,
 }
 
 impl FilesForProgress {
-    // If there are neither files to upgrade nor files to split/compact, there's nothing to do.
+    /// If there are neither files to upgrade nor files to split/compact, there's nothing to do.
     pub fn is_empty(&self) -> bool {
         self.upgrade.is_empty() && matches!(self.split_or_compact, FilesToSplitOrCompact::None(..))
     }
@@ -86,26 +89,37 @@ pub enum FilesToSplitOrCompact {
 /// Reasons why there's nothing to split or compact
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum NoneReason {
+    /// There were no files to begin with.
     NoInputFiles,
+    /// The files are too large to compact but none of them can be split.
     NoFilesToSplitFound,
 }
 
 /// Reasons why there are files to split
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum SplitReason {
+    /// Start-level files overlap with more than one target-level file.
     ReduceOverlap,
+    /// The smallest set of files to compact is too large, so large files are split first.
     ReduceLargeFileSize,
+    /// Files are compacted and the output is split into multiple files.
     CompactAndSplitOutput(CompactReason),
+    /// A single L0 file overlaps with too much other L0 data.
     HighL0OverlapSingleFile,
+    /// The overlapping L0 files are too large in total to compact at once.
     HighL0OverlapTotalBacklog,
+    /// The target-level files overlapping the selected start-level files pull in too many further files.
     StartLevelOverlapsTooBig,
 }
 
 /// Reasons why there are files to compact
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum CompactReason {
+    /// There are many small files that are compacted together.
     ManySmallFiles,
+    /// All files fit into a single compaction.
     TotalSizeLessThanMaxCompactSize,
+    /// A subset of the files fits into a single compaction.
     FoundSubsetLessThanMaxCompactSize,
 }
 
@@ -153,7 +167,9 @@ impl FilesToSplitOrCompact {
 /// File to split and their split times
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct FileToSplit {
+    /// The file to split.
     pub file: ParquetFile,
+    /// Times at which the file is split.
     pub split_times: Vec<i64>,
 }
 
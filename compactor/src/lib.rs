@@ -221,11 +221,23 @@ mod round_info;
 
 // publically expose items needed for testing
 pub use components::{
-    df_planner::panic::PanicDataFusionPlanner, hardcoded::hardcoded_components,
-    namespaces_source::mock::NamespaceWrapper, parquet_files_sink::ParquetFilesSink, Components,
+    compaction_job_stream::once::OnceCompactionJobStream,
+    compaction_jobs_source::mock::MockCompactionJobsSource,
+    df_planner::panic::PanicDataFusionPlanner,
+    hardcoded::hardcoded_components,
+    namespaces_source::mock::NamespaceWrapper,
+    parquet_files_sink::ParquetFilesSink,
+    pause_gate::{mock::MockPauseGate, PauseGate},
+    Components,
+};
+pub use driver::{
+    compact, dry_classify, estimate_output_sizes, CompactionProgress, DryClassification,
 };
-pub use driver::compact;
 pub use error::DynError;
+pub use file_classification::{
+    CompactReason, FileClassification, FileToSplit, FilesForProgress, FilesToSplitOrCompact,
+    NoneReason, SplitReason,
+};
 pub use partition_info::PartitionInfo;
 pub use plan_ir::PlanIR;
 pub use round_info::RoundInfo;
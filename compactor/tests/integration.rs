@@ -1,9 +1,18 @@
 use arrow_util::assert_batches_sorted_eq;
+use compactor::{hardcoded_components, CompactionProgress, MockPauseGate};
 use compactor_test_utils::{format_files, list_object_store, TestSetup};
 use data_types::{CompactionLevel, ParquetFile, PartitionId};
+use datafusion::parquet::basic::Compression;
+use iox_time::Time;
+use metric::{Attributes, DurationHistogram, Metric};
+use parquet_file::{metadata::IoxParquetMetaData, ParquetFilePath};
+use std::{sync::Arc, time::Duration};
+use tokio::sync::watch;
 
 mod layouts;
 
+use layouts::{layout_setup_builder, parquet_builder};
+
 #[tokio::test]
 async fn test_compact_no_file() {
     test_helpers::maybe_start_logging();
@@ -466,6 +475,365 @@ async fn test_partition_fail() {
     .await;
 }
 
+#[tokio::test]
+async fn test_fail_on_concurrent_modification() {
+    test_helpers::maybe_start_logging();
+
+    // Create a test setup with 6 files, using the production error handling
+    let setup = TestSetup::builder()
+        .await
+        .with_files()
+        .await
+        .with_fail_on_concurrent_modification()
+        .with_retryable_errors()
+        .build()
+        .await;
+
+    let file_ids = |files: Vec<ParquetFile>| files.into_iter().map(|f| f.id).collect::<Vec<_>>();
+    let catalog_files_pre = setup.list_by_table().await;
+    assert!(!catalog_files_pre.is_empty());
+
+    setup.run_compact_with_concurrent_modification().await;
+
+    // the compaction round was NOT committed, i.e. no output files were created
+    let catalog_files_post = setup.list_by_table().await;
+    assert_eq!(
+        file_ids(catalog_files_pre.clone()),
+        file_ids(catalog_files_post)
+    );
+
+    // the conflict is retryable, so the partition is NOT skipped...
+    assert_skipped_compactions(&setup, []).await;
+
+    // ...and compacted in a later round
+    setup.run_compact().await;
+    let catalog_files_post = setup.list_by_table_not_to_delete().await;
+    assert_ne!(file_ids(catalog_files_pre), file_ids(catalog_files_post));
+    assert_skipped_compactions(&setup, []).await;
+}
+
+#[tokio::test]
+async fn test_parquet_compression() {
+    test_helpers::maybe_start_logging();
+
+    // Create a test setup with 6 files
+    let setup = TestSetup::builder()
+        .await
+        .with_files()
+        .await
+        .with_parquet_compression(Compression::SNAPPY)
+        .build()
+        .await;
+
+    let input_ids = setup
+        .list_by_table()
+        .await
+        .into_iter()
+        .map(|f| f.id)
+        .collect::<Vec<_>>();
+
+    setup.run_compact().await;
+
+    let output_files = setup
+        .list_by_table_not_to_delete()
+        .await
+        .into_iter()
+        .filter(|f| !input_ids.contains(&f.id))
+        .collect::<Vec<_>>();
+    assert!(!output_files.is_empty());
+
+    for file in output_files {
+        let path = ParquetFilePath::from(&file).object_store_path();
+        let data = setup
+            .catalog
+            .object_store
+            .get(&path)
+            .await
+            .unwrap()
+            .bytes()
+            .await
+            .unwrap();
+        let meta = IoxParquetMetaData::from_file_bytes(data)
+            .unwrap()
+            .expect("file has metadata")
+            .decode()
+            .unwrap();
+
+        for row_group in meta.parquet_row_group_metadata() {
+            for column in row_group.columns() {
+                assert_eq!(column.compression(), Compression::SNAPPY);
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_max_rounds() {
+    test_helpers::maybe_start_logging();
+
+    // Create a test setup with 6 files, which takes 2 rounds to compact (see `test_compact_target_level`)
+    let setup = TestSetup::builder()
+        .await
+        .with_files()
+        .await
+        .with_max_num_files_per_plan(10)
+        .with_min_num_l1_files_to_compact(2)
+        .with_max_rounds(1)
+        .build()
+        .await;
+
+    setup.run_compact().await;
+
+    // only the first round (L0s -> L1s) was run
+    let files = setup.list_by_table_not_to_delete().await;
+    assert_levels(
+        &files,
+        vec![
+            (7, CompactionLevel::FileNonOverlapped),
+            (8, CompactionLevel::FileNonOverlapped),
+        ],
+    );
+
+    assert_skipped_compactions(
+        &setup,
+        [(
+            setup.partition_info.partition_id,
+            "max rounds exceeded: partition still needs compaction after 1 rounds",
+        )],
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_dry_classify() {
+    test_helpers::maybe_start_logging();
+
+    // no files, nothing to do
+    let setup = TestSetup::builder().await.build().await;
+    assert!(setup.dry_classify().await.is_none());
+
+    // Create a test setup with 6 files
+    let setup = TestSetup::builder()
+        .await
+        .with_files()
+        .await
+        .with_max_num_files_per_plan(10)
+        .with_min_num_l1_files_to_compact(2)
+        .build()
+        .await;
+
+    let classification = setup
+        .dry_classify()
+        .await
+        .expect("partition needs compaction");
+
+    // the first round compacts the L0s into L1s
+    assert!(!classification.branches.is_empty());
+    for branch in &classification.branches {
+        assert_eq!(branch.target_level, CompactionLevel::FileNonOverlapped);
+    }
+    let num_files_to_compact: usize = classification
+        .branches
+        .iter()
+        .map(|branch| branch.num_files_to_compact())
+        .sum();
+    assert!(num_files_to_compact > 0);
+
+    // every file is considered exactly once
+    let mut classified_ids = classification
+        .branches
+        .iter()
+        .flat_map(|branch| {
+            branch
+                .files_to_make_progress_on
+                .upgrade
+                .iter()
+                .chain(branch.files_to_make_progress_on.split_or_compact.files())
+                .chain(branch.files_to_keep.iter())
+        })
+        .chain(classification.files_later.iter())
+        .map(|f| f.id.get())
+        .collect::<Vec<_>>();
+    classified_ids.sort();
+    assert_eq!(classified_ids, vec![1, 2, 3, 4, 5, 6]);
+
+    // nothing was compacted
+    let files = setup.list_by_table_not_to_delete().await;
+    assert_levels(
+        &files,
+        vec![
+            (1, CompactionLevel::FileNonOverlapped),
+            (2, CompactionLevel::Initial),
+            (3, CompactionLevel::Initial),
+            (4, CompactionLevel::FileNonOverlapped),
+            (5, CompactionLevel::Initial),
+            (6, CompactionLevel::Initial),
+        ],
+    );
+}
+
+#[tokio::test]
+async fn test_dry_classify_max_num_files_per_plan() {
+    test_helpers::maybe_start_logging();
+
+    let setup = layout_setup_builder()
+        .await
+        .with_max_num_files_per_plan(50)
+        .build()
+        .await;
+
+    // 200 small L0 files that all overlap
+    for i in 0..200 {
+        setup
+            .partition
+            .create_parquet_file(
+                parquet_builder()
+                    .with_min_time(0)
+                    .with_max_time(100)
+                    .with_max_l0_created_at(Time::from_timestamp_nanos(i)),
+            )
+            .await;
+    }
+
+    let classification = setup
+        .dry_classify()
+        .await
+        .expect("partition needs compaction");
+
+    assert!(classification.round_info.is_many_small_files());
+    assert_eq!(classification.branches.len(), 4);
+    for branch in &classification.branches {
+        let num_files = branch.files_to_make_progress_on.upgrade.len()
+            + branch
+                .files_to_make_progress_on
+                .split_or_compact
+                .files()
+                .len()
+            + branch.files_to_keep.len();
+        assert!(num_files <= 50, "branch has {num_files} files");
+    }
+}
+
+#[tokio::test]
+async fn test_components_display() {
+    test_helpers::maybe_start_logging();
+
+    let setup = TestSetup::builder().await.build().await;
+    let components = hardcoded_components(&setup.config);
+
+    let rendered = components.to_string();
+    let lines = rendered.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "components");
+    assert_eq!(lines.len(), 20);
+    assert!(lines[17].starts_with("├── changed_files_filter: "));
+    assert!(lines[18].starts_with("├── pause_gate: noop"));
+    assert!(lines[19].starts_with("└── phase_metrics: metrics"));
+
+    for line in [
+        "├── compaction_job_stream: ",
+        "├── commit: CommitToScheduler",
+        "├── df_planner: ",
+        "├── scratchpad_gen: ",
+    ] {
+        assert!(
+            lines.iter().any(|l| l.starts_with(line)),
+            "missing {line:?} in:\n{rendered}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_pause_gate() {
+    test_helpers::maybe_start_logging();
+
+    // Create a test setup with 6 files
+    let setup = TestSetup::builder().await.with_files().await.build().await;
+    let files_before = setup.list_by_table_not_to_delete().await;
+    assert_eq!(files_before.len(), 6);
+
+    let gate = Arc::new(MockPauseGate::new(false));
+    let compaction = setup.run_compact_with_pause_gate(Arc::clone(&gate) as _);
+    tokio::pin!(compaction);
+
+    // nothing happens while the gate is closed
+    tokio::time::timeout(Duration::from_millis(100), &mut compaction)
+        .await
+        .unwrap_err();
+    assert_eq!(setup.list_by_table_not_to_delete().await, files_before);
+
+    // compaction proceeds once it opens
+    gate.open();
+    compaction.await;
+    assert_ne!(setup.list_by_table_not_to_delete().await, files_before);
+}
+
+#[tokio::test]
+async fn test_phase_metrics() {
+    test_helpers::maybe_start_logging();
+
+    // Create a test setup with 6 files
+    let setup = TestSetup::builder().await.with_files().await.build().await;
+    setup.run_compact().await;
+
+    let metric = setup
+        .config
+        .metric_registry
+        .get_instrument::<Metric<DurationHistogram>>("iox_compactor_phase_duration")
+        .expect("metric registered");
+    for phase in ["classify", "run_plans", "upload", "commit"] {
+        let samples = metric
+            .get_observer(&Attributes::from(&[("phase", phase)]))
+            .expect("phase recorded")
+            .fetch()
+            .sample_count();
+        assert!(samples > 0, "no observation for phase {phase}");
+    }
+}
+
+#[tokio::test]
+async fn test_compaction_progress() {
+    test_helpers::maybe_start_logging();
+
+    // Create a test setup with 6 files
+    let setup = TestSetup::builder().await.with_files().await.build().await;
+
+    let (tx, rx) = watch::channel(CompactionProgress::default());
+    assert_eq!(*rx.borrow(), CompactionProgress::default());
+
+    // the single partition completes
+    setup.run_compact_with_progress(tx).await;
+    assert_eq!(
+        *rx.borrow(),
+        CompactionProgress {
+            partitions_started: 1,
+            partitions_completed: 1,
+            partitions_failed: 0,
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_duplicate_jobs() {
+    test_helpers::maybe_start_logging();
+
+    // Create a test setup with 6 files
+    let setup = TestSetup::builder().await.with_files().await.build().await;
+    let files_before = setup.list_by_table_not_to_delete().await;
+
+    // the source emits the partition twice, but it is only compacted once
+    let (tx, rx) = watch::channel(CompactionProgress::default());
+    setup.run_compact_with_duplicate_jobs(tx).await;
+    assert_eq!(
+        *rx.borrow(),
+        CompactionProgress {
+            partitions_started: 1,
+            partitions_completed: 1,
+            partitions_failed: 0,
+        }
+    );
+    assert_ne!(setup.list_by_table_not_to_delete().await, files_before);
+}
+
 #[tokio::test]
 async fn test_shadow_mode() {
     test_helpers::maybe_start_logging();
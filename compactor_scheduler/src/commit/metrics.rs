@@ -3,7 +3,7 @@ use std::{collections::HashMap, fmt::Display};
 use async_trait::async_trait;
 use data_types::{CompactionLevel, ParquetFile, ParquetFileId, ParquetFileParams, PartitionId};
 use itertools::Itertools;
-use metric::{Registry, U64Histogram, U64HistogramOptions};
+use metric::{Registry, U64Counter, U64Histogram, U64HistogramOptions};
 
 use super::{Commit, Error};
 
@@ -101,6 +101,29 @@ impl Histogram {
     }
 }
 
+/// Counters by the target level of the commit.
+#[derive(Debug)]
+struct TargetLevelCounter(HashMap<CompactionLevel, U64Counter>);
+
+impl TargetLevelCounter {
+    fn new(registry: &Registry, name: &'static str, description: &'static str) -> Self {
+        let metric = registry.register_metric::<U64Counter>(name, description);
+        Self(
+            CompactionLevel::all()
+                .iter()
+                .map(|level| (*level, metric.recorder(&[("target_level", level.name())])))
+                .collect(),
+        )
+    }
+
+    fn inc(&self, target_level: CompactionLevel, v: u64) {
+        self.0
+            .get(&target_level)
+            .expect("all compaction levels covered")
+            .inc(v);
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct MetricsCommitWrapper<T>
 where
@@ -111,6 +134,8 @@ where
     job_files: Histogram,
     job_bytes: Histogram,
     job_rows: Histogram,
+    target_level_files: TargetLevelCounter,
+    target_level_bytes: TargetLevelCounter,
     inner: T,
 }
 
@@ -119,6 +144,8 @@ const METRIC_NAME_FILE_ROWS: &str = "iox_compactor_commit_file_rows";
 const METRIC_NAME_JOB_FILES: &str = "iox_compactor_commit_job_files";
 const METRIC_NAME_JOB_BYTES: &str = "iox_compactor_commit_job_bytes";
 const METRIC_NAME_JOB_ROWS: &str = "iox_compactor_commit_job_rows";
+const METRIC_NAME_TARGET_LEVEL_FILES: &str = "iox_compactor_commit_target_level_files";
+const METRIC_NAME_TARGET_LEVEL_BYTES: &str = "iox_compactor_commit_target_level_bytes";
 
 impl<T> MetricsCommitWrapper<T>
 where
@@ -156,6 +183,16 @@ where
                 "Number of rows committed by the compactor, per job",
                 HistogramType::Rows,
             ),
+            target_level_files: TargetLevelCounter::new(
+                registry,
+                METRIC_NAME_TARGET_LEVEL_FILES,
+                "Number of files created by the compactor, by target level of the commit",
+            ),
+            target_level_bytes: TargetLevelCounter::new(
+                registry,
+                METRIC_NAME_TARGET_LEVEL_BYTES,
+                "Number of bytes written by the compactor, by target level of the commit",
+            ),
             inner,
         }
     }
@@ -227,6 +264,14 @@ where
                 .record(f.row_count as u64);
         }
 
+        // per target level metrics
+        self.target_level_files
+            .inc(target_level, create.len() as u64);
+        self.target_level_bytes.inc(
+            target_level,
+            create.iter().map(|f| f.file_size_bytes as u64).sum::<u64>(),
+        );
+
         // per-partition metrics
         for file_level in CompactionLevel::all() {
             let create = create
@@ -307,7 +352,7 @@ mod tests {
     use crate::commit::mock::{CommitHistoryEntry, MockCommit};
     use assert_matches::assert_matches;
     use iox_tests::{partition_identifier, ParquetFileBuilder};
-    use metric::{assert_histogram, Attributes};
+    use metric::{assert_counter, assert_histogram, Attributes};
     use std::sync::Arc;
 
     #[test]
@@ -390,6 +435,21 @@ mod tests {
             }
         }
 
+        for metric_name in [
+            METRIC_NAME_TARGET_LEVEL_FILES,
+            METRIC_NAME_TARGET_LEVEL_BYTES,
+        ] {
+            for target_level in CompactionLevel::all() {
+                assert_counter!(
+                    registry,
+                    U64Counter,
+                    metric_name,
+                    labels = Attributes::from(&[("target_level", target_level.name())]),
+                    value = 0,
+                );
+            }
+        }
+
         let ids = commit
             .commit(
                 partition_id_1,
@@ -447,6 +507,37 @@ mod tests {
             sum = 30_007,
         );
 
+        assert_counter!(
+            registry,
+            U64Counter,
+            METRIC_NAME_TARGET_LEVEL_FILES,
+            labels = Attributes::from(&[("target_level", "L1")]),
+            value = 1,
+        );
+        assert_counter!(
+            registry,
+            U64Counter,
+            METRIC_NAME_TARGET_LEVEL_BYTES,
+            labels = Attributes::from(&[("target_level", "L1")]),
+            value = 10_016,
+        );
+        for target_level in ["L0", "L2"] {
+            assert_counter!(
+                registry,
+                U64Counter,
+                METRIC_NAME_TARGET_LEVEL_FILES,
+                labels = Attributes::from(&[("target_level", target_level)]),
+                value = 0,
+            );
+            assert_counter!(
+                registry,
+                U64Counter,
+                METRIC_NAME_TARGET_LEVEL_BYTES,
+                labels = Attributes::from(&[("target_level", target_level)]),
+                value = 0,
+            );
+        }
+
         assert_eq!(
             inner.history(),
             vec![
@@ -20,6 +20,9 @@ pub enum ErrorKind {
     /// Could not access the object store.
     ObjectStore,
 
+    /// Could not access the catalog.
+    Catalog,
+
     /// We ran out of memory (OOM).
     OutOfMemory,
 
@@ -37,6 +40,7 @@ impl ErrorKind {
     pub fn name(&self) -> &'static str {
         match self {
             Self::ObjectStore => "object_store",
+            Self::Catalog => "catalog",
             Self::OutOfMemory => "out_of_memory",
             Self::Timeout => "timeout",
             Self::Unknown(_) => "unknown",
@@ -36,7 +36,8 @@ use self::{
     partitions_source::{
         catalog_all::CatalogAllPartitionsSource,
         catalog_to_compact::CatalogToCompactPartitionsSource,
-        filter::FilterPartitionsSourceWrapper, never_skipped::NeverSkippedPartitionsSource,
+        filter::FilterPartitionsSourceWrapper, filtering_metrics::FilteringMetrics,
+        never_skipped::NeverSkippedPartitionsSource,
     },
     partitions_subset_source::skipped::SkippedPartitionsSource,
 };
@@ -85,7 +86,7 @@ impl LocalScheduler {
             config.clone(),
             backoff_config.clone(),
             Arc::clone(&catalog),
-            metrics,
+            Arc::clone(&metrics),
             shadow_mode,
         );
 
@@ -94,6 +95,7 @@ impl LocalScheduler {
             backoff_config.clone(),
             Arc::clone(&catalog),
             Arc::clone(&time_provider),
+            &metrics,
         );
 
         let (partitions_source, commit, partition_done_sink) = Self::build_partition_done_sink(
@@ -118,6 +120,7 @@ impl LocalScheduler {
         backoff_config: BackoffConfig,
         catalog: Arc<dyn Catalog>,
         time_provider: Arc<dyn TimeProvider>,
+        metrics: &metric::Registry,
     ) -> Arc<dyn PartitionsSource> {
         let shard_config = config.shard_config;
 
@@ -145,6 +148,7 @@ impl LocalScheduler {
             partitions_source = Arc::new(NeverSkippedPartitionsSource::new(
                 partitions_source,
                 SkippedPartitionsSource::new(backoff_config, Arc::clone(&catalog)),
+                FilteringMetrics::new(metrics, "never_skipped"),
             ));
         };
 
@@ -163,6 +167,7 @@ impl LocalScheduler {
         Arc::new(FilterPartitionsSourceWrapper::new(
             AndIdOnlyPartitionFilter::new(id_only_partition_filters),
             partitions_source,
+            FilteringMetrics::new(metrics, "filter"),
         ))
     }
 
@@ -40,7 +40,9 @@ impl IdOnlyPartitionFilter for AndIdOnlyPartitionFilter {
 mod tests {
     use std::collections::HashSet;
 
-    use crate::local_scheduler::id_only_partition_filter::by_id::ByIdPartitionFilter;
+    use crate::local_scheduler::id_only_partition_filter::{
+        by_id::ByIdPartitionFilter, by_id_range::ByIdRangePartitionFilter,
+    };
 
     use super::*;
 
@@ -87,4 +89,22 @@ mod tests {
         assert!(!filter.apply(PartitionId::new(3)));
         assert!(!filter.apply(PartitionId::new(4)));
     }
+
+    #[test]
+    fn test_apply_and_range() {
+        let filter = AndIdOnlyPartitionFilter::new(vec![
+            Arc::new(ByIdRangePartitionFilter::new(
+                PartitionId::new(10),
+                PartitionId::new(20),
+            )),
+            Arc::new(ByIdPartitionFilter::new(HashSet::from([
+                PartitionId::new(1),
+                PartitionId::new(15),
+            ]))),
+        ]);
+        assert!(filter.apply(PartitionId::new(15)));
+        assert!(!filter.apply(PartitionId::new(1)));
+        assert!(!filter.apply(PartitionId::new(10)));
+        assert!(!filter.apply(PartitionId::new(21)));
+    }
 }
@@ -0,0 +1,81 @@
+use std::fmt::Display;
+
+use data_types::PartitionId;
+
+use super::IdOnlyPartitionFilter;
+
+/// Apply a range [`IdOnlyPartitionFilter`].
+/// PartitionId must be within the inclusive range `min..=max`.
+#[derive(Debug)]
+pub(crate) struct ByIdRangePartitionFilter {
+    min: PartitionId,
+    max: PartitionId,
+}
+
+impl ByIdRangePartitionFilter {
+    #[cfg(test)]
+    pub(crate) fn new(min: PartitionId, max: PartitionId) -> Self {
+        assert!(
+            min <= max,
+            "min ({min}) must not be greater than max ({max})"
+        );
+        Self { min, max }
+    }
+}
+
+impl Display for ByIdRangePartitionFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "by_id_range")
+    }
+}
+
+impl IdOnlyPartitionFilter for ByIdRangePartitionFilter {
+    fn apply(&self, partition_id: PartitionId) -> bool {
+        (self.min..=self.max).contains(&partition_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            ByIdRangePartitionFilter::new(PartitionId::new(1), PartitionId::new(2)).to_string(),
+            "by_id_range"
+        );
+    }
+
+    #[test]
+    fn test_apply() {
+        let filter = ByIdRangePartitionFilter::new(PartitionId::new(10), PartitionId::new(20));
+
+        // inside
+        assert!(filter.apply(PartitionId::new(15)));
+
+        // boundaries
+        assert!(filter.apply(PartitionId::new(10)));
+        assert!(filter.apply(PartitionId::new(20)));
+
+        // outside
+        assert!(!filter.apply(PartitionId::new(9)));
+        assert!(!filter.apply(PartitionId::new(21)));
+        assert!(!filter.apply(PartitionId::new(-1)));
+    }
+
+    #[test]
+    fn test_single_id() {
+        let filter = ByIdRangePartitionFilter::new(PartitionId::new(5), PartitionId::new(5));
+
+        assert!(filter.apply(PartitionId::new(5)));
+        assert!(!filter.apply(PartitionId::new(4)));
+        assert!(!filter.apply(PartitionId::new(6)));
+    }
+
+    #[test]
+    #[should_panic(expected = "must not be greater than max")]
+    fn test_invalid_range() {
+        ByIdRangePartitionFilter::new(PartitionId::new(2), PartitionId::new(1));
+    }
+}
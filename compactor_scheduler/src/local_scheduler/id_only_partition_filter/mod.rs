@@ -6,6 +6,9 @@ use data_types::PartitionId;
 
 pub(crate) mod and;
 pub(crate) mod by_id;
+pub(crate) mod by_id_range;
+pub(crate) mod not;
+pub(crate) mod or;
 pub(crate) mod shard;
 
 /// Filters partition based on ID.
@@ -0,0 +1,83 @@
+use std::{fmt::Display, sync::Arc};
+
+use data_types::PartitionId;
+
+use super::IdOnlyPartitionFilter;
+
+/// Invert an [`IdOnlyPartitionFilter`].
+#[derive(Debug)]
+pub(crate) struct NotIdOnlyPartitionFilter {
+    inner: Arc<dyn IdOnlyPartitionFilter>,
+}
+
+impl NotIdOnlyPartitionFilter {
+    #[cfg(test)]
+    pub(crate) fn new(inner: Arc<dyn IdOnlyPartitionFilter>) -> Self {
+        Self { inner }
+    }
+}
+
+impl Display for NotIdOnlyPartitionFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "not({})", self.inner)
+    }
+}
+
+impl IdOnlyPartitionFilter for NotIdOnlyPartitionFilter {
+    fn apply(&self, partition_id: PartitionId) -> bool {
+        !self.inner.apply(partition_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::local_scheduler::id_only_partition_filter::{
+        and::AndIdOnlyPartitionFilter, by_id::ByIdPartitionFilter,
+        by_id_range::ByIdRangePartitionFilter,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            NotIdOnlyPartitionFilter::new(Arc::new(ByIdPartitionFilter::new(Default::default())))
+                .to_string(),
+            "not(by_id)",
+        );
+    }
+
+    #[test]
+    fn test_apply() {
+        let filter =
+            NotIdOnlyPartitionFilter::new(Arc::new(ByIdPartitionFilter::new(HashSet::from([
+                PartitionId::new(1),
+                PartitionId::new(10),
+            ]))));
+
+        assert!(!filter.apply(PartitionId::new(1)));
+        assert!(!filter.apply(PartitionId::new(10)));
+        assert!(filter.apply(PartitionId::new(2)));
+    }
+
+    #[test]
+    fn test_apply_except() {
+        // everything in the range except for some IDs
+        let filter = AndIdOnlyPartitionFilter::new(vec![
+            Arc::new(ByIdRangePartitionFilter::new(
+                PartitionId::new(10),
+                PartitionId::new(20),
+            )),
+            Arc::new(NotIdOnlyPartitionFilter::new(Arc::new(
+                ByIdPartitionFilter::new(HashSet::from([PartitionId::new(15)])),
+            ))),
+        ]);
+        assert_eq!(filter.to_string(), "and([by_id_range, not(by_id)])");
+
+        assert!(filter.apply(PartitionId::new(14)));
+        assert!(!filter.apply(PartitionId::new(15)));
+        assert!(!filter.apply(PartitionId::new(21)));
+    }
+}
@@ -0,0 +1,90 @@
+use std::{fmt::Display, sync::Arc};
+
+use data_types::PartitionId;
+
+use super::IdOnlyPartitionFilter;
+
+/// Apply a series of ORed [`IdOnlyPartitionFilter`].
+#[derive(Debug)]
+pub(crate) struct OrIdOnlyPartitionFilter {
+    filters: Vec<Arc<dyn IdOnlyPartitionFilter>>,
+}
+
+impl OrIdOnlyPartitionFilter {
+    /// Create a new [`OrIdOnlyPartitionFilter`] from a series of IdOnlyPartitionFilters
+    #[cfg(test)]
+    pub(crate) fn new(filters: Vec<Arc<dyn IdOnlyPartitionFilter>>) -> Self {
+        Self { filters }
+    }
+}
+
+impl Display for OrIdOnlyPartitionFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "or([")?;
+        for (i, sub) in self.filters.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{sub}")?;
+        }
+        write!(f, "])")
+    }
+}
+
+impl IdOnlyPartitionFilter for OrIdOnlyPartitionFilter {
+    fn apply(&self, partition_id: PartitionId) -> bool {
+        self.filters.iter().any(|filter| filter.apply(partition_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::local_scheduler::id_only_partition_filter::{
+        by_id::ByIdPartitionFilter, by_id_range::ByIdRangePartitionFilter,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(OrIdOnlyPartitionFilter::new(vec![]).to_string(), "or([])",);
+        assert_eq!(
+            OrIdOnlyPartitionFilter::new(vec![
+                Arc::new(ByIdPartitionFilter::new(Default::default())),
+                Arc::new(ByIdRangePartitionFilter::new(
+                    PartitionId::new(1),
+                    PartitionId::new(2)
+                ))
+            ])
+            .to_string(),
+            "or([by_id, by_id_range])",
+        );
+    }
+
+    #[test]
+    fn test_apply_empty() {
+        let filter = OrIdOnlyPartitionFilter::new(vec![]);
+        assert!(!filter.apply(PartitionId::new(1)));
+    }
+
+    #[test]
+    fn test_apply_or() {
+        let filter = OrIdOnlyPartitionFilter::new(vec![
+            Arc::new(ByIdRangePartitionFilter::new(
+                PartitionId::new(10),
+                PartitionId::new(20),
+            )),
+            Arc::new(ByIdPartitionFilter::new(HashSet::from([
+                PartitionId::new(1),
+                PartitionId::new(15),
+            ]))),
+        ]);
+        assert!(filter.apply(PartitionId::new(1)));
+        assert!(filter.apply(PartitionId::new(10)));
+        assert!(filter.apply(PartitionId::new(15)));
+        assert!(!filter.apply(PartitionId::new(2)));
+        assert!(!filter.apply(PartitionId::new(21)));
+    }
+}
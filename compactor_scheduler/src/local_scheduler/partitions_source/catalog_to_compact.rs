@@ -243,6 +243,46 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn watermark_advances() {
+        let catalog = Arc::new(MemCatalog::new(Default::default()));
+        let time_provider = Arc::new(MockProvider::new(catalog.time_provider().now()));
+
+        let partition = PartitionBuilder::new(1)
+            .with_new_file_at(Timestamp::from(time_provider.minutes_ago(2)))
+            .build();
+        catalog.add_partition(partition).await;
+
+        let partitions_source = CatalogToCompactPartitionsSource::new(
+            Default::default(),
+            Arc::clone(&catalog) as _,
+            Duration::from_secs(60 * 10),
+            None,
+            Arc::<MockProvider>::clone(&time_provider) as _,
+        );
+
+        let fetch = || async {
+            let mut ids = partitions_source.fetch().await;
+            ids.sort();
+            ids
+        };
+
+        // the first query finds everything within the lookback and remembers its end time as the watermark
+        let watermark = time_provider.now();
+        assert_eq!(fetch().await, partition_ids(&[1]));
+
+        // a partition gets a new file after the first query
+        let partition = PartitionBuilder::new(2)
+            .with_new_file_at(Timestamp::from(watermark + Duration::from_secs(30)))
+            .build();
+        catalog.add_partition(partition).await;
+
+        // the second query starts at the watermark, so it only returns the newer partition even though the older one
+        // is still within the configured lookback
+        time_provider.inc(Duration::from_secs(60));
+        assert_eq!(fetch().await, partition_ids(&[2]));
+    }
+
     #[tokio::test]
     async fn max_specified() {
         let catalog = Arc::new(MemCatalog::new(Default::default()));
@@ -5,6 +5,8 @@ use data_types::PartitionId;
 
 use crate::{IdOnlyPartitionFilter, PartitionsSource};
 
+use super::filtering_metrics::FilteringMetrics;
+
 /// An implementation of [`PartitionsSource`]
 /// that filters the results of another [`PartitionsSource`].
 #[derive(Debug)]
@@ -15,6 +17,7 @@ where
 {
     filter: F,
     inner: T,
+    metrics: FilteringMetrics,
 }
 
 impl<T, F> FilterPartitionsSourceWrapper<T, F>
@@ -23,8 +26,12 @@ where
     F: IdOnlyPartitionFilter,
 {
     /// Create a new [`FilterPartitionsSourceWrapper`].
-    pub(crate) fn new(filter: F, inner: T) -> Self {
-        Self { filter, inner }
+    pub(crate) fn new(filter: F, inner: T, metrics: FilteringMetrics) -> Self {
+        Self {
+            filter,
+            inner,
+            metrics,
+        }
     }
 }
 
@@ -45,11 +52,63 @@ where
     F: IdOnlyPartitionFilter,
 {
     async fn fetch(&self) -> Vec<PartitionId> {
-        self.inner
-            .fetch()
-            .await
+        let partitions = self.inner.fetch().await;
+        let n_in = partitions.len();
+
+        let partitions = partitions
             .into_iter()
             .filter(|id| self.filter.apply(*id))
-            .collect()
+            .collect::<Vec<_>>();
+        self.metrics.record(n_in, partitions.len());
+
+        partitions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use metric::Registry;
+
+    use crate::MockPartitionsSource;
+
+    use super::{
+        super::{
+            super::id_only_partition_filter::by_id::ByIdPartitionFilter,
+            filtering_metrics::tests::assert_filtering_metrics,
+        },
+        *,
+    };
+
+    #[test]
+    fn test_display() {
+        let source = FilterPartitionsSourceWrapper::new(
+            ByIdPartitionFilter::new(HashSet::default()),
+            MockPartitionsSource::new(vec![]),
+            FilteringMetrics::new(&Registry::new(), "filter"),
+        );
+        assert_eq!(source.to_string(), "filter(by_id, mock)");
+    }
+
+    #[tokio::test]
+    async fn test_fetch() {
+        let p_1 = PartitionId::new(1);
+        let p_2 = PartitionId::new(2);
+        let p_3 = PartitionId::new(3);
+        let p_4 = PartitionId::new(4);
+
+        let registry = Registry::new();
+        let source = FilterPartitionsSourceWrapper::new(
+            ByIdPartitionFilter::new(HashSet::from([p_1, p_3])),
+            MockPartitionsSource::new(vec![p_1, p_2, p_3, p_4]),
+            FilteringMetrics::new(&registry, "filter"),
+        );
+
+        assert_filtering_metrics(&registry, "filter", 0, 0);
+
+        assert_eq!(source.fetch().await, vec![p_1, p_3]);
+
+        assert_filtering_metrics(&registry, "filter", 4, 2);
     }
 }
@@ -0,0 +1,78 @@
+use metric::{Registry, U64Counter};
+
+const METRIC_NAME_PARTITIONS_FILTERED: &str = "iox_compactor_partitions_source_filtered";
+
+/// Counts the partitions entering (`direction="in"`) and leaving (`direction="out"`) a filtering
+/// stage of a [`PartitionsSource`](crate::PartitionsSource), labeled by the name of the stage.
+///
+/// The difference between the two is the number of partitions dropped by that stage.
+#[derive(Debug, Clone)]
+pub(crate) struct FilteringMetrics {
+    partitions_in: U64Counter,
+    partitions_out: U64Counter,
+}
+
+impl FilteringMetrics {
+    /// Create a new [`FilteringMetrics`] for the given stage.
+    pub(crate) fn new(registry: &Registry, stage: &'static str) -> Self {
+        let metric = registry.register_metric::<U64Counter>(
+            METRIC_NAME_PARTITIONS_FILTERED,
+            "Number of partitions entering and leaving a filtering stage of the compactor partitions source. This contains the sum over ALL fetches (i.e. the same partition may be counted multiple times).",
+        );
+
+        Self {
+            partitions_in: metric.recorder(&[("stage", stage), ("direction", "in")]),
+            partitions_out: metric.recorder(&[("stage", stage), ("direction", "out")]),
+        }
+    }
+
+    /// Record that `n_in` partitions entered the stage, of which `n_out` passed it.
+    pub(crate) fn record(&self, n_in: usize, n_out: usize) {
+        self.partitions_in.inc(n_in as u64);
+        self.partitions_out.inc(n_out as u64);
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use metric::{assert_counter, Attributes};
+
+    use super::*;
+
+    /// Assert the in and out counters of the given stage.
+    pub(crate) fn assert_filtering_metrics(
+        registry: &Registry,
+        stage: &'static str,
+        n_in: u64,
+        n_out: u64,
+    ) {
+        assert_counter!(
+            registry,
+            U64Counter,
+            METRIC_NAME_PARTITIONS_FILTERED,
+            labels = Attributes::from(&[("stage", stage), ("direction", "in")]),
+            value = n_in,
+        );
+        assert_counter!(
+            registry,
+            U64Counter,
+            METRIC_NAME_PARTITIONS_FILTERED,
+            labels = Attributes::from(&[("stage", stage), ("direction", "out")]),
+            value = n_out,
+        );
+    }
+
+    #[test]
+    fn test_record() {
+        let registry = Registry::new();
+        let metrics_a = FilteringMetrics::new(&registry, "a");
+        let metrics_b = FilteringMetrics::new(&registry, "b");
+
+        metrics_a.record(4, 2);
+        metrics_a.record(3, 3);
+        metrics_b.record(5, 0);
+
+        assert_filtering_metrics(&registry, "a", 7, 5);
+        assert_filtering_metrics(&registry, "b", 5, 0);
+    }
+}
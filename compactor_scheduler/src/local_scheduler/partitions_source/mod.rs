@@ -6,4 +6,5 @@
 pub(crate) mod catalog_all;
 pub(crate) mod catalog_to_compact;
 pub(crate) mod filter;
+pub(crate) mod filtering_metrics;
 pub(crate) mod never_skipped;
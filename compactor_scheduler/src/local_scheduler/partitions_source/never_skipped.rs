@@ -5,7 +5,9 @@ use data_types::PartitionId;
 
 use crate::PartitionsSource;
 
-use super::super::partitions_subset_source::PartitionsSubsetSource;
+use super::{
+    super::partitions_subset_source::PartitionsSubsetSource, filtering_metrics::FilteringMetrics,
+};
 
 #[derive(Debug)]
 pub(crate) struct NeverSkippedPartitionsSource<T, I>
@@ -17,6 +19,7 @@ where
     skipped_source: T,
     // The inner source of partitions for compaction.
     inner: I,
+    metrics: FilteringMetrics,
 }
 
 impl<T, I> NeverSkippedPartitionsSource<T, I>
@@ -24,10 +27,11 @@ where
     T: PartitionsSubsetSource,
     I: PartitionsSource,
 {
-    pub(crate) fn new(inner: I, skipped_source: T) -> Self {
+    pub(crate) fn new(inner: I, skipped_source: T, metrics: FilteringMetrics) -> Self {
         Self {
             inner,
             skipped_source,
+            metrics,
         }
     }
 }
@@ -52,10 +56,15 @@ where
         let partitions = self.inner.fetch().await;
         let skipped = self.skipped_source.fetch(&partitions).await;
 
-        partitions
+        let n_in = partitions.len();
+
+        let partitions = partitions
             .into_iter()
             .filter(|p| !skipped.contains(p))
-            .collect()
+            .collect::<Vec<_>>();
+        self.metrics.record(n_in, partitions.len());
+
+        partitions
     }
 }
 
@@ -63,10 +72,16 @@ where
 mod tests {
     use std::collections::HashSet;
 
+    use metric::Registry;
+
     use crate::MockPartitionsSource;
 
     use super::{
-        super::super::partitions_subset_source::mock::MockInclusionPartitionsSubsetSource, *,
+        super::{
+            super::partitions_subset_source::mock::MockInclusionPartitionsSubsetSource,
+            filtering_metrics::tests::assert_filtering_metrics,
+        },
+        *,
     };
 
     #[test]
@@ -74,6 +89,7 @@ mod tests {
         let filter = NeverSkippedPartitionsSource::new(
             MockPartitionsSource::new(vec![]),
             MockInclusionPartitionsSubsetSource::new(HashSet::default()),
+            FilteringMetrics::new(&Registry::new(), "never_skipped"),
         );
         assert_eq!(filter.to_string(), "never_skipped(mock)");
     }
@@ -85,12 +101,15 @@ mod tests {
         let p_3 = PartitionId::new(3);
         let p_4 = PartitionId::new(4);
 
+        let registry = Registry::new();
         let skipped = HashSet::from([p_1, p_2]);
         let set_transformer = NeverSkippedPartitionsSource::new(
             MockPartitionsSource::new(vec![p_1, p_2, p_3, p_4]),
             MockInclusionPartitionsSubsetSource::new(skipped),
+            FilteringMetrics::new(&registry, "never_skipped"),
         );
 
         assert_eq!(set_transformer.fetch().await[..], [p_3, p_4],);
+        assert_filtering_metrics(&registry, "never_skipped", 4, 2);
     }
 }
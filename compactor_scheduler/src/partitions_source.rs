@@ -1,6 +1,7 @@
 use std::{
     fmt::{Debug, Display},
     sync::Arc,
+    time::Duration,
 };
 
 use async_trait::async_trait;
@@ -36,6 +37,7 @@ mod mock {
     #[derive(Debug)]
     pub(crate) struct MockPartitionsSource {
         partitions: Mutex<Vec<PartitionId>>,
+        delay: Option<Duration>,
     }
 
     impl MockPartitionsSource {
@@ -43,6 +45,16 @@ mod mock {
         pub(crate) fn new(partitions: Vec<PartitionId>) -> Self {
             Self {
                 partitions: Mutex::new(partitions),
+                delay: None,
+            }
+        }
+
+        /// Sleep for `delay` before returning from [`fetch`](PartitionsSource::fetch), to simulate a slow catalog.
+        #[cfg(test)]
+        pub(crate) fn with_delay(self, delay: Duration) -> Self {
+            Self {
+                delay: Some(delay),
+                ..self
             }
         }
 
@@ -62,6 +74,9 @@ mod mock {
     #[async_trait]
     impl PartitionsSource for MockPartitionsSource {
         async fn fetch(&self) -> Vec<PartitionId> {
+            if let Some(delay) = self.delay {
+                tokio::time::sleep(delay).await;
+            }
             self.partitions.lock().clone()
         }
     }
@@ -89,4 +104,15 @@ mod tests {
         source.set(parts.clone());
         assert_eq!(source.fetch().await, parts,);
     }
+
+    #[tokio::test]
+    async fn test_fetch_with_delay() {
+        let parts = vec![PartitionId::new(5), PartitionId::new(1)];
+        let delay = Duration::from_millis(50);
+        let source = MockPartitionsSource::new(parts.clone()).with_delay(delay);
+
+        let start = tokio::time::Instant::now();
+        assert_eq!(source.fetch().await, parts,);
+        assert!(start.elapsed() >= delay);
+    }
 }
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use data_types::{CompactionLevel, ParquetFileId, ParquetFileParams};
+use datafusion::physical_plan::SendableRecordBatchStream;
+use iox_catalog::interface::Catalog;
+
+use compactor::{DynError, ParquetFilesSink, PartitionInfo, PlanIR};
+
+/// Simulates another compactor that compacts the same files at the same time.
+///
+/// Soft-deletes the input files of every plan in the catalog before handing the plan to the inner sink, so the
+/// compactor finds the files modified when it is about to commit.
+#[derive(Debug)]
+pub struct ConcurrentModificationSink {
+    inner: Arc<dyn ParquetFilesSink>,
+    catalog: Arc<dyn Catalog>,
+}
+
+impl ConcurrentModificationSink {
+    pub fn new(inner: Arc<dyn ParquetFilesSink>, catalog: Arc<dyn Catalog>) -> Self {
+        Self { inner, catalog }
+    }
+}
+
+impl std::fmt::Display for ConcurrentModificationSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "concurrent_modification({})", self.inner)
+    }
+}
+
+#[async_trait]
+impl ParquetFilesSink for ConcurrentModificationSink {
+    async fn stream_into_file_sink(
+        &self,
+        streams: Vec<SendableRecordBatchStream>,
+        partition_info: Arc<PartitionInfo>,
+        target_level: CompactionLevel,
+        plan_ir: &PlanIR,
+    ) -> Result<Vec<ParquetFileParams>, DynError> {
+        let delete = plan_ir
+            .input_parquet_files()
+            .into_iter()
+            .map(|f| f.id)
+            .collect::<Vec<ParquetFileId>>();
+        self.catalog
+            .repositories()
+            .await
+            .parquet_files()
+            .create_upgrade_delete(&delete, &[], &[], CompactionLevel::Initial)
+            .await?;
+
+        self.inner
+            .stream_into_file_sink(streams, partition_info, target_level, plan_ir)
+            .await
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
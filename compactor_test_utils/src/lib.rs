@@ -18,13 +18,14 @@
 use workspace_hack as _;
 
 mod commit_wrapper;
+mod concurrent_modification;
 mod display;
 mod simulator;
 
 pub use display::{display_format, display_size, format_files, format_files_split};
 
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     future::Future,
     num::NonZeroUsize,
     sync::{atomic::AtomicUsize, Arc, Mutex},
@@ -33,17 +34,19 @@ use std::{
 
 use crate::{
     commit_wrapper::{CommitRecorderBuilder, InvariantCheck},
+    concurrent_modification::ConcurrentModificationSink,
     simulator::ParquetFileSimulator,
 };
 use async_trait::async_trait;
 use backoff::BackoffConfig;
 use compactor::{
-    compact, config::Config, hardcoded_components, Components, PanicDataFusionPlanner,
-    PartitionInfo,
+    compact, config::Config, dry_classify, hardcoded_components, CompactionProgress, Components,
+    DryClassification, MockCompactionJobsSource, OnceCompactionJobStream, PanicDataFusionPlanner,
+    PartitionInfo, PauseGate,
 };
-use compactor_scheduler::SchedulerConfig;
+use compactor_scheduler::{CompactionJob, SchedulerConfig};
 use data_types::{ColumnType, CompactionLevel, ParquetFile, TableId};
-use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::{arrow::record_batch::RecordBatch, parquet::basic::Compression};
 use datafusion_util::config::register_iox_object_store;
 use futures::TryStreamExt;
 use iox_catalog::interface::Catalog;
@@ -54,8 +57,12 @@ use iox_tests::{
 };
 use iox_time::{MockProvider, Time, TimeProvider};
 use object_store::{path::Path, DynObjectStore};
-use parquet_file::storage::{ParquetStorage, StorageId};
+use parquet_file::{
+    serialize,
+    storage::{ParquetStorage, StorageId},
+};
 use schema::sort::SortKey;
+use tokio::sync::watch;
 use trace::{RingBufferTraceCollector, TraceCollector};
 use tracker::AsyncSemaphoreMetrics;
 
@@ -139,11 +146,14 @@ impl TestSetupBuilder<false> {
             partition_concurrency: NonZeroUsize::new(1).unwrap(),
             df_concurrency: NonZeroUsize::new(1).unwrap(),
             partition_scratchpad_concurrency: NonZeroUsize::new(1).unwrap(),
+            upload_concurrency: NonZeroUsize::new(1).unwrap(),
             max_desired_file_size_bytes: MAX_DESIRE_FILE_SIZE,
             percentage_max_file_size: PERCENTAGE_MAX_FILE_SIZE,
             split_percentage: SPLIT_PERCENTAGE,
             partition_timeout: Duration::from_secs(3_600),
+            partition_timeout_overrides: HashMap::new(),
             shadow_mode: false,
+            fail_on_concurrent_modification: false,
             enable_scratchpad: true,
             min_num_l1_files_to_compact: MIN_NUM_L1_FILES_TO_COMPACT,
             process_once: true,
@@ -153,6 +163,18 @@ impl TestSetupBuilder<false> {
             max_num_columns_per_table: 200,
             max_num_files_per_plan: 200,
             max_partition_fetch_queries_per_second: None,
+            audit_log_path: None,
+            max_consecutive_failures: None,
+            fetch_batch_size: None,
+            max_rounds: None,
+            webhook_url: None,
+            max_input_files_per_plan: None,
+            parquet_compression: serialize::default_compression(),
+            prioritize_by_l0_count: false,
+            verify_uploads: false,
+            cache_partition_files: false,
+            object_store_error_budget: None,
+            object_store_error_backoff: Duration::from_secs(60),
         };
 
         let bytes_written = Arc::new(AtomicUsize::new(0));
@@ -512,6 +534,19 @@ impl<const WITH_FILES: bool> TestSetupBuilder<WITH_FILES> {
         self
     }
 
+    /// Fail compaction rounds instead of committing if the files were modified concurrently
+    pub fn with_fail_on_concurrent_modification(mut self) -> Self {
+        self.config.fail_on_concurrent_modification = true;
+        self
+    }
+
+    /// Only pass non-retryable errors to the scheduler (and mark the partition as skipped), like production does
+    /// (all errors are fatal by default in tests)
+    pub fn with_retryable_errors(mut self) -> Self {
+        self.config.all_errors_are_fatal = false;
+        self
+    }
+
     /// set min_num_l1_files_to_compact
     pub fn with_min_num_l1_files_to_compact(mut self, min_num_l1_files_to_compact: usize) -> Self {
         self.config.min_num_l1_files_to_compact = min_num_l1_files_to_compact;
@@ -565,6 +600,25 @@ impl<const WITH_FILES: bool> TestSetupBuilder<WITH_FILES> {
         self
     }
 
+    /// Set the maximum number of compaction rounds per partition
+    pub fn with_max_rounds(mut self, max_rounds: usize) -> Self {
+        self.config.max_rounds = Some(NonZeroUsize::new(max_rounds).unwrap());
+        self
+    }
+
+    /// Set max_input_files_per_plan
+    pub fn with_max_input_files_per_plan(mut self, max_input_files_per_plan: usize) -> Self {
+        self.config.max_input_files_per_plan =
+            Some(NonZeroUsize::new(max_input_files_per_plan).unwrap());
+        self
+    }
+
+    /// Set the compression codec of the parquet files written by the compactor
+    pub fn with_parquet_compression(mut self, parquet_compression: Compression) -> Self {
+        self.config.parquet_compression = parquet_compression;
+        self
+    }
+
     /// Create a [`TestSetup`]
     pub async fn build(self) -> TestSetup {
         let candidate_partition = Arc::new(PartitionInfo {
@@ -660,7 +714,25 @@ impl TestSetup {
     /// Run compaction job saving simulator state, if any
     pub async fn run_compact(&self) -> CompactResult {
         let components = hardcoded_components(&self.config);
-        self.run_compact_impl(Arc::clone(&components)).await
+        self.run_compact_impl(Arc::clone(&components), None).await
+    }
+
+    /// Run compaction job reporting the overall progress to `progress`
+    pub async fn run_compact_with_progress(
+        &self,
+        progress: watch::Sender<CompactionProgress>,
+    ) -> CompactResult {
+        let components = hardcoded_components(&self.config);
+        self.run_compact_impl(Arc::clone(&components), Some(progress))
+            .await
+    }
+
+    /// Classify the files of the first round without compacting them
+    pub async fn dry_classify(&self) -> Option<DryClassification> {
+        let components = hardcoded_components(&self.config);
+        dry_classify(&components, self.partition_info.partition_id)
+            .await
+            .expect("dry classify failed")
     }
 
     /// run a compaction plan where the df planner will panic
@@ -670,10 +742,56 @@ impl TestSetup {
             df_planner: Arc::new(PanicDataFusionPlanner::new()),
             ..components.as_ref().clone()
         });
-        self.run_compact_impl(components).await
+        self.run_compact_impl(components, None).await
+    }
+
+    /// run a compaction plan that waits for the given pause gate before compacting the partition
+    pub async fn run_compact_with_pause_gate(
+        &self,
+        pause_gate: Arc<dyn PauseGate>,
+    ) -> CompactResult {
+        let components = hardcoded_components(&self.config);
+        let components = Arc::new(Components {
+            pause_gate,
+            ..components.as_ref().clone()
+        });
+        self.run_compact_impl(components, None).await
     }
 
-    async fn run_compact_impl(&self, components: Arc<Components>) -> CompactResult {
+    /// run a compaction where the job stream emits the partition twice, reporting the overall progress to `progress`
+    pub async fn run_compact_with_duplicate_jobs(
+        &self,
+        progress: watch::Sender<CompactionProgress>,
+    ) -> CompactResult {
+        let components = hardcoded_components(&self.config);
+        let job = CompactionJob::new(self.partition_info.partition_id);
+        let components = Arc::new(Components {
+            compaction_job_stream: Arc::new(OnceCompactionJobStream::new(
+                MockCompactionJobsSource::new(vec![job.clone(), job]),
+            )),
+            ..components.as_ref().clone()
+        });
+        self.run_compact_impl(components, Some(progress)).await
+    }
+
+    /// run a compaction job while another process soft-deletes the input files of every plan
+    pub async fn run_compact_with_concurrent_modification(&self) -> CompactResult {
+        let components = hardcoded_components(&self.config);
+        let components = Arc::new(Components {
+            parquet_files_sink: Arc::new(ConcurrentModificationSink::new(
+                Arc::clone(&components.parquet_files_sink),
+                Arc::clone(&self.catalog.catalog),
+            )),
+            ..components.as_ref().clone()
+        });
+        self.run_compact_impl(components, None).await
+    }
+
+    async fn run_compact_impl(
+        &self,
+        components: Arc<Components>,
+        progress: Option<watch::Sender<CompactionProgress>>,
+    ) -> CompactResult {
         // clear any existing log entries, if any
         self.run_log.lock().unwrap().clear();
 
@@ -699,9 +817,14 @@ impl TestSetup {
         compact(
             trace_collector,
             NonZeroUsize::new(10).unwrap(),
+            config.fetch_batch_size,
             config.partition_timeout,
+            &config.partition_timeout_overrides,
+            config.max_rounds,
+            config.upload_concurrency,
             df_semaphore,
             &components,
+            progress,
         )
         .await;
 
@@ -491,6 +491,81 @@ pub struct SkippedCompaction {
     pub limit_num_files_first_in_partition: i64,
 }
 
+impl SkippedCompaction {
+    /// Classify the free-form [`reason`](Self::reason) of this record.
+    pub fn reason_kind(&self) -> SkippedCompactionReason {
+        SkippedCompactionReason::parse(&self.reason)
+    }
+}
+
+/// Aggregated statistics of the parquet files of a partition that are not marked for deletion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, sqlx::FromRow)]
+pub struct PartitionFileStats {
+    /// the partition
+    pub partition_id: PartitionId,
+    /// number of [`CompactionLevel::Initial`] files
+    pub num_l0_files: i64,
+    /// total size of all files, in bytes
+    pub total_file_size_bytes: i64,
+}
+
+/// Coarse classification of the reason why compaction of a partition was skipped.
+///
+/// The catalog only stores the reason as text, so this is derived from that string on a best-effort basis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SkippedCompactionReason {
+    /// Compaction did not make any progress within the partition timeout.
+    Timeout,
+    /// The partition has more (or larger) files than a single compaction plan may contain.
+    TooManyFiles,
+    /// Compaction ran out of memory or another resource.
+    ResourceExhausted,
+    /// The partition was skipped by an operator.
+    Manual,
+    /// Any other reason.
+    Unknown,
+}
+
+impl SkippedCompactionReason {
+    /// Classify a reason string as stored in the catalog.
+    ///
+    /// Unrecognized strings map to [`Unknown`](Self::Unknown).
+    pub fn parse(reason: &str) -> Self {
+        let reason = reason.trim().to_lowercase();
+
+        if reason.starts_with("manual") {
+            Self::Manual
+        } else if reason.starts_with("timeout") || reason.contains("deadline has elapsed") {
+            Self::Timeout
+        } else if reason.contains("too many files")
+            || reason.contains("exceed max compact size limit")
+        {
+            Self::TooManyFiles
+        } else if reason.contains("resources exhausted") || reason.contains("out of memory") {
+            Self::ResourceExhausted
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// Short, stable name of this reason, e.g. for metrics and logs.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Timeout => "timeout",
+            Self::TooManyFiles => "too_many_files",
+            Self::ResourceExhausted => "resource_exhausted",
+            Self::Manual => "manual",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+impl Display for SkippedCompactionReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
 use generated_types::influxdata::iox::compactor::v1 as compactor_proto;
 impl From<SkippedCompaction> for compactor_proto::SkippedCompaction {
     fn from(skipped_compaction: SkippedCompaction) -> Self {
@@ -2609,4 +2684,57 @@ mod tests {
         assert_eq!(tr.start(), 1);
         assert_eq!(tr.end(), 1);
     }
+
+    #[test]
+    fn test_skipped_compaction_reason() {
+        let cases = [
+            (
+                "timeout without making any progress",
+                SkippedCompactionReason::Timeout,
+            ),
+            ("deadline has elapsed", SkippedCompactionReason::Timeout),
+            (
+                "partition 1 has overlapped files that exceed max compact size limit 10. \
+                This may happen if a large amount of data has the same timestamp",
+                SkippedCompactionReason::TooManyFiles,
+            ),
+            (
+                "too many files: 1000",
+                SkippedCompactionReason::TooManyFiles,
+            ),
+            (
+                "Resources exhausted: Failed to allocate additional 1024 bytes",
+                SkippedCompactionReason::ResourceExhausted,
+            ),
+            (
+                "Manually skipped by operator",
+                SkippedCompactionReason::Manual,
+            ),
+            (
+                "serialize\ncaused by\nJoin Error (panic)",
+                SkippedCompactionReason::Unknown,
+            ),
+            ("", SkippedCompactionReason::Unknown),
+            ("\u{0}\u{fffd}garbage", SkippedCompactionReason::Unknown),
+        ];
+
+        for (reason, expected) in cases {
+            let skipped = SkippedCompaction {
+                partition_id: PartitionId::new(1),
+                reason: reason.to_string(),
+                skipped_at: Timestamp::new(0),
+                estimated_bytes: 0,
+                limit_bytes: 0,
+                num_files: 0,
+                limit_num_files: 0,
+                limit_num_files_first_in_partition: 0,
+            };
+            assert_eq!(skipped.reason_kind(), expected, "reason: {reason:?}");
+        }
+
+        assert_eq!(
+            SkippedCompactionReason::TooManyFiles.to_string(),
+            "too_many_files"
+        );
+    }
 }
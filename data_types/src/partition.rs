@@ -4,7 +4,7 @@ use super::{TableId, Timestamp};
 
 use schema::sort::SortKey;
 use sha2::Digest;
-use std::{fmt::Display, sync::Arc};
+use std::{fmt::Display, num::ParseIntError, str::FromStr, sync::Arc};
 use thiserror::Error;
 
 /// Unique ID for a `Partition` during the transition from catalog-assigned sequential
@@ -117,6 +117,34 @@ impl std::fmt::Display for PartitionId {
     }
 }
 
+/// Reasons a string isn't a valid `PartitionId`.
+#[derive(Debug, Error)]
+pub enum PartitionIdParseError {
+    /// The string is not a valid integer.
+    #[error("Could not interpret {input:?} as `PartitionId`: {source}")]
+    InvalidInteger {
+        /// The string used in the attempt to create a `PartitionId`
+        input: String,
+        /// The underlying parse error
+        source: ParseIntError,
+    },
+}
+
+/// Parses the format produced by [`Display`], i.e. the plain integer ID.
+impl FromStr for PartitionId {
+    type Err = PartitionIdParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.trim()
+            .parse()
+            .map(Self)
+            .map_err(|source| PartitionIdParseError::InvalidInteger {
+                input: s.to_owned(),
+                source,
+            })
+    }
+}
+
 /// Defines a partition via an arbitrary string within a table within
 /// a namespace.
 ///
@@ -479,6 +507,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn partition_id_display_parse_roundtrip() {
+        for id in [0, 1, 42, -1, i64::MAX, i64::MIN] {
+            let id = PartitionId::new(id);
+            assert_eq!(id.to_string().parse::<PartitionId>().unwrap(), id);
+        }
+
+        assert_eq!(" 7\n".parse::<PartitionId>().unwrap(), PartitionId::new(7));
+    }
+
+    #[test]
+    fn partition_id_parse_invalid() {
+        for input in ["", "abc", "1.5", "0x10", "1 2", "99999999999999999999"] {
+            let err = input.parse::<PartitionId>().unwrap_err();
+            assert!(
+                err.to_string()
+                    .starts_with(&format!("Could not interpret {input:?} as `PartitionId`")),
+                "unexpected error for {input:?}: {err}"
+            );
+        }
+    }
+
     proptest! {
         #[test]
         fn partition_hash_id_representations(
@@ -0,0 +1,312 @@
+//! Audit logging of incoming FlightSQL commands
+//!
+//! Query text may contain sensitive values (e.g. `WHERE email = 'jane@example.com'`), so all string literals are
+//! masked before the text is logged. SQL is masked using the SQL tokenizer, InfluxQL is parsed so that regular
+//! expression literals (e.g. `WHERE email =~ /jane@.*/`) are masked as well.
+use std::{convert::Infallible, sync::Arc};
+
+use arrow::datatypes::SchemaRef;
+use bytes::Bytes;
+use datafusion::{
+    physical_plan::ExecutionPlan,
+    sql::sqlparser::{
+        dialect::GenericDialect,
+        tokenizer::{Token, Tokenizer},
+    },
+};
+use influxdb_influxql_parser::{
+    literal::Literal,
+    parse_statements,
+    visit_mut::{Recursion, VisitableMut, VisitorMut},
+};
+use iox_query::{exec::IOxSessionContext, QueryNamespace};
+use observability_deps::tracing::info;
+
+use crate::{cache::MetadataCache, error::Result, FlightSQLCommand, FlightSQLPlanner};
+
+/// Replacement for the value of string and regular expression literals in redacted query text.
+const REDACTED_VALUE: &str = "***";
+
+/// Replacement for SQL string literals in redacted query text.
+const REDACTED_LITERAL: &str = "'***'";
+
+/// Logged instead of the query text if it cannot be tokenized, since we cannot tell which parts are literals.
+const UNPARSEABLE_QUERY: &str = "<unparseable query>";
+
+/// Wrapper around [`FlightSQLPlanner`] that logs every command with its namespace and redacted query text before
+/// handing it on, to provide an audit trail of FlightSQL requests.
+#[derive(Debug, Default)]
+pub struct AuditFlightSQLPlanner {}
+
+impl AuditFlightSQLPlanner {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Logs and forwards to [`FlightSQLPlanner::get_schema`].
+    pub async fn get_schema(
+        namespace_name: impl Into<String> + Send,
+        cmd: FlightSQLCommand,
+        ctx: &IOxSessionContext,
+    ) -> Result<SchemaRef> {
+        let namespace_name = namespace_name.into();
+        log_command(&namespace_name, "GetFlightInfo", &cmd);
+        FlightSQLPlanner::get_schema(namespace_name, cmd, ctx).await
+    }
+
+    /// Logs and forwards to [`FlightSQLPlanner::do_get`].
+    pub async fn do_get(
+        namespace_name: impl Into<String> + Send,
+        database: Arc<dyn QueryNamespace>,
+        cmd: FlightSQLCommand,
+        metadata_cache: &MetadataCache,
+        ctx: &IOxSessionContext,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let namespace_name = namespace_name.into();
+        log_command(&namespace_name, "DoGet", &cmd);
+        FlightSQLPlanner::do_get(namespace_name, database, cmd, metadata_cache, ctx).await
+    }
+
+    /// Logs and forwards to [`FlightSQLPlanner::do_action`].
+    pub async fn do_action(
+        namespace_name: impl Into<String> + Send,
+        database: Arc<dyn QueryNamespace>,
+        cmd: FlightSQLCommand,
+        ctx: &IOxSessionContext,
+    ) -> Result<Bytes> {
+        let namespace_name = namespace_name.into();
+        log_command(&namespace_name, "DoAction", &cmd);
+        FlightSQLPlanner::do_action(namespace_name, database, cmd, ctx).await
+    }
+
+    /// Logs and forwards to [`FlightSQLPlanner::do_put`].
+    pub async fn do_put(
+        namespace_name: impl Into<String> + Send,
+        database: Arc<dyn QueryNamespace>,
+        cmd: FlightSQLCommand,
+        ctx: &IOxSessionContext,
+    ) -> Result<Bytes> {
+        let namespace_name = namespace_name.into();
+        log_command(&namespace_name, "DoPut", &cmd);
+        FlightSQLPlanner::do_put(namespace_name, database, cmd, ctx).await
+    }
+}
+
+fn log_command(namespace_name: &str, method: &'static str, cmd: &FlightSQLCommand) {
+    match redacted_query_text(cmd) {
+        Some(query) => info!(
+            %namespace_name,
+            method,
+            cmd = cmd.name(),
+            %query,
+            "FlightSQL command",
+        ),
+        None => info!(%namespace_name, method, cmd = cmd.name(), "FlightSQL command"),
+    }
+}
+
+/// Returns a description of `cmd` that is safe to log, i.e. its name and the redacted query text it carries.
+pub fn redact_command(cmd: &FlightSQLCommand) -> String {
+    match redacted_query_text(cmd) {
+        Some(query) => format!("{}{}", cmd.name(), query),
+        None => cmd.to_string(),
+    }
+}
+
+/// Returns the redacted query text carried by `cmd`, if any.
+fn redacted_query_text(cmd: &FlightSQLCommand) -> Option<String> {
+    match cmd {
+        FlightSQLCommand::CommandInfluxQLStatementQuery(cmd) => Some(redact_influxql(&cmd.query)),
+        cmd => query_text(cmd).map(redact_query),
+    }
+}
+
+/// Returns the query text carried by `cmd`, if any.
+fn query_text(cmd: &FlightSQLCommand) -> Option<&str> {
+    match cmd {
+        FlightSQLCommand::CommandStatementQuery(cmd) => Some(&cmd.query),
+        FlightSQLCommand::CommandInfluxQLStatementQuery(cmd) => Some(&cmd.query),
+        FlightSQLCommand::CommandPreparedStatementQuery(handle)
+        | FlightSQLCommand::ActionClosePreparedStatementRequest(handle) => Some(handle.query()),
+        FlightSQLCommand::CommandStatementUpdate(cmd) => Some(&cmd.query),
+        FlightSQLCommand::ActionCreatePreparedStatementRequest(cmd) => Some(&cmd.query),
+        FlightSQLCommand::CommandGetSqlInfo(_)
+        | FlightSQLCommand::CommandGetCatalogs(_)
+        | FlightSQLCommand::CommandGetCrossReference(_)
+        | FlightSQLCommand::CommandGetDbSchemas(_)
+        | FlightSQLCommand::CommandGetExportedKeys(_)
+        | FlightSQLCommand::CommandGetImportedKeys(_)
+        | FlightSQLCommand::CommandGetPrimaryKeys(_)
+        | FlightSQLCommand::CommandGetTables(_)
+        | FlightSQLCommand::CommandGetXdbcTypeInfo(_)
+        | FlightSQLCommand::CommandGetTableTypes(_)
+        | FlightSQLCommand::ActionBeginTransactionRequest(_)
+        | FlightSQLCommand::ActionEndTransactionRequest(_) => None,
+    }
+}
+
+/// Returns `query` with every string literal replaced by [`REDACTED_LITERAL`].
+///
+/// Identifiers (including quoted ones), keywords and numbers are kept.
+pub fn redact_query(query: &str) -> String {
+    let dialect = GenericDialect {};
+    let Ok(tokens) = Tokenizer::new(&dialect, query).tokenize() else {
+        return UNPARSEABLE_QUERY.to_string();
+    };
+
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            Token::SingleQuotedString(_)
+            | Token::DoubleQuotedString(_)
+            | Token::DollarQuotedString(_)
+            | Token::NationalStringLiteral(_)
+            | Token::EscapedStringLiteral(_)
+            | Token::HexStringLiteral(_)
+            | Token::SingleQuotedByteStringLiteral(_)
+            | Token::DoubleQuotedByteStringLiteral(_)
+            | Token::RawStringLiteral(_) => REDACTED_LITERAL.to_string(),
+            token => token.to_string(),
+        })
+        .collect()
+}
+
+/// Returns the InfluxQL `query` with the value of every string and regular expression literal replaced by
+/// [`REDACTED_VALUE`].
+///
+/// The query is parsed, so the result is the normalized form of the statements rather than the original text.
+pub fn redact_influxql(query: &str) -> String {
+    let Ok(mut statements) = parse_statements(query) else {
+        return UNPARSEABLE_QUERY.to_string();
+    };
+
+    statements
+        .iter_mut()
+        .map(|statement| {
+            statement
+                .accept(&mut RedactLiterals)
+                .expect("redacting literals is infallible");
+            statement.to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Replaces the value of string and regular expression literals by [`REDACTED_VALUE`].
+struct RedactLiterals;
+
+impl VisitorMut for RedactLiterals {
+    type Error = Infallible;
+
+    fn pre_visit_literal(&mut self, n: &mut Literal) -> Result<Recursion, Self::Error> {
+        match n {
+            Literal::String(v) => *v = REDACTED_VALUE.to_string(),
+            Literal::Regex(v) => *v = REDACTED_VALUE.into(),
+            _ => {}
+        }
+        Ok(Recursion::Continue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow_flight::sql::CommandStatementQuery;
+    use test_helpers::tracing::TracingCapture;
+
+    use crate::CommandInfluxQLStatementQuery;
+
+    use super::*;
+
+    #[test]
+    fn test_redact_query() {
+        assert_eq!(
+            redact_query("SELECT * FROM cpu WHERE host = 'alice.example.com' AND usage > 0.5"),
+            "SELECT * FROM cpu WHERE host = '***' AND usage > 0.5"
+        );
+        assert_eq!(
+            redact_query(r#"SELECT "my field" FROM "my table" WHERE name = 'it''s me'"#),
+            r#"SELECT "my field" FROM "my table" WHERE name = '***'"#
+        );
+        assert_eq!(redact_query("SELECT 1"), "SELECT 1");
+        assert_eq!(redact_query("SELECT 'unterminated"), UNPARSEABLE_QUERY);
+    }
+
+    #[test]
+    fn test_redact_influxql() {
+        assert_eq!(
+            redact_influxql(
+                "SELECT usage FROM cpu WHERE host = 'alice.example.com' AND usage > 0.5"
+            ),
+            "SELECT usage FROM cpu WHERE host = '***' AND usage > 0.5"
+        );
+        assert_eq!(
+            redact_influxql("SELECT usage FROM cpu WHERE host =~ /alice\\.example\\.com/"),
+            "SELECT usage FROM cpu WHERE host =~ /***/"
+        );
+        assert_eq!(
+            redact_influxql("SHOW MEASUREMENTS; SELECT usage FROM cpu WHERE host !~ /bob/"),
+            "SHOW MEASUREMENTS; SELECT usage FROM cpu WHERE host !~ /***/"
+        );
+        assert_eq!(redact_influxql("SELECT 'unterminated"), UNPARSEABLE_QUERY);
+    }
+
+    #[test]
+    fn test_redact_command() {
+        assert_eq!(
+            redact_command(&FlightSQLCommand::CommandStatementQuery(
+                CommandStatementQuery {
+                    query: "SELECT * FROM users WHERE email = 'jane@example.com'".to_string(),
+                    transaction_id: None,
+                }
+            )),
+            "CommandStatementQuerySELECT * FROM users WHERE email = '***'"
+        );
+        assert_eq!(
+            redact_command(&FlightSQLCommand::CommandInfluxQLStatementQuery(
+                CommandInfluxQLStatementQuery {
+                    query: "SELECT * FROM users WHERE email =~ /jane@.*/".to_string(),
+                }
+            )),
+            "CommandInfluxQLStatementQuerySELECT * FROM users WHERE email =~ /***/"
+        );
+        assert_eq!(
+            redact_command(&FlightSQLCommand::CommandGetTableTypes(Default::default())),
+            "CommandGetTableTypes"
+        );
+    }
+
+    #[test]
+    fn test_log_command() {
+        let capture = TracingCapture::new();
+
+        log_command(
+            "my_db",
+            "DoGet",
+            &FlightSQLCommand::CommandStatementQuery(CommandStatementQuery {
+                query: "SELECT * FROM users WHERE email = 'jane@example.com'".to_string(),
+                transaction_id: None,
+            }),
+        );
+        log_command(
+            "my_db",
+            "DoGet",
+            &FlightSQLCommand::CommandInfluxQLStatementQuery(CommandInfluxQLStatementQuery {
+                query: "SELECT * FROM users WHERE email = 'jane@example.com'".to_string(),
+            }),
+        );
+        log_command(
+            "my_db",
+            "GetFlightInfo",
+            &FlightSQLCommand::CommandGetTableTypes(Default::default()),
+        );
+
+        let logs = capture.to_string();
+        assert!(!logs.contains("jane@example.com"), "{logs}");
+        assert_eq!(
+            logs,
+            "level = INFO; message = FlightSQL command; namespace_name = my_db; method = \"DoGet\"; cmd = \"CommandStatementQuery\"; query = SELECT * FROM users WHERE email = '***'; \n\
+             level = INFO; message = FlightSQL command; namespace_name = my_db; method = \"DoGet\"; cmd = \"CommandInfluxQLStatementQuery\"; query = SELECT * FROM users WHERE email = '***'; \n\
+             level = INFO; message = FlightSQL command; namespace_name = my_db; method = \"GetFlightInfo\"; cmd = \"CommandGetTableTypes\"; "
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! Cache for FlightSQL metadata results
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use arrow::record_batch::RecordBatch;
+use iox_time::{Time, TimeProvider};
+use observability_deps::tracing::debug;
+
+use crate::error::Result;
+
+/// Identifies a cached metadata result.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum MetadataCacheKey {
+    /// Result of `CommandGetCatalogs`
+    Catalogs { namespace_name: String },
+    /// Result of `CommandGetDbSchemas`
+    DbSchemas {
+        namespace_name: String,
+        catalog: Option<String>,
+        db_schema_filter_pattern: Option<String>,
+    },
+}
+
+/// Caches the record batches of catalog and db-schema introspection requests per namespace.
+///
+/// These rarely change but are requested by every (re)connecting client, so entries are kept for `ttl` before
+/// being recomputed. The cache is owned by the server and passed to [`FlightSQLPlanner::do_get`].
+///
+/// [`FlightSQLPlanner::do_get`]: crate::FlightSQLPlanner::do_get
+#[derive(Debug)]
+pub struct MetadataCache {
+    time_provider: Arc<dyn TimeProvider>,
+    ttl: Duration,
+    entries: Mutex<HashMap<MetadataCacheKey, (Time, RecordBatch)>>,
+    misses: AtomicU64,
+}
+
+impl MetadataCache {
+    pub fn new(time_provider: Arc<dyn TimeProvider>, ttl: Duration) -> Self {
+        Self {
+            time_provider,
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Return the cached batch for `key` or compute (and cache) it using `f`.
+    ///
+    /// Errors are NOT cached.
+    pub(crate) fn get_or_try_insert_with<F>(
+        &self,
+        key: MetadataCacheKey,
+        f: F,
+    ) -> Result<RecordBatch>
+    where
+        F: FnOnce() -> Result<RecordBatch>,
+    {
+        let now = self.time_provider.now();
+
+        if let Some((expires_at, batch)) = self.entries.lock().expect("not poisoned").get(&key) {
+            if *expires_at > now {
+                debug!(?key, "FlightSQL metadata cache hit");
+                return Ok(batch.clone());
+            }
+        }
+
+        debug!(?key, "FlightSQL metadata cache miss");
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let batch = f()?;
+
+        let mut entries = self.entries.lock().expect("not poisoned");
+        entries.retain(|_, (expires_at, _)| *expires_at > now);
+        entries.insert(key, (now + self.ttl, batch.clone()));
+
+        Ok(batch)
+    }
+
+    /// Number of times a result had to be computed.
+    #[cfg(test)]
+    pub(crate) fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::{
+        array::{ArrayRef, StringArray},
+        datatypes::{DataType, Field, Schema},
+    };
+    use iox_time::MockProvider;
+
+    use super::*;
+
+    fn batch(s: &str) -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![Field::new("s", DataType::Utf8, false)]));
+        let array = Arc::new(StringArray::from_iter_values([s])) as ArrayRef;
+        RecordBatch::try_new(schema, vec![array]).unwrap()
+    }
+
+    fn key(namespace_name: &str) -> MetadataCacheKey {
+        MetadataCacheKey::Catalogs {
+            namespace_name: namespace_name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_ttl() {
+        let time_provider = Arc::new(MockProvider::new(Time::MIN));
+        let cache = MetadataCache::new(Arc::clone(&time_provider) as _, Duration::from_secs(10));
+        let get = |k: &str, v: &str| {
+            cache
+                .get_or_try_insert_with(key(k), || Ok(batch(v)))
+                .unwrap()
+        };
+
+        assert_eq!(get("a", "1"), batch("1"));
+        assert_eq!(get("a", "2"), batch("1"));
+        assert_eq!(get("b", "3"), batch("3"));
+        assert_eq!(cache.misses(), 2);
+
+        // errors are not cached
+        cache
+            .get_or_try_insert_with(key("c"), || {
+                Err(crate::Error::UnsupportedMessageType {
+                    description: "foo".to_string(),
+                })
+            })
+            .unwrap_err();
+        assert_eq!(get("c", "4"), batch("4"));
+
+        // expires
+        time_provider.inc(Duration::from_secs(10));
+        assert_eq!(get("a", "5"), batch("5"));
+        assert_eq!(get("c", "6"), batch("6"));
+        assert_eq!(cache.misses(), 6);
+    }
+}
@@ -3,17 +3,24 @@
 use std::fmt::Display;
 
 use arrow_flight::sql::{
-    ActionClosePreparedStatementRequest, ActionCreatePreparedStatementRequest, Any,
-    CommandGetCatalogs, CommandGetCrossReference, CommandGetDbSchemas, CommandGetExportedKeys,
-    CommandGetImportedKeys, CommandGetPrimaryKeys, CommandGetSqlInfo, CommandGetTableTypes,
-    CommandGetTables, CommandGetXdbcTypeInfo, CommandPreparedStatementQuery, CommandStatementQuery,
+    ActionBeginTransactionRequest, ActionClosePreparedStatementRequest,
+    ActionCreatePreparedStatementRequest, ActionEndTransactionRequest, Any, CommandGetCatalogs,
+    CommandGetCrossReference, CommandGetDbSchemas, CommandGetExportedKeys, CommandGetImportedKeys,
+    CommandGetPrimaryKeys, CommandGetSqlInfo, CommandGetTableTypes, CommandGetTables,
+    CommandGetXdbcTypeInfo, CommandPreparedStatementQuery, CommandStatementQuery,
+    CommandStatementUpdate, ProstMessageExt,
 };
 use bytes::Bytes;
 use prost::Message;
-use snafu::ResultExt;
+use snafu::{ensure, ResultExt};
 
 use crate::error::*;
 
+/// First byte of handles that carry the dataset schema.
+///
+/// Handles without a schema are the plain UTF-8 query text, which can never start with this byte.
+const HANDLE_WITH_SCHEMA_MARKER: u8 = 0xFF;
+
 /// Represents a prepared statement "handle". IOx passes all state
 /// required to run the prepared statement back and forth to the
 /// client, so any querier instance can run it
@@ -21,11 +28,27 @@ use crate::error::*;
 pub struct PreparedStatementHandle {
     /// The raw SQL query text
     query: String,
+
+    /// The IPC encoded schema that was returned to the client when the
+    /// statement was prepared, if any.
+    dataset_schema: Option<Bytes>,
 }
 
 impl PreparedStatementHandle {
     pub fn new(query: String) -> Self {
-        Self { query }
+        Self {
+            query,
+            dataset_schema: None,
+        }
+    }
+
+    /// Remember the IPC encoded `dataset_schema` of the statement, so
+    /// executions can detect that the schema changed.
+    pub fn with_dataset_schema(self, dataset_schema: Bytes) -> Self {
+        Self {
+            dataset_schema: Some(dataset_schema),
+            ..self
+        }
     }
 
     /// return the query
@@ -33,16 +56,54 @@ impl PreparedStatementHandle {
         self.query.as_ref()
     }
 
+    /// return the IPC encoded dataset schema, if the handle carries one
+    pub fn dataset_schema(&self) -> Option<&Bytes> {
+        self.dataset_schema.as_ref()
+    }
+
     fn try_decode(handle: Bytes) -> Result<Self> {
-        // Note: in IOx  handles are the entire decoded query
+        // Note: in IOx  handles are the entire decoded query, optionally
+        // followed by the dataset schema
         // It will likely need to get more sophisticated as part of
         // https://github.com/influxdata/influxdb_iox/issues/6699
-        let query = String::from_utf8(handle.to_vec()).context(InvalidHandleSnafu)?;
-        Ok(Self { query })
+        if handle.first() != Some(&HANDLE_WITH_SCHEMA_MARKER) {
+            let query = String::from_utf8(handle.to_vec()).context(InvalidHandleSnafu)?;
+            return Ok(Self::new(query));
+        }
+
+        ensure!(
+            handle.len() >= 5,
+            MalformedHandleSnafu {
+                reason: "truncated query length",
+            }
+        );
+        let query_len = u32::from_be_bytes(handle[1..5].try_into().expect("4 bytes")) as usize;
+        ensure!(
+            handle.len() >= 5 + query_len,
+            MalformedHandleSnafu {
+                reason: "truncated query",
+            }
+        );
+
+        let query =
+            String::from_utf8(handle[5..5 + query_len].to_vec()).context(InvalidHandleSnafu)?;
+        Ok(Self {
+            query,
+            dataset_schema: Some(handle.slice(5 + query_len..)),
+        })
     }
 
     fn encode(self) -> Bytes {
-        Bytes::from(self.query.into_bytes())
+        let Some(dataset_schema) = self.dataset_schema else {
+            return Bytes::from(self.query.into_bytes());
+        };
+
+        let mut buf = Vec::with_capacity(5 + self.query.len() + dataset_schema.len());
+        buf.push(HANDLE_WITH_SCHEMA_MARKER);
+        buf.extend_from_slice(&(self.query.len() as u32).to_be_bytes());
+        buf.extend_from_slice(self.query.as_bytes());
+        buf.extend_from_slice(&dataset_schema);
+        Bytes::from(buf)
     }
 }
 
@@ -55,7 +116,31 @@ impl Display for PreparedStatementHandle {
 /// Encode a PreparedStatementHandle as Bytes
 impl From<PreparedStatementHandle> for Bytes {
     fn from(value: PreparedStatementHandle) -> Self {
-        Self::from(value.query.into_bytes())
+        value.encode()
+    }
+}
+
+/// IOx specific extension to FlightSQL: run an InfluxQL query.
+///
+/// There is no FlightSQL message for InfluxQL, so this is packed into a
+/// `prost::Any` using an IOx specific type URL.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CommandInfluxQLStatementQuery {
+    /// The raw InfluxQL query text
+    #[prost(string, tag = "1")]
+    pub query: String,
+}
+
+impl ProstMessageExt for CommandInfluxQLStatementQuery {
+    fn type_url() -> &'static str {
+        "type.googleapis.com/influxdata.iox.flightsql.v1.CommandInfluxQLStatementQuery"
+    }
+
+    fn as_any(&self) -> Any {
+        Any {
+            type_url: Self::type_url().to_string(),
+            value: self.encode_to_vec().into(),
+        }
     }
 }
 
@@ -70,8 +155,12 @@ impl From<PreparedStatementHandle> for Bytes {
 pub enum FlightSQLCommand {
     /// Run a normal query
     CommandStatementQuery(CommandStatementQuery),
+    /// Run an InfluxQL query (IOx specific)
+    CommandInfluxQLStatementQuery(CommandInfluxQLStatementQuery),
     /// Run a prepared statement.
     CommandPreparedStatementQuery(PreparedStatementHandle),
+    /// Run an update statement (e.g. DML / DDL)
+    CommandStatementUpdate(CommandStatementUpdate),
     /// Get information about the SQL supported
     CommandGetSqlInfo(CommandGetSqlInfo),
     /// Get a list of the available catalogs. See [`CommandGetCatalogs`] for details.
@@ -103,6 +192,10 @@ pub enum FlightSQLCommand {
     ActionCreatePreparedStatementRequest(ActionCreatePreparedStatementRequest),
     /// Close a prepared statement
     ActionClosePreparedStatementRequest(PreparedStatementHandle),
+    /// Begin a transaction. IOx is read-only, so this is a no-op.
+    ActionBeginTransactionRequest(ActionBeginTransactionRequest),
+    /// Commit or roll back a transaction. IOx is read-only, so this is a no-op.
+    ActionEndTransactionRequest(ActionEndTransactionRequest),
 }
 
 impl Display for FlightSQLCommand {
@@ -111,7 +204,13 @@ impl Display for FlightSQLCommand {
             Self::CommandStatementQuery(CommandStatementQuery { query, .. }) => {
                 write!(f, "CommandStatementQuery{query}")
             }
+            Self::CommandInfluxQLStatementQuery(CommandInfluxQLStatementQuery { query }) => {
+                write!(f, "CommandInfluxQLStatementQuery{query}")
+            }
             Self::CommandPreparedStatementQuery(h) => write!(f, "CommandPreparedStatementQuery{h}"),
+            Self::CommandStatementUpdate(CommandStatementUpdate { query, .. }) => {
+                write!(f, "CommandStatementUpdate{query}")
+            }
             Self::CommandGetSqlInfo(CommandGetSqlInfo { info: _ }) => {
                 write!(f, "CommandGetSqlInfo(...)")
             }
@@ -243,11 +342,111 @@ impl Display for FlightSQLCommand {
             Self::ActionClosePreparedStatementRequest(h) => {
                 write!(f, "ActionClosePreparedStatementRequest{h}")
             }
+            Self::ActionBeginTransactionRequest(ActionBeginTransactionRequest {}) => {
+                write!(f, "ActionBeginTransactionRequest")
+            }
+            Self::ActionEndTransactionRequest(req) => {
+                write!(
+                    f,
+                    "ActionEndTransactionRequest(action={})",
+                    req.action().as_str_name()
+                )
+            }
         }
     }
 }
 
+/// Broad category of a [`FlightSQLCommand`], e.g. to label metrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CommandKind {
+    /// Catalog / schema / server metadata requests (`CommandGet*`).
+    Metadata,
+    /// Statements that are planned and executed against data, including the execution of prepared statements.
+    Query,
+    /// Creation and closing of prepared statements.
+    PreparedStatement,
+    /// Other actions, e.g. transaction handling.
+    Action,
+}
+
+impl CommandKind {
+    /// Return all variants.
+    pub fn variants() -> &'static [Self] {
+        &[
+            Self::Metadata,
+            Self::Query,
+            Self::PreparedStatement,
+            Self::Action,
+        ]
+    }
+
+    /// Return static name.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Metadata => "metadata",
+            Self::Query => "query",
+            Self::PreparedStatement => "prepared_statement",
+            Self::Action => "action",
+        }
+    }
+}
+
+impl Display for CommandKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
 impl FlightSQLCommand {
+    /// Category of the command.
+    pub fn kind(&self) -> CommandKind {
+        match self {
+            Self::CommandStatementQuery(_)
+            | Self::CommandInfluxQLStatementQuery(_)
+            | Self::CommandPreparedStatementQuery(_)
+            | Self::CommandStatementUpdate(_) => CommandKind::Query,
+            Self::CommandGetSqlInfo(_)
+            | Self::CommandGetCatalogs(_)
+            | Self::CommandGetCrossReference(_)
+            | Self::CommandGetDbSchemas(_)
+            | Self::CommandGetExportedKeys(_)
+            | Self::CommandGetImportedKeys(_)
+            | Self::CommandGetPrimaryKeys(_)
+            | Self::CommandGetTables(_)
+            | Self::CommandGetXdbcTypeInfo(_)
+            | Self::CommandGetTableTypes(_) => CommandKind::Metadata,
+            Self::ActionCreatePreparedStatementRequest(_)
+            | Self::ActionClosePreparedStatementRequest(_) => CommandKind::PreparedStatement,
+            Self::ActionBeginTransactionRequest(_) | Self::ActionEndTransactionRequest(_) => {
+                CommandKind::Action
+            }
+        }
+    }
+
+    /// Name of the command, without any of its parameters.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::CommandStatementQuery(_) => "CommandStatementQuery",
+            Self::CommandInfluxQLStatementQuery(_) => "CommandInfluxQLStatementQuery",
+            Self::CommandPreparedStatementQuery(_) => "CommandPreparedStatementQuery",
+            Self::CommandStatementUpdate(_) => "CommandStatementUpdate",
+            Self::CommandGetSqlInfo(_) => "CommandGetSqlInfo",
+            Self::CommandGetCatalogs(_) => "CommandGetCatalogs",
+            Self::CommandGetCrossReference(_) => "CommandGetCrossReference",
+            Self::CommandGetDbSchemas(_) => "CommandGetDbSchemas",
+            Self::CommandGetExportedKeys(_) => "CommandGetExportedKeys",
+            Self::CommandGetImportedKeys(_) => "CommandGetImportedKeys",
+            Self::CommandGetPrimaryKeys(_) => "CommandGetPrimaryKeys",
+            Self::CommandGetTables(_) => "CommandGetTables",
+            Self::CommandGetXdbcTypeInfo(_) => "CommandGetXdbcTypeInfo",
+            Self::CommandGetTableTypes(_) => "CommandGetTableTypes",
+            Self::ActionCreatePreparedStatementRequest(_) => "ActionCreatePreparedStatementRequest",
+            Self::ActionClosePreparedStatementRequest(_) => "ActionClosePreparedStatementRequest",
+            Self::ActionBeginTransactionRequest(_) => "ActionBeginTransactionRequest",
+            Self::ActionEndTransactionRequest(_) => "ActionEndTransactionRequest",
+        }
+    }
+
     /// Figure out and decode the specific FlightSQL command in `msg`
     /// and decode it to a native IOx / Rust struct
     pub fn try_decode(msg: Bytes) -> Result<Self> {
@@ -255,6 +454,8 @@ impl FlightSQLCommand {
 
         if let Some(decoded_cmd) = Any::unpack::<CommandStatementQuery>(&msg)? {
             Ok(Self::CommandStatementQuery(decoded_cmd))
+        } else if let Some(decoded_cmd) = Any::unpack::<CommandInfluxQLStatementQuery>(&msg)? {
+            Ok(Self::CommandInfluxQLStatementQuery(decoded_cmd))
         } else if let Some(decoded_cmd) = Any::unpack::<CommandPreparedStatementQuery>(&msg)? {
             let CommandPreparedStatementQuery {
                 prepared_statement_handle,
@@ -262,6 +463,8 @@ impl FlightSQLCommand {
             // Decode to IOx specific structure
             let handle = PreparedStatementHandle::try_decode(prepared_statement_handle)?;
             Ok(Self::CommandPreparedStatementQuery(handle))
+        } else if let Some(decoded_cmd) = Any::unpack::<CommandStatementUpdate>(&msg)? {
+            Ok(Self::CommandStatementUpdate(decoded_cmd))
         } else if let Some(decoded_cmd) = Any::unpack::<CommandGetSqlInfo>(&msg)? {
             Ok(Self::CommandGetSqlInfo(decoded_cmd))
         } else if let Some(decoded_cmd) = Any::unpack::<CommandGetCatalogs>(&msg)? {
@@ -293,6 +496,10 @@ impl FlightSQLCommand {
             } = decoded_cmd;
             let handle = PreparedStatementHandle::try_decode(prepared_statement_handle)?;
             Ok(Self::ActionClosePreparedStatementRequest(handle))
+        } else if let Some(decoded_cmd) = Any::unpack::<ActionBeginTransactionRequest>(&msg)? {
+            Ok(Self::ActionBeginTransactionRequest(decoded_cmd))
+        } else if let Some(decoded_cmd) = Any::unpack::<ActionEndTransactionRequest>(&msg)? {
+            Ok(Self::ActionEndTransactionRequest(decoded_cmd))
         } else {
             UnsupportedMessageTypeSnafu {
                 description: &msg.type_url,
@@ -305,6 +512,7 @@ impl FlightSQLCommand {
     pub fn try_encode(self) -> Result<Bytes> {
         let msg = match self {
             Self::CommandStatementQuery(cmd) => Any::pack(&cmd),
+            Self::CommandInfluxQLStatementQuery(cmd) => Any::pack(&cmd),
             Self::CommandPreparedStatementQuery(handle) => {
                 let prepared_statement_handle = handle.encode();
                 let cmd = CommandPreparedStatementQuery {
@@ -312,6 +520,7 @@ impl FlightSQLCommand {
                 };
                 Any::pack(&cmd)
             }
+            Self::CommandStatementUpdate(cmd) => Any::pack(&cmd),
             Self::CommandGetSqlInfo(cmd) => Any::pack(&cmd),
             Self::CommandGetCatalogs(cmd) => Any::pack(&cmd),
             Self::CommandGetCrossReference(cmd) => Any::pack(&cmd),
@@ -329,7 +538,116 @@ impl FlightSQLCommand {
                     prepared_statement_handle,
                 })
             }
+            Self::ActionBeginTransactionRequest(cmd) => Any::pack(&cmd),
+            Self::ActionEndTransactionRequest(cmd) => Any::pack(&cmd),
         }?;
         Ok(msg.encode_to_vec().into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kind() {
+        let handle = || PreparedStatementHandle::new(String::from("SELECT 1"));
+        let cases = [
+            (
+                FlightSQLCommand::CommandStatementQuery(CommandStatementQuery {
+                    query: String::from("SELECT 1"),
+                    transaction_id: None,
+                }),
+                CommandKind::Query,
+            ),
+            (
+                FlightSQLCommand::CommandInfluxQLStatementQuery(CommandInfluxQLStatementQuery {
+                    query: String::from("SHOW MEASUREMENTS"),
+                }),
+                CommandKind::Query,
+            ),
+            (
+                FlightSQLCommand::CommandPreparedStatementQuery(handle()),
+                CommandKind::Query,
+            ),
+            (
+                FlightSQLCommand::CommandStatementUpdate(CommandStatementUpdate::default()),
+                CommandKind::Query,
+            ),
+            (
+                FlightSQLCommand::CommandGetSqlInfo(CommandGetSqlInfo::default()),
+                CommandKind::Metadata,
+            ),
+            (
+                FlightSQLCommand::CommandGetCatalogs(CommandGetCatalogs::default()),
+                CommandKind::Metadata,
+            ),
+            (
+                FlightSQLCommand::CommandGetCrossReference(CommandGetCrossReference::default()),
+                CommandKind::Metadata,
+            ),
+            (
+                FlightSQLCommand::CommandGetDbSchemas(CommandGetDbSchemas::default()),
+                CommandKind::Metadata,
+            ),
+            (
+                FlightSQLCommand::CommandGetExportedKeys(CommandGetExportedKeys::default()),
+                CommandKind::Metadata,
+            ),
+            (
+                FlightSQLCommand::CommandGetImportedKeys(CommandGetImportedKeys::default()),
+                CommandKind::Metadata,
+            ),
+            (
+                FlightSQLCommand::CommandGetPrimaryKeys(CommandGetPrimaryKeys::default()),
+                CommandKind::Metadata,
+            ),
+            (
+                FlightSQLCommand::CommandGetTables(CommandGetTables::default()),
+                CommandKind::Metadata,
+            ),
+            (
+                FlightSQLCommand::CommandGetXdbcTypeInfo(CommandGetXdbcTypeInfo::default()),
+                CommandKind::Metadata,
+            ),
+            (
+                FlightSQLCommand::CommandGetTableTypes(CommandGetTableTypes::default()),
+                CommandKind::Metadata,
+            ),
+            (
+                FlightSQLCommand::ActionCreatePreparedStatementRequest(
+                    ActionCreatePreparedStatementRequest::default(),
+                ),
+                CommandKind::PreparedStatement,
+            ),
+            (
+                FlightSQLCommand::ActionClosePreparedStatementRequest(handle()),
+                CommandKind::PreparedStatement,
+            ),
+            (
+                FlightSQLCommand::ActionBeginTransactionRequest(
+                    ActionBeginTransactionRequest::default(),
+                ),
+                CommandKind::Action,
+            ),
+            (
+                FlightSQLCommand::ActionEndTransactionRequest(
+                    ActionEndTransactionRequest::default(),
+                ),
+                CommandKind::Action,
+            ),
+        ];
+
+        for (cmd, expected) in cases {
+            assert_eq!(cmd.kind(), expected, "{}", cmd.name());
+        }
+    }
+
+    #[test]
+    fn test_kind_name() {
+        for kind in CommandKind::variants() {
+            assert_eq!(kind.to_string(), kind.name());
+        }
+        assert_eq!(CommandKind::PreparedStatement.name(), "prepared_statement");
+    }
+}
@@ -0,0 +1,107 @@
+//! FlightSQL column metadata for schemas returned to clients
+//!
+//! The FlightSQL spec defines a set of Arrow field metadata keys that BI tools use to size and lay out columns, see
+//! the docs of `CommandGetTables` in `FlightSql.proto`.
+
+use std::sync::Arc;
+
+use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+
+/// Column precision / size: the maximum number of characters for strings, the number of decimal digits for numbers
+/// and the number of fractional second digits for timestamps.
+pub(crate) const PRECISION_KEY: &str = "ARROW:FLIGHT:SQL:PRECISION";
+
+/// Precision reported for strings, which have no declared maximum length in IOx.
+pub(crate) const UNBOUNDED_PRECISION: i32 = i32::MAX;
+
+/// Returns the precision / size hint for a column of the given type, if there is one.
+fn precision(data_type: &DataType) -> Option<i32> {
+    match data_type {
+        DataType::Timestamp(unit, _) => Some(match unit {
+            TimeUnit::Second => 0,
+            TimeUnit::Millisecond => 3,
+            TimeUnit::Microsecond => 6,
+            TimeUnit::Nanosecond => 9,
+        }),
+        DataType::Utf8 | DataType::LargeUtf8 => Some(UNBOUNDED_PRECISION),
+        DataType::Dictionary(_, value_type) => precision(value_type),
+        DataType::Boolean => Some(1),
+        DataType::Int64 => Some(19),
+        DataType::UInt64 => Some(20),
+        DataType::Float64 => Some(15),
+        _ => None,
+    }
+}
+
+/// Returns a copy of `schema` whose fields carry FlightSQL column metadata derived from their IOx types.
+///
+/// Existing field metadata is kept.
+pub(crate) fn with_column_metadata(schema: &Schema) -> Schema {
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|field| {
+            let Some(precision) = precision(field.data_type()) else {
+                return Arc::clone(field);
+            };
+
+            let mut metadata = field.metadata().clone();
+            metadata.insert(PRECISION_KEY.to_string(), precision.to_string());
+            Arc::new(Field::clone(field).with_metadata(metadata))
+        })
+        .collect::<Vec<_>>();
+
+    Schema::new_with_metadata(fields, schema.metadata().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_with_column_metadata() {
+        let schema = Schema::new(vec![
+            Field::new(
+                "tag",
+                DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+                true,
+            )
+            .with_metadata(HashMap::from([(
+                "iox::column::type".to_string(),
+                "iox::column_type::tag".to_string(),
+            )])),
+            Field::new("f", DataType::Float64, true),
+            Field::new("bytes", DataType::Binary, true),
+            Field::new(
+                "time",
+                DataType::Timestamp(TimeUnit::Nanosecond, None),
+                false,
+            ),
+        ]);
+
+        let schema = with_column_metadata(&schema);
+
+        let metadata = |name: &str| schema.field_with_name(name).unwrap().metadata().clone();
+        assert_eq!(
+            metadata("tag"),
+            HashMap::from([
+                (
+                    "iox::column::type".to_string(),
+                    "iox::column_type::tag".to_string()
+                ),
+                (PRECISION_KEY.to_string(), i32::MAX.to_string()),
+            ])
+        );
+        assert_eq!(
+            metadata("f"),
+            HashMap::from([(PRECISION_KEY.to_string(), "15".to_string())])
+        );
+        assert_eq!(metadata("bytes"), HashMap::new());
+        assert_eq!(
+            metadata("time"),
+            HashMap::from([(PRECISION_KEY.to_string(), "9".to_string())])
+        );
+    }
+}
@@ -19,6 +19,17 @@ pub enum Error {
     #[snafu(display("Invalid PreparedStatement handle (invalid UTF-8:) {}", source))]
     InvalidHandle { source: FromUtf8Error },
 
+    #[snafu(display("Invalid PreparedStatement handle: {}", reason))]
+    MalformedHandle { reason: String },
+
+    /// The schema of a prepared statement differs from the one returned to the client when it was prepared.
+    #[snafu(display(
+        "Schema of prepared statement changed since it was prepared: expected [{}], got [{}]",
+        expected,
+        actual
+    ))]
+    PreparedStatementSchemaChanged { expected: String, actual: String },
+
     #[snafu(display("{}", source))]
     #[snafu(context(false))]
     Flight { source: FlightError },
@@ -34,8 +45,34 @@ pub enum Error {
     #[snafu(display("Unsupported FlightSQL message type: {}", description))]
     UnsupportedMessageType { description: String },
 
-    #[snafu(display("Protocol error. Method {} does not expect '{:?}'", method, cmd))]
-    Protocol { cmd: String, method: &'static str },
+    #[snafu(display("Invalid namespace '{}': {}", namespace_name, reason))]
+    InvalidNamespace {
+        namespace_name: String,
+        reason: &'static str,
+    },
+
+    #[snafu(display("Table not found: {}.{}.{}", catalog, db_schema, table))]
+    TableNotFound {
+        catalog: String,
+        db_schema: String,
+        table: String,
+    },
+
+    #[snafu(display("Statement not supported by IOx: {}", statement))]
+    UnsupportedStatement { statement: String },
+
+    #[snafu(display("Invalid transaction: {}", reason))]
+    InvalidTransaction { reason: &'static str },
+
+    /// A valid FlightSQL command was sent to an RPC that does not handle it, e.g. an action to `DoGet`.
+    ///
+    /// `cmd` is the name of the command (see [`FlightSQLCommand::name`](crate::FlightSQLCommand::name)) and
+    /// `method` the name of the Flight RPC.
+    #[snafu(display("FlightSQL command {} is not supported by the {} RPC", cmd, method))]
+    UnsupportedForMethod {
+        cmd: &'static str,
+        method: &'static str,
+    },
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
@@ -45,6 +82,11 @@ impl From<Error> for DataFusionError {
         match value {
             Error::DataFusion { source } => source,
             Error::Arrow { source } => Self::ArrowError(source),
+            value @ (Error::InvalidNamespace { .. }
+            | Error::InvalidTransaction { .. }
+            | Error::TableNotFound { .. }
+            | Error::PreparedStatementSchemaChanged { .. }) => Self::Plan(value.to_string()),
+            value @ Error::UnsupportedStatement { .. } => Self::NotImplemented(value.to_string()),
             value => Self::External(Box::new(value)),
         }
     }
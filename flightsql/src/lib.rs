@@ -17,12 +17,20 @@
 // Workaround for "unused crate" lint false positives.
 use workspace_hack as _;
 
+mod audit;
+mod cache;
 mod cmd;
+mod column_metadata;
 mod error;
+mod like;
 mod planner;
 mod sql_info;
 mod xdbc_type_info;
 
-pub use cmd::{FlightSQLCommand, PreparedStatementHandle};
+pub use audit::{redact_command, redact_influxql, redact_query, AuditFlightSQLPlanner};
+pub use cache::MetadataCache;
+pub use cmd::{
+    CommandInfluxQLStatementQuery, CommandKind, FlightSQLCommand, PreparedStatementHandle,
+};
 pub use error::{Error, Result};
 pub use planner::FlightSQLPlanner;
@@ -0,0 +1,120 @@
+//! SQL `LIKE` pattern matching for FlightSQL metadata filters
+//!
+//! The FlightSQL spec defines the `*_filter_pattern` fields of the metadata commands as `LIKE` patterns:
+//!
+//! * `%` matches any sequence of zero or more characters
+//! * `_` matches exactly one character
+//! * `\` escapes the next character, so `\%` and `\_` match a literal `%` and `_`
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    /// `%`
+    AnySequence,
+    /// `_`
+    AnyChar,
+    /// Any other (possibly escaped) character
+    Literal(char),
+}
+
+fn tokenize(pattern: &str) -> Vec<Token> {
+    let mut tokens = Vec::with_capacity(pattern.len());
+    let mut chars = pattern.chars();
+
+    while let Some(c) = chars.next() {
+        let token = match c {
+            '%' => Token::AnySequence,
+            '_' => Token::AnyChar,
+            // a trailing backslash matches itself
+            '\\' => Token::Literal(chars.next().unwrap_or('\\')),
+            c => Token::Literal(c),
+        };
+        tokens.push(token);
+    }
+
+    tokens
+}
+
+/// Returns true if `s` matches the `LIKE` `pattern`.
+pub(crate) fn like(pattern: &str, s: &str) -> bool {
+    let tokens = tokenize(pattern);
+    let chars = s.chars().collect::<Vec<_>>();
+
+    let (mut t, mut c) = (0, 0);
+    // position of the last `%` and the character it was tried at, for backtracking
+    let mut backtrack = None;
+
+    while c < chars.len() {
+        match tokens.get(t) {
+            Some(Token::AnySequence) => {
+                backtrack = Some((t, c));
+                t += 1;
+            }
+            Some(Token::AnyChar) => {
+                t += 1;
+                c += 1;
+            }
+            Some(Token::Literal(l)) if *l == chars[c] => {
+                t += 1;
+                c += 1;
+            }
+            _ => match backtrack {
+                // let the last `%` consume one more character
+                Some((star_t, star_c)) => {
+                    backtrack = Some((star_t, star_c + 1));
+                    t = star_t + 1;
+                    c = star_c + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    tokens[t..].iter().all(|t| *t == Token::AnySequence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_literal() {
+        assert!(like("", ""));
+        assert!(like("iox", "iox"));
+        assert!(!like("iox", "io"));
+        assert!(!like("iox", "ioxx"));
+        assert!(!like("", "iox"));
+    }
+
+    #[test]
+    fn test_any_sequence() {
+        assert!(like("prod%", "prod"));
+        assert!(like("prod%", "production"));
+        assert!(!like("prod%", "dev_production"));
+        assert!(like("%prod%", "dev_production"));
+        assert!(like("%_schema", "information_schema"));
+        assert!(like("%", ""));
+        assert!(like("a%b%c", "aXbYbZc"));
+        assert!(!like("a%b%c", "aXbYbZ"));
+    }
+
+    #[test]
+    fn test_any_char() {
+        assert!(like("io_", "iox"));
+        assert!(!like("io_", "io"));
+        assert!(!like("io_", "ioxx"));
+        assert!(like("_o_", "iox"));
+        assert!(like("_", "ü"));
+    }
+
+    #[test]
+    fn test_escape() {
+        assert!(like(r"100\%", "100%"));
+        assert!(!like(r"100\%", "1000"));
+        assert!(like(r"my\_db", "my_db"));
+        assert!(!like(r"my\_db", "myXdb"));
+        assert!(like(r"%\%%", "50% off"));
+        assert!(!like(r"%\%%", "50 percent off"));
+        assert!(like(r"a\\b", r"a\b"));
+        assert!(like(r"trailing\", r"trailing\"));
+    }
+}
@@ -10,27 +10,38 @@ use arrow::{
 };
 use arrow_flight::{
     sql::{
+        ActionBeginTransactionRequest, ActionBeginTransactionResult,
         ActionCreatePreparedStatementRequest, ActionCreatePreparedStatementResult, Any,
         CommandGetCatalogs, CommandGetCrossReference, CommandGetDbSchemas, CommandGetExportedKeys,
         CommandGetImportedKeys, CommandGetPrimaryKeys, CommandGetSqlInfo, CommandGetTableTypes,
-        CommandGetTables, CommandGetXdbcTypeInfo, CommandStatementQuery,
+        CommandGetTables, CommandGetXdbcTypeInfo, CommandStatementQuery, CommandStatementUpdate,
+        DoPutUpdateResult, EndTransaction,
     },
     IpcMessage, SchemaAsIpc,
 };
 use arrow_util::flight::prepare_schema_for_flight;
 use bytes::Bytes;
 use datafusion::{
-    logical_expr::{LogicalPlan, TableType},
+    logical_expr::{LogicalPlan, Statement, TableType},
     physical_plan::ExecutionPlan,
     sql::TableReference,
 };
 use iox_query::{exec::IOxSessionContext, QueryNamespace};
+use iox_query_influxql::frontend::planner::InfluxQLQueryPlanner;
 use observability_deps::tracing::debug;
 use once_cell::sync::Lazy;
 use prost::Message;
+use snafu::ensure;
 
-use crate::{error::*, sql_info::iox_sql_info_data, xdbc_type_info::xdbc_type_info_data};
-use crate::{FlightSQLCommand, PreparedStatementHandle};
+use crate::{
+    cache::{MetadataCache, MetadataCacheKey},
+    column_metadata::with_column_metadata,
+    error::*,
+    like::like,
+    sql_info::iox_sql_info_data,
+    xdbc_type_info::xdbc_type_info_data,
+};
+use crate::{CommandInfluxQLStatementQuery, FlightSQLCommand, PreparedStatementHandle};
 
 /// Logic for creating plans for various Flight messages against a query database
 #[derive(Debug, Default)]
@@ -47,13 +58,16 @@ impl FlightSQLPlanner {
         cmd: FlightSQLCommand,
         ctx: &IOxSessionContext,
     ) -> Result<SchemaRef> {
-        let namespace_name = namespace_name.into();
+        let namespace_name = validate_namespace_name(namespace_name.into())?;
         debug!(%namespace_name, %cmd, "Handling flightsql get_flight_info (get schema)");
 
         match cmd {
             FlightSQLCommand::CommandStatementQuery(CommandStatementQuery { query, .. }) => {
                 get_schema_for_query(&query, ctx).await
             }
+            FlightSQLCommand::CommandInfluxQLStatementQuery(CommandInfluxQLStatementQuery {
+                query,
+            }) => get_schema_for_influxql_query(&query, ctx).await,
             FlightSQLCommand::CommandPreparedStatementQuery(handle) => {
                 get_schema_for_query(handle.query(), ctx).await
             }
@@ -81,9 +95,12 @@ impl FlightSQLPlanner {
             FlightSQLCommand::CommandGetXdbcTypeInfo(CommandGetXdbcTypeInfo { .. }) => {
                 Ok(Arc::clone(&GET_XDBC_TYPE_INFO_SCHEMA))
             }
-            FlightSQLCommand::ActionCreatePreparedStatementRequest(_)
-            | FlightSQLCommand::ActionClosePreparedStatementRequest(_) => ProtocolSnafu {
-                cmd: format!("{cmd:?}"),
+            FlightSQLCommand::CommandStatementUpdate(_)
+            | FlightSQLCommand::ActionCreatePreparedStatementRequest(_)
+            | FlightSQLCommand::ActionClosePreparedStatementRequest(_)
+            | FlightSQLCommand::ActionBeginTransactionRequest(_)
+            | FlightSQLCommand::ActionEndTransactionRequest(_) => UnsupportedForMethodSnafu {
+                cmd: cmd.name(),
                 method: "GetFlightInfo",
             }
             .fail(),
@@ -91,13 +108,16 @@ impl FlightSQLPlanner {
     }
 
     /// Returns a plan that computes results requested in msg
+    ///
+    /// Catalog and db-schema metadata is served from `metadata_cache` if possible.
     pub async fn do_get(
         namespace_name: impl Into<String> + Send,
         _database: Arc<dyn QueryNamespace>,
         cmd: FlightSQLCommand,
+        metadata_cache: &MetadataCache,
         ctx: &IOxSessionContext,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        let namespace_name = namespace_name.into();
+        let namespace_name = validate_namespace_name(namespace_name.into())?;
         debug!(%namespace_name, %cmd, "Handling flightsql do_get");
 
         match cmd {
@@ -105,10 +125,18 @@ impl FlightSQLPlanner {
                 debug!(%query, "Planning FlightSQL query");
                 Ok(ctx.sql_to_physical_plan(&query).await?)
             }
+            FlightSQLCommand::CommandInfluxQLStatementQuery(CommandInfluxQLStatementQuery {
+                query,
+            }) => {
+                debug!(%query, "Planning FlightSQL InfluxQL query");
+                Ok(InfluxQLQueryPlanner::new().query(&query, ctx).await?)
+            }
             FlightSQLCommand::CommandPreparedStatementQuery(handle) => {
                 let query = handle.query();
                 debug!(%query, "Planning FlightSQL prepared query");
-                Ok(ctx.sql_to_physical_plan(query).await?)
+                let plan = ctx.sql_to_physical_plan(query).await?;
+                ensure_prepared_schema(&handle, plan.schema())?;
+                Ok(plan)
             }
             FlightSQLCommand::CommandGetSqlInfo(cmd) => {
                 debug!(?cmd, "Planning GetSqlInfo query");
@@ -117,7 +145,7 @@ impl FlightSQLPlanner {
             }
             FlightSQLCommand::CommandGetCatalogs(cmd) => {
                 debug!("Planning GetCatalogs query");
-                let plan = plan_get_catalogs(ctx, cmd).await?;
+                let plan = plan_get_catalogs(ctx, metadata_cache, &namespace_name, cmd).await?;
                 Ok(ctx.create_physical_plan(&plan).await?)
             }
             FlightSQLCommand::CommandGetCrossReference(CommandGetCrossReference {
@@ -155,7 +183,7 @@ impl FlightSQLPlanner {
                     db_schema_filter_pattern=?cmd.db_schema_filter_pattern,
                     "Planning GetDbSchemas query"
                 );
-                let plan = plan_get_db_schemas(ctx, cmd).await?;
+                let plan = plan_get_db_schemas(ctx, metadata_cache, &namespace_name, cmd).await?;
                 Ok(ctx.create_physical_plan(&plan).await?)
             }
             FlightSQLCommand::CommandGetExportedKeys(CommandGetExportedKeys {
@@ -222,9 +250,12 @@ impl FlightSQLPlanner {
                 let plan = plan_get_xdbc_type_info(ctx, cmd).await?;
                 Ok(ctx.create_physical_plan(&plan).await?)
             }
-            FlightSQLCommand::ActionClosePreparedStatementRequest(_)
-            | FlightSQLCommand::ActionCreatePreparedStatementRequest(_) => ProtocolSnafu {
-                cmd: format!("{cmd:?}"),
+            FlightSQLCommand::CommandStatementUpdate(_)
+            | FlightSQLCommand::ActionClosePreparedStatementRequest(_)
+            | FlightSQLCommand::ActionCreatePreparedStatementRequest(_)
+            | FlightSQLCommand::ActionBeginTransactionRequest(_)
+            | FlightSQLCommand::ActionEndTransactionRequest(_) => UnsupportedForMethodSnafu {
+                cmd: cmd.name(),
                 method: "DoGet",
             }
             .fail(),
@@ -240,7 +271,7 @@ impl FlightSQLPlanner {
         cmd: FlightSQLCommand,
         ctx: &IOxSessionContext,
     ) -> Result<Bytes> {
-        let namespace_name = namespace_name.into();
+        let namespace_name = validate_namespace_name(namespace_name.into())?;
         debug!(%namespace_name, %cmd, "Handling flightsql do_action");
 
         match cmd {
@@ -255,7 +286,8 @@ impl FlightSQLPlanner {
 
                 let dataset_schema = get_schema_for_query(&query, ctx).await?;
                 let dataset_schema = encode_schema(dataset_schema.as_ref())?;
-                let handle = PreparedStatementHandle::new(query);
+                let handle =
+                    PreparedStatementHandle::new(query).with_dataset_schema(dataset_schema.clone());
 
                 let result = ActionCreatePreparedStatementResult {
                     prepared_statement_handle: Bytes::from(handle),
@@ -273,13 +305,91 @@ impl FlightSQLPlanner {
                 // Nothing really to do
                 Ok(Bytes::new())
             }
-            _ => ProtocolSnafu {
-                cmd: format!("{cmd:?}"),
+            FlightSQLCommand::ActionBeginTransactionRequest(ActionBeginTransactionRequest {}) => {
+                debug!("Beginning read-only transaction");
+
+                // IOx is read-only, so every client sees the same (no-op) transaction
+                let result = ActionBeginTransactionResult {
+                    transaction_id: Bytes::from_static(READ_ONLY_TRANSACTION_ID),
+                };
+
+                let msg = Any::pack(&result)?;
+                Ok(msg.encode_to_vec().into())
+            }
+            FlightSQLCommand::ActionEndTransactionRequest(req) => {
+                let action = req.action();
+                debug!(
+                    action = action.as_str_name(),
+                    "Ending read-only transaction"
+                );
+
+                ensure!(
+                    req.transaction_id == READ_ONLY_TRANSACTION_ID,
+                    InvalidTransactionSnafu {
+                        reason: "unknown transaction id",
+                    }
+                );
+                ensure!(
+                    action != EndTransaction::Unspecified,
+                    InvalidTransactionSnafu {
+                        reason: "neither commit nor rollback requested",
+                    }
+                );
+
+                // Nothing was changed, so there is nothing to commit or roll back
+                Ok(Bytes::new())
+            }
+            _ => UnsupportedForMethodSnafu {
+                cmd: cmd.name(),
                 method: "DoAction",
             }
             .fail(),
         }
     }
+
+    /// Handles the update specified in `msg` and returns bytes for
+    /// the `app_metadata` of the [`arrow_flight::PutResult`], which
+    /// is an encoded [`DoPutUpdateResult`]
+    pub async fn do_put(
+        namespace_name: impl Into<String> + Send,
+        _database: Arc<dyn QueryNamespace>,
+        cmd: FlightSQLCommand,
+        ctx: &IOxSessionContext,
+    ) -> Result<Bytes> {
+        let namespace_name = validate_namespace_name(namespace_name.into())?;
+        debug!(%namespace_name, %cmd, "Handling flightsql do_put");
+
+        match cmd {
+            FlightSQLCommand::CommandStatementUpdate(CommandStatementUpdate { query, .. }) => {
+                debug!(%query, "Planning FlightSQL update");
+                let record_count = execute_update(&query, ctx).await?;
+
+                let result = DoPutUpdateResult { record_count };
+                Ok(result.encode_to_vec().into())
+            }
+            _ => UnsupportedForMethodSnafu {
+                cmd: cmd.name(),
+                method: "DoPut",
+            }
+            .fail(),
+        }
+    }
+}
+
+/// Reject namespace names that can not refer to a namespace, before
+/// attempting to plan anything.
+///
+/// Note that whether the namespace exists is checked by the caller when
+/// resolving the [`QueryNamespace`].
+fn validate_namespace_name(namespace_name: String) -> Result<String> {
+    ensure!(
+        !namespace_name.trim().is_empty(),
+        InvalidNamespaceSnafu {
+            namespace_name,
+            reason: "namespace name is empty",
+        }
+    );
+    Ok(namespace_name)
 }
 
 /// Return the schema for the specified query
@@ -287,6 +397,28 @@ async fn get_schema_for_query(query: &str, ctx: &IOxSessionContext) -> Result<Sc
     Ok(get_schema_for_plan(ctx.sql_to_logical_plan(query).await?))
 }
 
+/// Return the schema for the specified InfluxQL query
+async fn get_schema_for_influxql_query(query: &str, ctx: &IOxSessionContext) -> Result<SchemaRef> {
+    let plan = InfluxQLQueryPlanner::new().query(query, ctx).await?;
+    Ok(prepare_schema_for_flight(plan.schema()))
+}
+
+/// Plan and run the update statement `query`, returning the number
+/// of affected rows.
+///
+/// Data can not be modified using FlightSQL, so the only supported
+/// statements are transaction control statements, sent by many
+/// clients (e.g. JDBC) when connecting. As IOx has no transactions,
+/// these do nothing.
+async fn execute_update(query: &str, ctx: &IOxSessionContext) -> Result<i64> {
+    match ctx.sql_to_logical_plan(query).await? {
+        LogicalPlan::Statement(Statement::TransactionStart(_) | Statement::TransactionEnd(_)) => {
+            Ok(0)
+        }
+        _ => UnsupportedStatementSnafu { statement: query }.fail(),
+    }
+}
+
 /// Return the schema for the specified logical plan
 fn get_schema_for_plan(logical_plan: LogicalPlan) -> SchemaRef {
     // gather real schema, but only
@@ -294,6 +426,50 @@ fn get_schema_for_plan(logical_plan: LogicalPlan) -> SchemaRef {
     prepare_schema_for_flight(schema)
 }
 
+/// Ensure that the schema of a prepared statement did not change since the
+/// `dataset_schema` carried by its handle was returned to the client.
+///
+/// Clients decode the results using that schema, so a diverging schema
+/// (e.g. because the underlying table changed) would silently be misread.
+/// Only column names and types are compared. Handles without a schema are
+/// not checked.
+fn ensure_prepared_schema(handle: &PreparedStatementHandle, schema: SchemaRef) -> Result<()> {
+    let Some(dataset_schema) = handle.dataset_schema() else {
+        return Ok(());
+    };
+    let expected = Schema::try_from(IpcMessage(dataset_schema.clone())).map_err(|e| {
+        Error::MalformedHandle {
+            reason: format!("invalid dataset schema: {e}"),
+        }
+    })?;
+    let actual = prepare_schema_for_flight(schema);
+
+    let columns = |schema: &Schema| {
+        schema
+            .fields()
+            .iter()
+            .map(|f| (f.name().clone(), f.data_type().clone()))
+            .collect::<Vec<_>>()
+    };
+    let (expected, actual) = (columns(&expected), columns(&actual));
+    if expected == actual {
+        return Ok(());
+    }
+
+    let describe = |columns: Vec<(String, DataType)>| {
+        columns
+            .into_iter()
+            .map(|(name, data_type)| format!("{name}: {data_type}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    PreparedStatementSchemaChangedSnafu {
+        expected: describe(expected),
+        actual: describe(actual),
+    }
+    .fail()
+}
+
 /// Encodes the schema IPC encoded (schema_bytes)
 fn encode_schema(schema: &Schema) -> Result<Bytes> {
     let options = IpcWriteOptions::default();
@@ -307,6 +483,9 @@ fn encode_schema(schema: &Schema) -> Result<Bytes> {
 }
 
 /// Return a `LogicalPlan` for GetSqlInfo
+///
+/// As required by the FlightSQL spec, all known info is returned if `info` is empty. Some drivers rely on this when
+/// connecting.
 async fn plan_get_sql_info(ctx: &IOxSessionContext, cmd: CommandGetSqlInfo) -> Result<LogicalPlan> {
     let batch = cmd.into_builder(iox_sql_info_data()).build()?;
     Ok(ctx.batch_to_logical_plan(batch)?)
@@ -315,13 +494,20 @@ async fn plan_get_sql_info(ctx: &IOxSessionContext, cmd: CommandGetSqlInfo) -> R
 /// Return a list of "catalogs" from the DataFusion catalog
 async fn plan_get_catalogs(
     ctx: &IOxSessionContext,
+    cache: &MetadataCache,
+    namespace_name: &str,
     cmd: CommandGetCatalogs,
 ) -> Result<LogicalPlan> {
-    let mut builder = cmd.into_builder();
-    for catalog_name in ctx.inner().catalog_names() {
-        builder.append(catalog_name);
-    }
-    let batch = builder.build()?;
+    let key = MetadataCacheKey::Catalogs {
+        namespace_name: namespace_name.to_string(),
+    };
+    let batch = cache.get_or_try_insert_with(key, || {
+        let mut builder = cmd.into_builder();
+        for catalog_name in ctx.inner().catalog_names() {
+            builder.append(catalog_name);
+        }
+        Ok(builder.build()?)
+    })?;
     Ok(ctx.batch_to_logical_plan(batch)?)
 }
 
@@ -339,62 +525,155 @@ async fn plan_get_cross_reference(
 }
 
 /// Return a list of schema from the DataFusion catalog
+///
+/// The `db_schema_filter_pattern` is a SQL `LIKE` pattern, see [`like`].
 async fn plan_get_db_schemas(
     ctx: &IOxSessionContext,
-    cmd: CommandGetDbSchemas,
+    cache: &MetadataCache,
+    namespace_name: &str,
+    mut cmd: CommandGetDbSchemas,
 ) -> Result<LogicalPlan> {
-    let mut builder = cmd.into_builder();
-    let catalog_list = ctx.inner().state().catalog_list();
-
-    for catalog_name in catalog_list.catalog_names() {
-        // we just got the catalog name from the catalog_list, so it
-        // should always be Some, but avoid unwrap to be safe
-        let Some(catalog) = catalog_list.catalog(&catalog_name) else {
-            continue
+    let key = MetadataCacheKey::DbSchemas {
+        namespace_name: namespace_name.to_string(),
+        catalog: cmd.catalog.clone(),
+        db_schema_filter_pattern: cmd.db_schema_filter_pattern.clone(),
+    };
+    let batch = cache.get_or_try_insert_with(key, || {
+        // filter here instead of in the builder so that escaping works as specified
+        let db_schema_filter_pattern = cmd.db_schema_filter_pattern.take();
+        let matches_filter = |schema_name: &str| {
+            db_schema_filter_pattern
+                .as_deref()
+                .map_or(true, |pattern| like(pattern, schema_name))
         };
 
-        builder.append(&catalog_name, "information_schema");
-        for schema_name in catalog.schema_names() {
-            builder.append(&catalog_name, &schema_name);
+        let mut builder = cmd.into_builder();
+        let catalog_list = ctx.inner().state().catalog_list();
+
+        for catalog_name in catalog_list.catalog_names() {
+            // we just got the catalog name from the catalog_list, so it
+            // should always be Some, but avoid unwrap to be safe
+            let Some(catalog) = catalog_list.catalog(&catalog_name) else {
+                continue
+            };
+
+            let schema_names = std::iter::once("information_schema".to_string())
+                .chain(catalog.schema_names())
+                .filter(|schema_name| matches_filter(schema_name));
+            for schema_name in schema_names {
+                builder.append(&catalog_name, &schema_name);
+            }
         }
-    }
 
-    let batch = builder.build()?;
+        Ok(builder.build()?)
+    })?;
     Ok(ctx.batch_to_logical_plan(batch)?)
 }
 
+/// Return the foreign keys that reference the primary key of `table`.
+///
+/// IOx has no foreign keys, so this is always empty for existing tables.
 async fn plan_get_exported_keys(
     ctx: &IOxSessionContext,
-    _catalog: Option<String>,
-    _db_schema: Option<String>,
-    _table: String,
+    catalog: Option<String>,
+    db_schema: Option<String>,
+    table: String,
 ) -> Result<LogicalPlan> {
+    ensure_table_exists(ctx, catalog.as_deref(), db_schema.as_deref(), &table)?;
+
     let batch = RecordBatch::new_empty(Arc::clone(&GET_EXPORTED_KEYS_SCHEMA));
     Ok(ctx.batch_to_logical_plan(batch)?)
 }
 
+/// Return the foreign keys of `table`.
+///
+/// IOx has no foreign keys, so this is always empty for existing tables.
 async fn plan_get_imported_keys(
     ctx: &IOxSessionContext,
-    _catalog: Option<String>,
-    _db_schema: Option<String>,
-    _table: String,
+    catalog: Option<String>,
+    db_schema: Option<String>,
+    table: String,
 ) -> Result<LogicalPlan> {
+    ensure_table_exists(ctx, catalog.as_deref(), db_schema.as_deref(), &table)?;
+
     let batch = RecordBatch::new_empty(Arc::clone(&GET_IMPORTED_KEYS_SCHEMA));
     Ok(ctx.batch_to_logical_plan(batch)?)
 }
 
+/// Return the primary key of `table`.
+///
+/// IOx tables do not declare primary keys, so this is always empty for existing tables.
 async fn plan_get_primary_keys(
     ctx: &IOxSessionContext,
-    _catalog: Option<String>,
-    _db_schema: Option<String>,
-    _table: String,
+    catalog: Option<String>,
+    db_schema: Option<String>,
+    table: String,
 ) -> Result<LogicalPlan> {
+    ensure_table_exists(ctx, catalog.as_deref(), db_schema.as_deref(), &table)?;
+
     let batch = RecordBatch::new_empty(Arc::clone(&GET_PRIMARY_KEYS_SCHEMA));
     Ok(ctx.batch_to_logical_plan(batch)?)
 }
 
+/// Error with [`Error::TableNotFound`] unless `table` exists.
+///
+/// `catalog` and `db_schema` default to the ones of the session, like for unqualified table names in SQL queries. An
+/// unknown catalog or schema means that the table does not exist either.
+fn ensure_table_exists(
+    ctx: &IOxSessionContext,
+    catalog: Option<&str>,
+    db_schema: Option<&str>,
+    table: &str,
+) -> Result<()> {
+    let session_cfg = ctx.inner().copied_config();
+    let cfg = &session_cfg.options().catalog;
+    let catalog = catalog.unwrap_or(&cfg.default_catalog);
+    let db_schema = db_schema.unwrap_or(&cfg.default_schema);
+
+    let exists = ctx
+        .inner()
+        .catalog(catalog)
+        .and_then(|catalog| catalog.schema(db_schema))
+        .is_some_and(|schema| schema.table_exist(table));
+    ensure!(
+        exists,
+        TableNotFoundSnafu {
+            catalog,
+            db_schema,
+            table,
+        }
+    );
+    Ok(())
+}
+
 /// Return a list of tables from the DataFusion catalog
-async fn plan_get_tables(ctx: &IOxSessionContext, cmd: CommandGetTables) -> Result<LogicalPlan> {
+///
+/// The `table_name_filter_pattern` is a SQL `LIKE` pattern, see [`like`]. All tables are returned if it is not set.
+///
+/// If `include_schema` is set, the fields of the embedded table schemas carry FlightSQL column metadata, see
+/// [`with_column_metadata`].
+async fn plan_get_tables(
+    ctx: &IOxSessionContext,
+    mut cmd: CommandGetTables,
+) -> Result<LogicalPlan> {
+    // filter here instead of in the builder so that escaping works as specified
+    let table_name_filter_pattern = cmd.table_name_filter_pattern.take();
+    let matches_filter = |table_name: &str| {
+        table_name_filter_pattern
+            .as_deref()
+            .map_or(true, |pattern| like(pattern, table_name))
+    };
+
+    // the builder only encodes the schema if it is included
+    let include_schema = cmd.include_schema;
+    let table_schema = |schema: &Schema| {
+        if include_schema {
+            with_column_metadata(schema)
+        } else {
+            schema.clone()
+        }
+    };
+
     let mut builder = cmd.into_builder();
     let catalog_list = ctx.inner().state().catalog_list();
 
@@ -411,6 +690,10 @@ async fn plan_get_tables(ctx: &IOxSessionContext, cmd: CommandGetTables) -> Resu
         // We ensure this list is kept in sync with tests
         let table_names = vec!["columns", "df_settings", "tables", "views"];
         for table_name in table_names {
+            if !matches_filter(table_name) {
+                continue;
+            }
+
             let schema_name = "information_schema";
             let table_ref = TableReference::full(&catalog_name, schema_name, table_name);
 
@@ -419,7 +702,7 @@ async fn plan_get_tables(ctx: &IOxSessionContext, cmd: CommandGetTables) -> Resu
             };
 
             let table_type = "VIEW";
-            let schema = Schema::from(table.schema());
+            let schema = table_schema(&Schema::from(table.schema()));
             builder.append(&catalog_name, schema_name, table_name, table_type, &schema)?;
         }
 
@@ -429,6 +712,10 @@ async fn plan_get_tables(ctx: &IOxSessionContext, cmd: CommandGetTables) -> Resu
             };
 
             for table_name in schema.table_names() {
+                if !matches_filter(&table_name) {
+                    continue;
+                }
+
                 let Some(table) = schema.table(&table_name).await else {
                     continue
                 };
@@ -440,7 +727,7 @@ async fn plan_get_tables(ctx: &IOxSessionContext, cmd: CommandGetTables) -> Resu
                     &schema_name,
                     &table_name,
                     table_type,
-                    table.schema().as_ref(),
+                    &table_schema(table.schema().as_ref()),
                 )?;
             }
         }
@@ -483,6 +770,11 @@ static GET_TABLE_TYPE_SCHEMA: Lazy<SchemaRef> = Lazy::new(|| {
     )]))
 });
 
+/// Transaction id handed out by `ActionBeginTransactionRequest`.
+///
+/// IOx is read-only, so there is no transaction state and all clients share this id.
+const READ_ONLY_TRANSACTION_ID: &[u8] = b"iox-read-only";
+
 static TABLE_TYPES_RECORD_BATCH: Lazy<RecordBatch> = Lazy::new(|| {
     // https://github.com/apache/arrow-datafusion/blob/26b8377b0690916deacf401097d688699026b8fb/datafusion/core/src/catalog/information_schema.rs#L285-L287
     // IOx doesn't support LOCAL TEMPORARY yet
@@ -592,3 +884,660 @@ static GET_XDBC_TYPE_INFO_SCHEMA: Lazy<SchemaRef> = Lazy::new(|| {
         Field::new("interval_precision", DataType::Int32, true),
     ]))
 });
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{BinaryArray, UInt32Array};
+    use arrow_flight::sql::{ActionEndTransactionRequest, CommandPreparedStatementQuery, SqlInfo};
+    use std::time::Duration;
+
+    use iox_query::{
+        exec::{ExecutionContextProvider, Executor},
+        test::{TestChunk, TestDatabase},
+    };
+    use iox_time::SystemProvider;
+
+    use super::*;
+
+    fn metadata_cache() -> MetadataCache {
+        MetadataCache::new(Arc::new(SystemProvider::new()), Duration::from_secs(10))
+    }
+
+    #[tokio::test]
+    async fn test_influxql_statement_query() {
+        let executor = Arc::new(Executor::new_testing());
+        let test_db = Arc::new(TestDatabase::new(Arc::clone(&executor)));
+        test_db.add_chunk(
+            "my_partition_key",
+            Arc::new(
+                TestChunk::new("h2o")
+                    .with_tag_column("foo")
+                    .with_i64_field_column("i64_field")
+                    .with_time_column()
+                    .with_one_row_of_data(),
+            ),
+        );
+        let ctx = test_db.new_query_context(None);
+
+        let cmd = FlightSQLCommand::CommandInfluxQLStatementQuery(CommandInfluxQLStatementQuery {
+            query: "SELECT i64_field FROM h2o".to_string(),
+        });
+
+        // round trips through the wire format
+        let cmd = FlightSQLCommand::try_decode(cmd.try_encode().unwrap()).unwrap();
+
+        let schema = FlightSQLPlanner::get_schema("my_db", cmd.clone(), &ctx)
+            .await
+            .unwrap();
+        let plan = FlightSQLPlanner::do_get("my_db", test_db as _, cmd, &metadata_cache(), &ctx)
+            .await
+            .unwrap();
+
+        let names = |schema: &Schema| {
+            schema
+                .fields()
+                .iter()
+                .map(|f| f.name().clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(&schema),
+            vec!["iox::measurement", "time", "i64_field"]
+        );
+        assert_eq!(names(&plan.schema()), names(&schema));
+    }
+
+    #[tokio::test]
+    async fn test_statement_update() {
+        let executor = Arc::new(Executor::new_testing());
+        let test_db = Arc::new(TestDatabase::new(Arc::clone(&executor)));
+        let ctx = test_db.new_query_context(None);
+
+        let update = |query: &str| {
+            FlightSQLCommand::CommandStatementUpdate(CommandStatementUpdate {
+                query: query.to_string(),
+                ..Default::default()
+            })
+        };
+
+        // transaction statements are no-ops
+        for query in ["START TRANSACTION", "COMMIT", "ROLLBACK"] {
+            let bytes =
+                FlightSQLPlanner::do_put("my_db", Arc::clone(&test_db) as _, update(query), &ctx)
+                    .await
+                    .unwrap();
+            let result = DoPutUpdateResult::decode(bytes).unwrap();
+            assert_eq!(result.record_count, 0);
+        }
+
+        // everything else is explicitly not supported
+        for query in ["CREATE TABLE foo (x INT)", "SELECT 1"] {
+            let err =
+                FlightSQLPlanner::do_put("my_db", Arc::clone(&test_db) as _, update(query), &ctx)
+                    .await
+                    .unwrap_err();
+            assert!(
+                matches!(&err, Error::UnsupportedStatement { statement } if statement == query),
+                "{err}"
+            );
+            assert_eq!(
+                err.to_string(),
+                format!("Statement not supported by IOx: {query}")
+            );
+        }
+
+        // updates are not queries
+        let err = FlightSQLPlanner::do_get(
+            "my_db",
+            test_db as _,
+            update("COMMIT"),
+            &metadata_cache(),
+            &ctx,
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                Error::UnsupportedForMethod {
+                    cmd: "CommandStatementUpdate",
+                    method: "DoGet",
+                }
+            ),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transaction_actions() {
+        let executor = Arc::new(Executor::new_testing());
+        let test_db = Arc::new(TestDatabase::new(Arc::clone(&executor)));
+        let ctx = test_db.new_query_context(None);
+
+        let do_action = |cmd: FlightSQLCommand| {
+            // round trips through the wire format
+            let cmd = FlightSQLCommand::try_decode(cmd.try_encode().unwrap()).unwrap();
+            FlightSQLPlanner::do_action("my_db", Arc::clone(&test_db) as _, cmd, &ctx)
+        };
+        let end = |transaction_id: Bytes, action: EndTransaction| {
+            FlightSQLCommand::ActionEndTransactionRequest(ActionEndTransactionRequest {
+                transaction_id,
+                action: action as i32,
+            })
+        };
+
+        let bytes = do_action(FlightSQLCommand::ActionBeginTransactionRequest(
+            ActionBeginTransactionRequest {},
+        ))
+        .await
+        .unwrap();
+        let msg: Any = Message::decode(bytes).unwrap();
+        let ActionBeginTransactionResult { transaction_id } = msg.unpack().unwrap().unwrap();
+
+        for action in [EndTransaction::Commit, EndTransaction::Rollback] {
+            let bytes = do_action(end(transaction_id.clone(), action))
+                .await
+                .unwrap();
+            assert!(bytes.is_empty());
+        }
+
+        let err = do_action(end(Bytes::from("foo"), EndTransaction::Commit))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid transaction: unknown transaction id"
+        );
+
+        let err = do_action(end(transaction_id, EndTransaction::Unspecified))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid transaction: neither commit nor rollback requested"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prepared_statement_schema() {
+        let executor = Arc::new(Executor::new_testing());
+        let test_db = Arc::new(TestDatabase::new(Arc::clone(&executor)));
+        test_db.add_chunk(
+            "my_partition_key",
+            Arc::new(
+                TestChunk::new("h2o")
+                    .with_tag_column("foo")
+                    .with_i64_field_column("i64_field")
+                    .with_time_column()
+                    .with_one_row_of_data(),
+            ),
+        );
+        let ctx = test_db.new_query_context(None);
+
+        let bytes = FlightSQLPlanner::do_action(
+            "my_db",
+            Arc::clone(&test_db) as _,
+            FlightSQLCommand::ActionCreatePreparedStatementRequest(
+                ActionCreatePreparedStatementRequest {
+                    query: "SELECT foo, i64_field FROM h2o".to_string(),
+                    ..Default::default()
+                },
+            ),
+            &ctx,
+        )
+        .await
+        .unwrap();
+        let msg: Any = Message::decode(bytes).unwrap();
+        let ActionCreatePreparedStatementResult {
+            prepared_statement_handle,
+            ..
+        } = msg.unpack().unwrap().unwrap();
+
+        // the schema is stable, so executing the handle works
+        let msg = Any::pack(&CommandPreparedStatementQuery {
+            prepared_statement_handle,
+        })
+        .unwrap();
+        let cmd = FlightSQLCommand::try_decode(msg.encode_to_vec().into()).unwrap();
+        let FlightSQLCommand::CommandPreparedStatementQuery(handle) = &cmd else {
+            panic!("unexpected command: {cmd}");
+        };
+        assert!(handle.dataset_schema().is_some());
+        FlightSQLPlanner::do_get(
+            "my_db",
+            Arc::clone(&test_db) as _,
+            cmd.clone(),
+            &metadata_cache(),
+            &ctx,
+        )
+        .await
+        .unwrap();
+
+        // handles without a schema are not checked
+        let cmd = FlightSQLCommand::CommandPreparedStatementQuery(PreparedStatementHandle::new(
+            "SELECT foo, i64_field FROM h2o".to_string(),
+        ));
+        FlightSQLPlanner::do_get(
+            "my_db",
+            Arc::clone(&test_db) as _,
+            cmd,
+            &metadata_cache(),
+            &ctx,
+        )
+        .await
+        .unwrap();
+
+        // a diverging schema is rejected
+        let stale_schema = Schema::new(vec![Field::new("foo", DataType::Int64, true)]);
+        let cmd = FlightSQLCommand::CommandPreparedStatementQuery(
+            PreparedStatementHandle::new("SELECT foo, i64_field FROM h2o".to_string())
+                .with_dataset_schema(encode_schema(&stale_schema).unwrap()),
+        );
+        let err = FlightSQLPlanner::do_get(
+            "my_db",
+            Arc::clone(&test_db) as _,
+            cmd,
+            &metadata_cache(),
+            &ctx,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Schema of prepared statement changed since it was prepared: \
+            expected [foo: Int64], got [foo: Utf8, i64_field: Int64]"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_for_method() {
+        let executor = Arc::new(Executor::new_testing());
+        let test_db = Arc::new(TestDatabase::new(Arc::clone(&executor)));
+        let ctx = test_db.new_query_context(None);
+
+        // prepared statement actions are not queries
+        let cmd = FlightSQLCommand::ActionCreatePreparedStatementRequest(
+            ActionCreatePreparedStatementRequest {
+                query: "SELECT 1".to_string(),
+                ..Default::default()
+            },
+        );
+        let err = FlightSQLPlanner::do_get(
+            "my_db",
+            Arc::clone(&test_db) as _,
+            cmd,
+            &metadata_cache(),
+            &ctx,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "FlightSQL command ActionCreatePreparedStatementRequest is not supported by the DoGet RPC"
+        );
+
+        let cmd = FlightSQLCommand::ActionClosePreparedStatementRequest(
+            PreparedStatementHandle::new("SELECT 1".to_string()),
+        );
+        let err = FlightSQLPlanner::do_get(
+            "my_db",
+            Arc::clone(&test_db) as _,
+            cmd,
+            &metadata_cache(),
+            &ctx,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "FlightSQL command ActionClosePreparedStatementRequest is not supported by the DoGet RPC"
+        );
+
+        // queries are not actions
+        let cmd = FlightSQLCommand::CommandGetCatalogs(CommandGetCatalogs {});
+        let err = FlightSQLPlanner::do_action("my_db", test_db as _, cmd, &ctx)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "FlightSQL command CommandGetCatalogs is not supported by the DoAction RPC"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_empty_namespace() {
+        let executor = Arc::new(Executor::new_testing());
+        let test_db = Arc::new(TestDatabase::new(Arc::clone(&executor)));
+        let ctx = test_db.new_query_context(None);
+        let cmd = FlightSQLCommand::CommandGetCatalogs(CommandGetCatalogs {});
+
+        let err = FlightSQLPlanner::get_schema("", cmd.clone(), &ctx)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidNamespace { .. }), "{err}");
+
+        let err = FlightSQLPlanner::do_get(" ", test_db as _, cmd, &metadata_cache(), &ctx)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidNamespace { .. }), "{err}");
+        assert_eq!(
+            err.to_string(),
+            "Invalid namespace ' ': namespace name is empty"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_catalogs_cached() {
+        let executor = Arc::new(Executor::new_testing());
+        let test_db = Arc::new(TestDatabase::new(Arc::clone(&executor)));
+        let ctx = test_db.new_query_context(None);
+        let cache = metadata_cache();
+
+        let plan_1 = plan_get_catalogs(&ctx, &cache, "my_db", CommandGetCatalogs {})
+            .await
+            .unwrap();
+        let plan_2 = plan_get_catalogs(&ctx, &cache, "my_db", CommandGetCatalogs {})
+            .await
+            .unwrap();
+        assert_eq!(cache.misses(), 1);
+        assert_eq!(plan_1.schema(), plan_2.schema());
+
+        // other namespaces are cached separately
+        plan_get_catalogs(&ctx, &cache, "other_db", CommandGetCatalogs {})
+            .await
+            .unwrap();
+        assert_eq!(cache.misses(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_db_schemas_filter_pattern() {
+        let executor = Arc::new(Executor::new_testing());
+        let test_db = Arc::new(TestDatabase::new(Arc::clone(&executor)));
+        let ctx = test_db.new_query_context(None);
+        let cache = metadata_cache();
+
+        let db_schemas = |pattern: Option<&str>| {
+            let ctx = &ctx;
+            let cache = &cache;
+            let cmd = CommandGetDbSchemas {
+                catalog: None,
+                db_schema_filter_pattern: pattern.map(ToString::to_string),
+            };
+            async move {
+                let plan = plan_get_db_schemas(ctx, cache, "my_db", cmd).await.unwrap();
+                let batches = ctx
+                    .collect(ctx.create_physical_plan(&plan).await.unwrap())
+                    .await
+                    .unwrap();
+                batches
+                    .iter()
+                    .flat_map(|batch| {
+                        batch
+                            .column_by_name("db_schema_name")
+                            .unwrap()
+                            .as_any()
+                            .downcast_ref::<StringArray>()
+                            .unwrap()
+                            .iter()
+                            .map(|s| s.unwrap().to_string())
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        assert_eq!(db_schemas(None).await, ["information_schema", "iox"]);
+        // prefix wildcard
+        assert_eq!(db_schemas(Some("info%")).await, ["information_schema"]);
+        // single-char wildcard
+        assert_eq!(db_schemas(Some("io_")).await, ["iox"]);
+        assert_eq!(db_schemas(Some("i_")).await, Vec::<String>::new());
+        // escaped literals
+        assert_eq!(db_schemas(Some(r"%\_schema")).await, ["information_schema"]);
+        assert_eq!(db_schemas(Some(r"io\%")).await, Vec::<String>::new());
+    }
+
+    #[tokio::test]
+    async fn test_get_tables_filter_pattern() {
+        let executor = Arc::new(Executor::new_testing());
+        let test_db = Arc::new(TestDatabase::new(Arc::clone(&executor)));
+        for table_name in ["cpu", "cpu_load", "cpuXload", "mem"] {
+            test_db.add_chunk(
+                "my_partition_key",
+                Arc::new(TestChunk::new(table_name).with_time_column()),
+            );
+        }
+        let ctx = test_db.new_query_context(None);
+
+        let tables = |pattern: Option<&str>| {
+            let ctx = &ctx;
+            let cmd = CommandGetTables {
+                catalog: None,
+                db_schema_filter_pattern: Some("iox".to_string()),
+                table_name_filter_pattern: pattern.map(ToString::to_string),
+                table_types: vec![],
+                include_schema: false,
+            };
+            async move {
+                let plan = plan_get_tables(ctx, cmd).await.unwrap();
+                let batches = ctx
+                    .collect(ctx.create_physical_plan(&plan).await.unwrap())
+                    .await
+                    .unwrap();
+                batches
+                    .iter()
+                    .flat_map(|batch| {
+                        batch
+                            .column_by_name("table_name")
+                            .unwrap()
+                            .as_any()
+                            .downcast_ref::<StringArray>()
+                            .unwrap()
+                            .iter()
+                            .map(|s| s.unwrap().to_string())
+                            .collect::<Vec<_>>()
+                    })
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        assert_eq!(tables(None).await, ["cpu", "cpuXload", "cpu_load", "mem"]);
+        assert_eq!(tables(Some("cpu%")).await, ["cpu", "cpuXload", "cpu_load"]);
+        assert_eq!(tables(Some("c_u")).await, ["cpu"]);
+        assert_eq!(tables(Some(r"cpu\_%")).await, ["cpu_load"]);
+        assert_eq!(tables(Some("disk%")).await, Vec::<String>::new());
+    }
+
+    #[tokio::test]
+    async fn test_get_sql_info() {
+        let executor = Arc::new(Executor::new_testing());
+        let test_db = Arc::new(TestDatabase::new(Arc::clone(&executor)));
+        let ctx = test_db.new_query_context(None);
+
+        let info_names = |info: Vec<u32>| {
+            let ctx = &ctx;
+            async move {
+                let plan = plan_get_sql_info(ctx, CommandGetSqlInfo { info })
+                    .await
+                    .unwrap();
+                let batches = ctx
+                    .collect(ctx.create_physical_plan(&plan).await.unwrap())
+                    .await
+                    .unwrap();
+                batches
+                    .iter()
+                    .flat_map(|batch| {
+                        batch
+                            .column_by_name("info_name")
+                            .unwrap()
+                            .as_any()
+                            .downcast_ref::<UInt32Array>()
+                            .unwrap()
+                            .values()
+                            .to_vec()
+                    })
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        // no info requested means all info
+        let all = info_names(vec![]).await;
+        assert!(all.len() > 2, "{all:?}");
+        for info in [
+            SqlInfo::FlightSqlServerName,
+            SqlInfo::FlightSqlServerVersion,
+            SqlInfo::FlightSqlServerReadOnly,
+            SqlInfo::SqlKeywords,
+        ] {
+            assert!(all.contains(&(info as u32)), "{info:?} missing in {all:?}");
+        }
+
+        // otherwise filter
+        let filtered = info_names(vec![
+            SqlInfo::FlightSqlServerName as u32,
+            SqlInfo::FlightSqlServerReadOnly as u32,
+        ])
+        .await;
+        assert_eq!(
+            filtered,
+            [
+                SqlInfo::FlightSqlServerName as u32,
+                SqlInfo::FlightSqlServerReadOnly as u32,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_tables_column_metadata() {
+        let executor = Arc::new(Executor::new_testing());
+        let test_db = Arc::new(TestDatabase::new(Arc::clone(&executor)));
+        test_db.add_chunk(
+            "my_partition_key",
+            Arc::new(TestChunk::new("cpu").with_time_column()),
+        );
+        let ctx = test_db.new_query_context(None);
+
+        let time_metadata = |include_schema: bool| {
+            let ctx = &ctx;
+            let cmd = CommandGetTables {
+                catalog: None,
+                db_schema_filter_pattern: Some("iox".to_string()),
+                table_name_filter_pattern: Some("cpu".to_string()),
+                table_types: vec![],
+                include_schema,
+            };
+            async move {
+                let plan = plan_get_tables(ctx, cmd).await.unwrap();
+                let batches = ctx
+                    .collect(ctx.create_physical_plan(&plan).await.unwrap())
+                    .await
+                    .unwrap();
+                assert_eq!(batches.len(), 1);
+
+                let table_schema = batches[0].column_by_name("table_schema")?;
+                let table_schema = table_schema
+                    .as_any()
+                    .downcast_ref::<BinaryArray>()
+                    .unwrap()
+                    .value(0);
+                let schema =
+                    Schema::try_from(IpcMessage(Bytes::copy_from_slice(table_schema))).unwrap();
+                Some(schema.field_with_name("time").unwrap().metadata().clone())
+            }
+        };
+
+        let metadata = time_metadata(true).await.unwrap();
+        assert_eq!(
+            metadata
+                .get("ARROW:FLIGHT:SQL:PRECISION")
+                .map(String::as_str),
+            Some("9")
+        );
+
+        // no schema, no metadata
+        assert_eq!(time_metadata(false).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_get_keys_table_existence() {
+        let executor = Arc::new(Executor::new_testing());
+        let test_db = Arc::new(TestDatabase::new(Arc::clone(&executor)));
+        test_db.add_chunk(
+            "my_partition_key",
+            Arc::new(TestChunk::new("cpu").with_time_column()),
+        );
+        let ctx = test_db.new_query_context(None);
+
+        let commands = |catalog: Option<&str>, db_schema: Option<&str>, table: &str| {
+            let catalog = catalog.map(ToString::to_string);
+            let db_schema = db_schema.map(ToString::to_string);
+            let table = table.to_string();
+            [
+                FlightSQLCommand::CommandGetExportedKeys(CommandGetExportedKeys {
+                    catalog: catalog.clone(),
+                    db_schema: db_schema.clone(),
+                    table: table.clone(),
+                }),
+                FlightSQLCommand::CommandGetImportedKeys(CommandGetImportedKeys {
+                    catalog: catalog.clone(),
+                    db_schema: db_schema.clone(),
+                    table: table.clone(),
+                }),
+                FlightSQLCommand::CommandGetPrimaryKeys(CommandGetPrimaryKeys {
+                    catalog,
+                    db_schema,
+                    table,
+                }),
+            ]
+        };
+
+        // existing tables have no keys
+        for (catalog, db_schema) in [
+            (None, None),
+            (Some("public"), None),
+            (None, Some("iox")),
+            (Some("public"), Some("iox")),
+        ] {
+            for cmd in commands(catalog, db_schema, "cpu") {
+                let plan = FlightSQLPlanner::do_get(
+                    "my_db",
+                    Arc::clone(&test_db) as _,
+                    cmd,
+                    &metadata_cache(),
+                    &ctx,
+                )
+                .await
+                .unwrap();
+                let batches = ctx.collect(plan).await.unwrap();
+                let num_rows: usize = batches.iter().map(|batch| batch.num_rows()).sum();
+                assert_eq!(num_rows, 0);
+            }
+        }
+
+        // unknown tables are rejected
+        for (catalog, db_schema, table, expected) in [
+            (None, None, "mem", "Table not found: public.iox.mem"),
+            (
+                None,
+                Some("other"),
+                "cpu",
+                "Table not found: public.other.cpu",
+            ),
+            (Some("other"), None, "cpu", "Table not found: other.iox.cpu"),
+        ] {
+            for cmd in commands(catalog, db_schema, table) {
+                let err = FlightSQLPlanner::do_get(
+                    "my_db",
+                    Arc::clone(&test_db) as _,
+                    cmd,
+                    &metadata_cache(),
+                    &ctx,
+                )
+                .await
+                .unwrap_err();
+                assert!(matches!(err, Error::TableNotFound { .. }), "{err}");
+                assert_eq!(err.to_string(), expected);
+            }
+        }
+    }
+}
@@ -230,7 +230,8 @@ mod tests {
     use chrono::TimeZone;
     use data_types::{
         ColumnId, ColumnSet, CompactionLevel, NamespaceId, ParquetFile, ParquetFileId,
-        ParquetFileParams, PartitionId, TableId, Timestamp, TransitionPartitionId,
+        ParquetFileParams, PartitionFileStats, PartitionId, TableId, Timestamp,
+        TransitionPartitionId,
     };
     use iox_catalog::{
         interface::Catalog,
@@ -548,6 +549,13 @@ mod tests {
                 .await
         }
 
+        async fn partition_file_stats(
+            &mut self,
+            partition_ids: &[PartitionId],
+        ) -> iox_catalog::interface::Result<Vec<PartitionFileStats>> {
+            self.inner.partition_file_stats(partition_ids).await
+        }
+
         async fn get_by_object_store_id(
             &mut self,
             object_store_id: Uuid,
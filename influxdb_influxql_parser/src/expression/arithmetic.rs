@@ -721,6 +721,9 @@ mod test {
         let (_, got) = arithmetic_expression("5 + $foo").unwrap();
         assert_eq!(got, binary_op!(5, Add, param!("foo")));
 
+        let (_, got) = arithmetic_expression("5 + ?").unwrap();
+        assert_eq!(got, binary_op!(5, Add, param!("")));
+
         // Following two tests validate that operators of higher precedence
         // are nested deeper in the AST.
 
@@ -1,7 +1,7 @@
 //! Types and parsers for literals.
 
-use crate::common::ws0;
-use crate::internal::{map_error, map_fail, ParseResult};
+use crate::common::{ws0, ParseError};
+use crate::internal::{map_error, map_fail, Error as InternalError, ParseResult};
 use crate::keywords::keyword;
 use crate::string::{regex, single_quoted_string, Regex};
 use crate::timestamp::Timestamp;
@@ -11,10 +11,14 @@ use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::{char, digit0, digit1};
 use nom::combinator::{map, opt, recognize, value};
-use nom::multi::fold_many1;
+use nom::multi::fold_many_m_n;
 use nom::sequence::{pair, preceded, separated_pair};
+use nom::Offset as _;
+use std::cmp::Ordering;
 use std::fmt;
 use std::fmt::{Display, Formatter, Write};
+use std::ops::{Add, Neg, Sub};
+use std::str::FromStr;
 
 /// Number of nanoseconds in a microsecond.
 const NANOS_PER_MICRO: i64 = 1000;
@@ -41,6 +45,11 @@ pub enum Literal {
     Unsigned(u64),
 
     /// Float literal.
+    ///
+    /// InfluxQL cannot represent non-finite values. A `NaN` or infinite float
+    /// is formatted as `NaN`, `+Inf` or `-Inf` respectively, like InfluxDB
+    /// does, which does not parse as InfluxQL. Use [`Literal::float`] to
+    /// construct a float literal that is guaranteed to round-trip.
     Float(f64),
 
     /// Unescaped string literal.
@@ -59,6 +68,58 @@ pub enum Literal {
     Timestamp(Timestamp),
 }
 
+impl Literal {
+    /// Returns a [`Literal::Float`] for `v`, or `None` if `v` is `NaN` or
+    /// infinite and therefore cannot be represented in InfluxQL.
+    pub fn float(v: f64) -> Option<Self> {
+        v.is_finite().then_some(Self::Float(v))
+    }
+
+    /// Returns the literal as an `f64`, if it is numeric.
+    ///
+    /// [`Literal::Integer`], [`Literal::Unsigned`] and [`Literal::Float`] are
+    /// converted to an `f64`, with a [`Literal::Boolean`] mapped to `1.0` or `0.0`.
+    /// All other variants return `None`.
+    pub fn try_as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Integer(v) => Some(*v as f64),
+            Self::Unsigned(v) => Some(*v as f64),
+            Self::Float(v) => Some(*v),
+            Self::Boolean(v) => Some(if *v { 1.0 } else { 0.0 }),
+            Self::String(_) | Self::Duration(_) | Self::Regex(_) | Self::Timestamp(_) => None,
+        }
+    }
+
+    /// Compares the values of two numeric literals, which may be of
+    /// different variants.
+    ///
+    /// [`Literal::Integer`] and [`Literal::Unsigned`] values are compared
+    /// exactly, and are converted to an `f64` when compared to a
+    /// [`Literal::Float`]. Returns `None` if either literal is not numeric or
+    /// a float is `NaN`.
+    pub fn numeric_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Integer(a), Self::Integer(b)) => Some(a.cmp(b)),
+            (Self::Unsigned(a), Self::Unsigned(b)) => Some(a.cmp(b)),
+            (Self::Integer(a), Self::Unsigned(b)) => Some(i128::from(*a).cmp(&i128::from(*b))),
+            (Self::Unsigned(a), Self::Integer(b)) => Some(i128::from(*a).cmp(&i128::from(*b))),
+            (
+                Self::Integer(_) | Self::Unsigned(_) | Self::Float(_),
+                Self::Integer(_) | Self::Unsigned(_) | Self::Float(_),
+            ) => self.try_as_f64()?.partial_cmp(&other.try_as_f64()?),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if both literals are numeric and have the same value,
+    /// such as `5` and `5.0`.
+    ///
+    /// See [`Literal::numeric_cmp`].
+    pub fn numeric_eq(&self, other: &Self) -> bool {
+        self.numeric_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
 impl From<String> for Literal {
     fn from(v: String) -> Self {
         Self::String(v)
@@ -106,6 +167,12 @@ impl Display for Literal {
         match self {
             Self::Integer(v) => write!(f, "{v}"),
             Self::Unsigned(v) => write!(f, "{v}"),
+            // `NaN` and infinity do not parse as InfluxQL, but formatting must
+            // not fail, so they are written the same way InfluxDB writes them
+            Self::Float(v) if v.is_nan() => f.write_str("NaN"),
+            Self::Float(v) if v.is_infinite() => {
+                f.write_str(if v.is_sign_positive() { "+Inf" } else { "-Inf" })
+            }
             Self::Float(v) => write!(f, "{v}"),
             Self::String(v) => {
                 f.write_char('\'')?;
@@ -231,7 +298,8 @@ fn boolean(i: &str) -> ParseResult<&str, bool> {
     alt((value(true, keyword("TRUE")), value(false, keyword("FALSE"))))(i)
 }
 
-#[derive(Clone)]
+/// Units of a duration fragment, ordered from the smallest to the largest unit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 enum DurationUnit {
     Nanosecond,
     Microsecond,
@@ -260,19 +328,178 @@ static DIVISORS: [(i64, &str); 8] = [
     (1, "ns"),
 ];
 
+impl Duration {
+    /// Returns the duration as a number of nanoseconds.
+    pub fn as_nanos(&self) -> i64 {
+        self.0
+    }
+
+    /// Checked addition. Returns `None` if overflow occurred.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    /// Checked subtraction. Returns `None` if overflow occurred.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+
+    /// Checked negation. Returns `None` if the duration is [`i64::MIN`] nanoseconds.
+    pub fn checked_neg(self) -> Option<Self> {
+        self.0.checked_neg().map(Self)
+    }
+
+    /// Saturating addition, clamping the result to the bounds of an `i64`.
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+
+    /// Saturating subtraction, clamping the result to the bounds of an `i64`.
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Saturating negation, which returns [`i64::MAX`] nanoseconds when
+    /// the duration is [`i64::MIN`] nanoseconds.
+    pub fn saturating_neg(self) -> Self {
+        Self(self.0.saturating_neg())
+    }
+
+    /// Converts the duration to a [`std::time::Duration`]. Returns `None` if
+    /// the duration is negative.
+    pub fn to_std(&self) -> Option<std::time::Duration> {
+        u64::try_from(self.0)
+            .ok()
+            .map(std::time::Duration::from_nanos)
+    }
+
+    /// Converts a [`std::time::Duration`] to a duration. Returns `None` if
+    /// the number of nanoseconds exceeds [`i64::MAX`].
+    pub fn from_std(d: std::time::Duration) -> Option<Self> {
+        i64::try_from(d.as_nanos()).ok().map(Self)
+    }
+
+    /// Converts a [`std::time::Duration`] to a duration, clamping the result
+    /// to [`i64::MAX`] nanoseconds.
+    pub fn saturating_from_std(d: std::time::Duration) -> Self {
+        Self::from_std(d).unwrap_or(Self(i64::MAX))
+    }
+
+    /// Decomposes the magnitude of the duration into weeks, days, hours, etc., using the
+    /// same units as [`Display`].
+    pub fn components(&self) -> DurationComponents {
+        let mut remainder = self.0.unsigned_abs();
+        let mut parts = [0_u64; 8];
+        for (part, (div, _)) in parts.iter_mut().zip(DIVISORS.iter()) {
+            let div = *div as u64;
+            *part = remainder / div;
+            remainder %= div;
+        }
+        let [weeks, days, hours, minutes, seconds, millis, micros, nanos] = parts;
+
+        DurationComponents {
+            negative: self.0.is_negative(),
+            weeks,
+            days,
+            hours,
+            minutes,
+            seconds,
+            millis,
+            micros,
+            nanos,
+        }
+    }
+}
+
+/// A [`Duration`] decomposed into its units, see [`Duration::components`].
+///
+/// Every unit holds the remainder of the next larger unit, e.g. `hours` is less than 24.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DurationComponents {
+    /// `true` if the duration is negative. All other fields describe its magnitude.
+    pub negative: bool,
+    /// Number of weeks.
+    pub weeks: u64,
+    /// Number of days, less than 7.
+    pub days: u64,
+    /// Number of hours, less than 24.
+    pub hours: u64,
+    /// Number of minutes, less than 60.
+    pub minutes: u64,
+    /// Number of seconds, less than 60.
+    pub seconds: u64,
+    /// Number of milliseconds, less than 1000.
+    pub millis: u64,
+    /// Number of microseconds, less than 1000.
+    pub micros: u64,
+    /// Number of nanoseconds, less than 1000.
+    pub nanos: u64,
+}
+
+/// The error returned when converting a negative [`Duration`] to a
+/// [`std::time::Duration`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NegativeDurationError(pub Duration);
+
+impl Display for NegativeDurationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "negative duration {}", self.0)
+    }
+}
+
+impl std::error::Error for NegativeDurationError {}
+
+impl TryFrom<Duration> for std::time::Duration {
+    type Error = NegativeDurationError;
+
+    fn try_from(value: Duration) -> Result<Self, Self::Error> {
+        value.to_std().ok_or(NegativeDurationError(value))
+    }
+}
+
+/// Adds two durations, saturating at the numeric bounds rather than overflowing.
+impl Add for Duration {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.saturating_add(rhs)
+    }
+}
+
+/// Subtracts two durations, saturating at the numeric bounds rather than overflowing.
+impl Sub for Duration {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.saturating_sub(rhs)
+    }
+}
+
+/// Negates a duration, saturating at the numeric bounds rather than overflowing.
+impl Neg for Duration {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.saturating_neg()
+    }
+}
+
 impl Display for Duration {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let v = if self.0.is_negative() {
+        if self.0.is_negative() {
             write!(f, "-")?;
-            -self.0
-        } else {
-            self.0
-        };
+        }
+        // Use the unsigned magnitude, so that i64::MIN does not overflow
+        let v = self.0.unsigned_abs();
         match v {
             0 => f.write_str("0s")?,
             mut i => {
                 // only return the divisors that are > self
-                for (div, unit) in DIVISORS.iter().filter(|(div, _)| v > *div) {
+                for (div, unit) in DIVISORS
+                    .iter()
+                    .map(|(div, unit)| (*div as u64, unit))
+                    .filter(|(div, _)| v > *div)
+                {
                     let units = i / div;
                     if units > 0 {
                         write!(f, "{units}{unit}")?;
@@ -288,6 +515,12 @@ impl Display for Duration {
 
 /// Parse the input for a InfluxQL duration fragment and returns the value in nanoseconds.
 fn single_duration(i: &str) -> ParseResult<&str, i64> {
+    map(duration_fragment, |(_, v)| v)(i)
+}
+
+/// Parse the input for a InfluxQL duration fragment and returns its unit and the value in
+/// nanoseconds.
+fn duration_fragment(i: &str) -> ParseResult<&str, (DurationUnit, i64)> {
     use DurationUnit::*;
 
     map_fail(
@@ -317,17 +550,83 @@ fn single_duration(i: &str) -> ParseResult<&str, i64> {
                 Day => v.checked_mul(NANOS_PER_DAY),
                 Week => v.checked_mul(NANOS_PER_WEEK),
             })
+            .map(|v| (unit, v))
             .ok_or("integer overflow")
         },
     )(i)
 }
 
-/// Parse the input for an InfluxQL duration.
+/// Maximum number of fragments of an InfluxQL duration accepted by [`duration`], e.g. `1h30m`
+/// consists of two fragments.
+pub(crate) const DEFAULT_MAX_DURATION_FRAGMENTS: usize = 16;
+
+/// Parse the input for an InfluxQL duration of at most [`DEFAULT_MAX_DURATION_FRAGMENTS`]
+/// fragments.
 pub(crate) fn duration(i: &str) -> ParseResult<&str, Duration> {
-    map(
-        fold_many1(single_duration, || 0, |acc, fragment| acc + fragment),
-        Duration,
-    )(i)
+    duration_with_max_fragments(DEFAULT_MAX_DURATION_FRAGMENTS)(i)
+}
+
+/// Returns a parser for an InfluxQL duration of at most `max_fragments` fragments.
+///
+/// Inputs with more fragments, such as `1s1s1s…`, fail rather than being summed up, which
+/// bounds the work spent on adversarial input.
+pub(crate) fn duration_with_max_fragments<'a>(
+    max_fragments: usize,
+) -> impl FnMut(&'a str) -> ParseResult<&'a str, Duration> {
+    move |i| {
+        let (remaining, d) = map(
+            fold_many_m_n(
+                1,
+                max_fragments,
+                single_duration,
+                || 0,
+                |acc, fragment| acc + fragment,
+            ),
+            Duration,
+        )(i)?;
+
+        match single_duration(remaining) {
+            Err(nom::Err::Error(_)) => Ok((remaining, d)),
+            _ => Err(nom::Err::Failure(InternalError::Syntax {
+                input: remaining,
+                message: "too many duration fragments",
+            })),
+        }
+    }
+}
+
+/// Parse the input for an InfluxQL duration, requiring the fragments to be in strictly
+/// descending unit order.
+///
+/// Unlike [`duration`], which accepts fragments in any order and sums repeated units,
+/// `2h30m` is accepted but `30m2h` and `2h3h` are rejected.
+pub(crate) fn duration_strict(i: &str) -> ParseResult<&str, Duration> {
+    let (mut remaining, (mut last_unit, mut acc)) = duration_fragment(i)?;
+
+    loop {
+        let (rem, (unit, fragment)) = match duration_fragment(remaining) {
+            Ok(res) => res,
+            Err(nom::Err::Error(_)) => return Ok((remaining, Duration(acc))),
+            Err(e) => return Err(e),
+        };
+
+        if unit == last_unit {
+            return Err(nom::Err::Failure(InternalError::Syntax {
+                input: remaining,
+                message: "duplicate duration unit",
+            }));
+        }
+        if unit > last_unit {
+            return Err(nom::Err::Failure(InternalError::Syntax {
+                input: remaining,
+                message: "duration units must be in descending order",
+            }));
+        }
+
+        acc += fragment;
+        last_unit = unit;
+        remaining = rem;
+    }
 }
 
 /// Parse an InfluxQL literal, except a [`Regex`].
@@ -354,6 +653,92 @@ pub(crate) fn literal_regex(i: &str) -> ParseResult<&str, Literal> {
     map(regex, Literal::Regex)(i)
 }
 
+/// Parse the input completely and return a [`Literal`].
+///
+/// All leading and trailing whitespace is consumed. If any input remains after parsing,
+/// an error is returned.
+pub fn parse_literal(input: &str) -> Result<Literal, ParseError> {
+    let mut i: &str = input;
+
+    // Consume whitespace from the input
+    (i, _) = ws0(i).expect("ws0 is infallible");
+
+    if i.is_empty() {
+        return Err(ParseError {
+            message: "unexpected eof".into(),
+            pos: 0,
+        });
+    }
+
+    let (mut i, lit) = match literal(i) {
+        Ok((i1, lit)) => (i1, lit),
+        Err(nom::Err::Failure(InternalError::Syntax {
+            input: pos,
+            message,
+        })) => {
+            return Err(ParseError {
+                message: message.into(),
+                pos: input.offset(pos),
+            })
+        }
+        // any other error indicates an invalid literal
+        Err(_) => {
+            return Err(ParseError {
+                message: "invalid literal".into(),
+                pos: input.offset(i),
+            })
+        }
+    };
+
+    // Consume remaining whitespace from the input
+    (i, _) = ws0(i).expect("ws0 is infallible");
+
+    if !i.is_empty() {
+        return Err(ParseError {
+            message: "invalid literal".into(),
+            pos: input.offset(i),
+        });
+    }
+
+    Ok(lit)
+}
+
+/// Parse the input as an InfluxQL duration whose fragments are in strictly descending unit
+/// order, such as `1h30m`.
+///
+/// This is stricter than the durations accepted by [`parse_literal`], which allows fragments in
+/// any order and repeated units.
+pub fn parse_duration_strict(input: &str) -> Result<Duration, ParseError> {
+    let i = input.trim();
+
+    match duration_strict(i) {
+        Ok(("", d)) => Ok(d),
+        Ok((rem, _)) => Err(ParseError {
+            message: "invalid duration".into(),
+            pos: input.offset(rem),
+        }),
+        Err(nom::Err::Failure(InternalError::Syntax {
+            input: pos,
+            message,
+        })) => Err(ParseError {
+            message: message.into(),
+            pos: input.offset(pos),
+        }),
+        Err(_) => Err(ParseError {
+            message: "invalid duration".into(),
+            pos: input.offset(i),
+        }),
+    }
+}
+
+impl FromStr for Literal {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_literal(s)
+    }
+}
+
 /// Returns `nanos` as a timestamp.
 pub fn nanos_to_timestamp(nanos: i64) -> Timestamp {
     let (secs, nsec) = num_integer::div_mod_floor(nanos, NANOS_PER_SEC);
@@ -368,7 +753,9 @@ pub fn nanos_to_timestamp(nanos: i64) -> Timestamp {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::assert_expect_error;
     use assert_matches::assert_matches;
+    use test_helpers::assert_error;
 
     #[test]
     fn test_literal_no_regex() {
@@ -412,6 +799,122 @@ mod test {
         assert_matches!(got, Literal::Regex(v) if v == "^(match|this)$".into());
     }
 
+    /// Validate the [`FromStr`] implementation for [`Literal`].
+    #[test]
+    fn test_literal_parse() {
+        let got: Literal = "42".parse().unwrap();
+        assert_matches!(got, Literal::Integer(42));
+
+        let got: Literal = " 'quick draw' ".parse().unwrap();
+        assert_matches!(got, Literal::String(v) if v == "quick draw");
+
+        let got: Literal = "/^(match|this)$/".parse().unwrap();
+        assert_matches!(got, Literal::Regex(v) if v == "^(match|this)$".into());
+
+        // Fallible cases
+
+        // Trailing input
+        assert_error!("42 abc".parse::<Literal>(), ref e @ ParseError { .. } if e.pos == 3);
+
+        // Not a literal
+        assert_error!("abc".parse::<Literal>(), ref e @ ParseError { .. } if e.pos == 0);
+
+        // Empty input
+        assert_error!("  ".parse::<Literal>(), ref e @ ParseError { .. } if e.message == "unexpected eof");
+    }
+
+    #[test]
+    fn test_literal_float() {
+        assert_matches!(Literal::float(42.5), Some(Literal::Float(v)) if v == 42.5);
+        assert_eq!(Literal::float(42.5).unwrap().to_string(), "42.5");
+
+        assert!(Literal::float(f64::NAN).is_none());
+        assert!(Literal::float(f64::INFINITY).is_none());
+        assert!(Literal::float(f64::NEG_INFINITY).is_none());
+
+        // Formatting a non-finite float constructed directly never fails.
+        assert_eq!(Literal::Float(f64::NAN).to_string(), "NaN");
+        assert_eq!(Literal::Float(f64::INFINITY).to_string(), "+Inf");
+        assert_eq!(Literal::Float(f64::NEG_INFINITY).to_string(), "-Inf");
+    }
+
+    #[test]
+    fn test_literal_try_as_f64() {
+        assert_eq!(Literal::Integer(-42).try_as_f64(), Some(-42.0));
+        assert_eq!(Literal::Unsigned(42).try_as_f64(), Some(42.0));
+        assert_eq!(Literal::Float(42.5).try_as_f64(), Some(42.5));
+        assert_eq!(Literal::Boolean(true).try_as_f64(), Some(1.0));
+        assert_eq!(Literal::Boolean(false).try_as_f64(), Some(0.0));
+
+        // Non-numeric literals
+        assert_eq!(Literal::String("42".into()).try_as_f64(), None);
+        assert_eq!(Literal::Regex("42".into()).try_as_f64(), None);
+        assert_eq!(Literal::Duration(Duration(42)).try_as_f64(), None);
+        assert_eq!(
+            Literal::Timestamp(nanos_to_timestamp(42)).try_as_f64(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_literal_numeric_cmp() {
+        // 5 vs 5.0
+        assert_eq!(
+            Literal::Unsigned(5).numeric_cmp(&Literal::Float(5.0)),
+            Some(Ordering::Equal)
+        );
+        assert!(Literal::Unsigned(5).numeric_eq(&Literal::Float(5.0)));
+        assert!(Literal::Float(5.0).numeric_eq(&Literal::Integer(5)));
+        assert_ne!(Literal::Unsigned(5), Literal::Float(5.0));
+
+        // 5 vs 6.0
+        assert_eq!(
+            Literal::Unsigned(5).numeric_cmp(&Literal::Float(6.0)),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            Literal::Float(6.0).numeric_cmp(&Literal::Unsigned(5)),
+            Some(Ordering::Greater)
+        );
+        assert!(!Literal::Unsigned(5).numeric_eq(&Literal::Float(6.0)));
+
+        // integers are compared exactly
+        assert_eq!(
+            Literal::Integer(-1).numeric_cmp(&Literal::Unsigned(u64::MAX)),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            Literal::Unsigned(u64::MAX).numeric_cmp(&Literal::Unsigned(u64::MAX - 1)),
+            Some(Ordering::Greater)
+        );
+        assert!(Literal::Integer(42).numeric_eq(&Literal::Unsigned(42)));
+
+        // NaN is not comparable
+        assert_eq!(
+            Literal::Float(f64::NAN).numeric_cmp(&Literal::Float(f64::NAN)),
+            None
+        );
+
+        // Non-numeric literals
+        assert_eq!(
+            Literal::String("5".into()).numeric_cmp(&Literal::Unsigned(5)),
+            None
+        );
+        assert!(!Literal::String("5".into()).numeric_eq(&Literal::Unsigned(5)));
+        assert_eq!(
+            Literal::Float(5.0).numeric_cmp(&Literal::Regex("5".into())),
+            None
+        );
+        assert_eq!(
+            Literal::Duration(Duration(5)).numeric_cmp(&Literal::Duration(Duration(5))),
+            None
+        );
+        assert_eq!(
+            Literal::Boolean(true).numeric_cmp(&Literal::Unsigned(1)),
+            None
+        );
+    }
+
     #[test]
     fn test_integer() {
         let (_, got) = integer("42").unwrap();
@@ -521,6 +1024,74 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_duration_max_fragments() {
+        let at_cap = "1s".repeat(DEFAULT_MAX_DURATION_FRAGMENTS);
+        let (rem, got) = duration(&at_cap).unwrap();
+        assert_eq!(rem, "");
+        assert_eq!(
+            got,
+            Duration(DEFAULT_MAX_DURATION_FRAGMENTS as i64 * NANOS_PER_SEC)
+        );
+
+        let over_cap = "1s".repeat(DEFAULT_MAX_DURATION_FRAGMENTS + 1);
+        assert_expect_error!(duration(&over_cap), "too many duration fragments");
+
+        // The cap is configurable
+        let (_, got) = duration_with_max_fragments(2)("1h30m").unwrap();
+        assert_eq!(got, Duration(NANOS_PER_HOUR + 30 * NANOS_PER_MIN));
+        assert_expect_error!(
+            duration_with_max_fragments(2)("1h30m10s"),
+            "too many duration fragments"
+        );
+
+        // Also surfaced when parsing a literal
+        assert_error!(
+            over_cap.parse::<Literal>(),
+            ref e @ ParseError { .. } if e.message == "too many duration fragments"
+        );
+    }
+
+    #[test]
+    fn test_duration_strict() {
+        let (_, got) = duration_strict("2h30m").unwrap();
+        assert_eq!(got, Duration(2 * NANOS_PER_HOUR + 30 * NANOS_PER_MIN));
+
+        let (_, got) = duration_strict("1w2d3h4m5s6ms7u8ns").unwrap();
+        assert_eq!(
+            got,
+            Duration(
+                NANOS_PER_WEEK
+                    + 2 * NANOS_PER_DAY
+                    + 3 * NANOS_PER_HOUR
+                    + 4 * NANOS_PER_MIN
+                    + 5 * NANOS_PER_SEC
+                    + 6 * NANOS_PER_MILLI
+                    + 7 * NANOS_PER_MICRO
+                    + 8
+            )
+        );
+
+        // Remaining input is not consumed
+        let (rem, got) = duration_strict("5s foo").unwrap();
+        assert_eq!(rem, " foo");
+        assert_eq!(got, Duration(5 * NANOS_PER_SEC));
+
+        // The lenient parser accepts both of the following
+        assert_expect_error!(
+            duration_strict("30m2h"),
+            "duration units must be in descending order"
+        );
+        assert_expect_error!(duration_strict("2h3h"), "duplicate duration unit");
+
+        assert_eq!(
+            parse_duration_strict(" 1h30m ").unwrap(),
+            Duration(NANOS_PER_HOUR + 30 * NANOS_PER_MIN)
+        );
+        assert_error!(parse_duration_strict("1h2h"), ref e @ ParseError { .. } if e.pos == 2 && e.message == "duplicate duration unit");
+        assert_error!(parse_duration_strict("1h foo"), ref e @ ParseError { .. } if e.pos == 2);
+    }
+
     #[test]
     fn test_display_duration() {
         let (_, d) = duration("3w2h15ms").unwrap();
@@ -550,6 +1121,148 @@ mod test {
         assert_eq!(d.to_string(), "20w6d13h11m10s9ms8us500ns");
     }
 
+    #[test]
+    fn test_duration_arithmetic() {
+        let a = Duration(2 * NANOS_PER_HOUR);
+        let b = Duration(30 * NANOS_PER_MIN);
+
+        assert_eq!(a + b, Duration(2 * NANOS_PER_HOUR + 30 * NANOS_PER_MIN));
+        assert_eq!(a - b, Duration(NANOS_PER_HOUR + 30 * NANOS_PER_MIN));
+        assert_eq!(b - a, Duration(-(NANOS_PER_HOUR + 30 * NANOS_PER_MIN)));
+        assert_eq!(-a, Duration(-2 * NANOS_PER_HOUR));
+        assert_eq!((a + b).as_nanos(), 2 * NANOS_PER_HOUR + 30 * NANOS_PER_MIN);
+
+        assert_eq!(a.checked_add(b), Some(a + b));
+        assert_eq!(a.checked_sub(b), Some(a - b));
+        assert_eq!(a.checked_neg(), Some(-a));
+
+        // Overflow
+        assert_eq!(Duration(i64::MAX).checked_add(Duration(1)), None);
+        assert_eq!(Duration(i64::MIN).checked_sub(Duration(1)), None);
+        assert_eq!(Duration(i64::MIN).checked_neg(), None);
+
+        // Saturating
+        assert_eq!(Duration(i64::MAX) + Duration(1), Duration(i64::MAX));
+        assert_eq!(Duration(i64::MIN) - Duration(1), Duration(i64::MIN));
+        assert_eq!(-Duration(i64::MIN), Duration(i64::MAX));
+
+        // Display of negated and combined durations
+        assert_eq!((-(a + b)).to_string(), "-2h30m");
+        assert_eq!((b - a).to_string(), "-1h30m");
+        assert_eq!(
+            Duration(i64::MIN).to_string(),
+            "-15250w1d23h47m16s854ms775us808ns"
+        );
+    }
+
+    #[test]
+    fn test_duration_components() {
+        let d = Duration(
+            20 * NANOS_PER_WEEK
+                + 6 * NANOS_PER_DAY
+                + 13 * NANOS_PER_HOUR
+                + 11 * NANOS_PER_MIN
+                + 10 * NANOS_PER_SEC
+                + 9 * NANOS_PER_MILLI
+                + 8 * NANOS_PER_MICRO
+                + 500,
+        );
+        let expected = DurationComponents {
+            negative: false,
+            weeks: 20,
+            days: 6,
+            hours: 13,
+            minutes: 11,
+            seconds: 10,
+            millis: 9,
+            micros: 8,
+            nanos: 500,
+        };
+        assert_eq!(d.components(), expected);
+        assert_eq!(
+            (-d).components(),
+            DurationComponents {
+                negative: true,
+                ..expected
+            }
+        );
+
+        assert_eq!(Duration(0).components(), DurationComponents::default());
+        assert_eq!(
+            Duration(NANOS_PER_WEEK).components(),
+            DurationComponents {
+                weeks: 1,
+                ..Default::default()
+            }
+        );
+
+        // i64::MIN does not overflow
+        assert_eq!(
+            Duration(i64::MIN).components(),
+            DurationComponents {
+                negative: true,
+                weeks: 15250,
+                days: 1,
+                hours: 23,
+                minutes: 47,
+                seconds: 16,
+                millis: 854,
+                micros: 775,
+                nanos: 808,
+            }
+        );
+    }
+
+    #[test]
+    fn test_duration_to_std() {
+        let d = Duration(90 * NANOS_PER_SEC + 5);
+        assert_eq!(d.to_std(), Some(std::time::Duration::new(90, 5)));
+        assert_eq!(
+            std::time::Duration::try_from(d),
+            Ok(std::time::Duration::new(90, 5))
+        );
+
+        assert_eq!(Duration(0).to_std(), Some(std::time::Duration::ZERO));
+        assert_eq!(
+            std::time::Duration::try_from(Duration(0)),
+            Ok(std::time::Duration::ZERO)
+        );
+
+        let d = Duration(-NANOS_PER_SEC);
+        assert_eq!(d.to_std(), None);
+        let err = std::time::Duration::try_from(d).unwrap_err();
+        assert_eq!(err, NegativeDurationError(d));
+        assert_eq!(err.to_string(), "negative duration -1s");
+    }
+
+    #[test]
+    fn test_duration_from_std() {
+        let d = std::time::Duration::new(90, 5);
+        assert_eq!(
+            Duration::from_std(d),
+            Some(Duration(90 * NANOS_PER_SEC + 5))
+        );
+        assert_eq!(
+            Duration::saturating_from_std(d),
+            Duration(90 * NANOS_PER_SEC + 5)
+        );
+        assert_eq!(
+            Duration::from_std(std::time::Duration::ZERO),
+            Some(Duration(0))
+        );
+
+        // round trip
+        let d = Duration(i64::MAX);
+        assert_eq!(Duration::from_std(d.to_std().unwrap()), Some(d));
+
+        // overflow
+        let d = std::time::Duration::MAX;
+        assert_eq!(Duration::from_std(d), None);
+        assert_eq!(Duration::saturating_from_std(d), Duration(i64::MAX));
+        let d = std::time::Duration::from_nanos(i64::MAX as u64 + 1);
+        assert_eq!(Duration::from_std(d), None);
+    }
+
     #[test]
     fn test_number() {
         // Test floating point numbers
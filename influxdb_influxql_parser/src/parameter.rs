@@ -4,6 +4,8 @@
 //! by a `$`. Per the original Go [implementation], the token following the `$` is
 //! parsed as an identifier, and therefore may appear in double quotes.
 //!
+//! A `?` is parsed as a positional bind parameter, which has no name.
+//!
 //! [bind parameter]: https://docs.influxdata.com/influxdb/v1.8/tools/api/#bind-parameters
 //! [implementation]: https://github.com/influxdata/influxql/blob/df51a45762be9c1b578f01718fa92d286a843fe9/scanner.go#L57-L62
 
@@ -13,7 +15,7 @@ use crate::{impl_tuple_clause, write_quoted_string};
 use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::{alphanumeric1, char};
-use nom::combinator::{map, recognize};
+use nom::combinator::{map, recognize, value};
 use nom::multi::many1_count;
 use nom::sequence::preceded;
 use std::fmt;
@@ -25,6 +27,8 @@ fn unquoted_parameter(i: &str) -> ParseResult<&str, &str> {
 }
 
 /// A type that represents an InfluxQL bind parameter.
+///
+/// A positional `?` parameter is represented by an empty name.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct BindParameter(pub(crate) String);
 
@@ -38,6 +42,9 @@ impl From<&str> for BindParameter {
 
 impl Display for BindParameter {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return f.write_char('?');
+        }
         f.write_char('$')?;
         write_quoted_string!(f, '"', self.0.as_str(), unquoted_parameter, '\n' => "\\n", '\\' => "\\\\", '"' => "\\\"");
         Ok(())
@@ -46,14 +53,17 @@ impl Display for BindParameter {
 
 /// Parses an InfluxQL [BindParameter].
 pub(crate) fn parameter(i: &str) -> ParseResult<&str, BindParameter> {
-    // See: https://github.com/influxdata/influxql/blob/df51a45762be9c1b578f01718fa92d286a843fe9/scanner.go#L358-L362
-    preceded(
-        char('$'),
-        alt((
-            map(unquoted_parameter, Into::into),
-            map(double_quoted_string, Into::into),
-        )),
-    )(i)
+    alt((
+        // See: https://github.com/influxdata/influxql/blob/df51a45762be9c1b578f01718fa92d286a843fe9/scanner.go#L358-L362
+        preceded(
+            char('$'),
+            alt((
+                map(unquoted_parameter, Into::into),
+                map(double_quoted_string, Into::into),
+            )),
+        ),
+        value(BindParameter(String::new()), char('?')),
+    ))(i)
 }
 
 #[cfg(test)]
@@ -82,12 +92,21 @@ mod test {
         let (_, got) = parameter("$\"quick draw\"").unwrap();
         assert_eq!(got, "quick draw".into());
 
+        // positional
+        let (rem, got) = parameter("? AND").unwrap();
+        assert_eq!(rem, " AND");
+        assert_eq!(got, "".into());
+
         // ┌─────────────────────────────┐
         // │       Fallible tests        │
         // └─────────────────────────────┘
 
         // missing `$` prefix
         parameter("cpu").unwrap_err();
+
+        // bare `$`
+        parameter("$").unwrap_err();
+        parameter("$ cpu").unwrap_err();
     }
 
     #[test]
@@ -103,5 +122,9 @@ mod test {
         // BindParameter displays unquoted output
         let got = BindParameter("quick_draw".into()).to_string();
         assert_eq!(got, "$quick_draw");
+
+        // positional BindParameter
+        let got = BindParameter("".into()).to_string();
+        assert_eq!(got, "?");
     }
 }
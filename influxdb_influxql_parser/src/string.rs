@@ -188,6 +188,62 @@ impl From<&str> for Regex {
     }
 }
 
+impl Regex {
+    /// Returns the flags set by a leading inline flag group, such as `(?i)`.
+    ///
+    /// The pattern, including any inline flags, is preserved verbatim, so flags
+    /// round-trip through [`Display`]. The flags supported by the Go RE2 syntax
+    /// used by InfluxQL (`i`, `m`, `s` and `U`) have the same meaning in the
+    /// Rust `regex` crate used by IOx, so no translation is required.
+    ///
+    /// Flags that are scoped to a group, such as `(?i:foo)`, or that are not
+    /// at the start of the pattern do not apply to the whole pattern and are
+    /// not reported.
+    pub fn flags(&self) -> RegexFlags {
+        let mut flags = RegexFlags::default();
+
+        let Some(rest) = self.0.strip_prefix("(?") else {
+            return flags;
+        };
+        let Some((group, _)) = rest.split_once(')') else {
+            return flags;
+        };
+
+        let mut value = true;
+        for c in group.chars() {
+            match c {
+                '-' => value = false,
+                'i' => flags.case_insensitive = value,
+                'm' => flags.multi_line = value,
+                's' => flags.dot_matches_new_line = value,
+                'U' => flags.swap_greed = value,
+                // not a flag group
+                _ => return RegexFlags::default(),
+            }
+        }
+
+        flags
+    }
+
+    /// Returns `true` if the regular expression matches case-insensitively.
+    pub fn is_case_insensitive(&self) -> bool {
+        self.flags().case_insensitive
+    }
+}
+
+/// Flags of a [`Regex`] that apply to the whole pattern.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RegexFlags {
+    /// `i`: letters match both upper and lower case.
+    pub case_insensitive: bool,
+    /// `m`: `^` and `$` match at the beginning and end of each line.
+    pub multi_line: bool,
+    /// `s`: `.` matches `\n`.
+    pub dot_matches_new_line: bool,
+    /// `U`: swap the meaning of `x*` and `x*?`.
+    pub swap_greed: bool,
+}
+
 /// Parse a regular expression, delimited by `/`.
 pub(crate) fn regex(i: &str) -> ParseResult<&str, Regex> {
     map(
@@ -346,4 +402,45 @@ mod test {
         // See: https://go.dev/play/p/_8J1v5-382G
         assert_expect_error!(regex(r#"/\/"#), "unterminated regex literal");
     }
+
+    #[test]
+    fn test_regex_flags() {
+        // case-insensitive flag round-trips through parse → display → parse
+        let (_, got) = regex("/(?i)foo/").unwrap();
+        assert_eq!(got, "(?i)foo".into());
+        assert!(got.is_case_insensitive());
+        let s = got.to_string();
+        assert_eq!(s, "/(?i)foo/");
+        let (_, got2) = regex(&s).unwrap();
+        assert_eq!(got2, got);
+
+        let (_, got) = regex("/(?is-m)foo/").unwrap();
+        assert_eq!(
+            got.flags(),
+            RegexFlags {
+                case_insensitive: true,
+                dot_matches_new_line: true,
+                ..Default::default()
+            }
+        );
+
+        let (_, got) = regex("/(?U)foo/").unwrap();
+        assert!(got.flags().swap_greed);
+
+        // no flags
+        let (_, got) = regex("/foo/").unwrap();
+        assert_eq!(got.flags(), RegexFlags::default());
+
+        // group-scoped flags do not apply to the whole pattern
+        let (_, got) = regex("/(?i:foo)bar/").unwrap();
+        assert!(!got.is_case_insensitive());
+
+        // flags not at the start do not apply to the whole pattern
+        let (_, got) = regex("/foo(?i)bar/").unwrap();
+        assert!(!got.is_case_insensitive());
+
+        // not a flag group
+        let (_, got) = regex("/(?P<name>foo)/").unwrap();
+        assert_eq!(got.flags(), RegexFlags::default());
+    }
 }
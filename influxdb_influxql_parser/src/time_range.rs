@@ -518,15 +518,13 @@ fn reduce_binary_lhs_duration(
     match rhs {
         Expr::Literal(ref val) => match val {
             // durations may be added and subtracted from other durations
-            Literal::Duration(Duration(v)) => match op {
-                BinaryOperator::Add => Ok(lit(Duration(
-                    lhs.checked_add(*v)
-                        .ok_or_else(|| error::map::expr("overflow"))?,
-                ))),
-                BinaryOperator::Sub => Ok(lit(Duration(
-                    lhs.checked_sub(*v)
-                        .ok_or_else(|| error::map::expr("overflow"))?,
-                ))),
+            Literal::Duration(v) => match op {
+                BinaryOperator::Add => Ok(lit(lhs
+                    .checked_add(*v)
+                    .ok_or_else(|| error::map::expr("overflow"))?)),
+                BinaryOperator::Sub => Ok(lit(lhs
+                    .checked_sub(*v)
+                    .ok_or_else(|| error::map::expr("overflow"))?)),
                 _ => error::expr(format!("found operator '{op}', expected +, -")),
             },
             // durations may only be scaled by float literals
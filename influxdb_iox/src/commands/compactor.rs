@@ -0,0 +1,192 @@
+//! This module implements the `compactor` CLI command
+
+use clap_blocks::catalog_dsn::CatalogDsnConfig;
+use comfy_table::{Cell, Table};
+use data_types::{PartitionId, SkippedCompaction};
+use iox_time::Time;
+use serde_json::{json, Value};
+use thiserror::Error;
+
+use crate::process_info::setup_metric_registry;
+
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Catalog error: {0}")]
+    Catalog(#[from] iox_catalog::interface::Error),
+
+    #[error("Catalog DSN error: {0}")]
+    CatalogDsn(#[from] clap_blocks::catalog_dsn::Error),
+
+    #[error("JSON Serialization error: {0}")]
+    Serde(#[from] serde_json::Error),
+}
+
+/// Various commands for manipulating compactor state in the catalog
+#[derive(Debug, clap::Parser)]
+pub struct Config {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+/// All possible subcommands for compactor
+#[derive(Debug, clap::Parser)]
+enum Command {
+    /// Manage partitions the compactor skips
+    #[clap(subcommand)]
+    Skipped(Skipped),
+}
+
+/// All possible subcommands for skipped compactions
+#[derive(Debug, clap::Parser)]
+enum Skipped {
+    /// List skipped compactions and why they were skipped
+    List(List),
+
+    /// Clear skipped compactions so that the affected partitions are compacted again
+    Clear(Clear),
+}
+
+#[derive(Debug, clap::Parser)]
+struct List {
+    #[clap(flatten)]
+    catalog_dsn: CatalogDsnConfig,
+
+    /// Print the skipped compactions as JSON instead of a table
+    #[clap(action, long = "json")]
+    json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+#[clap(group(
+            // Exactly one of "partition_id" and "all" must be given.
+            // NOTE: It takes the variable names and not the flag long names.
+            clap::ArgGroup::new("target")
+                .required(true)
+                .args(&["partition_id", "all"])
+        ))]
+struct Clear {
+    #[clap(flatten)]
+    catalog_dsn: CatalogDsnConfig,
+
+    /// Clear the skipped compaction of this partition
+    #[clap(action, long = "partition-id")]
+    partition_id: Option<i64>,
+
+    /// Clear ALL skipped compactions, requires `--force`
+    #[clap(action, long = "all", requires = "force")]
+    all: bool,
+
+    /// Confirm clearing all skipped compactions
+    #[clap(action, long = "force")]
+    force: bool,
+}
+
+pub async fn command(config: Config) -> Result<(), Error> {
+    match config.command {
+        Command::Skipped(Skipped::List(command)) => {
+            let metrics = setup_metric_registry();
+            let catalog = command.catalog_dsn.get_catalog("cli", metrics).await?;
+
+            let mut skipped_compactions = catalog
+                .repositories()
+                .await
+                .partitions()
+                .list_skipped_compactions()
+                .await?;
+            skipped_compactions.sort_by_key(|skipped| skipped.partition_id);
+
+            if command.json {
+                let skipped_compactions = skipped_compactions.iter().map(to_json).collect();
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&Value::Array(skipped_compactions))?
+                );
+            } else {
+                println!("{}", create_table(&skipped_compactions));
+            }
+        }
+        Command::Skipped(Skipped::Clear(command)) => {
+            let metrics = setup_metric_registry();
+            let catalog = command.catalog_dsn.get_catalog("cli", metrics).await?;
+            let mut repos = catalog.repositories().await;
+
+            let partition_ids = match command.partition_id {
+                Some(partition_id) => vec![PartitionId::new(partition_id)],
+                None => repos
+                    .partitions()
+                    .list_skipped_compactions()
+                    .await?
+                    .into_iter()
+                    .map(|skipped| skipped.partition_id)
+                    .collect(),
+            };
+
+            let mut n_cleared = 0;
+            for partition_id in partition_ids {
+                if repos
+                    .partitions()
+                    .delete_skipped_compactions(partition_id)
+                    .await?
+                    .is_some()
+                {
+                    n_cleared += 1;
+                }
+            }
+
+            println!("Cleared {n_cleared} skipped compaction(s)");
+        }
+    }
+
+    Ok(())
+}
+
+fn skipped_at(skipped_compaction: &SkippedCompaction) -> String {
+    Time::from_timestamp_nanos(skipped_compaction.skipped_at.get()).to_rfc3339()
+}
+
+fn to_json(skipped_compaction: &SkippedCompaction) -> Value {
+    json!({
+        "partition_id": skipped_compaction.partition_id.get(),
+        "reason": skipped_compaction.reason,
+        "skipped_at": skipped_at(skipped_compaction),
+        "estimated_bytes": skipped_compaction.estimated_bytes,
+        "limit_bytes": skipped_compaction.limit_bytes,
+        "num_files": skipped_compaction.num_files,
+        "limit_num_files": skipped_compaction.limit_num_files,
+    })
+}
+
+/// Turn skipped compaction records into a table
+fn create_table(skipped_compactions: &[SkippedCompaction]) -> Table {
+    let mut table = Table::new();
+    table.load_preset("||--+-++|    ++++++");
+
+    let headers: Vec<_> = [
+        "partition_id",
+        "reason",
+        "skipped_at",
+        "estimated_bytes",
+        "limit_bytes",
+        "num_files",
+        "limit_num_files",
+    ]
+    .into_iter()
+    .map(Cell::new)
+    .collect();
+    table.set_header(headers);
+
+    for skipped_compaction in skipped_compactions {
+        table.add_row(vec![
+            Cell::new(skipped_compaction.partition_id.to_string()),
+            Cell::new(&skipped_compaction.reason),
+            Cell::new(skipped_at(skipped_compaction)),
+            Cell::new(skipped_compaction.estimated_bytes.to_string()),
+            Cell::new(skipped_compaction.limit_bytes.to_string()),
+            Cell::new(skipped_compaction.num_files.to_string()),
+            Cell::new(skipped_compaction.limit_num_files.to_string()),
+        ]);
+    }
+
+    table
+}
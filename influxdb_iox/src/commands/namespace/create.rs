@@ -1,4 +1,4 @@
-use influxdb_iox_client::connection::Connection;
+use influxdb_iox_client::{connection::Connection, error::Error as ClientError};
 
 use crate::commands::{namespace::Result, partition_template::PartitionTemplateConfig};
 use influxdb_iox_client::namespace::generated_types::ServiceProtectionLimits;
@@ -27,6 +27,10 @@ pub struct Config {
     /// Partition template
     #[clap(flatten)]
     partition_template_config: PartitionTemplateConfig,
+
+    /// Do not fail if the namespace already exists. The existing namespace is left unchanged.
+    #[clap(action, long = "if-not-exists")]
+    if_not_exists: bool,
 }
 
 #[derive(Debug, clap::Args)]
@@ -62,6 +66,7 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
         retention_hours,
         service_protection_limits,
         partition_template_config,
+        if_not_exists,
     } = config;
 
     let mut client = influxdb_iox_client::namespace::Client::new(connection);
@@ -74,14 +79,22 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
         // internally
         Some(retention_hours as i64 * 60 * 60 * 1_000_000_000)
     };
-    let namespace = client
+    let res = client
         .create_namespace(
             &namespace,
             retention,
             service_protection_limits.into(),
             partition_template_config.partition_template,
         )
-        .await?;
+        .await;
+    let namespace = match res {
+        Ok(namespace) => namespace,
+        Err(ClientError::AlreadyExists(_)) if if_not_exists => {
+            println!("Namespace {namespace} already exists, skipped");
+            return Ok(());
+        }
+        Err(e) => return Err(e.into()),
+    };
     println!("{}", serde_json::to_string_pretty(&namespace)?);
 
     Ok(())
@@ -100,6 +113,15 @@ mod tests {
 
         assert_eq!(config.namespace, "namespace");
         assert_eq!(config.partition_template_config.partition_template, None);
+        assert!(!config.if_not_exists);
+    }
+
+    #[test]
+    fn valid_if_not_exists() {
+        let config = Config::try_parse_from(["server", "namespace", "--if-not-exists"]).unwrap();
+
+        assert_eq!(config.namespace, "namespace");
+        assert!(config.if_not_exists);
     }
 
     // Valid config with partition template
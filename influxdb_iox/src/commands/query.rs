@@ -47,6 +47,14 @@ pub struct Config {
     /// Query type used
     #[clap(short = 'l', long = "lang", default_value = "sql")]
     query_lang: QueryLanguage,
+
+    /// Also print the physical plan of the query with execution metrics
+    /// (rows and elapsed time per operator), as returned by
+    /// `EXPLAIN ANALYZE`.
+    ///
+    /// Note that this runs the query a second time.
+    #[clap(long, action)]
+    profile: bool,
 }
 
 #[derive(Debug, Clone, ValueEnum)]
@@ -82,8 +90,41 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
         format,
         query,
         query_lang,
+        profile,
     } = config;
 
+    let profile_query = profile.then(|| format!("EXPLAIN ANALYZE {query}"));
+    let batches = run_query(&mut client, &namespace, query, &query_lang).await?;
+
+    match (&query_lang, &format) {
+        (QueryLanguage::InfluxQL, OutputFormat::Pretty) => {
+            write_columnar(std::io::stdout(), &batches, Options::default())?
+        }
+        _ => {
+            let format: QueryOutputFormat = format.clone().into();
+            let formatted_result = format.format(&batches)?;
+            println!("{formatted_result}");
+        }
+    }
+
+    if let Some(profile_query) = profile_query {
+        let batches = run_query(&mut client, &namespace, profile_query, &query_lang).await?;
+
+        // the plan is not a series, so it is never formatted as InfluxQL
+        let format: QueryOutputFormat = format.into();
+        let formatted_profile = format.format(&batches)?;
+        println!("\nQuery profile:\n{formatted_profile}");
+    }
+
+    Ok(())
+}
+
+async fn run_query(
+    client: &mut flight::Client,
+    namespace: &str,
+    query: String,
+    query_lang: &QueryLanguage,
+) -> Result<Vec<RecordBatch>> {
     let mut query_results = match query_lang {
         QueryLanguage::Sql => client.sql(namespace, query).await,
         QueryLanguage::InfluxQL => client.influxql(namespace, query).await,
@@ -103,16 +144,5 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
     // preserve schema so we print table headers even for empty results
     batches.push(RecordBatch::new_empty(schema));
 
-    match (query_lang, &format) {
-        (QueryLanguage::InfluxQL, OutputFormat::Pretty) => {
-            write_columnar(std::io::stdout(), &batches, Options::default())?
-        }
-        _ => {
-            let format: QueryOutputFormat = format.into();
-            let formatted_result = format.format(&batches)?;
-            println!("{formatted_result}");
-        }
-    }
-
-    Ok(())
+    Ok(batches)
 }
@@ -5,7 +5,7 @@ use crate::process_info::setup_metric_registry;
 use super::main;
 use clap_blocks::{
     catalog_dsn::CatalogDsnConfig,
-    compactor::CompactorConfig,
+    compactor::{CompactorConfig, ParquetCompression},
     compactor_scheduler::CompactorSchedulerConfig,
     gossip::GossipConfig,
     ingester::IngesterConfig,
@@ -345,6 +345,17 @@ pub struct Config {
     )]
     pub querier_max_concurrent_queries: usize,
 
+    /// Additional parquet stores of the querier, e.g. for a cold storage tier
+    /// (comma-separated `ID:DIRECTORY` pairs).
+    #[clap(
+        long = "querier-parquet-store-dirs",
+        env = "INFLUXDB_IOX_QUERIER_PARQUET_STORE_DIRS",
+        value_delimiter = ',',
+        value_parser = clap_blocks::querier::parse_parquet_store_dir,
+        action
+    )]
+    pub querier_parquet_store_dirs: Vec<(&'static str, PathBuf)>,
+
     /// Size of memory pool used during query exec, in bytes.
     #[clap(
         long = "exec-mem-pool-bytes",
@@ -381,6 +392,7 @@ impl Config {
             querier_ram_pool_metadata_bytes,
             querier_ram_pool_data_bytes,
             querier_max_concurrent_queries,
+            querier_parquet_store_dirs,
             exec_mem_pool_bytes,
             single_tenant_deployment,
         } = self;
@@ -476,6 +488,10 @@ impl Config {
             persist_max_parallelism,
             persist_queue_depth,
             persist_hot_partition_cost,
+            persist_hot_partition_batch_window_millis: 0,
+            persist_hot_partition_target_latency_seconds: 0,
+            persist_hot_partition_min_cost: persist_hot_partition_cost,
+            sort_query_output: false,
             rpc_write_max_incoming_bytes: 1024 * 1024 * 1024, // 1GiB
             gossip_config: GossipConfig::disabled(),
         };
@@ -503,19 +519,36 @@ impl Config {
             compaction_partition_concurrency: compactor_concurrency,
             compaction_df_concurrency: compactor_concurrency,
             compaction_partition_scratchpad_concurrency: compactor_concurrency,
+            compaction_upload_concurrency: compactor_concurrency,
             query_exec_thread_count: Some(num_threads),
             exec_mem_pool_bytes,
             max_desired_file_size_bytes: 100 * 1024 * 1024, // 100 MB
             percentage_max_file_size: 30,
             split_percentage: 80,
             partition_timeout_secs: 30 * 60, // 30 minutes
+            partition_timeout_overrides: Default::default(),
             shadow_mode: false,
+            fail_on_concurrent_modification: false,
             enable_scratchpad: true,
             min_num_l1_files_to_compact: 1,
             process_once: false,
             max_num_columns_per_table: 200,
-            max_num_files_per_plan: 200,
+            max_num_files_per_plan: NonZeroUsize::new(200).unwrap(),
             max_partition_fetch_queries_per_second: Some(500),
+            audit_log_path: None,
+            max_consecutive_failures: None,
+            fetch_batch_size: None,
+            max_rounds: None,
+            webhook_url: None,
+            max_input_files_per_plan: None,
+            compaction_parquet_compression: ParquetCompression::Zstd(
+                ParquetCompression::DEFAULT_ZSTD_LEVEL,
+            ),
+            prioritize_by_l0_count: false,
+            verify_uploads: false,
+            cache_partition_files: false,
+            object_store_error_budget: None,
+            object_store_error_backoff_secs: 60,
         };
 
         let querier_config = QuerierConfig {
@@ -525,9 +558,17 @@ impl Config {
             ram_pool_metadata_bytes: querier_ram_pool_metadata_bytes,
             ram_pool_data_bytes: querier_ram_pool_data_bytes,
             max_concurrent_queries: querier_max_concurrent_queries,
+            max_query_result_bytes: None,
             exec_mem_pool_bytes,
             ingester_circuit_breaker_threshold: u64::MAX, // never for all-in-one-mode
+            ingester_connection_pool_size: NonZeroUsize::new(1).unwrap(),
+            disable_ingester_queries: false,
             datafusion_config: Default::default(),
+            slow_query_threshold: None,
+            namespace_allowlist: vec![],
+            namespace_denylist: vec![],
+            flightsql_metadata_cache_ttl: Duration::from_secs(10),
+            parquet_store_dirs: querier_parquet_store_dirs,
         };
 
         SpecializedConfig {
@@ -675,11 +716,17 @@ pub async fn command(config: Config) -> Result<()> {
     .await;
 
     info!(ingester_addresses = ?querier_config.ingester_addresses, "starting querier");
+    let parquet_stores = querier_config
+        .parquet_stores()?
+        .into_iter()
+        .map(|(id, store)| ParquetStorage::new(store, StorageId::from(id)))
+        .collect();
     let querier = create_querier_server_type(QuerierServerTypeArgs {
         common_state: &common_state,
         metric_registry: Arc::clone(&metrics),
         catalog,
         object_store,
+        parquet_stores,
         exec,
         time_provider,
         querier_config,
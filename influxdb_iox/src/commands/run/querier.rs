@@ -17,6 +17,7 @@ use ioxd_querier::{create_querier_server_type, QuerierServerTypeArgs};
 use object_store::DynObjectStore;
 use object_store_metrics::ObjectStoreMetrics;
 use observability_deps::tracing::*;
+use parquet_file::storage::{ParquetStorage, StorageId};
 use std::{num::NonZeroUsize, sync::Arc};
 use thiserror::Error;
 
@@ -107,11 +108,19 @@ pub async fn command(config: Config) -> Result<(), Error> {
         Arc::clone(&metric_registry),
     ));
 
+    let parquet_stores = config
+        .querier_config
+        .parquet_stores()?
+        .into_iter()
+        .map(|(id, store)| ParquetStorage::new(store, StorageId::from(id)))
+        .collect();
+
     let server_type = create_querier_server_type(QuerierServerTypeArgs {
         common_state: &common_state,
         metric_registry: Arc::clone(&metric_registry),
         catalog,
         object_store,
+        parquet_stores,
         exec,
         time_provider,
         querier_config: config.querier_config,
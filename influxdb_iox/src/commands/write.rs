@@ -1,5 +1,8 @@
 use futures::{stream::BoxStream, StreamExt};
-use influxdb_iox_client::{connection::Connection, write};
+use influxdb_iox_client::{
+    connection::Connection,
+    write::{self, Precision},
+};
 use observability_deps::tracing::{debug, info};
 use snafu::{ensure, OptionExt, ResultExt, Snafu};
 use std::{
@@ -7,6 +10,7 @@ use std::{
     io::{BufReader, Read},
     num::NonZeroUsize,
     path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
     time::Instant,
 };
 
@@ -59,6 +63,18 @@ pub struct Config {
     #[clap(action, long, short = 'c', default_value = "10")]
     max_concurrent_uploads: usize,
 
+    /// If specified, restricts the number of lines of line protocol
+    /// sent per request to this many lines, in addition to
+    /// --max-request-payload-size-bytes. The batches are sent one at a
+    /// time, ignoring --max-concurrent-uploads. Defaults to no limit
+    #[clap(action, long)]
+    batch_size: Option<NonZeroUsize>,
+
+    /// The precision of the timestamps in the written data: ns, us, ms
+    /// or s. Defaults to ns
+    #[clap(action, long, default_value = "ns")]
+    precision: Precision,
+
     /// The namespace into which to write, in the form <org_id>_<bucket_id>
     #[clap(action)]
     namespace: String,
@@ -77,14 +93,25 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
         file_names,
         max_request_payload_size_bytes,
         max_concurrent_uploads,
+        batch_size,
+        precision,
     } = config;
 
     let max_concurrent_uploads =
         NonZeroUsize::new(max_concurrent_uploads).context(MaxConcurrentUploadsVerficationSnafu)?;
+    // batches are written sequentially
+    let max_concurrent_uploads = match batch_size {
+        Some(_) => NonZeroUsize::new(1).unwrap(),
+        None => max_concurrent_uploads,
+    };
 
     info!(
         num_files = file_names.len(),
-        max_request_payload_size_bytes, max_concurrent_uploads, "Beginning upload"
+        max_request_payload_size_bytes,
+        max_concurrent_uploads,
+        ?batch_size,
+        %precision,
+        "Beginning upload"
     );
 
     // first pass is to check that all the files exist and can be
@@ -105,6 +132,9 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
         }
     );
 
+    let total_lines = AtomicUsize::new(0);
+    let total_lines = &total_lines;
+
     // if everything looked good, go through and read the files out
     // them in parallel.
     let lp_stream = futures_util::stream::iter(file_names)
@@ -129,14 +159,12 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
         })
         // now flatten out the stream of streams and ignore any errors actually reading the files
         .flat_map(|stream| stream)
-        .filter_map(|res| async move {
+        .filter_map(move |res| async move {
             match res {
                 Ok(lp_data) => {
-                    debug!(
-                        len = lp_data.len(),
-                        lines = lp_data.lines().count(),
-                        "Sending block of lines"
-                    );
+                    let lines = lp_data.lines().filter(|l| !l.trim().is_empty()).count();
+                    total_lines.fetch_add(lines, Ordering::Relaxed);
+                    debug!(len = lp_data.len(), lines, "Sending block of lines");
                     Some(lp_data)
                 }
                 Err(e) => {
@@ -148,7 +176,9 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
 
     let mut client = write::Client::new(connection)
         .with_max_concurrent_uploads(max_concurrent_uploads)
-        .with_max_request_payload_size_bytes(Some(max_request_payload_size_bytes));
+        .with_max_request_payload_size_bytes(Some(max_request_payload_size_bytes))
+        .with_max_request_lines(batch_size)
+        .with_precision(precision);
 
     let total_bytes = client
         .write_lp_stream(namespace, lp_stream)
@@ -158,7 +188,8 @@ pub async fn command(connection: Connection, config: Config) -> Result<()> {
     let elapsed = Instant::now() - start;
     let mb = (total_bytes as f64) / (1024.0 * 1024.0);
     let mb_per_sec = (mb / (elapsed.as_millis() as f64)) * (1000.0);
-    println!("{total_bytes} Bytes OK in {elapsed:?}. {mb_per_sec:.2} MB/sec");
+    let total_lines = total_lines.load(Ordering::Relaxed);
+    println!("{total_bytes} Bytes OK ({total_lines} lines) in {elapsed:?}. {mb_per_sec:.2} MB/sec");
 
     Ok(())
 }
@@ -258,5 +289,21 @@ mod test {
             Some(config.max_request_payload_size_bytes),
             DEFAULT_MAX_REQUEST_PAYLOAD_SIZE_BYTES
         );
+        assert_eq!(config.precision, Precision::Nanoseconds);
+    }
+
+    #[test]
+    fn precision() {
+        let config =
+            Config::try_parse_from(vec!["write", "--precision", "ms", "my_db", "file1"]).unwrap();
+        assert_eq!(config.precision, Precision::Milliseconds);
+
+        let err = Config::try_parse_from(vec!["write", "--precision", "h", "my_db", "file1"])
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("invalid precision 'h', expected one of: ns, us, ms, s"),
+            "{err}"
+        );
     }
 }
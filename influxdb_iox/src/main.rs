@@ -38,6 +38,7 @@ use trace_exporters::{
 
 mod commands {
     pub mod catalog;
+    pub mod compactor;
     pub mod debug;
     pub mod namespace;
     pub mod partition_template;
@@ -207,6 +208,9 @@ enum Command {
     /// Various commands for catalog manipulation
     Catalog(commands::catalog::Config),
 
+    /// Various commands for manipulating compactor state in the catalog
+    Compactor(commands::compactor::Config),
+
     /// Interrogate internal data
     Debug(commands::debug::Config),
 
@@ -344,6 +348,13 @@ fn main() -> Result<(), std::io::Error> {
                     std::process::exit(ReturnCode::Failure as _)
                 }
             }
+            Some(Command::Compactor(config)) => {
+                let _tracing_guard = handle_init_logs(init_simple_logs(log_verbose_count));
+                if let Err(e) = commands::compactor::command(config).await {
+                    eprintln!("{e}");
+                    std::process::exit(ReturnCode::Failure as _)
+                }
+            }
             Some(Command::Debug(config)) => {
                 let _tracing_guard = handle_init_logs(init_simple_logs(log_verbose_count));
                 if let Err(e) = commands::debug::command(|| connection(grpc_host), config).await {
@@ -734,6 +734,185 @@ async fn create_tables_write_and_query() {
     .await
 }
 
+/// Test the write CLI command with a timestamp precision
+#[tokio::test]
+async fn write_with_precision() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::Custom(Box::new(|state: &mut StepTestState| {
+                async {
+                    let router_addr = state.cluster().router().router_http_base().to_string();
+                    let namespace = state.cluster().namespace();
+
+                    let mut lp_file =
+                        tempfile::NamedTempFile::new().expect("Error making temp file");
+                    std::io::Write::write_all(
+                        &mut lp_file,
+                        b"precision_test,tag=A val=1i 1700000000\n",
+                    )
+                    .expect("Error writing temp file");
+
+                    // second precision timestamps are scaled to nanoseconds
+                    Command::cargo_bin("influxdb_iox")
+                        .unwrap()
+                        .arg("-v")
+                        .arg("-h")
+                        .arg(&router_addr)
+                        .arg("write")
+                        .arg("--precision")
+                        .arg("s")
+                        .arg(namespace)
+                        .arg(lp_file.path())
+                        .assert()
+                        .success()
+                        .stdout(predicate::str::contains("Bytes OK"));
+
+                    // invalid precisions are rejected
+                    Command::cargo_bin("influxdb_iox")
+                        .unwrap()
+                        .arg("-v")
+                        .arg("-h")
+                        .arg(&router_addr)
+                        .arg("write")
+                        .arg("--precision")
+                        .arg("h")
+                        .arg(namespace)
+                        .arg(lp_file.path())
+                        .assert()
+                        .failure()
+                        .stderr(predicate::str::contains(
+                            "invalid precision 'h', expected one of: ns, us, ms, s",
+                        ));
+                }
+                .boxed()
+            })),
+            Step::Custom(Box::new(|state: &mut StepTestState| {
+                async {
+                    wait_for_query_result(
+                        state,
+                        "SELECT * from precision_test",
+                        None,
+                        "| A   | 2023-11-14T22:13:20Z | 1   |",
+                    )
+                    .await;
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .run()
+    .await
+}
+
+/// Test the write CLI command splitting the input into batches of lines
+#[tokio::test]
+async fn write_with_batch_size() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::Custom(Box::new(|state: &mut StepTestState| {
+                async {
+                    let router_addr = state.cluster().router().router_http_base().to_string();
+                    let namespace = state.cluster().namespace();
+
+                    let lp = (1..=10)
+                        .map(|i| format!("batch_test,tag=A val={i}i {i}"))
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    let mut lp_file =
+                        tempfile::NamedTempFile::new().expect("Error making temp file");
+                    std::io::Write::write_all(&mut lp_file, lp.as_bytes())
+                        .expect("Error writing temp file");
+
+                    // 10 lines are sent in 4 batches
+                    Command::cargo_bin("influxdb_iox")
+                        .unwrap()
+                        .arg("-v")
+                        .arg("-h")
+                        .arg(&router_addr)
+                        .arg("write")
+                        .arg("--batch-size")
+                        .arg("3")
+                        .arg(namespace)
+                        .arg(lp_file.path())
+                        .assert()
+                        .success()
+                        .stdout(predicate::str::contains("Bytes OK (10 lines)"));
+                }
+                .boxed()
+            })),
+            Step::Custom(Box::new(|state: &mut StepTestState| {
+                async {
+                    wait_for_query_result(
+                        state,
+                        "SELECT count(*), sum(val) from batch_test",
+                        None,
+                        "| 10       | 55                  |",
+                    )
+                    .await;
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .run()
+    .await
+}
+
+/// Test the query CLI command with profiling enabled
+#[tokio::test]
+async fn query_profile() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![
+            Step::WriteLineProtocol("profiled_table,tag=A val=42i 123456".into()),
+            Step::Custom(Box::new(|state: &mut StepTestState| {
+                async {
+                    let querier_addr = state.cluster().querier().querier_grpc_base().to_string();
+                    let namespace = state.cluster().namespace();
+
+                    Command::cargo_bin("influxdb_iox")
+                        .unwrap()
+                        .arg("-h")
+                        .arg(&querier_addr)
+                        .arg("query")
+                        .arg("--profile")
+                        .arg(namespace)
+                        .arg("select tag, val from profiled_table")
+                        .assert()
+                        .success()
+                        // the results
+                        .stdout(predicate::str::contains("| A   | 42  |"))
+                        // followed by the plan with metrics
+                        .stdout(predicate::str::contains("Query profile:"))
+                        .stdout(predicate::str::contains("Plan with Metrics"))
+                        .stdout(predicate::str::contains("output_rows=1"))
+                        .stdout(predicate::str::contains("elapsed_compute="));
+                }
+                .boxed()
+            })),
+        ],
+    )
+    .run()
+    .await
+}
+
 /// Test error handling for the query CLI command
 #[tokio::test]
 async fn query_error_handling() {
@@ -1445,6 +1624,64 @@ async fn namespace_create_service_limits() {
     .await
 }
 
+#[tokio::test]
+async fn namespace_create_if_not_exists() {
+    test_helpers::maybe_start_logging();
+    let database_url = maybe_skip_integration!();
+    let mut cluster = MiniCluster::create_shared(database_url).await;
+
+    StepTest::new(
+        &mut cluster,
+        vec![Step::Custom(Box::new(|state: &mut StepTestState| {
+            async {
+                let addr = state.cluster().router().router_grpc_base().to_string();
+                let namespace = "ns_if_not_exists";
+
+                let create = || {
+                    let mut cmd = Command::cargo_bin("influxdb_iox").unwrap();
+                    cmd.arg("-h")
+                        .arg(&addr)
+                        .arg("namespace")
+                        .arg("create")
+                        .arg(namespace)
+                        .arg("--if-not-exists");
+                    cmd
+                };
+
+                create()
+                    .assert()
+                    .success()
+                    .stdout(predicate::str::contains(format!(
+                        r#""name": "{namespace}""#
+                    )));
+
+                // creating it again is a no-op
+                create()
+                    .assert()
+                    .success()
+                    .stdout(predicate::str::contains(format!(
+                        "Namespace {namespace} already exists, skipped"
+                    )));
+
+                // ... but still fails without the flag
+                Command::cargo_bin("influxdb_iox")
+                    .unwrap()
+                    .arg("-h")
+                    .arg(&addr)
+                    .arg("namespace")
+                    .arg("create")
+                    .arg(namespace)
+                    .assert()
+                    .failure()
+                    .stderr(predicate::str::contains("already exists"));
+            }
+            .boxed()
+        }))],
+    )
+    .run()
+    .await
+}
+
 /// Test setting partition template while creating namespaces, negative tests
 #[tokio::test]
 async fn namespace_create_partition_template_negative() {
@@ -1,6 +1,13 @@
 use assert_cmd::Command;
+use data_types::PartitionKey;
 use futures::FutureExt;
+use iox_catalog::{
+    interface::Catalog,
+    sqlite::{SqliteCatalog, SqliteConnectionOptions},
+    test_helpers::{arbitrary_namespace, arbitrary_table},
+};
 use predicates::prelude::*;
+use tempfile::TempDir;
 use test_helpers_end_to_end::{
     maybe_skip_integration, MiniCluster, Step, StepTest, StepTestState, TestConfig,
 };
@@ -257,3 +264,138 @@ async fn sharded_compactor_1_never_compacts_partition_1() {
     .run()
     .await
 }
+
+#[tokio::test]
+async fn clear_skipped_compactions() {
+    test_helpers::maybe_start_logging();
+
+    let tmpdir = TempDir::new().unwrap();
+    let (catalog, dsn) = sqlite_catalog(&tmpdir).await;
+
+    // skip compacting a partition
+    let mut repos = catalog.repositories().await;
+    let namespace = arbitrary_namespace(&mut *repos, "ns").await;
+    let table = arbitrary_table(&mut *repos, "table", &namespace).await;
+    let partition = repos
+        .partitions()
+        .create_or_get(PartitionKey::from("k"), table.id)
+        .await
+        .unwrap();
+    repos
+        .partitions()
+        .record_skipped_compaction(partition.id, "broken", 1, 2, 3, 4, 5)
+        .await
+        .unwrap();
+    let skipped = repos
+        .partitions()
+        .get_in_skipped_compactions(&[partition.id])
+        .await
+        .unwrap();
+    assert_eq!(skipped.len(), 1);
+
+    // clearing everything must be confirmed
+    Command::cargo_bin("influxdb_iox")
+        .unwrap()
+        .args([
+            "compactor",
+            "skipped",
+            "clear",
+            "--catalog-dsn",
+            &dsn,
+            "--all",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--force"));
+
+    Command::cargo_bin("influxdb_iox")
+        .unwrap()
+        .args(["compactor", "skipped", "clear", "--catalog-dsn", &dsn])
+        .arg("--partition-id")
+        .arg(partition.id.get().to_string())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Cleared 1 skipped compaction(s)"));
+
+    // the partition is eligible for compaction again
+    let skipped = repos
+        .partitions()
+        .get_in_skipped_compactions(&[partition.id])
+        .await
+        .unwrap();
+    assert!(skipped.is_empty());
+
+    // nothing left to clear
+    Command::cargo_bin("influxdb_iox")
+        .unwrap()
+        .args(["compactor", "skipped", "clear", "--catalog-dsn", &dsn])
+        .args(["--all", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Cleared 0 skipped compaction(s)"));
+}
+
+#[tokio::test]
+async fn list_skipped_compactions() {
+    test_helpers::maybe_start_logging();
+
+    let tmpdir = TempDir::new().unwrap();
+    let (catalog, dsn) = sqlite_catalog(&tmpdir).await;
+
+    let mut repos = catalog.repositories().await;
+    let namespace = arbitrary_namespace(&mut *repos, "ns").await;
+    let table = arbitrary_table(&mut *repos, "table", &namespace).await;
+    for (key, reason) in [("a", "too many files"), ("b", "out of memory")] {
+        let partition = repos
+            .partitions()
+            .create_or_get(PartitionKey::from(key), table.id)
+            .await
+            .unwrap();
+        repos
+            .partitions()
+            .record_skipped_compaction(partition.id, reason, 1, 2, 3, 4, 5)
+            .await
+            .unwrap();
+    }
+
+    Command::cargo_bin("influxdb_iox")
+        .unwrap()
+        .args(["compactor", "skipped", "list", "--catalog-dsn", &dsn])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("too many files")
+                .and(predicate::str::contains("out of memory"))
+                .and(predicate::str::contains("limit_num_files")),
+        );
+
+    Command::cargo_bin("influxdb_iox")
+        .unwrap()
+        .args([
+            "compactor",
+            "skipped",
+            "list",
+            "--catalog-dsn",
+            &dsn,
+            "--json",
+        ])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains(r#""reason": "too many files""#)
+                .and(predicate::str::contains(r#""reason": "out of memory""#)),
+        );
+}
+
+/// Create and set up a SQLite catalog in `tmpdir`, returning it together with its DSN.
+async fn sqlite_catalog(tmpdir: &TempDir) -> (SqliteCatalog, String) {
+    let file_path = tmpdir.path().join("catalog.sqlite").display().to_string();
+    let dsn = format!("sqlite://{file_path}");
+
+    let catalog = SqliteCatalog::connect(SqliteConnectionOptions { file_path }, Default::default())
+        .await
+        .unwrap();
+    catalog.setup().await.unwrap();
+
+    (catalog, dsn)
+}
@@ -1,4 +1,4 @@
-use std::{fmt::Debug, num::NonZeroUsize, sync::Arc};
+use std::{fmt::Debug, num::NonZeroUsize, str::FromStr, sync::Arc};
 
 use client_util::{connection::HttpConnection, namespace_translation::split_namespace};
 use futures_util::{future::BoxFuture, FutureExt, Stream, StreamExt, TryStreamExt};
@@ -12,6 +12,59 @@ use reqwest::{Body, Method};
 /// The default value for the maximum size of each request, in bytes
 pub const DEFAULT_MAX_REQUEST_PAYLOAD_SIZE_BYTES: Option<usize> = Some(1024 * 1024);
 
+/// The precision of the timestamps in written line protocol.
+///
+/// Timestamps are scaled to nanoseconds by the server, see the `precision`
+/// parameter of the [V2 Write API].
+///
+/// [V2 Write API]: https://docs.influxdata.com/influxdb/v2.6/api/#operation/PostWrite
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Precision {
+    /// Seconds.
+    Seconds,
+    /// Milliseconds.
+    Milliseconds,
+    /// Microseconds.
+    Microseconds,
+    /// Nanoseconds.
+    #[default]
+    Nanoseconds,
+}
+
+impl Precision {
+    /// The value of the `precision` request parameter.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Seconds => "s",
+            Self::Milliseconds => "ms",
+            Self::Microseconds => "us",
+            Self::Nanoseconds => "ns",
+        }
+    }
+}
+
+impl std::fmt::Display for Precision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Precision {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "s" => Ok(Self::Seconds),
+            "ms" => Ok(Self::Milliseconds),
+            "us" => Ok(Self::Microseconds),
+            "ns" => Ok(Self::Nanoseconds),
+            _ => Err(format!(
+                "invalid precision '{s}', expected one of: ns, us, ms, s"
+            )),
+        }
+    }
+}
+
 /// An IOx Write API client.
 ///
 /// ```no_run
@@ -56,8 +109,15 @@ pub struct Client {
     /// setting `max_request_payload_size_bytes` to `None`.
     max_request_payload_size_bytes: Option<usize>,
 
+    /// If `Some`, restricts the number of lines of line protocol sent
+    /// per request. Defaults to `None`.
+    max_request_lines: Option<NonZeroUsize>,
+
     /// Makes this many concurrent requests at a time. Defaults to 1
     max_concurrent_uploads: NonZeroUsize,
+
+    /// The precision of the written timestamps. Defaults to nanoseconds.
+    precision: Precision,
 }
 
 impl Client {
@@ -71,7 +131,9 @@ impl Client {
         Self {
             inner,
             max_request_payload_size_bytes: DEFAULT_MAX_REQUEST_PAYLOAD_SIZE_BYTES,
+            max_request_lines: None,
             max_concurrent_uploads: NonZeroUsize::new(1).unwrap(),
+            precision: Precision::default(),
         }
     }
 
@@ -89,6 +151,16 @@ impl Client {
         }
     }
 
+    /// If `Some` is specified, restricts the number of lines of line
+    /// protocol sent per request to this many lines, in addition to
+    /// any payload size limit. Defaults to `None`.
+    pub fn with_max_request_lines(self, max_request_lines: Option<NonZeroUsize>) -> Self {
+        Self {
+            max_request_lines,
+            ..self
+        }
+    }
+
     /// The client makes this many concurrent uploads at a
     /// time. Defaults to 1.
     pub fn with_max_concurrent_uploads(self, max_concurrent_uploads: NonZeroUsize) -> Self {
@@ -98,6 +170,12 @@ impl Client {
         }
     }
 
+    /// Interpret the timestamps of the written line protocol with the
+    /// given precision. Defaults to nanoseconds.
+    pub fn with_precision(self, precision: Precision) -> Self {
+        Self { precision, ..self }
+    }
+
     /// Write the [LineProtocol] formatted string in `lp_data` to
     /// namespace `namespace`.
     ///
@@ -136,6 +214,8 @@ impl Client {
 
         let max_concurrent_uploads: usize = self.max_concurrent_uploads.into();
         let max_request_payload_size_bytes = self.max_request_payload_size_bytes;
+        let max_request_lines = self.max_request_lines;
+        let precision = self.precision;
 
         // make a stream and process in parallel
         let results = sources
@@ -144,6 +224,7 @@ impl Client {
                 split_lp(
                     source,
                     max_request_payload_size_bytes,
+                    max_request_lines,
                     max_concurrent_uploads,
                 )
             })
@@ -153,9 +234,11 @@ impl Client {
                 let bucket_id = bucket_id.to_string();
                 let inner = Arc::clone(&self.inner);
 
-                tokio::task::spawn(
-                    async move { inner.write_source(org_id, bucket_id, source).await },
-                )
+                tokio::task::spawn(async move {
+                    inner
+                        .write_source(org_id, bucket_id, precision, source)
+                        .await
+                })
             })
             // Do the uploads in parallel
             .buffered(max_concurrent_uploads)
@@ -174,14 +257,16 @@ impl Client {
 /// Something that knows how to send http data. Exists so it can be
 /// mocked out for testing
 trait RequestMaker: Debug + Send + Sync {
-    /// Write the body data to the specified org, bucket, and
-    /// returning the number of bytes written
+    /// Write the body data with timestamps of the given precision to
+    /// the specified org, bucket, and returning the number of bytes
+    /// written
     ///
     /// (this is implemented manually to avoid `async_trait`)
     fn write_source(
         &self,
         org_id: String,
         bucket_id: String,
+        precision: Precision,
         body: String,
     ) -> BoxFuture<'_, Result<usize, Error>>;
 }
@@ -191,6 +276,7 @@ impl RequestMaker for HttpConnection {
         &self,
         org_id: String,
         bucket_id: String,
+        precision: Precision,
         body: String,
     ) -> BoxFuture<'_, Result<usize, Error>> {
         let write_url = format!("{}api/v2/write", self.uri());
@@ -203,7 +289,11 @@ impl RequestMaker for HttpConnection {
             let response = self
                 .client()
                 .request(Method::POST, &write_url)
-                .query(&[("bucket", bucket_id), ("org", org_id)])
+                .query(&[
+                    ("bucket", bucket_id),
+                    ("org", org_id),
+                    ("precision", precision.to_string()),
+                ])
                 .body(body)
                 .send()
                 .await
@@ -218,23 +308,25 @@ impl RequestMaker for HttpConnection {
 }
 
 /// splits input line protocol into one or more sizes of at most
-/// `max_chunk` on line breaks in a separte tokio task
+/// `max_chunk_size` bytes and `max_chunk_lines` lines on line breaks
+/// in a separte tokio task
 fn split_lp(
     input: String,
     max_chunk_size: Option<usize>,
+    max_chunk_lines: Option<NonZeroUsize>,
     max_concurrent_uploads: usize,
 ) -> impl Stream<Item = String> {
     let (tx, rx) = tokio::sync::mpsc::channel(max_concurrent_uploads);
 
     tokio::task::spawn(async move {
-        match max_chunk_size {
-            None => {
+        match (max_chunk_size, max_chunk_lines) {
+            (None, None) => {
                 // ignore errors (means the receiver hung up but nothing to communicate
                 tx.send(input).await.ok();
             }
-            Some(max_chunk_size) => {
+            (max_chunk_size, max_chunk_lines) => {
                 // use the actual line protocol parser to split on valid boundaries
-                let mut acc = LineAccumulator::new(max_chunk_size);
+                let mut acc = LineAccumulator::new(max_chunk_size, max_chunk_lines);
                 for l in influxdb_line_protocol::split_lines(&input) {
                     if let Some(chunk) = acc.push(l) {
                         // abort if receiver has hungup
@@ -255,21 +347,34 @@ fn split_lp(
 #[derive(Debug)]
 struct LineAccumulator {
     current_chunk: String,
-    max_chunk_size: usize,
+    current_lines: usize,
+    max_chunk_size: Option<usize>,
+    max_chunk_lines: Option<NonZeroUsize>,
 }
 
 impl LineAccumulator {
-    fn new(max_chunk_size: usize) -> Self {
+    fn new(max_chunk_size: Option<usize>, max_chunk_lines: Option<NonZeroUsize>) -> Self {
         Self {
-            current_chunk: String::with_capacity(max_chunk_size),
+            current_chunk: String::with_capacity(max_chunk_size.unwrap_or_default()),
+            current_lines: 0,
             max_chunk_size,
+            max_chunk_lines,
         }
     }
 
     // Add data `l` to the current chunk being created, returning the
     // current chunk if complete.
     fn push(&mut self, l: &str) -> Option<String> {
-        let chunk = if self.current_chunk.len() + l.len() + 1 > self.max_chunk_size {
+        let too_large = self
+            .max_chunk_size
+            .map(|max| self.current_chunk.len() + l.len() + 1 > max)
+            .unwrap_or_default();
+        let too_many_lines = self
+            .max_chunk_lines
+            .map(|max| self.current_lines >= max.get())
+            .unwrap_or_default();
+
+        let chunk = if too_large || too_many_lines {
             self.flush()
         } else {
             None
@@ -280,6 +385,7 @@ impl LineAccumulator {
         }
 
         self.current_chunk += l;
+        self.current_lines += 1;
         chunk
     }
 
@@ -287,8 +393,9 @@ impl LineAccumulator {
     /// `self.current_chunk.len()` is zero
     fn flush(&mut self) -> Option<String> {
         if !self.current_chunk.is_empty() {
-            let mut new_chunk = String::with_capacity(self.max_chunk_size);
+            let mut new_chunk = String::with_capacity(self.max_chunk_size.unwrap_or_default());
             std::mem::swap(&mut new_chunk, &mut self.current_chunk);
+            self.current_lines = 0;
             Some(new_chunk)
         } else {
             None
@@ -312,6 +419,7 @@ mod tests {
         let expected = vec![MockRequest {
             org_id: "orgname".into(),
             bucket_id: "bucketname".into(),
+            precision: Precision::Nanoseconds,
             body: data.into(),
         }];
 
@@ -323,6 +431,45 @@ mod tests {
         assert_eq!(num_bytes, 11);
     }
 
+    #[tokio::test]
+    async fn test_precision() {
+        let mock = Arc::new(MockRequestMaker::new());
+
+        let namespace = "orgname_bucketname";
+        let data = "m,t=foo f=4 1";
+
+        let expected = vec![MockRequest {
+            org_id: "orgname".into(),
+            bucket_id: "bucketname".into(),
+            precision: Precision::Seconds,
+            body: data.into(),
+        }];
+
+        Client::new_with_maker(Arc::clone(&mock) as _)
+            .with_precision("s".parse().unwrap())
+            .write_lp(namespace, data)
+            .await
+            .unwrap();
+        assert_eq!(expected, mock.requests());
+    }
+
+    #[test]
+    fn test_parse_precision() {
+        for p in [
+            Precision::Seconds,
+            Precision::Milliseconds,
+            Precision::Microseconds,
+            Precision::Nanoseconds,
+        ] {
+            assert_eq!(p.to_string().parse::<Precision>(), Ok(p));
+        }
+
+        assert_eq!(
+            "h".parse::<Precision>(),
+            Err("invalid precision 'h', expected one of: ns, us, ms, s".to_string())
+        );
+    }
+
     #[tokio::test]
     async fn test_max_request_payload_size() {
         let mock = Arc::new(MockRequestMaker::new());
@@ -337,11 +484,13 @@ mod tests {
             MockRequest {
                 org_id: "orgname".into(),
                 bucket_id: "bucketname".into(),
+                precision: Precision::Nanoseconds,
                 body: "m,t=foo f=4\nm,t=bar f=3".into(),
             },
             MockRequest {
                 org_id: "orgname".into(),
                 bucket_id: "bucketname".into(),
+                precision: Precision::Nanoseconds,
                 body: "m,t=fooddddddd f=4".into(),
             },
         ];
@@ -356,6 +505,33 @@ mod tests {
         assert_eq!(num_bytes, 41);
     }
 
+    #[tokio::test]
+    async fn test_max_request_lines() {
+        let mock = Arc::new(MockRequestMaker::new());
+
+        let namespace = "orgname_bucketname";
+        let data = "m,t=a f=1\nm,t=b f=2\nm,t=c f=3\nm,t=d f=4\nm,t=e f=5";
+
+        // expect the data to be broken up into chunks of two lines
+        let expected = ["m,t=a f=1\nm,t=b f=2", "m,t=c f=3\nm,t=d f=4", "m,t=e f=5"]
+            .into_iter()
+            .map(|body| MockRequest {
+                org_id: "orgname".into(),
+                bucket_id: "bucketname".into(),
+                precision: Precision::Nanoseconds,
+                body: body.into(),
+            })
+            .collect::<Vec<_>>();
+
+        Client::new_with_maker(Arc::clone(&mock) as _)
+            .with_max_request_payload_size_bytes(None)
+            .with_max_request_lines(Some(NonZeroUsize::new(2).unwrap()))
+            .write_lp(namespace, data)
+            .await
+            .unwrap();
+        assert_eq!(expected, mock.requests());
+    }
+
     #[tokio::test]
     async fn test_write_lp_stream() {
         let mock = Arc::new(MockRequestMaker::new());
@@ -372,11 +548,13 @@ mod tests {
             MockRequest {
                 org_id: "orgname".into(),
                 bucket_id: "bucketname".into(),
+                precision: Precision::Nanoseconds,
                 body: "m,t=foo f=4".into(),
             },
             MockRequest {
                 org_id: "orgname".into(),
                 bucket_id: "bucketname".into(),
+                precision: Precision::Nanoseconds,
                 body: "m,t=bar f=3".into(),
             },
         ];
@@ -393,6 +571,7 @@ mod tests {
     struct MockRequest {
         org_id: String,
         bucket_id: String,
+        precision: Precision,
         body: String,
     }
 
@@ -419,6 +598,7 @@ mod tests {
             &self,
             org_id: String,
             bucket_id: String,
+            precision: Precision,
             body: String,
         ) -> BoxFuture<'_, Result<usize, Error>> {
             let sz = body.len();
@@ -426,6 +606,7 @@ mod tests {
             self.requests.lock().unwrap().push(MockRequest {
                 org_id,
                 bucket_id,
+                precision,
                 body,
             });
 
@@ -273,13 +273,14 @@ where
         let partitions = self.partitions().into_iter().filter_map(move |p| {
             let mut span = span.child("partition read");
 
-            let (id, completed_persistence_count, data, partition_key) = {
+            let (id, completed_persistence_count, data, partition_key, sort_key) = {
                 let mut p = p.lock();
                 (
                     p.partition_id().clone(),
                     p.completed_persistence_count(),
                     p.get_query_data(&projection),
                     p.partition_key().clone(),
+                    p.sort_key().clone(),
                 )
             };
 
@@ -325,6 +326,7 @@ where
                         id,
                         completed_persistence_count,
                     )
+                    .with_sort_key(sort_key)
                 }
                 None => PartitionResponse::new(vec![], id, completed_persistence_count),
             };
@@ -44,7 +44,8 @@ use crate::{
     },
     query::{
         exec_instrumentation::QueryExecInstrumentation,
-        result_instrumentation::QueryResultInstrumentation, tracing::QueryExecTracing,
+        result_instrumentation::QueryResultInstrumentation, sort::QueryResultSorter,
+        tracing::QueryExecTracing,
     },
     server::grpc::GrpcDelegate,
     timestamp_oracle::TimestampOracle,
@@ -255,6 +256,21 @@ pub enum InitError {
 /// Decreasing this value increases the frequency of persist operations, and
 /// usually decreases the size of the resulting parquet files.
 ///
+/// If `persist_hot_partition_batch_window` is set, partitions that exceed the
+/// cost limit are accumulated for that duration and then enqueued for
+/// persistence as a single batch, reducing churn in the persist system when
+/// many partitions become hot at the same time.
+///
+/// If `persist_hot_partition_target_latency` is set, the cost limit is lowered
+/// (down to `persist_hot_partition_min_cost`) while persist operations take
+/// longer than the target latency to complete, and raised back once they
+/// recover. This keeps buffered memory bounded when the persist backend slows
+/// down.
+///
+/// If `sort_query_output` is true, the data of each partition returned by a
+/// query is sorted by the partition's sort key (when known). By default data is
+/// returned in buffer order.
+///
 /// [`MutableBatch::size_data()`]: mutable_batch::MutableBatch::size_data
 #[allow(clippy::too_many_arguments)]
 pub async fn new<F>(
@@ -267,6 +283,10 @@ pub async fn new<F>(
     persist_workers: usize,
     persist_queue_depth: usize,
     persist_hot_partition_cost: usize,
+    persist_hot_partition_batch_window: Option<Duration>,
+    persist_hot_partition_target_latency: Option<Duration>,
+    persist_hot_partition_min_cost: usize,
+    sort_query_output: bool,
     object_store: ParquetStorage,
     gossip: GossipConfig,
     shutdown: F,
@@ -361,11 +381,18 @@ where
     // runs, such as if the configuration of the ingester was changed to persist
     // smaller partitions in-between executions because it was OOMing during WAL
     // replay (and the configuration was changed to mitigate it).
-    let hot_partition_persister = HotPartitionPersister::new(
+    let mut hot_partition_persister = HotPartitionPersister::new(
         Arc::clone(&persist_handle),
         persist_hot_partition_cost,
         &metrics,
     );
+    if let Some(window) = persist_hot_partition_batch_window {
+        hot_partition_persister = hot_partition_persister.with_batch_window(window);
+    }
+    if let Some(target_latency) = persist_hot_partition_target_latency {
+        hot_partition_persister = hot_partition_persister
+            .with_adaptive_cost_limit(target_latency, persist_hot_partition_min_cost);
+    }
 
     let buffer = Arc::new(BufferTree::new(
         namespace_name_provider,
@@ -414,7 +441,10 @@ where
     );
 
     // And the chain of QueryExec that forms the read path.
-    let read_path = QueryResultInstrumentation::new(Arc::clone(&buffer), &metrics);
+    let read_path = QueryResultInstrumentation::new(
+        QueryResultSorter::new(Arc::clone(&buffer), sort_query_output),
+        &metrics,
+    );
     let read_path = QueryExecInstrumentation::new(
         "buffer",
         QueryExecTracing::new(read_path, "buffer"),
@@ -10,7 +10,7 @@ use parquet_file::storage::ParquetStorage;
 use schema::sort::adjust_sort_key_columns;
 use sharder::JumpHash;
 use tokio::{
-    sync::{mpsc, oneshot, Semaphore, TryAcquireError},
+    sync::{mpsc, oneshot, OwnedSemaphorePermit, Semaphore, TryAcquireError},
     time::Instant,
 };
 
@@ -159,6 +159,14 @@ pub(crate) struct PersistHandle {
     /// key, ensuring sort key updates are serialised per-partition.
     worker_queues: JumpHash<mpsc::UnboundedSender<PersistRequest>>,
 
+    /// The total number of permits in `sem`.
+    persist_queue_depth: usize,
+
+    /// Serialises obtaining the permits of a batch in
+    /// [`PersistQueue::enqueue_batch()`], so that concurrent batches cannot
+    /// each hold a part of the permits they wait for.
+    batch_permits: tokio::sync::Mutex<()>,
+
     /// Marks and recovers the saturation state of the persist system.
     persist_state: Arc<PersistState>,
 
@@ -305,70 +313,18 @@ impl PersistHandle {
             global_queue: global_tx,
             worker_queues: JumpHash::new(tx_handles),
             worker_tasks,
+            persist_queue_depth,
+            batch_permits: Default::default(),
             persist_state,
             enqueued_jobs,
         }
     }
 
-    fn assign_worker(&self, r: PersistRequest) {
-        debug!(
-            partition_id = %r.partition_id(),
-            "enqueue persist job to assigned worker"
-        );
-
-        // Consistently map partition tasks for this partition ID to the
-        // same worker.
-        self.worker_queues
-            .hash(r.partition_id())
-            .send(r)
-            .expect("persist worker stopped");
-    }
-}
-
-#[async_trait]
-impl PersistQueue for PersistHandle {
-    /// Place `data` from `partition` into the persistence queue.
-    ///
-    /// This call (asynchronously) waits for space to become available in the
-    /// persistence queue.
-    ///
-    /// Once persistence is complete, the partition will be locked and the sort
-    /// key will be updated (if necessary), and
-    /// [`PartitionData::mark_persisted()`] is called with `data` to mark the
-    /// task as complete.
-    ///
-    /// Once all persistence related tasks for `data` are complete, the returned
-    /// channel publishes a notification.
-    ///
-    /// Persist tasks may be re-ordered w.r.t their submission order for
-    /// performance reasons.
-    ///
-    /// # Panics
-    ///
-    /// Panics if the assigned persist worker task has stopped.
-    ///
-    /// Panics (asynchronously) if the [`PartitionData`]'s sort key is updated
-    /// between persistence starting and ending.
-    ///
-    /// This will panic (asynchronously) if `data` was not from `partition`.
-    #[allow(clippy::async_yields_async)] // Callers may want to wait async
-    async fn enqueue(
-        &self,
-        partition: Arc<Mutex<PartitionData>>,
-        data: PersistingData,
-    ) -> oneshot::Receiver<()> {
-        let partition_id = data.partition_id().clone();
-        debug!(%partition_id, "enqueuing persistence task");
-
-        // Record a starting timestamp, and increment the number of persist jobs
-        // before waiting on the semaphore - this ensures the difference between
-        // started and completed includes the full count of pending jobs (even
-        // those blocked waiting for queue capacity).
-        let enqueued_at = Instant::now();
-        self.enqueued_jobs.inc(1);
-
+    /// Obtain a persist task permit, (asynchronously) waiting for space to
+    /// become available in the persistence queue.
+    async fn acquire_permit(&self) -> OwnedSemaphorePermit {
         // Try and acquire the persist task permit immediately.
-        let permit = match Arc::clone(&self.sem).try_acquire_owned() {
+        match Arc::clone(&self.sem).try_acquire_owned() {
             Ok(p) => p, // Success!
             Err(TryAcquireError::Closed) => panic!("persist work semaphore is closed"),
             Err(TryAcquireError::NoPermits) => {
@@ -394,7 +350,36 @@ impl PersistQueue for PersistHandle {
                     .await
                     .expect("persist work semaphore is closed")
             }
-        };
+        }
+    }
+
+    fn assign_worker(&self, r: PersistRequest) {
+        debug!(
+            partition_id = %r.partition_id(),
+            "enqueue persist job to assigned worker"
+        );
+
+        // Consistently map partition tasks for this partition ID to the
+        // same worker.
+        self.worker_queues
+            .hash(r.partition_id())
+            .send(r)
+            .expect("persist worker stopped");
+    }
+
+    /// Build the persist task request for `data` from `partition`.
+    ///
+    /// Returns the request if it can be executed on any worker and should be
+    /// placed into the global queue, or [`None`] if it was assigned to the
+    /// worker of `partition`.
+    fn build_request(
+        &self,
+        partition: Arc<Mutex<PartitionData>>,
+        data: PersistingData,
+        permit: OwnedSemaphorePermit,
+        enqueued_at: Instant,
+    ) -> (Option<PersistRequest>, oneshot::Receiver<()>) {
+        let partition_id = data.partition_id().clone();
 
         // If the persist job has a known sort key, and it can be determined
         // that the persist job does not require updating that sort key, it can
@@ -445,7 +430,7 @@ impl PersistQueue for PersistHandle {
                     // This persist operation will not require a sort key
                     // update.
                     debug!(%partition_id, "enqueue persist job to global work queue");
-                    self.global_queue.send(r).await.expect("no persist workers");
+                    return (Some(r), notify);
                 }
             }
             None => {
@@ -457,8 +442,109 @@ impl PersistQueue for PersistHandle {
             }
         }
 
+        (None, notify)
+    }
+}
+
+#[async_trait]
+impl PersistQueue for PersistHandle {
+    /// Place `data` from `partition` into the persistence queue.
+    ///
+    /// This call (asynchronously) waits for space to become available in the
+    /// persistence queue.
+    ///
+    /// Once persistence is complete, the partition will be locked and the sort
+    /// key will be updated (if necessary), and
+    /// [`PartitionData::mark_persisted()`] is called with `data` to mark the
+    /// task as complete.
+    ///
+    /// Once all persistence related tasks for `data` are complete, the returned
+    /// channel publishes a notification.
+    ///
+    /// Persist tasks may be re-ordered w.r.t their submission order for
+    /// performance reasons.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the assigned persist worker task has stopped.
+    ///
+    /// Panics (asynchronously) if the [`PartitionData`]'s sort key is updated
+    /// between persistence starting and ending.
+    ///
+    /// This will panic (asynchronously) if `data` was not from `partition`.
+    #[allow(clippy::async_yields_async)] // Callers may want to wait async
+    async fn enqueue(
+        &self,
+        partition: Arc<Mutex<PartitionData>>,
+        data: PersistingData,
+    ) -> oneshot::Receiver<()> {
+        let partition_id = data.partition_id().clone();
+        debug!(%partition_id, "enqueuing persistence task");
+
+        // Record a starting timestamp, and increment the number of persist jobs
+        // before waiting on the semaphore - this ensures the difference between
+        // started and completed includes the full count of pending jobs (even
+        // those blocked waiting for queue capacity).
+        let enqueued_at = Instant::now();
+        self.enqueued_jobs.inc(1);
+
+        let permit = self.acquire_permit().await;
+
+        let (r, notify) = self.build_request(partition, data, permit, enqueued_at);
+        if let Some(r) = r {
+            self.global_queue.send(r).await.expect("no persist workers");
+        }
+
         notify
     }
+
+    /// Place all partitions in `batch` into the persistence queue as a group,
+    /// (asynchronously) blocking until all are enqueued.
+    ///
+    /// The permits for all entries are obtained before any entry is enqueued,
+    /// so the batch only enters the persistence queue once there is space for
+    /// all of it. Each entry is then enqueued on its own as done by
+    /// [`PersistQueue::enqueue()`], persists in parallel with the other entries
+    /// and releases its permit as soon as it completes.
+    ///
+    /// A batch larger than the persistence queue depth is split into groups of
+    /// at most the queue depth.
+    #[allow(clippy::async_yields_async)] // Callers may want to wait async
+    async fn enqueue_batch(
+        &self,
+        mut batch: Vec<(Arc<Mutex<PartitionData>>, PersistingData)>,
+    ) -> Vec<oneshot::Receiver<()>> {
+        let mut handles = Vec::with_capacity(batch.len());
+
+        while !batch.is_empty() {
+            let rest = batch.split_off(batch.len().min(self.persist_queue_depth));
+            debug!(n_partitions = batch.len(), "enqueuing persistence batch");
+
+            let enqueued_at = Instant::now();
+            self.enqueued_jobs.inc(batch.len() as _);
+
+            let permits = {
+                let _guard = self.batch_permits.lock().await;
+                let mut permits = Vec::with_capacity(batch.len());
+                for _ in 0..batch.len() {
+                    permits.push(self.acquire_permit().await);
+                }
+                permits
+            };
+
+            for ((partition, data), permit) in batch.into_iter().zip(permits) {
+                let (r, notify) = self.build_request(partition, data, permit, enqueued_at);
+                if let Some(r) = r {
+                    self.global_queue.send(r).await.expect("no persist workers");
+                }
+                handles.push(notify);
+            }
+
+            batch = rest;
+        }
+
+        handles
+    }
 }
 
 #[derive(Debug)]
@@ -869,6 +955,83 @@ mod tests {
         assert_eq!(msg.partition_id(), &*ARBITRARY_TRANSITION_PARTITION_ID);
     }
 
+    /// A test that a batch of partitions acquires the permits for all entries
+    /// before enqueuing them, and that every entry is enqueued (and releases
+    /// its permit) on its own.
+    #[tokio::test]
+    async fn test_persist_enqueue_batch() {
+        let storage = ParquetStorage::new(Arc::new(InMemory::default()), StorageId::from("iox"));
+        let metrics = Arc::new(metric::Registry::default());
+        let catalog = Arc::new(MemCatalog::new(Arc::clone(&metrics)));
+
+        let mut handle = PersistHandle::new(
+            1,
+            3,
+            Arc::new(IngestState::default()),
+            Arc::new(Executor::new_testing()),
+            storage,
+            catalog,
+            Arc::new(MockCompletionObserver::default()),
+            &metrics,
+        );
+
+        // Kill the workers, and replace the queues so we can inspect the
+        // enqueue output.
+        handle.worker_tasks = vec![];
+
+        let (global_tx, global_rx) = async_channel::unbounded();
+        handle.global_queue = global_tx;
+
+        let (worker1_tx, mut worker1_rx) = mpsc::unbounded_channel();
+        let (worker2_tx, mut worker2_rx) = mpsc::unbounded_channel();
+        handle.worker_queues = JumpHash::new([worker1_tx, worker2_tx]);
+
+        // Two partitions that do not require a sort key update, and one with
+        // no known sort key.
+        let mut batch = vec![];
+        for sort_key in [
+            Some(SortKey::from_columns(["time", "good"])),
+            None,
+            Some(SortKey::from_columns(["time", "good"])),
+        ] {
+            let p = new_partition(SortKeyState::Provided(sort_key)).await;
+            let data = p.lock().mark_persisting().unwrap();
+            batch.push((p, data));
+        }
+
+        let notify = handle.enqueue_batch(batch).await;
+        assert_eq!(notify.len(), 3);
+
+        // All permits were taken by the batch.
+        assert_eq!(handle.sem.available_permits(), 0);
+        assert_metric_counter(&metrics, "ingester_persist_enqueued_jobs", 3);
+
+        // The partitions with a known sort key were sent to the global queue
+        // individually.
+        let global1 = global_rx
+            .try_recv()
+            .expect("task should be in global queue");
+        let global2 = global_rx
+            .try_recv()
+            .expect("task should be in global queue");
+        assert!(global_rx.is_empty());
+
+        // And the partition without a sort key was assigned to a worker.
+        let assigned = worker1_rx
+            .try_recv()
+            .or_else(|_| worker2_rx.try_recv())
+            .expect("message was not found in either worker");
+        assert_eq!(assigned.partition_id(), &*ARBITRARY_TRANSITION_PARTITION_ID);
+
+        // Each entry releases its permit as soon as it completes.
+        drop(global1);
+        assert_eq!(handle.sem.available_permits(), 1);
+        drop(assigned);
+        assert_eq!(handle.sem.available_permits(), 2);
+        drop(global2);
+        assert_eq!(handle.sem.available_permits(), 3);
+    }
+
     /// A test that a ensures tasks waiting to be enqueued (waiting on the
     /// semaphore) appear in the metrics.
     #[tokio::test]
@@ -1,21 +1,122 @@
-use std::{fmt::Debug, sync::Arc};
+use std::{
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 
+use metric::{U64Histogram, U64HistogramOptions};
 use observability_deps::tracing::info;
 use parking_lot::{Mutex, MutexGuard};
+use tokio::sync::oneshot;
 
-use crate::buffer_tree::{partition::PartitionData, post_write::PostWriteObserver};
+use crate::buffer_tree::{
+    partition::{persisting::PersistingData, PartitionData},
+    post_write::PostWriteObserver,
+};
 
 use super::queue::PersistQueue;
 
+/// Adapts the effective persist cost limit of a [`HotPartitionPersister`] to
+/// the observed latency of the persist operations it enqueues.
+///
+/// Each persist that completes slower than `target_latency` halves the
+/// effective limit (down to `min_cost`), causing smaller partitions to be
+/// persisted more eagerly and keeping buffered memory bounded while the
+/// persist backend is slow. Each persist completing within `target_latency`
+/// doubles it again, up to the configured maximum. A batch of persists is
+/// observed once, when all of them completed.
+#[derive(Debug)]
+pub(crate) struct AdaptiveCostLimit {
+    target_latency: Duration,
+    min_cost: usize,
+    max_cost: usize,
+    effective: AtomicUsize,
+}
+
+impl AdaptiveCostLimit {
+    fn new(target_latency: Duration, min_cost: usize, max_cost: usize) -> Self {
+        Self {
+            target_latency,
+            min_cost: min_cost.min(max_cost),
+            max_cost,
+            effective: AtomicUsize::new(max_cost),
+        }
+    }
+
+    /// The current effective persist cost limit.
+    pub(crate) fn get(&self) -> usize {
+        self.effective.load(Ordering::Relaxed)
+    }
+
+    /// Adjust the effective limit given the `latency` of a completed persist.
+    pub(crate) fn observe_latency(&self, latency: Duration) {
+        let _ = self
+            .effective
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |v| {
+                Some(if latency > self.target_latency {
+                    (v / 2).max(self.min_cost)
+                } else {
+                    v.saturating_mul(2).min(self.max_cost)
+                })
+            });
+    }
+
+    /// Wait for all persist operations enqueued together at `started` to
+    /// complete and adjust the effective limit once, by the latency of the
+    /// slowest of them.
+    ///
+    /// Nothing is observed if any of them does not complete.
+    async fn observe_completion(
+        &self,
+        started: Instant,
+        done: impl IntoIterator<Item = oneshot::Receiver<()>>,
+    ) {
+        for done in done {
+            if done.await.is_err() {
+                return;
+            }
+        }
+        self.observe_latency(started.elapsed());
+    }
+}
+
 /// A [`PostWriteObserver`] that triggers persistence of a partition when the
 /// estimated persistence cost exceeds a pre-configured limit.
+///
+/// If a batch window is configured with
+/// [`HotPartitionPersister::with_batch_window()`], partitions triggered within
+/// the window are enqueued together with [`PersistQueue::enqueue_batch()`]
+/// once the window has elapsed.
+///
+/// If an [`AdaptiveCostLimit`] is configured with
+/// [`HotPartitionPersister::with_adaptive_cost_limit()`], the limit is lowered
+/// while persist operations are slow.
 #[derive(Debug)]
 pub(crate) struct HotPartitionPersister<P> {
     persist_handle: P,
     max_estimated_persist_cost: usize,
 
+    /// The duration hot partitions are accumulated for before being enqueued
+    /// as a batch, if any.
+    batch_window: Option<Duration>,
+
+    /// Hot partitions marked as persisting, waiting for the current batch
+    /// window to elapse.
+    pending: Arc<Mutex<Vec<(Arc<Mutex<PartitionData>>, PersistingData)>>>,
+
+    /// Adjusts the effective persist cost limit based on persist latency, if
+    /// enabled.
+    adaptive_cost_limit: Option<Arc<AdaptiveCostLimit>>,
+
     /// A metric tracking the number of partitions persisted as "hot partitions".
     persist_count: metric::U64Counter,
+
+    /// The distribution of persist cost estimates of partitions after each
+    /// observed write.
+    persist_cost_estimate: U64Histogram,
 }
 
 impl<P> HotPartitionPersister<P>
@@ -34,10 +135,71 @@ where
                 because the persist cost exceeded the pre-configured limit",
             )
             .recorder(&[]);
+        let persist_cost_estimate = metrics
+            .register_metric_with_options::<U64Histogram, _>(
+                "ingester_persist_hot_partition_cost_estimate",
+                "distribution of the estimated persist cost of partitions after \
+                each buffered write",
+                || {
+                    U64HistogramOptions::new([
+                        4_u64.pow(5),  // 1 kibibyte
+                        4_u64.pow(6),  // 4 kibibytes
+                        4_u64.pow(7),  // 16 kibibytes
+                        4_u64.pow(8),  // 64 kibibytes
+                        4_u64.pow(9),  // 256 kibibytes
+                        4_u64.pow(10), // 1 mebibyte
+                        4_u64.pow(11), // 4 mebibytes
+                        4_u64.pow(12), // 16 mebibytes
+                        4_u64.pow(13), // 64 mebibytes
+                        4_u64.pow(14), // 256 mebibytes
+                        4_u64.pow(15), // 1 gibibyte
+                        u64::MAX,
+                    ])
+                },
+            )
+            .recorder(&[]);
         Self {
             persist_handle,
             max_estimated_persist_cost,
+            batch_window: None,
+            pending: Default::default(),
+            adaptive_cost_limit: None,
             persist_count,
+            persist_cost_estimate,
+        }
+    }
+
+    /// Accumulate hot partitions for `window` and enqueue them as a single
+    /// batch, instead of enqueuing each partition as soon as it is marked as
+    /// persisting.
+    pub(crate) fn with_batch_window(mut self, window: Duration) -> Self {
+        self.batch_window = Some(window);
+        self
+    }
+
+    /// Lower the persist cost limit (down to `min_cost`) while persist
+    /// operations take longer than `target_latency` to complete, and raise it
+    /// back once they recover.
+    ///
+    /// See [`AdaptiveCostLimit`].
+    pub(crate) fn with_adaptive_cost_limit(
+        mut self,
+        target_latency: Duration,
+        min_cost: usize,
+    ) -> Self {
+        self.adaptive_cost_limit = Some(Arc::new(AdaptiveCostLimit::new(
+            target_latency,
+            min_cost,
+            self.max_estimated_persist_cost,
+        )));
+        self
+    }
+
+    /// The persist cost limit currently in effect.
+    fn effective_max_estimated_persist_cost(&self) -> usize {
+        match &self.adaptive_cost_limit {
+            Some(v) => v.get(),
+            None => self.max_estimated_persist_cost,
         }
     }
 
@@ -59,11 +221,41 @@ where
 
         // Perform the enqueue in a separate task, to avoid blocking this
         // writer if the persist system is saturated.
+        //
+        // There is no need to await on the completion handles, unless the
+        // persist latency is used to adapt the cost limit.
         let persist_handle = self.persist_handle.clone();
-        tokio::spawn(async move {
-            // There is no need to await on the completion handle.
-            persist_handle.enqueue(partition, data).await;
-        });
+        let adaptive_cost_limit = self.adaptive_cost_limit.clone();
+        match self.batch_window {
+            None => {
+                tokio::spawn(async move {
+                    let started = Instant::now();
+                    let done = persist_handle.enqueue(partition, data).await;
+                    if let Some(limit) = adaptive_cost_limit {
+                        limit.observe_completion(started, [done]).await;
+                    }
+                });
+            }
+            Some(window) => {
+                let mut pending = self.pending.lock();
+
+                // The first partition of a batch starts the window.
+                if pending.is_empty() {
+                    let pending = Arc::clone(&self.pending);
+                    tokio::spawn(async move {
+                        tokio::time::sleep(window).await;
+                        let batch = std::mem::take(&mut *pending.lock());
+                        let started = Instant::now();
+                        let done = persist_handle.enqueue_batch(batch).await;
+                        if let Some(limit) = adaptive_cost_limit {
+                            limit.observe_completion(started, done).await;
+                        }
+                    });
+                }
+
+                pending.push((partition, data));
+            }
+        }
         // Update any exported metrics.
         self.persist_count.inc(1);
     }
@@ -85,6 +277,7 @@ where
         // This observer is called after a successful write, therefore
         // persisting the partition MUST have a non-zero cost.
         assert!(cost_estimate > 0);
+        self.persist_cost_estimate.record(cost_estimate as u64);
 
         // If the estimated persist cost is over the limit, mark the
         // partition as persisting.
@@ -93,7 +286,7 @@ where
         // accurate buffer costing - if the lock were to be released, more
         // writes could be added to the buffer in parallel, exceeding the
         // limit before it was marked as persisting.
-        if cost_estimate >= self.max_estimated_persist_cost {
+        if cost_estimate >= self.effective_max_estimated_persist_cost() {
             self.persist(cost_estimate, partition, guard)
         }
     }
@@ -107,6 +300,7 @@ mod tests {
     use data_types::SequenceNumber;
     use mutable_batch_lp::test_helpers::lp_to_mutable_batch;
     use parking_lot::Mutex;
+    use test_helpers::timeout::FutureTimeout;
 
     use crate::{
         persist::queue::mock::MockPersistQueue,
@@ -127,7 +321,8 @@ mod tests {
         .1;
         p.buffer_write(mb, SequenceNumber::new(1))
             .expect("write should succeed");
-        let max_cost = p.persist_cost_estimate() + 1; // Require additional data to be buffered before enqueuing
+        let first_cost = p.persist_cost_estimate();
+        let max_cost = first_cost + 1; // Require additional data to be buffered before enqueuing
         assert_eq!(p.completed_persistence_count(), 0);
         let p = Arc::new(Mutex::new(p));
 
@@ -167,6 +362,7 @@ mod tests {
                 .get_query_data(&OwnedProjection::default())
                 .expect("should have query adaptor")
         };
+        let second_cost = p.lock().persist_cost_estimate();
 
         hot_partition_persister.observe(Arc::clone(&p), p.lock());
 
@@ -184,6 +380,15 @@ mod tests {
             value = 1,
         );
 
+        // Both observed cost estimates are recorded.
+        metric::assert_histogram!(
+            metrics,
+            U64Histogram,
+            "ingester_persist_hot_partition_cost_estimate",
+            samples = 2,
+            sum = (first_cost + second_cost) as u64,
+        );
+
         // Check persist completion.
         drop(hot_partition_persister);
         Arc::try_unwrap(persist_handle)
@@ -192,4 +397,170 @@ mod tests {
             .await;
         assert_eq!(p.lock().completed_persistence_count(), 1);
     }
+
+    #[test]
+    fn test_adaptive_cost_limit() {
+        let limit = AdaptiveCostLimit::new(Duration::from_secs(1), 100, 1_000);
+        assert_eq!(limit.get(), 1_000);
+
+        // Rising latency lowers the limit, down to the minimum.
+        for want in [500, 250, 125, 100, 100] {
+            limit.observe_latency(Duration::from_secs(2));
+            assert_eq!(limit.get(), want);
+        }
+
+        // Recovered latency raises it back, up to the maximum.
+        for want in [200, 400, 800, 1_000, 1_000] {
+            limit.observe_latency(Duration::from_millis(100));
+            assert_eq!(limit.get(), want);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_adaptive_cost_limit_batch_completion() {
+        let limit = AdaptiveCostLimit::new(Duration::from_secs(1), 100, 1_000);
+        limit.observe_latency(Duration::from_secs(2));
+        limit.observe_latency(Duration::from_secs(2));
+        assert_eq!(limit.get(), 250);
+
+        // A completed batch is observed once, not once per partition.
+        let (senders, receivers): (Vec<_>, Vec<_>) = (0..3).map(|_| oneshot::channel()).unzip();
+        for tx in senders {
+            tx.send(()).unwrap();
+        }
+        limit.observe_completion(Instant::now(), receivers).await;
+        assert_eq!(limit.get(), 500);
+
+        // A batch with an incomplete persist is not observed.
+        let (tx1, rx1) = oneshot::channel();
+        let (tx2, rx2) = oneshot::channel::<()>();
+        tx1.send(()).unwrap();
+        drop(tx2);
+        limit.observe_completion(Instant::now(), [rx1, rx2]).await;
+        assert_eq!(limit.get(), 500);
+    }
+
+    #[tokio::test]
+    async fn test_hot_partition_persist_adaptive() {
+        let mut p = PartitionDataBuilder::new().build();
+
+        let mb = lp_to_mutable_batch(&format!(
+            r#"{},city=Hereford  people=1,crisps="good" 10"#,
+            &*ARBITRARY_TABLE_NAME
+        ))
+        .1;
+        p.buffer_write(mb, SequenceNumber::new(1))
+            .expect("write should succeed");
+        let cost = p.persist_cost_estimate();
+        let p = Arc::new(Mutex::new(p));
+
+        let metrics = metric::Registry::default();
+        let persist_handle = Arc::new(MockPersistQueue::default());
+
+        // The partition is below the configured limit.
+        let hot_partition_persister =
+            HotPartitionPersister::new(Arc::clone(&persist_handle), cost * 4, &metrics)
+                .with_adaptive_cost_limit(Duration::from_secs(1), 1);
+
+        hot_partition_persister.observe(Arc::clone(&p), p.lock());
+        tokio::task::yield_now().await;
+        assert!(persist_handle.calls().is_empty());
+
+        // Simulate slow persist operations.
+        let limit = hot_partition_persister
+            .adaptive_cost_limit
+            .clone()
+            .expect("adaptive limit configured");
+        limit.observe_latency(Duration::from_secs(5));
+        limit.observe_latency(Duration::from_secs(5));
+        assert_eq!(
+            hot_partition_persister.effective_max_estimated_persist_cost(),
+            cost
+        );
+
+        // The same partition is now persisted.
+        hot_partition_persister.observe(Arc::clone(&p), p.lock());
+        tokio::task::yield_now().await;
+        assert_eq!(persist_handle.calls().len(), 1);
+
+        // Wait for the enqueue task to observe the persist completion.
+        drop(hot_partition_persister);
+        drop(limit);
+        async {
+            while Arc::strong_count(&persist_handle) > 1 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }
+        .with_timeout_panic(Duration::from_secs(5))
+        .await;
+        Arc::try_unwrap(persist_handle)
+            .expect("should be no more refs")
+            .join()
+            .await;
+        assert_eq!(p.lock().completed_persistence_count(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_hot_partition_persist_batch() {
+        const WINDOW: Duration = Duration::from_millis(100);
+
+        let new_partition = || {
+            let mut p = PartitionDataBuilder::new().build();
+            let mb = lp_to_mutable_batch(&format!(
+                r#"{},city=Hereford  people=1,crisps="good" 10"#,
+                &*ARBITRARY_TABLE_NAME
+            ))
+            .1;
+            p.buffer_write(mb, SequenceNumber::new(1))
+                .expect("write should succeed");
+            Arc::new(Mutex::new(p))
+        };
+        let p1 = new_partition();
+        let p2 = new_partition();
+
+        let metrics = metric::Registry::default();
+        let persist_handle = Arc::new(MockPersistQueue::default());
+
+        // Any write makes the partitions hot.
+        let hot_partition_persister =
+            HotPartitionPersister::new(Arc::clone(&persist_handle), 1, &metrics)
+                .with_batch_window(WINDOW);
+
+        hot_partition_persister.observe(Arc::clone(&p1), p1.lock());
+        hot_partition_persister.observe(Arc::clone(&p2), p2.lock());
+
+        // Nothing is enqueued before the window elapses.
+        tokio::task::yield_now().await;
+        assert!(persist_handle.calls().is_empty());
+        assert!(persist_handle.batch_calls().is_empty());
+
+        tokio::time::sleep(WINDOW).await;
+        while persist_handle.batch_calls().is_empty() {
+            tokio::task::yield_now().await;
+        }
+
+        // Both partitions are enqueued as one batch.
+        assert_matches!(persist_handle.batch_calls().as_slice(), [batch] => {
+            assert_matches!(batch.as_slice(), [got1, got2] => {
+                assert!(Arc::ptr_eq(got1, &p1));
+                assert!(Arc::ptr_eq(got2, &p2));
+            });
+        });
+        assert_eq!(persist_handle.calls().len(), 2);
+
+        metric::assert_counter!(
+            metrics,
+            metric::U64Counter,
+            "ingester_persist_hot_partition_enqueue_count",
+            value = 2,
+        );
+
+        drop(hot_partition_persister);
+        Arc::try_unwrap(persist_handle)
+            .expect("should be no more refs")
+            .join()
+            .await;
+        assert_eq!(p1.lock().completed_persistence_count(), 1);
+        assert_eq!(p2.lock().completed_persistence_count(), 1);
+    }
 }
@@ -25,6 +25,25 @@ pub trait PersistQueue: Send + Sync + Debug {
         partition: Arc<Mutex<PartitionData>>,
         data: PersistingData,
     ) -> oneshot::Receiver<()>;
+
+    /// Place all partitions in `batch` into the persistence queue as a group,
+    /// (asynchronously) blocking until all are enqueued.
+    ///
+    /// Returns one completion handle per entry in `batch`, in the same order.
+    ///
+    /// The default implementation enqueues each entry in order using
+    /// [`PersistQueue::enqueue()`].
+    #[allow(clippy::async_yields_async)]
+    async fn enqueue_batch(
+        &self,
+        batch: Vec<(Arc<Mutex<PartitionData>>, PersistingData)>,
+    ) -> Vec<oneshot::Receiver<()>> {
+        let mut handles = Vec::with_capacity(batch.len());
+        for (partition, data) in batch {
+            handles.push(self.enqueue(partition, data).await);
+        }
+        handles
+    }
 }
 
 #[async_trait]
@@ -40,6 +59,14 @@ where
     ) -> oneshot::Receiver<()> {
         (**self).enqueue(partition, data).await
     }
+
+    #[allow(clippy::async_yields_async)]
+    async fn enqueue_batch(
+        &self,
+        batch: Vec<(Arc<Mutex<PartitionData>>, PersistingData)>,
+    ) -> Vec<oneshot::Receiver<()>> {
+        (**self).enqueue_batch(batch).await
+    }
 }
 
 /// This needs to be pub for the benchmarks but should not be used outside the crate.
@@ -70,6 +97,8 @@ pub(crate) mod mock {
     struct State {
         /// Observed PartitionData instances.
         calls: Vec<Arc<Mutex<PartitionData>>>,
+        /// Observed calls to [`PersistQueue::enqueue_batch()`].
+        batch_calls: Vec<Vec<Arc<Mutex<PartitionData>>>>,
         /// Spawned tasks that call [`PartitionData::mark_persisted()`] - may
         /// have already terminated.
         handles: Vec<JoinHandle<()>>,
@@ -117,6 +146,11 @@ pub(crate) mod mock {
             self.state.lock().calls.clone()
         }
 
+        /// Return the [`PartitionData`] of each observed batch enqueue.
+        pub fn batch_calls(&self) -> Vec<Vec<Arc<Mutex<PartitionData>>>> {
+            self.state.lock().batch_calls.clone()
+        }
+
         /// Wait for all outstanding mock persist jobs to complete, propagating
         /// any panics.
         pub async fn join(self) {
@@ -184,5 +218,22 @@ pub(crate) mod mock {
 
             rx
         }
+
+        #[allow(clippy::async_yields_async)]
+        async fn enqueue_batch(
+            &self,
+            batch: Vec<(Arc<Mutex<PartitionData>>, PersistingData)>,
+        ) -> Vec<oneshot::Receiver<()>> {
+            self.state
+                .lock()
+                .batch_calls
+                .push(batch.iter().map(|(p, _)| Arc::clone(p)).collect());
+
+            let mut handles = Vec::with_capacity(batch.len());
+            for (partition, data) in batch {
+                handles.push(self.enqueue(partition, data).await);
+            }
+            handles
+        }
     }
 }
@@ -9,6 +9,9 @@ pub(crate) mod projection;
 pub(crate) mod partition_response;
 pub(crate) mod response;
 
+// Post-processing
+pub(crate) mod sort;
+
 // Instrumentation
 pub(crate) mod exec_instrumentation;
 pub(crate) mod result_instrumentation;
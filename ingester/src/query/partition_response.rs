@@ -5,6 +5,8 @@
 use arrow::record_batch::RecordBatch;
 use data_types::TransitionPartitionId;
 
+use crate::buffer_tree::partition::SortKeyState;
+
 /// Response data for a single partition.
 #[derive(Debug)]
 pub(crate) struct PartitionResponse {
@@ -16,6 +18,9 @@ pub(crate) struct PartitionResponse {
 
     /// Count of persisted Parquet files for this partition by this ingester instance.
     completed_persistence_count: u64,
+
+    /// The sort key of this partition, if known.
+    sort_key: Option<SortKeyState>,
 }
 
 impl PartitionResponse {
@@ -28,9 +33,16 @@ impl PartitionResponse {
             batches: data,
             id,
             completed_persistence_count,
+            sort_key: None,
         }
     }
 
+    /// Attach the [`SortKeyState`] of the partition the data was read from.
+    pub(crate) fn with_sort_key(mut self, sort_key: SortKeyState) -> Self {
+        self.sort_key = Some(sort_key);
+        self
+    }
+
     pub(crate) fn id(&self) -> &TransitionPartitionId {
         &self.id
     }
@@ -39,6 +51,18 @@ impl PartitionResponse {
         self.completed_persistence_count
     }
 
+    pub(crate) fn sort_key(&self) -> Option<&SortKeyState> {
+        self.sort_key.as_ref()
+    }
+
+    /// Returns true if this partition carries no record batches.
+    ///
+    /// Empty partitions are still meaningful to the querier, as they carry the
+    /// [`completed_persistence_count`](Self::completed_persistence_count).
+    pub(crate) fn is_empty(&self) -> bool {
+        self.batches.is_empty()
+    }
+
     pub(crate) fn into_record_batches(self) -> Vec<RecordBatch> {
         self.batches
     }
@@ -42,6 +42,9 @@
 //! the lifetime of the [`QueryResponse`] itself, and observes the [`RecordBatch`]es
 //! produced by each [`PartitionResponse`].
 //!
+//! The in-memory size of every [`RecordBatch`] yielded to the caller is added
+//! to a byte counter as the stream is drained, for egress accounting.
+//!
 //!
 //! [`RecordBatch`]: arrow::record_batch::RecordBatch
 
@@ -55,7 +58,7 @@ use async_trait::async_trait;
 use data_types::{NamespaceId, TableId};
 use futures::Stream;
 use iox_time::{SystemProvider, Time, TimeProvider};
-use metric::{DurationHistogram, Metric, U64Histogram, U64HistogramOptions};
+use metric::{DurationHistogram, Metric, U64Counter, U64Histogram, U64HistogramOptions};
 use observability_deps::tracing::debug;
 use pin_project::{pin_project, pinned_drop};
 use predicate::Predicate;
@@ -99,6 +102,11 @@ pub(crate) struct QueryResultInstrumentation<T, P = SystemProvider> {
     row_hist: U64Histogram,
     record_batch_hist: U64Histogram,
     partition_hist: U64Histogram,
+
+    /// The total in-memory size of all [`RecordBatch`] sent to clients.
+    ///
+    /// [`RecordBatch`]: arrow::record_batch::RecordBatch
+    result_bytes: U64Counter,
 }
 
 impl<T> QueryResultInstrumentation<T> {
@@ -163,6 +171,13 @@ impl<T> QueryResultInstrumentation<T> {
             )
             .recorder(&[]);
 
+        let result_bytes = metrics
+            .register_metric::<U64Counter>(
+                "ingester_query_result_bytes",
+                "total in-memory size of query result record batches sent to clients",
+            )
+            .recorder(&[]);
+
         Self {
             inner,
             time_provider: Default::default(),
@@ -171,6 +186,7 @@ impl<T> QueryResultInstrumentation<T> {
             row_hist,
             record_batch_hist,
             partition_hist,
+            result_bytes,
         }
     }
 }
@@ -189,6 +205,7 @@ impl<T, P> QueryResultInstrumentation<T, P> {
             row_hist: self.row_hist,
             record_batch_hist: self.record_batch_hist,
             partition_hist: self.partition_hist,
+            result_bytes: self.result_bytes,
         }
     }
 }
@@ -225,6 +242,7 @@ where
             self.row_hist.clone(),
             self.record_batch_hist.clone(),
             self.partition_hist.clone(),
+            self.result_bytes.clone(),
         );
 
         Ok(QueryResponse::new(PartitionStream::new(stream)))
@@ -282,6 +300,11 @@ where
     row_hist: U64Histogram,
     record_batch_hist: U64Histogram,
     partition_hist: U64Histogram,
+
+    /// Incremented with the size of each [`RecordBatch`] as it is yielded.
+    ///
+    /// [`RecordBatch`]: arrow::record_batch::RecordBatch
+    result_bytes: U64Counter,
 }
 
 impl<S, P> QueryMetricContext<S, P>
@@ -298,6 +321,7 @@ where
         row_hist: U64Histogram,
         record_batch_hist: U64Histogram,
         partition_hist: U64Histogram,
+        result_bytes: U64Counter,
     ) -> Self {
         Self {
             inner: stream,
@@ -309,6 +333,7 @@ where
             row_hist,
             record_batch_hist,
             partition_hist,
+            result_bytes,
             partition_count: 0,
             row_count: Default::default(),
             record_batch_count: Default::default(),
@@ -344,6 +369,11 @@ where
                 );
                 this.record_batch_count
                     .fetch_add(data.len(), Ordering::Relaxed);
+                this.result_bytes.inc(
+                    data.iter()
+                        .map(|batch| batch.get_array_memory_size() as u64)
+                        .sum(),
+                );
 
                 Poll::Ready(Some(PartitionResponse::new(data, id, persist_count)))
             }
@@ -435,7 +465,7 @@ mod tests {
     use arrow::array::{Float32Array, Int64Array};
     use futures::{stream, StreamExt};
     use iox_time::MockProvider;
-    use metric::{assert_histogram, Attributes};
+    use metric::{assert_counter, assert_histogram, Attributes};
     use std::{sync::Arc, time::Duration};
 
     const TIME_STEP: Duration = Duration::from_secs(42);
@@ -774,4 +804,76 @@ mod tests {
             sum = TIME_STEP, // It was recorded as an incomplete request
         );
     }
+
+    /// The in-memory size of each record batch is counted as the partitions
+    /// are yielded to the caller.
+    #[tokio::test]
+    async fn test_result_bytes() {
+        let metrics = metric::Registry::default();
+
+        let (batch_1, _schema) = make_batch!(
+            Int64Array("a" => vec![1, 2, 3, 4, 5]),
+            Float32Array("b" => vec![4.1, 4.2, 4.3, 4.4, 5.0]),
+        );
+        let (batch_2, _schema) = make_batch!(
+            Int64Array("c" => vec![1, 2, 3, 4, 5]),
+        );
+        let (batch_3, _schema) = make_batch!(
+            Float32Array("d" => vec![1.1]),
+        );
+        let partition_1_bytes =
+            (batch_1.get_array_memory_size() + batch_2.get_array_memory_size()) as u64;
+        let partition_2_bytes = batch_3.get_array_memory_size() as u64;
+
+        let stream = PartitionStream::new(stream::iter([
+            PartitionResponse::new(
+                vec![batch_1, batch_2],
+                ARBITRARY_TRANSITION_PARTITION_ID.clone(),
+                42,
+            ),
+            PartitionResponse::new(vec![batch_3], ARBITRARY_TRANSITION_PARTITION_ID.clone(), 42),
+        ]));
+
+        let mock_inner = MockQueryExec::default().with_result(Ok(QueryResponse::new(stream)));
+        let layer = QueryResultInstrumentation::new(mock_inner, &metrics);
+
+        let response = layer
+            .query_exec(
+                ARBITRARY_NAMESPACE_ID,
+                ARBITRARY_TABLE_ID,
+                OwnedProjection::default(),
+                None,
+                None,
+            )
+            .await
+            .expect("query should succeed");
+
+        assert_counter!(
+            metrics,
+            U64Counter,
+            "ingester_query_result_bytes",
+            value = 0,
+        );
+
+        let mut partitions = response.into_partition_stream();
+
+        // Only the batches that were yielded so far are counted.
+        partitions.next().await.expect("should yield partition");
+        assert_counter!(
+            metrics,
+            U64Counter,
+            "ingester_query_result_bytes",
+            value = partition_1_bytes,
+        );
+
+        // Drain the rest of the stream.
+        partitions.next().await.expect("should yield partition");
+        assert!(partitions.next().await.is_none());
+        assert_counter!(
+            metrics,
+            U64Counter,
+            "ingester_query_result_bytes",
+            value = partition_1_bytes + partition_2_bytes,
+        );
+    }
 }
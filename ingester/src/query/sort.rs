@@ -0,0 +1,261 @@
+//! A [`QueryExec`] decorator that sorts the data of each partition by the
+//! partition's sort key.
+
+use std::sync::Arc;
+
+use arrow::{
+    compute::{concat_batches, lexsort_to_indices, take, SortColumn},
+    datatypes::Schema,
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use arrow_util::util::ensure_schema;
+use async_trait::async_trait;
+use data_types::{NamespaceId, TableId};
+use futures::StreamExt;
+use observability_deps::tracing::warn;
+use predicate::Predicate;
+use schema::sort::SortKey;
+use trace::span::Span;
+
+use super::{
+    partition_response::PartitionResponse,
+    projection::OwnedProjection,
+    response::{PartitionStream, QueryResponse},
+    QueryError, QueryExec,
+};
+
+/// A [`QueryExec`] decorator that, when enabled, sorts the data of each
+/// [`PartitionResponse`] by the sort key of its partition.
+///
+/// Pre-sorted data allows the querier to skip (or cheapen) the sort it would
+/// otherwise have to perform before merging ingester data with persisted
+/// Parquet files.
+///
+/// The record batches of a sorted partition are concatenated into a single
+/// batch with the merged schema of all batches. Partitions without a (known)
+/// sort key are passed through unchanged, as are sort key columns that are
+/// not part of the projection.
+///
+/// When disabled, the inner response is returned as-is, in buffer order.
+#[derive(Debug)]
+pub(crate) struct QueryResultSorter<T> {
+    inner: T,
+    enabled: bool,
+}
+
+impl<T> QueryResultSorter<T> {
+    pub(crate) fn new(inner: T, enabled: bool) -> Self {
+        Self { inner, enabled }
+    }
+}
+
+#[async_trait]
+impl<T> QueryExec for QueryResultSorter<T>
+where
+    T: QueryExec<Response = QueryResponse>,
+{
+    type Response = QueryResponse;
+
+    async fn query_exec(
+        &self,
+        namespace_id: NamespaceId,
+        table_id: TableId,
+        projection: OwnedProjection,
+        span: Option<Span>,
+        predicate: Option<Predicate>,
+    ) -> Result<Self::Response, QueryError> {
+        let response = self
+            .inner
+            .query_exec(namespace_id, table_id, projection, span, predicate)
+            .await?;
+
+        if !self.enabled {
+            return Ok(response);
+        }
+
+        let partitions = response.into_partition_stream().then(sort_partition);
+        Ok(QueryResponse::new(PartitionStream::new(partitions)))
+    }
+}
+
+async fn sort_partition(p: PartitionResponse) -> PartitionResponse {
+    if p.is_empty() {
+        return p;
+    }
+
+    let sort_key = match p.sort_key() {
+        Some(v) => v.get().await,
+        None => None,
+    };
+    let Some(sort_key) = sort_key else {
+        return p;
+    };
+
+    let id = p.id().clone();
+    let completed_persistence_count = p.completed_persistence_count();
+    let batches = p.into_record_batches();
+
+    let batches = match sort_batches(&batches, &sort_key) {
+        Ok(batch) => vec![batch],
+        Err(e) => {
+            // The data is still correct, only the querier has to sort it.
+            warn!(
+                error=%e,
+                partition_id=%id,
+                %sort_key,
+                "failed to sort partition query data"
+            );
+            batches
+        }
+    };
+
+    PartitionResponse::new(batches, id, completed_persistence_count)
+}
+
+/// Concatenate `batches` and sort the result by the columns of `sort_key`
+/// that are present in the data.
+fn sort_batches(batches: &[RecordBatch], sort_key: &SortKey) -> Result<RecordBatch, ArrowError> {
+    let schema = Arc::new(Schema::try_merge(
+        batches.iter().map(|b| b.schema().as_ref().clone()),
+    )?);
+    let batches = batches
+        .iter()
+        .map(|b| ensure_schema(&schema, b))
+        .collect::<Result<Vec<_>, _>>()?;
+    let batch = concat_batches(&schema, &batches)?;
+
+    let sort_columns = sort_key
+        .iter()
+        .filter_map(|(col, options)| {
+            let idx = schema.index_of(col).ok()?;
+            Some(SortColumn {
+                values: Arc::clone(batch.column(idx)),
+                options: Some(*options),
+            })
+        })
+        .collect::<Vec<_>>();
+    if sort_columns.is_empty() {
+        return Ok(batch);
+    }
+
+    let indices = lexsort_to_indices(&sort_columns, None)?;
+    let columns = batch
+        .columns()
+        .iter()
+        .map(|c| take(c.as_ref(), &indices, None))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    RecordBatch::try_new(schema, columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{ArrayRef, Int64Array, StringArray, TimestampNanosecondArray};
+    use arrow_util::assert_batches_eq;
+    use futures::stream;
+
+    use super::*;
+    use crate::{
+        buffer_tree::partition::SortKeyState,
+        query::mock_query_exec::MockQueryExec,
+        test_util::{
+            ARBITRARY_NAMESPACE_ID, ARBITRARY_TABLE_ID, ARBITRARY_TRANSITION_PARTITION_ID,
+        },
+    };
+
+    fn batch(region: &[&str], v: &[i64], time: &[i64]) -> RecordBatch {
+        RecordBatch::try_from_iter([
+            (
+                "region",
+                Arc::new(StringArray::from(region.to_vec())) as ArrayRef,
+            ),
+            ("v", Arc::new(Int64Array::from(v.to_vec())) as ArrayRef),
+            (
+                "time",
+                Arc::new(TimestampNanosecondArray::from(time.to_vec())) as ArrayRef,
+            ),
+        ])
+        .unwrap()
+    }
+
+    async fn query(enabled: bool, sort_key: Option<SortKey>) -> Vec<RecordBatch> {
+        let partition = PartitionResponse::new(
+            vec![
+                batch(&["west", "east"], &[1, 2], &[20, 10]),
+                batch(&["east", "west"], &[3, 4], &[5, 1]),
+            ],
+            ARBITRARY_TRANSITION_PARTITION_ID.clone(),
+            42,
+        )
+        .with_sort_key(SortKeyState::Provided(sort_key));
+
+        let mock_inner = MockQueryExec::default().with_result(Ok(QueryResponse::new(
+            PartitionStream::new(stream::iter([partition])),
+        )));
+
+        let partitions = QueryResultSorter::new(mock_inner, enabled)
+            .query_exec(
+                ARBITRARY_NAMESPACE_ID,
+                ARBITRARY_TABLE_ID,
+                OwnedProjection::default(),
+                None,
+                None,
+            )
+            .await
+            .expect("query should succeed")
+            .into_partition_stream()
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(partitions.len(), 1);
+        let partition = partitions.into_iter().next().unwrap();
+        assert_eq!(partition.id(), &*ARBITRARY_TRANSITION_PARTITION_ID);
+        assert_eq!(partition.completed_persistence_count(), 42);
+        partition.into_record_batches()
+    }
+
+    #[tokio::test]
+    async fn test_sorted_by_sort_key() {
+        let batches = query(true, Some(SortKey::from_columns(["region", "time"]))).await;
+
+        assert_eq!(batches.len(), 1);
+        assert_batches_eq!(
+            [
+                "+--------+---+--------------------------------+",
+                "| region | v | time                           |",
+                "+--------+---+--------------------------------+",
+                "| east   | 3 | 1970-01-01T00:00:00.000000005Z |",
+                "| east   | 2 | 1970-01-01T00:00:00.000000010Z |",
+                "| west   | 4 | 1970-01-01T00:00:00.000000001Z |",
+                "| west   | 1 | 1970-01-01T00:00:00.000000020Z |",
+                "+--------+---+--------------------------------+",
+            ],
+            &batches
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unsorted_by_default() {
+        let want = [
+            "+--------+---+--------------------------------+",
+            "| region | v | time                           |",
+            "+--------+---+--------------------------------+",
+            "| west   | 1 | 1970-01-01T00:00:00.000000020Z |",
+            "| east   | 2 | 1970-01-01T00:00:00.000000010Z |",
+            "| east   | 3 | 1970-01-01T00:00:00.000000005Z |",
+            "| west   | 4 | 1970-01-01T00:00:00.000000001Z |",
+            "+--------+---+--------------------------------+",
+        ];
+
+        // Disabled
+        let batches = query(false, Some(SortKey::from_columns(["region", "time"]))).await;
+        assert_eq!(batches.len(), 2);
+        assert_batches_eq!(want, &batches);
+
+        // No sort key
+        let batches = query(true, None).await;
+        assert_eq!(batches.len(), 2);
+        assert_batches_eq!(want, &batches);
+    }
+}
@@ -167,6 +167,10 @@ impl TestContextBuilder {
             persist_workers,
             max_persist_queue_depth,
             persist_hot_partition_cost,
+            None,
+            None,
+            persist_hot_partition_cost,
+            false,
             storage.clone(),
             GossipConfig::default(),
             shutdown_rx.map(|v| v.expect("shutdown sender dropped without calling shutdown")),
@@ -5,8 +5,8 @@ use data_types::{
     partition_template::{NamespacePartitionTemplateOverride, TablePartitionTemplateOverride},
     Column, ColumnType, ColumnsByName, CompactionLevel, Namespace, NamespaceId, NamespaceName,
     NamespaceSchema, NamespaceServiceProtectionLimitsOverride, ParquetFile, ParquetFileId,
-    ParquetFileParams, Partition, PartitionHashId, PartitionId, PartitionKey, SkippedCompaction,
-    Table, TableId, TableSchema, Timestamp, TransitionPartitionId,
+    ParquetFileParams, Partition, PartitionFileStats, PartitionHashId, PartitionId, PartitionKey,
+    SkippedCompaction, Table, TableId, TableSchema, Timestamp, TransitionPartitionId,
 };
 use iox_time::TimeProvider;
 use snafu::{OptionExt, Snafu};
@@ -499,6 +499,15 @@ pub trait ParquetFileRepo: Send + Sync {
         partition_id: &TransitionPartitionId,
     ) -> Result<Vec<ParquetFile>>;
 
+    /// Count the [`CompactionLevel::Initial`] files and sum up the size of all files of the given partitions, using
+    /// only the files that are NOT marked as [`to_delete`](ParquetFile::to_delete).
+    ///
+    /// Partitions without such files are not part of the result.
+    async fn partition_file_stats(
+        &mut self,
+        partition_ids: &[PartitionId],
+    ) -> Result<Vec<PartitionFileStats>>;
+
     /// Return the parquet file with the given object store id
     // used heavily in tests for verification of catalog state.
     async fn get_by_object_store_id(
@@ -2851,6 +2860,37 @@ pub(crate) mod test_helpers {
         let mut expected_ids = vec![parquet_file.id, level1_file.id];
         expected_ids.sort();
         assert_eq!(file_ids, expected_ids);
+
+        // The file statistics use the same files: the deleted file is ignored, and partitions without files are
+        // not returned.
+        let mut stats = repos
+            .parquet_files()
+            .partition_file_stats(&[partition.id, partition2.id, PartitionId::new(i64::MAX)])
+            .await
+            .unwrap();
+        stats.sort();
+        let file_size_bytes = parquet_file_params.file_size_bytes;
+        assert_eq!(
+            stats,
+            [
+                PartitionFileStats {
+                    partition_id: partition.id,
+                    num_l0_files: 1,
+                    total_file_size_bytes: 2 * file_size_bytes,
+                },
+                PartitionFileStats {
+                    partition_id: partition2.id,
+                    num_l0_files: 1,
+                    total_file_size_bytes: file_size_bytes,
+                },
+            ]
+        );
+        assert!(repos
+            .parquet_files()
+            .partition_file_stats(&[])
+            .await
+            .unwrap()
+            .is_empty());
     }
 
     async fn test_update_to_compaction_level_1(catalog: Arc<dyn Catalog>) {
@@ -18,8 +18,8 @@ use data_types::{
     },
     Column, ColumnId, ColumnType, CompactionLevel, Namespace, NamespaceId, NamespaceName,
     NamespaceServiceProtectionLimitsOverride, ParquetFile, ParquetFileId, ParquetFileParams,
-    Partition, PartitionHashId, PartitionId, PartitionKey, SkippedCompaction, Table, TableId,
-    Timestamp, TransitionPartitionId,
+    Partition, PartitionFileStats, PartitionHashId, PartitionId, PartitionKey, SkippedCompaction,
+    Table, TableId, Timestamp, TransitionPartitionId,
 };
 use iox_time::{SystemProvider, TimeProvider};
 use snafu::ensure;
@@ -917,6 +917,42 @@ impl ParquetFileRepo for MemTxn {
             .collect())
     }
 
+    async fn partition_file_stats(
+        &mut self,
+        partition_ids: &[PartitionId],
+    ) -> Result<Vec<PartitionFileStats>> {
+        let stage = self.stage();
+
+        Ok(stage
+            .partitions
+            .iter()
+            .filter(|p| partition_ids.contains(&p.id))
+            .filter_map(|partition| {
+                let files = stage
+                    .parquet_files
+                    .iter()
+                    .filter(|f| match &f.partition_id {
+                        TransitionPartitionId::Deterministic(hash_id) => partition
+                            .hash_id()
+                            .map(|p_hash_id| p_hash_id == hash_id)
+                            .unwrap_or(false),
+                        TransitionPartitionId::Deprecated(id) => id == &partition.id,
+                    })
+                    .filter(|f| f.to_delete.is_none())
+                    .collect::<Vec<_>>();
+
+                (!files.is_empty()).then(|| PartitionFileStats {
+                    partition_id: partition.id,
+                    num_l0_files: files
+                        .iter()
+                        .filter(|f| f.compaction_level == CompactionLevel::Initial)
+                        .count() as i64,
+                    total_file_size_bytes: files.iter().map(|f| f.file_size_bytes).sum(),
+                })
+            })
+            .collect())
+    }
+
     async fn get_by_object_store_id(
         &mut self,
         object_store_id: Uuid,
@@ -22,8 +22,8 @@ use data_types::{
     },
     Column, ColumnType, CompactionLevel, Namespace, NamespaceId, NamespaceName,
     NamespaceServiceProtectionLimitsOverride, ParquetFile, ParquetFileId, ParquetFileParams,
-    Partition, PartitionHashId, PartitionId, PartitionKey, SkippedCompaction, Table, TableId,
-    Timestamp, TransitionPartitionId,
+    Partition, PartitionFileStats, PartitionHashId, PartitionId, PartitionKey, SkippedCompaction,
+    Table, TableId, Timestamp, TransitionPartitionId,
 };
 use iox_time::{SystemProvider, TimeProvider};
 use metric::{Attributes, Instrument, MetricKind};
@@ -1659,6 +1659,30 @@ WHERE partition.id = $1
             .map_err(|e| Error::SqlxError { source: e })
     }
 
+    async fn partition_file_stats(
+        &mut self,
+        partition_ids: &[PartitionId],
+    ) -> Result<Vec<PartitionFileStats>> {
+        sqlx::query_as::<_, PartitionFileStats>(
+            r#"
+SELECT partition.id AS partition_id,
+       COUNT(*) FILTER (WHERE parquet_file.compaction_level = $2) AS num_l0_files,
+       SUM(parquet_file.file_size_bytes)::BIGINT AS total_file_size_bytes
+FROM parquet_file
+INNER JOIN partition
+ON partition.id = parquet_file.partition_id OR partition.hash_id = parquet_file.partition_hash_id
+WHERE partition.id = ANY($1)
+  AND parquet_file.to_delete IS NULL
+GROUP BY partition.id;
+        "#,
+        )
+        .bind(partition_ids) // $1
+        .bind(CompactionLevel::Initial) // $2
+        .fetch_all(&mut self.inner)
+        .await
+        .map_err(|e| Error::SqlxError { source: e })
+    }
+
     async fn get_by_object_store_id(
         &mut self,
         object_store_id: Uuid,
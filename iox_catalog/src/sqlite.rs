@@ -20,8 +20,8 @@ use data_types::{
     },
     Column, ColumnId, ColumnSet, ColumnType, CompactionLevel, Namespace, NamespaceId,
     NamespaceName, NamespaceServiceProtectionLimitsOverride, ParquetFile, ParquetFileId,
-    ParquetFileParams, Partition, PartitionHashId, PartitionId, PartitionKey, SkippedCompaction,
-    Table, TableId, Timestamp, TransitionPartitionId,
+    ParquetFileParams, Partition, PartitionFileStats, PartitionHashId, PartitionId, PartitionKey,
+    SkippedCompaction, Table, TableId, Timestamp, TransitionPartitionId,
 };
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, fmt::Display};
@@ -1426,6 +1426,31 @@ WHERE partition.id = $1
             .collect())
     }
 
+    async fn partition_file_stats(
+        &mut self,
+        partition_ids: &[PartitionId],
+    ) -> Result<Vec<PartitionFileStats>> {
+        let ids = partition_ids.iter().map(|p| p.get()).collect::<Vec<_>>();
+        sqlx::query_as::<_, PartitionFileStats>(
+            r#"
+SELECT partition.id AS partition_id,
+       SUM(CASE WHEN parquet_file.compaction_level = $2 THEN 1 ELSE 0 END) AS num_l0_files,
+       SUM(parquet_file.file_size_bytes) AS total_file_size_bytes
+FROM parquet_file
+INNER JOIN partition
+ON partition.id = parquet_file.partition_id OR partition.hash_id = parquet_file.partition_hash_id
+WHERE partition.id IN (SELECT value FROM json_each($1))
+  AND parquet_file.to_delete IS NULL
+GROUP BY partition.id;
+        "#,
+        )
+        .bind(Json(&ids[..])) // $1
+        .bind(CompactionLevel::Initial) // $2
+        .fetch_all(self.inner.get_mut())
+        .await
+        .map_err(|e| Error::SqlxError { source: e })
+    }
+
     async fn get_by_object_store_id(
         &mut self,
         object_store_id: Uuid,
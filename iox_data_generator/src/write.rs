@@ -354,10 +354,14 @@ impl InnerPointsWriter {
                     let stream = Box::pin(MemoryStream::new(vec![record_batch]));
                     let meta = IoxMetadata::external(crate::now_ns(), &*measurement);
                     let pool = unbounded_memory_pool();
-                    let (data, _parquet_file_meta) =
-                        serialize::to_parquet_bytes(stream, &meta, pool)
-                            .await
-                            .context(ParquetSerializationSnafu)?;
+                    let (data, _parquet_file_meta) = serialize::to_parquet_bytes(
+                        stream,
+                        &meta,
+                        pool,
+                        serialize::default_compression(),
+                    )
+                    .await
+                    .context(ParquetSerializationSnafu)?;
                     let data = Bytes::from(data);
 
                     let mut filename = dir_path.clone();
@@ -41,6 +41,15 @@ extensions_options! {
         /// [`target_partitions`]: datafusion::common::config::ExecutionOptions::target_partitions
         pub max_parquet_fanout: usize, default = 40
 
+        /// Maximum number of bytes that a query may produce, measured as the in-memory size of the result record
+        /// batches. Queries exceeding this limit are aborted with a [`ResourcesExhausted`] error.
+        ///
+        /// `0` means unlimited.
+        ///
+        ///
+        /// [`ResourcesExhausted`]: datafusion::error::DataFusionError::ResourcesExhausted
+        pub max_query_result_bytes: usize, default = 0
+
         /// Cuttoff date for InfluxQL metadata queries.
         pub influxql_metadata_cutoff: MetadataCutoff, default = MetadataCutoff::Relative(Duration::from_secs(3600 * 24))
     }
@@ -373,6 +373,7 @@ mod tests {
     };
     use datafusion::{
         datasource::{provider_as_source, MemTable},
+        error::DataFusionError,
         logical_expr::LogicalPlanBuilder,
     };
     use stringset::StringSet;
@@ -540,6 +541,44 @@ mod tests {
         assert_eq!(results, to_set(&["f1", "f2"]));
     }
 
+    #[tokio::test]
+    async fn executor_max_query_result_bytes() {
+        let data = to_string_array(&["foo", "bar", "baz", "foo"]);
+        let batch = RecordBatch::try_from_iter_with_nullable(vec![("a", data, true)])
+            .expect("created new record batch");
+        let batch_size = batch.get_array_memory_size();
+        let plan = make_plan(batch.schema(), vec![batch.clone(), batch]);
+
+        let exec = Executor::new_testing();
+
+        // unlimited by default
+        let ctx = exec.new_context(ExecutorType::Query);
+        let physical_plan = ctx.create_physical_plan(&plan).await.unwrap();
+        let results = ctx.collect(physical_plan).await.unwrap();
+        assert_eq!(results.len(), 2);
+
+        // limit fits the entire result
+        let ctx = exec
+            .new_execution_config(ExecutorType::Query)
+            .with_config_option("iox.max_query_result_bytes", &(2 * batch_size).to_string())
+            .build();
+        let physical_plan = ctx.create_physical_plan(&plan).await.unwrap();
+        let results = ctx.collect(physical_plan).await.unwrap();
+        assert_eq!(results.len(), 2);
+
+        // limit exceeded by the second batch
+        let ctx = exec
+            .new_execution_config(ExecutorType::Query)
+            .with_config_option("iox.max_query_result_bytes", &batch_size.to_string())
+            .build();
+        let physical_plan = ctx.create_physical_plan(&plan).await.unwrap();
+        let err = ctx.collect(physical_plan).await.unwrap_err();
+        assert!(
+            matches!(err, DataFusionError::ResourcesExhausted(_)),
+            "unexpected error: {err}"
+        );
+    }
+
     /// return a set for testing
     fn to_set(strs: &[&str]) -> StringSetRef {
         StringSetRef::new(strs.iter().map(|s| s.to_string()).collect::<StringSet>())
@@ -242,6 +242,16 @@ impl IOxSessionConfig {
         self
     }
 
+    /// Set DataFusion [config option], failing if the key is unknown or the value is invalid.
+    ///
+    /// See [`with_config_option`](Self::with_config_option) for the lenient version.
+    ///
+    /// [config option]: datafusion::common::config::ConfigOptions
+    pub fn try_with_config_option(mut self, key: &str, value: &str) -> Result<Self> {
+        self.session_config.options_mut().set(key, value)?;
+        Ok(self)
+    }
+
     /// Create an ExecutionContext suitable for executing DataFusion plans
     pub fn build(self) -> IOxSessionContext {
         let maybe_span = self.span_ctx.child_span("Query Execution");
@@ -403,22 +413,37 @@ impl IOxSessionContext {
         &self,
         physical_plan: Arc<dyn ExecutionPlan>,
     ) -> Result<SendableRecordBatchStream> {
-        match physical_plan.output_partitioning().partition_count() {
-            0 => Ok(Box::pin(EmptyRecordBatchStream::new(
-                physical_plan.schema(),
-            ))),
-            1 => self.execute_stream_partitioned(physical_plan, 0).await,
+        let stream = match physical_plan.output_partitioning().partition_count() {
+            0 => Box::pin(EmptyRecordBatchStream::new(physical_plan.schema())),
+            1 => self.execute_stream_partitioned(physical_plan, 0).await?,
             _ => {
                 // Merge into a single partition
                 self.execute_stream_partitioned(
                     Arc::new(CoalescePartitionsExec::new(physical_plan)),
                     0,
                 )
-                .await
+                .await?
             }
+        };
+
+        match self.max_query_result_bytes() {
+            0 => Ok(stream),
+            max_bytes => Ok(limit_result_bytes(stream, max_bytes)),
         }
     }
 
+    /// Maximum result size of a query, see [`IoxConfigExt::max_query_result_bytes`]. `0` means unlimited.
+    fn max_query_result_bytes(&self) -> usize {
+        self.inner
+            .state()
+            .config()
+            .options()
+            .extensions
+            .get::<IoxConfigExt>()
+            .map(|ext| ext.max_query_result_bytes)
+            .unwrap_or_default()
+    }
+
     /// Executes a single partition of a physical plan and produces a
     /// `SendableRecordBatchStream` to stream over the result that
     /// iterates over the results. The creation of the stream is
@@ -710,3 +735,36 @@ impl SessionContextIOxExt for SessionState {
             .and_then(|span| span.as_ref().as_ref().map(|span| span.ctx.clone()))
     }
 }
+
+/// Fails the stream with [`Error::ResourcesExhausted`] once the in-memory size of the produced record
+/// batches exceeds `max_bytes`.
+///
+/// The stream ends after the error.
+fn limit_result_bytes(
+    stream: SendableRecordBatchStream,
+    max_bytes: usize,
+) -> SendableRecordBatchStream {
+    let schema = stream.schema();
+    let stream = stream.scan(Some(0usize), move |total, res| {
+        let Some(total_bytes) = total.as_mut() else {
+            return futures::future::ready(None);
+        };
+
+        let res = res.and_then(|batch| {
+            *total_bytes += batch.get_array_memory_size();
+            if *total_bytes > max_bytes {
+                Err(Error::ResourcesExhausted(format!(
+                    "Query result exceeds the limit of {max_bytes} bytes"
+                )))
+            } else {
+                Ok(batch)
+            }
+        });
+        if res.is_err() {
+            *total = None;
+        }
+
+        futures::future::ready(Some(res))
+    });
+    Box::pin(RecordBatchStreamAdapter::new(schema, stream))
+}
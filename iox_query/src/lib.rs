@@ -92,45 +92,60 @@ pub trait QueryChunk: Debug + Send + Sync + 'static {
     fn as_any(&self) -> &dyn Any;
 }
 
+/// Outcome of a query, passed to the function of a [`QueryCompletedToken`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct QueryCompletion {
+    /// If this query completed successfully
+    pub success: bool,
+
+    /// Number of rows returned by this query, if known
+    pub rows: Option<u64>,
+}
+
 /// A `QueryCompletedToken` is returned by `record_query` implementations of
 /// a `QueryNamespace`. It is used to trigger side-effects (such as query timing)
 /// on query completion.
 ///
 pub struct QueryCompletedToken {
-    /// If this query completed successfully
-    success: bool,
+    /// How this query completed
+    completion: QueryCompletion,
 
     /// Function invoked when the token is dropped. It is passed the
-    /// vaue of `self.success`
-    f: Option<Box<dyn FnOnce(bool) + Send>>,
+    /// vaue of `self.completion`
+    f: Option<Box<dyn FnOnce(QueryCompletion) + Send>>,
 }
 
 impl Debug for QueryCompletedToken {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("QueryCompletedToken")
-            .field("success", &self.success)
+            .field("completion", &self.completion)
             .finish()
     }
 }
 
 impl QueryCompletedToken {
-    pub fn new(f: impl FnOnce(bool) + Send + 'static) -> Self {
+    pub fn new(f: impl FnOnce(QueryCompletion) + Send + 'static) -> Self {
         Self {
-            success: false,
+            completion: QueryCompletion::default(),
             f: Some(Box::new(f)),
         }
     }
 
     /// Record that this query completed successfully
     pub fn set_success(&mut self) {
-        self.success = true;
+        self.completion.success = true;
+    }
+
+    /// Record the number of rows returned by this query
+    pub fn set_rows(&mut self, rows: u64) {
+        self.completion.rows = Some(rows);
     }
 }
 
 impl Drop for QueryCompletedToken {
     fn drop(&mut self) {
         if let Some(f) = self.f.take() {
-            (f)(self.success)
+            (f)(self.completion)
         }
     }
 }
@@ -82,10 +82,7 @@ use iox_query::exec::IOxSessionContext;
 use iox_query::logical_optimizer::range_predicate::find_time_range;
 use itertools::Itertools;
 use observability_deps::tracing::debug;
-use query_functions::{
-    clean_non_meta_escapes,
-    selectors::{selector_first, selector_last, selector_max, selector_min},
-};
+use query_functions::selectors::{selector_first, selector_last, selector_max, selector_min};
 use schema::{
     InfluxColumnType, InfluxFieldType, Schema, INFLUXQL_MEASUREMENT_COLUMN_NAME,
     INFLUXQL_METADATA_KEY,
@@ -98,7 +95,7 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use super::parse_regex;
-use super::util::contains_expr;
+use super::util::{contains_expr, regex_to_df_expr};
 
 /// The column index of the measurement column.
 const MEASUREMENT_COLUMN_INDEX: u32 = 0;
@@ -1764,6 +1761,18 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
     ) -> Result<Expr> {
         let ConditionalBinary { lhs, op, rhs } = expr;
 
+        if let (
+            ConditionalOperator::EqRegex | ConditionalOperator::NotEqRegex,
+            Some(IQLExpr::Literal(Literal::Regex(re))),
+        ) = (op, rhs.expr())
+        {
+            return Ok(regex_to_df_expr(
+                self.conditional_to_df_expr(lhs, schema)?,
+                re,
+                *op == ConditionalOperator::NotEqRegex,
+            ));
+        }
+
         Ok(binary_expr(
             self.conditional_to_df_expr(lhs, schema)?,
             conditional_op_to_operator(*op)?,
@@ -1846,13 +1855,17 @@ impl<'a> InfluxQLToLogicalPlan<'a> {
                 Literal::Duration(v) => {
                     Ok(lit(ScalarValue::IntervalMonthDayNano(Some((**v).into()))))
                 }
-                Literal::Regex(re) => match scope {
+                Literal::Regex(_) => match scope {
                     // a regular expression in a projection list is unexpected,
                     // as it should have been expanded by the rewriter.
                     ExprScope::Projection => {
                         error::internal("unexpected regular expression found in projection")
                     }
-                    ExprScope::Where => Ok(lit(clean_non_meta_escapes(re.as_str()))),
+                    // the parser only accepts regular expressions as the right-hand side
+                    // of =~ and !~, which are mapped by `binary_conditional_to_df_expr`.
+                    ExprScope::Where => error::internal(
+                        "unexpected regular expression found outside of =~ or !~ condition",
+                    ),
                 },
             },
             // A DISTINCT <ident> clause should have been replaced by `rewrite_statement`.
@@ -3,7 +3,7 @@ use arrow::datatypes::{DataType, TimeUnit};
 use datafusion::common::tree_node::{Transformed, TreeNode, VisitRecursion};
 use datafusion::common::{DFSchemaRef, Result};
 use datafusion::logical_expr::utils::expr_as_column_expr;
-use datafusion::logical_expr::{lit, Expr, ExprSchemable, LogicalPlan, Operator};
+use datafusion::logical_expr::{binary_expr, lit, Expr, ExprSchemable, LogicalPlan, Operator};
 use datafusion::scalar::ScalarValue;
 use influxdb_influxql_parser::expression::BinaryOperator;
 use influxdb_influxql_parser::literal::Number;
@@ -93,6 +93,21 @@ pub(crate) fn parse_regex(re: &Regex) -> Result<regex::Regex> {
         .map_err(|e| error::map::query(format!("invalid regular expression '{re}': {e}")))
 }
 
+/// Returns a DataFusion expression that matches `expr` against the InfluxQL regular expression `re`,
+/// or does not match it, if `negated` is `true`.
+///
+/// InfluxQL uses the RE2 syntax of Go, which, like the `regex` crate used by DataFusion, is unanchored
+/// unless the pattern contains `^` or `$` and supports the same inline flags, such as `(?i)` (see
+/// [`Regex::flags`]). Only the escaped non-meta characters accepted by Go need translating.
+pub(in crate::plan) fn regex_to_df_expr(expr: Expr, re: &Regex, negated: bool) -> Expr {
+    let op = if negated {
+        Operator::RegexNotMatch
+    } else {
+        Operator::RegexMatch
+    };
+    binary_expr(expr, op, lit(clean_non_meta_escapes(re.as_str())))
+}
+
 /// Returns `n` as a scalar value of the specified `data_type`.
 fn number_to_scalar(n: &Number, data_type: &DataType) -> Result<ScalarValue> {
     Ok(match (n, data_type) {
@@ -235,3 +250,57 @@ where
     .expect("no way to return error during recursion");
     exprs
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Array, BooleanArray, StringArray};
+    use arrow::datatypes::{Field as ArrowField, Schema};
+    use arrow::record_batch::RecordBatch;
+    use datafusion::execution::context::ExecutionProps;
+    use datafusion::logical_expr::col;
+    use datafusion_util::create_physical_expr_from_schema;
+
+    /// Evaluate `expr` for each of `values`, provided as column `tag0`.
+    fn evaluate(expr: &Expr, values: &[&str]) -> Vec<bool> {
+        let schema = Arc::new(Schema::new(vec![ArrowField::new(
+            "tag0",
+            DataType::Utf8,
+            false,
+        )]));
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![Arc::new(StringArray::from(values.to_vec()))],
+        )
+        .unwrap();
+
+        let expr = create_physical_expr_from_schema(&ExecutionProps::new(), expr, &schema).unwrap();
+        let result = expr.evaluate(&batch).unwrap().into_array(batch.num_rows());
+        let result = result.as_any().downcast_ref::<BooleanArray>().unwrap();
+        result.iter().map(|v| v.unwrap()).collect()
+    }
+
+    #[test]
+    fn test_regex_to_df_expr() {
+        let values = ["foo", "foobar", "barfoo", "FOO"];
+
+        let expr = regex_to_df_expr(col("tag0"), &Regex::from("^foo"), false);
+        assert_eq!(expr.to_string(), "tag0 ~ Utf8(\"^foo\")");
+        assert_eq!(evaluate(&expr, &values), [true, true, false, false]);
+
+        let expr = regex_to_df_expr(col("tag0"), &Regex::from("^foo"), true);
+        assert_eq!(evaluate(&expr, &values), [false, false, true, true]);
+
+        // unanchored
+        let expr = regex_to_df_expr(col("tag0"), &Regex::from("foo"), false);
+        assert_eq!(evaluate(&expr, &values), [true, true, true, false]);
+
+        // inline flags
+        let expr = regex_to_df_expr(col("tag0"), &Regex::from("(?i)^foo$"), false);
+        assert_eq!(evaluate(&expr, &values), [true, false, false, true]);
+
+        // escaped non-meta characters, valid in Go
+        let expr = regex_to_df_expr(col("tag0"), &Regex::from(r"^foo\:"), false);
+        assert_eq!(evaluate(&expr, &["foo:", "foo"]), [true, false]);
+    }
+}
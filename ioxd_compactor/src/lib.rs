@@ -17,8 +17,9 @@ use workspace_hack as _;
 
 use async_trait::async_trait;
 use backoff::BackoffConfig;
-use clap_blocks::compactor::CompactorConfig;
+use clap_blocks::compactor::{CompactorConfig, ParquetCompression};
 use compactor::{compactor::Compactor, config::Config};
+use data_types::PartitionId;
 use hyper::{Body, Request, Response};
 use iox_catalog::interface::Catalog;
 use iox_query::exec::Executor;
@@ -32,6 +33,7 @@ use ioxd_common::{
     setup_builder,
 };
 use metric::Registry;
+use parquet::basic::{Compression, ZstdLevel};
 use parquet_file::storage::ParquetStorage;
 use std::{
     fmt::{Debug, Display},
@@ -174,11 +176,18 @@ pub async fn create_compactor_server_type(
         df_concurrency: compactor_config.compaction_df_concurrency,
         partition_scratchpad_concurrency: compactor_config
             .compaction_partition_scratchpad_concurrency,
+        upload_concurrency: compactor_config.compaction_upload_concurrency,
         max_desired_file_size_bytes: compactor_config.max_desired_file_size_bytes,
         percentage_max_file_size: compactor_config.percentage_max_file_size,
         split_percentage: compactor_config.split_percentage,
         partition_timeout: Duration::from_secs(compactor_config.partition_timeout_secs),
+        partition_timeout_overrides: compactor_config
+            .partition_timeout_overrides
+            .into_iter()
+            .map(|(partition_id, secs)| (PartitionId::new(partition_id), Duration::from_secs(secs)))
+            .collect(),
         shadow_mode: compactor_config.shadow_mode,
+        fail_on_concurrent_modification: compactor_config.fail_on_concurrent_modification,
         enable_scratchpad: compactor_config.enable_scratchpad,
         min_num_l1_files_to_compact: compactor_config.min_num_l1_files_to_compact,
         process_once: compactor_config.process_once,
@@ -186,9 +195,25 @@ pub async fn create_compactor_server_type(
         parquet_files_sink_override: None,
         all_errors_are_fatal: false,
         max_num_columns_per_table: compactor_config.max_num_columns_per_table,
-        max_num_files_per_plan: compactor_config.max_num_files_per_plan,
+        max_num_files_per_plan: compactor_config.max_num_files_per_plan.get(),
         max_partition_fetch_queries_per_second: compactor_config
             .max_partition_fetch_queries_per_second,
+        audit_log_path: compactor_config.audit_log_path,
+        max_consecutive_failures: compactor_config.max_consecutive_failures,
+        fetch_batch_size: compactor_config.fetch_batch_size,
+        max_rounds: compactor_config.max_rounds,
+        webhook_url: compactor_config.webhook_url,
+        max_input_files_per_plan: compactor_config.max_input_files_per_plan,
+        parquet_compression: convert_parquet_compression(
+            compactor_config.compaction_parquet_compression,
+        ),
+        prioritize_by_l0_count: compactor_config.prioritize_by_l0_count,
+        verify_uploads: compactor_config.verify_uploads,
+        cache_partition_files: compactor_config.cache_partition_files,
+        object_store_error_budget: compactor_config.object_store_error_budget,
+        object_store_error_backoff: Duration::from_secs(
+            compactor_config.object_store_error_backoff_secs,
+        ),
     });
 
     Arc::new(CompactorServerType::new(
@@ -197,3 +222,13 @@ pub async fn create_compactor_server_type(
         common_state,
     ))
 }
+
+fn convert_parquet_compression(compression: ParquetCompression) -> Compression {
+    match compression {
+        ParquetCompression::Uncompressed => Compression::UNCOMPRESSED,
+        ParquetCompression::Snappy => Compression::SNAPPY,
+        ParquetCompression::Zstd(level) => Compression::ZSTD(
+            ZstdLevel::try_new(level).expect("ZSTD level validated by the config parser"),
+        ),
+    }
+}
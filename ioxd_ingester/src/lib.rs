@@ -228,6 +228,16 @@ pub async fn create_ingester_server_type(
         ingester_config.persist_max_parallelism,
         ingester_config.persist_queue_depth,
         ingester_config.persist_hot_partition_cost,
+        Some(Duration::from_millis(
+            ingester_config.persist_hot_partition_batch_window_millis,
+        ))
+        .filter(|d| !d.is_zero()),
+        Some(Duration::from_secs(
+            ingester_config.persist_hot_partition_target_latency_seconds,
+        ))
+        .filter(|d| !d.is_zero()),
+        ingester_config.persist_hot_partition_min_cost,
+        ingester_config.sort_query_output,
         object_store,
         gossip,
         shutdown_rx.map(|v| v.expect("shutdown sender dropped without calling shutdown")),
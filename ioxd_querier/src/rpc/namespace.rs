@@ -133,6 +133,7 @@ mod tests {
                 Some(create_ingester_connection_for_testing()),
                 QuerierDatabase::MAX_CONCURRENT_QUERIES_MAX,
                 Arc::new(HashMap::default()),
+                None,
             )
             .await
             .unwrap(),
@@ -166,6 +167,7 @@ mod tests {
                 Some(create_ingester_connection_for_testing()),
                 QuerierDatabase::MAX_CONCURRENT_QUERIES_MAX,
                 Arc::new(HashMap::default()),
+                None,
             )
             .await
             .unwrap(),
@@ -199,6 +201,78 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_get_namespaces_retention_and_limits() {
+        let catalog = TestCatalog::new();
+
+        let catalog_cache = Arc::new(QuerierCatalogCache::new_testing(
+            catalog.catalog(),
+            catalog.time_provider(),
+            catalog.metric_registry(),
+            catalog.object_store(),
+            &Handle::current(),
+        ));
+        let db = Arc::new(
+            QuerierDatabase::new(
+                catalog_cache,
+                catalog.metric_registry(),
+                catalog.exec(),
+                Some(create_ingester_connection_for_testing()),
+                QuerierDatabase::MAX_CONCURRENT_QUERIES_MAX,
+                Arc::new(HashMap::default()),
+                None,
+            )
+            .await
+            .unwrap(),
+        );
+
+        let service = NamespaceServiceImpl::new(db);
+        let retention_period_ns = Some(7 * 24 * 3_600 * 1_000_000_000);
+        catalog
+            .create_namespace_with_retention("limited", retention_period_ns)
+            .await;
+        catalog
+            .create_namespace_with_retention("infinite", None)
+            .await;
+        {
+            let mut repos = catalog.catalog.repositories().await;
+            repos
+                .namespaces()
+                .update_table_limit("limited", 42)
+                .await
+                .unwrap();
+            repos
+                .namespaces()
+                .update_column_limit("limited", 7)
+                .await
+                .unwrap();
+        }
+
+        // values are read from the catalog, not the defaults
+        let namespaces = get_namespaces(&service).await;
+        assert_eq!(
+            namespaces,
+            proto::GetNamespacesResponse {
+                namespaces: vec![
+                    proto::Namespace {
+                        id: 1,
+                        name: "limited".to_string(),
+                        retention_period_ns,
+                        max_tables: 42,
+                        max_columns_per_table: 7,
+                    },
+                    proto::Namespace {
+                        id: 2,
+                        name: "infinite".to_string(),
+                        retention_period_ns: None,
+                        max_tables: TEST_MAX_TABLES,
+                        max_columns_per_table: TEST_MAX_COLUMNS_PER_TABLE,
+                    },
+                ]
+            }
+        );
+    }
+
     async fn get_namespaces(service: &NamespaceServiceImpl) -> proto::GetNamespacesResponse {
         let request = proto::GetNamespacesRequest {};
 
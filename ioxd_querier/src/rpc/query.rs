@@ -1,5 +1,5 @@
 use authz::Authorizer;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use arrow_flight::flight_service_server::{
     FlightService as Flight, FlightServiceServer as FlightServer,
@@ -10,8 +10,9 @@ use querier::QuerierDatabase;
 pub fn make_flight_server(
     server: Arc<QuerierDatabase>,
     authz: Option<Arc<dyn Authorizer>>,
+    flightsql_metadata_cache_ttl: Duration,
 ) -> FlightServer<impl Flight> {
-    service_grpc_flight::make_server(server, authz)
+    service_grpc_flight::make_server(server, authz, flightsql_metadata_cache_ttl)
 }
 
 pub fn make_storage_server(server: Arc<QuerierDatabase>) -> StorageServer<impl Storage> {
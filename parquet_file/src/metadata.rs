@@ -1048,10 +1048,14 @@ mod tests {
         let batch = RecordBatch::try_new(schema, vec![data, timestamps]).unwrap();
         let stream = Box::pin(MemoryStream::new(vec![batch.clone()]));
 
-        let (bytes, file_meta) =
-            crate::serialize::to_parquet_bytes(stream, &meta, unbounded_memory_pool())
-                .await
-                .expect("should serialize");
+        let (bytes, file_meta) = crate::serialize::to_parquet_bytes(
+            stream,
+            &meta,
+            unbounded_memory_pool(),
+            crate::serialize::default_compression(),
+        )
+        .await
+        .expect("should serialize");
 
         // Verify if the parquet file meta data has values
         assert!(!file_meta.row_groups.is_empty());
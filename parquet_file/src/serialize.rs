@@ -26,6 +26,11 @@ use crate::{
 /// Parquet row group write size
 pub const ROW_GROUP_WRITE_SIZE: usize = 1024 * 1024;
 
+/// Compression codec used for parquet files unless configured otherwise.
+pub fn default_compression() -> Compression {
+    Compression::ZSTD(Default::default())
+}
+
 /// ensure read and write work well together
 /// Skip clippy due to <https://github.com/rust-lang/rust-clippy/issues/8159>.
 #[allow(clippy::assertions_on_constants)]
@@ -98,6 +103,8 @@ impl From<crate::writer::Error> for CodecError {
 /// yielded by the stream must be of the same schema, or this call will return
 /// an error.
 ///
+/// The column chunks are compressed using `compression`.
+///
 /// IOx metadata is encoded into the parquet file's metadata under the key
 /// [`METADATA_KEY`], with a base64-wrapped, protobuf serialized
 /// [`proto::IoxMetadata`] structure.
@@ -123,6 +130,7 @@ pub async fn to_parquet<W>(
     meta: &IoxMetadata,
     pool: Arc<dyn MemoryPool>,
     sink: W,
+    compression: Compression,
 ) -> Result<parquet::format::FileMetaData, CodecError>
 where
     W: Write + Send,
@@ -135,7 +143,7 @@ where
     pin_mut!(stream);
 
     // Serialize the IoxMetadata to the protobuf bytes.
-    let props = writer_props(meta)?;
+    let props = writer_props(meta, compression)?;
     let write_batch_size = props.write_batch_size();
     let max_row_group_size = props.max_row_group_size();
 
@@ -172,6 +180,7 @@ pub async fn to_parquet_bytes(
     batches: SendableRecordBatchStream,
     meta: &IoxMetadata,
     pool: Arc<dyn MemoryPool>,
+    compression: Compression,
 ) -> Result<(Vec<u8>, parquet::format::FileMetaData), CodecError> {
     let mut bytes = vec![];
 
@@ -181,7 +190,7 @@ pub async fn to_parquet_bytes(
     );
 
     // Serialize the record batches into the in-memory buffer
-    let meta = to_parquet(batches, meta, pool, &mut bytes).await?;
+    let meta = to_parquet(batches, meta, pool, &mut bytes, compression).await?;
     bytes.shrink_to_fit();
 
     trace!(?meta, "generated parquet file metadata");
@@ -192,13 +201,16 @@ pub async fn to_parquet_bytes(
 /// Helper to construct [`WriterProperties`] , serialising the given
 /// [`IoxMetadata`] and embedding it as a key=value property keyed by
 /// [`METADATA_KEY`].
-fn writer_props(meta: &IoxMetadata) -> Result<WriterProperties, prost::EncodeError> {
+fn writer_props(
+    meta: &IoxMetadata,
+    compression: Compression,
+) -> Result<WriterProperties, prost::EncodeError> {
     let builder = WriterProperties::builder()
         .set_key_value_metadata(Some(vec![KeyValue {
             key: METADATA_KEY.to_string(),
             value: Some(meta.to_base64()?),
         }]))
-        .set_compression(Compression::ZSTD(Default::default()))
+        .set_compression(compression)
         .set_max_row_group_size(ROW_GROUP_WRITE_SIZE);
 
     Ok(builder.build())
@@ -237,9 +249,14 @@ mod tests {
         let batch = RecordBatch::try_from_iter([("a", to_string_array(&["value"]))]).unwrap();
         let stream = Box::pin(MemoryStream::new(vec![batch.clone()]));
 
-        let (bytes, _file_meta) = to_parquet_bytes(stream, &meta, unbounded_memory_pool())
-            .await
-            .expect("should serialize");
+        let (bytes, _file_meta) = to_parquet_bytes(
+            stream,
+            &meta,
+            unbounded_memory_pool(),
+            default_compression(),
+        )
+        .await
+        .expect("should serialize");
 
         let bytes = Bytes::from(bytes);
         // Read the metadata from the file bytes.
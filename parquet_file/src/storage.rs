@@ -26,6 +26,7 @@ use datafusion::{
 use datafusion_util::config::{iox_session_config, register_iox_object_store};
 use object_store::{DynObjectStore, ObjectMeta};
 use observability_deps::tracing::*;
+use parquet::basic::Compression;
 use schema::Projection;
 use std::{
     fmt::Display,
@@ -171,6 +172,9 @@ pub struct ParquetStorage {
 
     /// Storage ID to hook it into DataFusion.
     id: StorageId,
+
+    /// Compression codec of uploaded parquet files.
+    compression: Compression,
 }
 
 impl Display for ParquetStorage {
@@ -187,7 +191,25 @@ impl ParquetStorage {
     /// Initialise a new [`ParquetStorage`] using `object_store` as the
     /// persistence layer.
     pub fn new(object_store: Arc<DynObjectStore>, id: StorageId) -> Self {
-        Self { object_store, id }
+        Self {
+            object_store,
+            id,
+            compression: serialize::default_compression(),
+        }
+    }
+
+    /// Compress uploaded parquet files using `compression` instead of the
+    /// [default](serialize::default_compression).
+    pub fn with_compression(self, compression: Compression) -> Self {
+        Self {
+            compression,
+            ..self
+        }
+    }
+
+    /// Get the compression codec of uploaded parquet files.
+    pub fn compression(&self) -> Compression {
+        self.compression
     }
 
     /// Get underlying object store.
@@ -237,7 +259,8 @@ impl ParquetStorage {
         //
         // This is not a huge concern, as the resulting parquet files are
         // currently smallish on average.
-        let (data, parquet_file_meta) = serialize::to_parquet_bytes(batches, meta, pool).await?;
+        let (data, parquet_file_meta) =
+            serialize::to_parquet_bytes(batches, meta, pool, self.compression).await?;
 
         // Read the IOx-specific parquet metadata from the file metadata
         let parquet_meta =
@@ -335,6 +358,28 @@ mod tests {
     use iox_time::Time;
     use std::collections::HashMap;
 
+    #[tokio::test]
+    async fn test_upload_compression() {
+        let object_store: Arc<DynObjectStore> = Arc::new(object_store::memory::InMemory::default());
+
+        let store = ParquetStorage::new(Arc::clone(&object_store), StorageId::from("iox"));
+        assert_eq!(store.compression(), serialize::default_compression());
+        let store = store.with_compression(Compression::SNAPPY);
+
+        let (partition_id, meta) = meta();
+        let batch = RecordBatch::try_from_iter([("a", to_string_array(&["value"]))]).unwrap();
+        let (file_meta, _file_size) = upload(&store, &partition_id, &meta, batch).await;
+
+        let file_meta = file_meta.decode().expect("should decode parquet metadata");
+        let row_groups = file_meta.parquet_row_group_metadata();
+        assert!(!row_groups.is_empty());
+        for row_group in row_groups {
+            for column in row_group.columns() {
+                assert_eq!(column.compression(), Compression::SNAPPY);
+            }
+        }
+    }
+
     #[tokio::test]
     async fn test_upload_metadata() {
         let object_store: Arc<DynObjectStore> = Arc::new(object_store::memory::InMemory::default());
@@ -5,6 +5,7 @@ use backoff::BackoffConfig;
 use cache_system::backend::policy::lru::ResourcePool;
 use iox_catalog::interface::Catalog;
 use iox_time::TimeProvider;
+use observability_deps::tracing::warn;
 use std::sync::Arc;
 use tokio::runtime::Handle;
 
@@ -44,6 +45,9 @@ pub struct CatalogCache {
     /// Object store cache.
     object_store_cache: ObjectStoreCache,
 
+    /// Parquet stores in addition to the [cached store](Self::parquet_store).
+    parquet_stores: Vec<ParquetStorage>,
+
     /// Metric registry
     metric_registry: Arc<metric::Registry>,
 
@@ -169,11 +173,31 @@ impl CatalogCache {
             parquet_file_cache,
             projected_schema_cache,
             object_store_cache,
+            parquet_stores: vec![],
             metric_registry,
             time_provider,
         }
     }
 
+    /// Add parquet stores next to the [cached store](Self::parquet_store),
+    /// e.g. for a cold storage tier.
+    ///
+    /// Stores with a [`StorageId`] that is already taken are ignored.
+    pub fn with_parquet_stores(
+        mut self,
+        parquet_stores: impl IntoIterator<Item = ParquetStorage>,
+    ) -> Self {
+        for parquet_store in parquet_stores {
+            let id = parquet_store.id();
+            if self.parquet_stores().iter().any(|s| s.id() == id) {
+                warn!(store_id = %id, "ignored parquet store with an ID that is already taken");
+                continue;
+            }
+            self.parquet_stores.push(parquet_store);
+        }
+        self
+    }
+
     /// Get underlying catalog
     pub(crate) fn catalog(&self) -> Arc<dyn Catalog> {
         Arc::clone(&self.catalog)
@@ -216,4 +240,77 @@ impl CatalogCache {
             StorageId::from("iox_cached"),
         )
     }
+
+    /// All parquet stores that need to be registered with the query runtime,
+    /// starting with the [cached store](Self::parquet_store).
+    ///
+    /// Each store has a distinct [`StorageId`].
+    pub fn parquet_stores(&self) -> Vec<ParquetStorage> {
+        std::iter::once(self.parquet_store())
+            .chain(self.parquet_stores.iter().cloned())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::object_store::memory::InMemory;
+    use ::object_store::DynObjectStore;
+    use iox_tests::TestCatalog;
+
+    use super::*;
+
+    fn catalog_cache(catalog: &TestCatalog) -> CatalogCache {
+        CatalogCache::new_testing(
+            catalog.catalog(),
+            catalog.time_provider(),
+            catalog.metric_registry(),
+            catalog.object_store(),
+            &Handle::current(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_parquet_stores() {
+        let catalog = TestCatalog::new();
+        let cold = ParquetStorage::new(Arc::new(InMemory::new()), StorageId::from("cold"));
+
+        let cache = catalog_cache(&catalog).with_parquet_stores([cold]);
+
+        let ids = cache
+            .parquet_stores()
+            .iter()
+            .map(|s| s.id())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            [StorageId::from("iox_cached"), StorageId::from("cold")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parquet_stores_duplicate_id() {
+        let catalog = TestCatalog::new();
+        let cold = Arc::new(InMemory::new()) as Arc<DynObjectStore>;
+        let store = |id: &'static str, object_store: Arc<DynObjectStore>| {
+            ParquetStorage::new(object_store, StorageId::from(id))
+        };
+
+        // the first store with an ID wins, the cached store ID is taken as well
+        let cache = catalog_cache(&catalog).with_parquet_stores([
+            store("cold", Arc::clone(&cold)),
+            store("cold", Arc::new(InMemory::new())),
+            store("iox_cached", Arc::new(InMemory::new())),
+        ]);
+
+        let stores = cache.parquet_stores();
+        assert_eq!(stores.len(), 2);
+        assert_eq!(stores[0].id(), StorageId::from("iox_cached"));
+        assert!(Arc::ptr_eq(
+            stores[0].object_store(),
+            cache.parquet_store().object_store()
+        ));
+        assert_eq!(stores[1].id(), StorageId::from("cold"));
+        assert!(Arc::ptr_eq(stores[1].object_store(), &cold));
+    }
 }
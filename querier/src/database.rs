@@ -15,7 +15,11 @@ use iox_catalog::interface::SoftDeletedRows;
 use iox_query::exec::Executor;
 use service_common::QueryNamespaceProvider;
 use snafu::Snafu;
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
 use trace::span::{Span, SpanRecorder};
 use tracker::{
     AsyncSemaphoreMetrics, InstrumentedAsyncOwnedSemaphorePermit, InstrumentedAsyncSemaphore,
@@ -70,6 +74,34 @@ pub struct QuerierDatabase {
 
     /// DataFusion config.
     datafusion_config: Arc<HashMap<String, String>>,
+
+    /// Namespaces served by this querier.
+    namespace_filter: NamespaceFilter,
+}
+
+/// Selects the namespaces that a querier serves.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum NamespaceFilter {
+    /// Serve all namespaces.
+    #[default]
+    All,
+
+    /// Only serve the given namespaces.
+    Allow(HashSet<String>),
+
+    /// Serve all but the given namespaces.
+    Deny(HashSet<String>),
+}
+
+impl NamespaceFilter {
+    /// Returns true if the namespace with the given name is served.
+    pub fn is_served(&self, name: &str) -> bool {
+        match self {
+            Self::All => true,
+            Self::Allow(names) => names.contains(name),
+            Self::Deny(names) => !names.contains(name),
+        }
+    }
 }
 
 #[async_trait]
@@ -85,6 +117,10 @@ impl QueryNamespaceProvider for QuerierDatabase {
         self.namespace(name, span, include_debug_info_tables).await
     }
 
+    fn serves_namespace(&self, name: &str) -> bool {
+        self.namespace_filter.is_served(name)
+    }
+
     async fn acquire_semaphore(&self, span: Option<Span>) -> InstrumentedAsyncOwnedSemaphorePermit {
         Arc::clone(&self.query_execution_semaphore)
             .acquire_owned(span)
@@ -109,6 +145,7 @@ impl QuerierDatabase {
         ingester_connection: Option<Arc<dyn IngesterConnection>>,
         max_concurrent_queries: usize,
         datafusion_config: Arc<HashMap<String, String>>,
+        slow_query_threshold: Option<Duration>,
     ) -> Result<Self, Error> {
         assert!(
             max_concurrent_queries <= Self::MAX_CONCURRENT_QUERIES_MAX,
@@ -123,7 +160,10 @@ impl QuerierDatabase {
             Arc::clone(&catalog_cache),
             Arc::clone(&metric_registry),
         ));
-        let query_log = Arc::new(QueryLog::new(QUERY_LOG_SIZE, catalog_cache.time_provider()));
+        let query_log = Arc::new(
+            QueryLog::new(QUERY_LOG_SIZE, catalog_cache.time_provider())
+                .with_slow_query_threshold(slow_query_threshold),
+        );
         let semaphore_metrics = Arc::new(AsyncSemaphoreMetrics::new(
             &metric_registry,
             &[("semaphore", "query_execution")],
@@ -143,9 +183,17 @@ impl QuerierDatabase {
             query_execution_semaphore,
             prune_metrics,
             datafusion_config,
+            namespace_filter: NamespaceFilter::All,
         })
     }
 
+    /// Only serve the namespaces selected by `namespace_filter`. Other namespaces are treated as if they did not
+    /// exist.
+    pub fn with_namespace_filter(mut self, namespace_filter: NamespaceFilter) -> Self {
+        self.namespace_filter = namespace_filter;
+        self
+    }
+
     /// Get namespace if it exists.
     ///
     /// This will await the internal namespace semaphore. Existence of namespaces is checked AFTER
@@ -156,6 +204,10 @@ impl QuerierDatabase {
         span: Option<Span>,
         include_debug_info_tables: bool,
     ) -> Option<Arc<QuerierNamespace>> {
+        if !self.namespace_filter.is_served(name) {
+            return None;
+        }
+
         let span_recorder = SpanRecorder::new(span);
         let name = Arc::from(name.to_owned());
         let ns = self
@@ -181,10 +233,10 @@ impl QuerierDatabase {
         })))
     }
 
-    /// Return all namespaces this querier knows about
+    /// Return all namespaces this querier knows about and serves
     pub async fn namespaces(&self) -> Vec<Namespace> {
         let catalog = &self.catalog_cache.catalog();
-        Backoff::new(&self.backoff_config)
+        let mut namespaces = Backoff::new(&self.backoff_config)
             .retry_all_errors("listing namespaces", || async {
                 catalog
                     .repositories()
@@ -194,7 +246,10 @@ impl QuerierDatabase {
                     .await
             })
             .await
-            .expect("retry forever")
+            .expect("retry forever");
+
+        namespaces.retain(|ns| self.namespace_filter.is_served(&ns.name));
+        namespaces
     }
 
     /// Return connection to ingester(s) to get and aggregate information from them
@@ -236,6 +291,7 @@ mod tests {
             Some(create_ingester_connection_for_testing()),
             QuerierDatabase::MAX_CONCURRENT_QUERIES_MAX.saturating_add(1),
             Arc::new(HashMap::default()),
+            None,
         )
         .await
         .unwrap();
@@ -267,6 +323,46 @@ mod tests {
         assert_eq!(namespaces[1].name, "ns2");
     }
 
+    #[tokio::test]
+    async fn test_namespace_allowlist() {
+        let catalog = TestCatalog::new();
+        let db = new_db(&catalog)
+            .await
+            .with_namespace_filter(NamespaceFilter::Allow(HashSet::from([String::from("ns1")])));
+
+        catalog.create_namespace_1hr_retention("ns1").await;
+        catalog.create_namespace_1hr_retention("ns2").await;
+
+        assert!(db.serves_namespace("ns1"));
+        assert!(!db.serves_namespace("ns2"));
+        assert!(db.namespace("ns1", None, true).await.is_some());
+        assert!(db.namespace("ns2", None, true).await.is_none());
+
+        let namespaces = db.namespaces().await;
+        assert_eq!(namespaces.len(), 1);
+        assert_eq!(namespaces[0].name, "ns1");
+    }
+
+    #[tokio::test]
+    async fn test_namespace_denylist() {
+        let catalog = TestCatalog::new();
+        let db = new_db(&catalog)
+            .await
+            .with_namespace_filter(NamespaceFilter::Deny(HashSet::from([String::from("ns1")])));
+
+        catalog.create_namespace_1hr_retention("ns1").await;
+        catalog.create_namespace_1hr_retention("ns2").await;
+
+        assert!(!db.serves_namespace("ns1"));
+        assert!(db.serves_namespace("ns2"));
+        assert!(db.namespace("ns1", None, true).await.is_none());
+        assert!(db.namespace("ns2", None, true).await.is_some());
+
+        let namespaces = db.namespaces().await;
+        assert_eq!(namespaces.len(), 1);
+        assert_eq!(namespaces[0].name, "ns2");
+    }
+
     async fn new_db(catalog: &Arc<TestCatalog>) -> QuerierDatabase {
         let catalog_cache = Arc::new(CatalogCache::new_testing(
             catalog.catalog(),
@@ -282,6 +378,7 @@ mod tests {
             Some(create_ingester_connection_for_testing()),
             QuerierDatabase::MAX_CONCURRENT_QUERIES_MAX,
             Arc::new(HashMap::default()),
+            None,
         )
         .await
         .unwrap()
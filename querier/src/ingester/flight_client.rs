@@ -9,7 +9,16 @@ use ingester_query_grpc::{influxdata::iox::ingester::v1 as proto, IngesterQueryR
 use observability_deps::tracing::{debug, warn};
 use prost::Message;
 use snafu::{ResultExt, Snafu};
-use std::{collections::HashMap, fmt::Debug, ops::DerefMut, sync::Arc};
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    num::NonZeroUsize,
+    ops::DerefMut,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 use trace::{ctx::SpanContext, span::SpanRecorder};
 use trace_http::ctx::format_jaeger_trace_context;
 
@@ -86,55 +95,29 @@ pub trait IngesterFlightClient: Debug + Send + Sync + 'static {
 }
 
 /// Default [`IngesterFlightClient`] implementation that uses a real connection
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct FlightClientImpl {
-    /// Cached connections
-    /// key: ingester_address (e.g. "http://ingester-1:8082")
-    /// value: CachedConnection
-    ///
-    /// Note: Use sync (parking_log) mutex because it is always held
-    /// for a very short period of time, and any actual connection (and
-    /// waiting) is done in CachedConnection
-    connections: parking_lot::Mutex<HashMap<String, CachedConnection>>,
+    /// Connections to the ingesters.
+    connections: ConnectionPool<GrpcConnectionFactory>,
 
     /// Name of the http header that will contain the tracing context value.
     trace_context_header_name: String,
 }
 
 impl FlightClientImpl {
-    /// Create new client.
-    pub fn new(trace_context_header_name: &str) -> Self {
+    /// Create new client that keeps `pool_size` connections to every ingester.
+    pub fn new(trace_context_header_name: &str, pool_size: NonZeroUsize) -> Self {
         Self {
+            connections: ConnectionPool::new(Arc::new(GrpcConnectionFactory), pool_size),
             trace_context_header_name: trace_context_header_name.to_string(),
-            ..Default::default()
         }
     }
-
-    /// Establish connection to given addr and perform handshake.
-    async fn connect(&self, ingester_address: Arc<str>) -> Result<Connection, Error> {
-        let cached_connection = {
-            let mut connections = self.connections.lock();
-            if let Some(cached_connection) = connections.get(ingester_address.as_ref()) {
-                cached_connection.clone()
-            } else {
-                // need to make a new one;
-                let cached_connection = CachedConnection::new(&ingester_address);
-                connections.insert(ingester_address.to_string(), cached_connection.clone());
-                cached_connection
-            }
-        };
-        cached_connection.connect().await
-    }
 }
 
 #[async_trait]
 impl IngesterFlightClient for FlightClientImpl {
     async fn invalidate_connection(&self, ingester_address: Arc<str>) {
-        let maybe_conn = self.connections.lock().remove(ingester_address.as_ref());
-
-        if let Some(conn) = maybe_conn {
-            conn.close().await;
-        }
+        self.connections.invalidate(&ingester_address).await;
     }
 
     async fn query(
@@ -149,7 +132,7 @@ impl IngesterFlightClient for FlightClientImpl {
         let connection = {
             let _span_recorder = span_recorder.child("connect");
 
-            self.connect(Arc::clone(&ingester_addr)).await?
+            self.connections.connect(&ingester_addr).await?
         };
 
         debug!(%ingester_addr, ?request, "Sending request to ingester");
@@ -357,23 +340,145 @@ where
     }
 }
 
-#[derive(Debug, Clone)]
-struct CachedConnection {
+/// Creates new connections to an ingester.
+#[async_trait]
+trait ConnectionFactory: Debug + Send + Sync + 'static {
+    type Connection: Clone + Debug + Send + 'static;
+
+    /// Establish a new connection to the given ingester.
+    async fn connect(&self, ingester_address: &str) -> Result<Self::Connection, Error>;
+}
+
+/// [`ConnectionFactory`] that connects via gRPC and performs a handshake.
+#[derive(Debug)]
+struct GrpcConnectionFactory;
+
+#[async_trait]
+impl ConnectionFactory for GrpcConnectionFactory {
+    type Connection = Connection;
+
+    async fn connect(&self, ingester_address: &str) -> Result<Connection, Error> {
+        let connection = connection::Builder::new()
+            .build(ingester_address)
+            .await
+            .context(ConnectingSnafu { ingester_address })?;
+
+        // sanity check w/ a handshake
+        let mut client = influxdb_iox_client::flight::Client::new(connection.clone());
+
+        // make contact with the ingester
+        client
+            .handshake()
+            .await
+            .context(HandshakeSnafu { ingester_address })?;
+
+        Ok(connection)
+    }
+}
+
+/// Lazily established connections, `pool_size` per ingester.
+///
+/// Requests are spread over the connections of an ingester in a round-robin fashion.
+#[derive(Debug)]
+struct ConnectionPool<F>
+where
+    F: ConnectionFactory,
+{
+    factory: Arc<F>,
+    pool_size: NonZeroUsize,
+
+    /// Cached connections
+    /// key: ingester_address (e.g. "http://ingester-1:8082")
+    /// value: `pool_size` CachedConnections
+    ///
+    /// Note: Use sync (parking_log) mutex because it is always held
+    /// for a very short period of time, and any actual connection (and
+    /// waiting) is done in CachedConnection
+    connections: parking_lot::Mutex<HashMap<String, Vec<CachedConnection<F>>>>,
+
+    /// Round-robin counter used to pick a connection.
+    next: AtomicUsize,
+}
+
+impl<F> ConnectionPool<F>
+where
+    F: ConnectionFactory,
+{
+    fn new(factory: Arc<F>, pool_size: NonZeroUsize) -> Self {
+        Self {
+            factory,
+            pool_size,
+            connections: Default::default(),
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// Get a connection to the given ingester, establishing it if needed.
+    async fn connect(&self, ingester_address: &Arc<str>) -> Result<F::Connection, Error> {
+        let cached_connection = {
+            let mut connections = self.connections.lock();
+            let pool = connections
+                .entry(ingester_address.to_string())
+                .or_insert_with(|| {
+                    // need to make new ones
+                    (0..self.pool_size.get())
+                        .map(|_| CachedConnection::new(ingester_address, Arc::clone(&self.factory)))
+                        .collect()
+                });
+            let idx = self.next.fetch_add(1, Ordering::Relaxed) % pool.len();
+            pool[idx].clone()
+        };
+        cached_connection.connect().await
+    }
+
+    /// Close all connections to the given ingester.
+    async fn invalidate(&self, ingester_address: &str) {
+        let maybe_pool = self.connections.lock().remove(ingester_address);
+
+        for conn in maybe_pool.into_iter().flatten() {
+            conn.close().await;
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CachedConnection<F>
+where
+    F: ConnectionFactory,
+{
     ingester_address: Arc<str>,
+    factory: Arc<F>,
     /// Real async mutex to
-    maybe_connection: Arc<tokio::sync::Mutex<Option<Connection>>>,
+    maybe_connection: Arc<tokio::sync::Mutex<Option<F::Connection>>>,
 }
 
-impl CachedConnection {
-    fn new(ingester_address: &Arc<str>) -> Self {
+impl<F> Clone for CachedConnection<F>
+where
+    F: ConnectionFactory,
+{
+    fn clone(&self) -> Self {
+        Self {
+            ingester_address: Arc::clone(&self.ingester_address),
+            factory: Arc::clone(&self.factory),
+            maybe_connection: Arc::clone(&self.maybe_connection),
+        }
+    }
+}
+
+impl<F> CachedConnection<F>
+where
+    F: ConnectionFactory,
+{
+    fn new(ingester_address: &Arc<str>, factory: Arc<F>) -> Self {
         Self {
             ingester_address: Arc::clone(ingester_address),
+            factory,
             maybe_connection: Arc::new(tokio::sync::Mutex::new(None)),
         }
     }
 
     /// Return the underlying connection, creating it if needed
-    async fn connect(&self) -> Result<Connection, Error> {
+    async fn connect(&self) -> Result<F::Connection, Error> {
         let mut maybe_connection = self.maybe_connection.lock().await;
 
         let ingester_address = self.ingester_address.as_ref();
@@ -385,19 +490,7 @@ impl CachedConnection {
         } else {
             debug!(%ingester_address, "Connecting to ingester");
 
-            let connection = connection::Builder::new()
-                .build(ingester_address)
-                .await
-                .context(ConnectingSnafu { ingester_address })?;
-
-            // sanity check w/ a handshake
-            let mut client = influxdb_iox_client::flight::Client::new(connection.clone());
-
-            // make contact with the ingester
-            client
-                .handshake()
-                .await
-                .context(HandshakeSnafu { ingester_address })?;
+            let connection = self.factory.connect(ingester_address).await?;
 
             *maybe_connection = Some(connection.clone());
             Ok(connection)
@@ -424,6 +517,54 @@ mod tests {
 
     use super::*;
 
+    #[tokio::test]
+    async fn test_connection_pool_size() {
+        let factory = Arc::new(MockConnectionFactory::default());
+        let pool = ConnectionPool::new(Arc::clone(&factory), NonZeroUsize::new(3).unwrap());
+        let addr_1: Arc<str> = Arc::from("http://ingester-1:8082");
+        let addr_2: Arc<str> = Arc::from("http://ingester-2:8082");
+
+        // connections are established lazily and then reused round-robin
+        let mut conns = vec![];
+        for _ in 0..6 {
+            conns.push(pool.connect(&addr_1).await.unwrap());
+        }
+        assert_eq!(conns, [0, 1, 2, 0, 1, 2]);
+        assert_eq!(factory.calls(), ["http://ingester-1:8082"; 3]);
+
+        // every ingester gets its own pool
+        pool.connect(&addr_2).await.unwrap();
+        assert_eq!(factory.calls().len(), 4);
+
+        // invalidation drops the whole pool of that ingester
+        pool.invalidate(&addr_1).await;
+        pool.connect(&addr_1).await.unwrap();
+        assert_eq!(factory.calls().len(), 5);
+    }
+
+    /// [`ConnectionFactory`] that hands out increasing numbers as connections.
+    #[derive(Debug, Default)]
+    struct MockConnectionFactory {
+        calls: parking_lot::Mutex<Vec<String>>,
+    }
+
+    impl MockConnectionFactory {
+        fn calls(&self) -> Vec<String> {
+            self.calls.lock().clone()
+        }
+    }
+
+    #[async_trait]
+    impl ConnectionFactory for MockConnectionFactory {
+        type Connection = usize;
+
+        async fn connect(&self, ingester_address: &str) -> Result<usize, Error> {
+            let mut calls = self.calls.lock();
+            calls.push(ingester_address.to_string());
+            Ok(calls.len() - 1)
+        }
+    }
+
     #[test]
     fn serialize_deeply_nested_and() {
         // we need more stack space so this doesn't overflow in dev builds
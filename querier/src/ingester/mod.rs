@@ -30,14 +30,16 @@ use iox_query::{
     QueryChunk, QueryChunkData,
 };
 use iox_time::{Time, TimeProvider};
-use metric::{DurationHistogram, Metric};
+use metric::{DurationHistogram, Metric, U64Counter};
 use observability_deps::tracing::{debug, trace, warn};
 use predicate::Predicate;
 use schema::{sort::SortKey, Schema};
 use snafu::{ensure, OptionExt, ResultExt, Snafu};
 use std::{
     any::Any,
+    borrow::Cow,
     collections::{HashMap, HashSet},
+    num::NonZeroUsize,
     sync::Arc,
     time::Duration,
 };
@@ -148,9 +150,11 @@ pub fn create_ingester_connections(
     ingester_addresses: Vec<Arc<str>>,
     catalog_cache: Arc<CatalogCache>,
     open_circuit_after_n_errors: u64,
+    connection_pool_size: NonZeroUsize,
     trace_context_header_name: &str,
 ) -> Arc<dyn IngesterConnection> {
-    // This backoff config is used to retry requests for a specific table-scoped query.
+    // This backoff config is used to retry requests for a specific table-scoped query. Failed
+    // connections are dropped, so every retry re-establishes the connection to the ingester.
     let retry_backoff_config = BackoffConfig {
         init_backoff: Duration::from_millis(100),
         max_backoff: Duration::from_secs(1),
@@ -173,6 +177,7 @@ pub fn create_ingester_connections(
         retry_backoff_config,
         circuit_breaker_backoff_config,
         open_circuit_after_n_errors,
+        connection_pool_size,
         trace_context_header_name,
     ))
 }
@@ -210,6 +215,9 @@ struct IngesterConnectionMetrics {
 
     /// Time spent waiting for a request that was cancelled.
     ingester_duration_cancelled: DurationHistogram,
+
+    /// Number of retries, each of which re-establishes the connection, per ingester.
+    reconnect_attempts: Metric<U64Counter>,
 }
 
 impl IngesterConnectionMetrics {
@@ -221,11 +229,16 @@ impl IngesterConnectionMetrics {
         let ingester_duration_success = ingester_duration.recorder(&[("result", "success")]);
         let ingester_duration_error = ingester_duration.recorder(&[("result", "error")]);
         let ingester_duration_cancelled = ingester_duration.recorder(&[("result", "cancelled")]);
+        let reconnect_attempts = metric_registry.register_metric(
+            "ingester_reconnect_attempts",
+            "number of attempts to re-establish a failed ingester connection",
+        );
 
         Self {
             ingester_duration_success,
             ingester_duration_error,
             ingester_duration_cancelled,
+            reconnect_attempts,
         }
     }
 }
@@ -344,9 +357,13 @@ impl IngesterConnectionImpl {
         backoff_config: BackoffConfig,
         circuit_breaker_backoff_config: BackoffConfig,
         open_circuit_after_n_errors: u64,
+        connection_pool_size: NonZeroUsize,
         trace_context_header_name: &str,
     ) -> Self {
-        let flight_client = Arc::new(FlightClientImpl::new(trace_context_header_name));
+        let flight_client = Arc::new(FlightClientImpl::new(
+            trace_context_header_name,
+            connection_pool_size,
+        ));
         let flight_client = Arc::new(InvalidateOnErrorFlightClient::new(flight_client));
         let flight_client = Arc::new(CircuitBreakerFlightClient::new(
             flight_client,
@@ -698,14 +715,23 @@ impl IngesterConnection for IngesterConnectionImpl {
             // wrap `execute` into an additional future so that we can measure the request time
             // INFO: create the measurement structure outside of the async block so cancellation is
             // always measured
+            let reconnect_attempts = metrics
+                .reconnect_attempts
+                .recorder([("ingester", Cow::Owned(ingester_address.to_string()))]);
             let measure_me = ObserveIngesterRequest::new(request.clone(), metrics, &span_recorder);
             async move {
                 let span_recorder = measure_me
                     .span_recorder()
                     .child("ingester request (retry block)");
 
+                // This is the only retry layer. Every failed attempt counts towards the circuit
+                // breaker and drops the broken connection, so each retry reconnects.
+                let mut first_attempt = true;
                 let res = Backoff::new(&backoff_config)
                     .retry_all_errors("ingester request", move || {
+                        if !std::mem::take(&mut first_attempt) {
+                            reconnect_attempts.inc(1);
+                        }
                         let request = request.clone();
                         let span_recorder = span_recorder.child("ingester request (single try)");
 
@@ -1043,7 +1069,7 @@ mod tests {
     use metric::Attributes;
     use mutable_batch_lp::test_helpers::lp_to_mutable_batch;
     use schema::{builder::SchemaBuilder, InfluxFieldType, Projection};
-    use std::collections::{BTreeSet, HashMap};
+    use std::collections::{BTreeSet, HashMap, VecDeque};
     use tokio::{runtime::Handle, sync::Mutex};
     use trace::{ctx::SpanContext, span::SpanStatus, RingBufferTraceCollector};
 
@@ -1113,6 +1139,44 @@ mod tests {
         assert_matches!(err, Error::RemoteQuery { .. });
     }
 
+    #[tokio::test]
+    async fn test_flight_reconnect_after_failure() {
+        let mock_flight_client = Arc::new(
+            MockFlightClient::new([(
+                "addr1",
+                Err(FlightClientError::Flight {
+                    source: tonic::Status::unavailable("ingester restarted").into(),
+                }),
+            )])
+            .await,
+        );
+        mock_flight_client
+            .push_response("addr1", Ok(MockQueryData { results: vec![] }))
+            .await;
+        let mut ingester_conn = mock_flight_client.ingester_conn().await;
+        ingester_conn.backoff_config = BackoffConfig {
+            init_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+            base: 1.,
+            deadline: None,
+        };
+
+        let partitions = get_partitions(&ingester_conn).await.unwrap();
+        assert!(partitions.is_empty());
+
+        // the failure is retried exactly once, by the connection-level retry loop
+        assert!(mock_flight_client.responses.lock().await["addr1"].is_empty());
+        let reconnect_attempts = mock_flight_client
+            .catalog
+            .metric_registry()
+            .get_instrument::<Metric<U64Counter>>("ingester_reconnect_attempts")
+            .expect("failed to read metric")
+            .get_observer(&Attributes::from(&[("ingester", "addr1")]))
+            .expect("failed to get observer")
+            .fetch();
+        assert_eq!(reconnect_attempts, 1);
+    }
+
     #[tokio::test]
     async fn test_flight_no_partitions() {
         let mock_flight_client = Arc::new(
@@ -1655,7 +1719,7 @@ mod tests {
     #[derive(Debug)]
     struct MockFlightClient {
         catalog: Arc<TestCatalog>,
-        responses: Mutex<HashMap<String, Result<MockQueryData, FlightClientError>>>,
+        responses: Mutex<HashMap<String, VecDeque<Result<MockQueryData, FlightClientError>>>>,
     }
 
     impl MockFlightClient {
@@ -1675,12 +1739,26 @@ mod tests {
                 responses: Mutex::new(
                     responses
                         .into_iter()
-                        .map(|(k, v)| (String::from(k), v))
+                        .map(|(k, v)| (String::from(k), VecDeque::from([v])))
                         .collect(),
                 ),
             }
         }
 
+        /// Mock another response for `ingester_address`, returned after all previous ones.
+        async fn push_response(
+            &self,
+            ingester_address: &str,
+            response: Result<MockQueryData, FlightClientError>,
+        ) {
+            self.responses
+                .lock()
+                .await
+                .get_mut(ingester_address)
+                .expect("ingester not mocked")
+                .push_back(response);
+        }
+
         async fn ingester_conn(self: &Arc<Self>) -> IngesterConnectionImpl {
             let ingester_addresses: BTreeSet<_> =
                 self.responses.lock().await.keys().cloned().collect();
@@ -1719,7 +1797,8 @@ mod tests {
             self.responses
                 .lock()
                 .await
-                .remove(ingester_address.as_ref())
+                .get_mut(ingester_address.as_ref())
+                .and_then(VecDeque::pop_front)
                 .expect("Response not mocked")
                 .map(|query_data| Box::new(query_data) as _)
         }
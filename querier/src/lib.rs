@@ -32,7 +32,7 @@ mod table;
 const CONCURRENT_CHUNK_CREATION_JOBS: usize = 100;
 
 pub use cache::CatalogCache as QuerierCatalogCache;
-pub use database::{Error as QuerierDatabaseError, QuerierDatabase};
+pub use database::{Error as QuerierDatabaseError, NamespaceFilter, QuerierDatabase};
 pub use ingester::{
     create_ingester_connection_for_testing, create_ingester_connections,
     flight_client::{
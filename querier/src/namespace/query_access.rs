@@ -96,8 +96,14 @@ impl QueryNamespace for QuerierNamespace {
         // will be set.
         let query_log = Arc::clone(&self.query_log);
         let trace_id = ctx.span().map(|s| s.ctx.trace_id);
-        let entry = query_log.push(self.id, query_type, query_text, trace_id);
-        QueryCompletedToken::new(move |success| query_log.set_completed(entry, success))
+        let entry = query_log.push(
+            self.id,
+            Arc::clone(&self.name),
+            query_type,
+            query_text,
+            trace_id,
+        );
+        QueryCompletedToken::new(move |completion| query_log.set_completed(entry, completion))
     }
 
     fn as_meta(&self) -> &dyn QueryNamespaceMeta {
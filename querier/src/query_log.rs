@@ -1,7 +1,8 @@
 //! Ring buffer of queries that have been run with some brief information
 
 use data_types::NamespaceId;
-use iox_query::QueryText;
+use flightsql::{redact_influxql, redact_query};
+use iox_query::{QueryCompletion, QueryText};
 use iox_time::{Time, TimeProvider};
 use observability_deps::tracing::warn;
 use parking_lot::Mutex;
@@ -20,6 +21,9 @@ pub struct QueryLogEntry {
     /// Namespace ID.
     pub namespace_id: NamespaceId,
 
+    /// Namespace name.
+    pub namespace_name: Arc<str>,
+
     /// The type of query
     pub query_type: String,
 
@@ -56,6 +60,7 @@ impl QueryLogEntry {
     /// Creates a new QueryLogEntry -- use `QueryLog::push` to add new entries to the log
     fn new(
         namespace_id: NamespaceId,
+        namespace_name: Arc<str>,
         query_type: String,
         query_text: QueryText,
         trace_id: Option<TraceId>,
@@ -63,6 +68,7 @@ impl QueryLogEntry {
     ) -> Self {
         Self {
             namespace_id,
+            namespace_name,
             query_type,
             query_text,
             trace_id,
@@ -112,6 +118,7 @@ pub struct QueryLog {
     log: Mutex<VecDeque<Arc<QueryLogEntry>>>,
     max_size: usize,
     time_provider: Arc<dyn TimeProvider>,
+    slow_query_threshold: Option<Duration>,
 }
 
 impl QueryLog {
@@ -122,18 +129,28 @@ impl QueryLog {
             log: Mutex::new(VecDeque::with_capacity(max_size)),
             max_size,
             time_provider,
+            slow_query_threshold: None,
         }
     }
 
+    /// Log a warning for every query that takes longer than `slow_query_threshold` to complete. Slow queries are not
+    /// logged if this is `None`.
+    pub fn with_slow_query_threshold(mut self, slow_query_threshold: Option<Duration>) -> Self {
+        self.slow_query_threshold = slow_query_threshold;
+        self
+    }
+
     pub fn push(
         &self,
         namespace_id: NamespaceId,
+        namespace_name: Arc<str>,
         query_type: impl Into<String>,
         query_text: QueryText,
         trace_id: Option<TraceId>,
     ) -> Arc<QueryLogEntry> {
         let entry = Arc::new(QueryLogEntry::new(
             namespace_id,
+            namespace_name,
             query_type.into(),
             query_text,
             trace_id,
@@ -161,15 +178,46 @@ impl QueryLog {
     }
 
     /// Marks the provided query entry as completed using the current time.
-    /// `success` specifies the query ran successfully
-    pub fn set_completed(&self, entry: Arc<QueryLogEntry>, success: bool) {
-        entry.set_completed(self.time_provider.now(), success)
+    /// `completion` specifies if the query ran successfully.
+    ///
+    /// Logs a warning if the query took longer than the slow query threshold. The query text is redacted, as it may
+    /// contain sensitive values.
+    pub fn set_completed(&self, entry: Arc<QueryLogEntry>, completion: QueryCompletion) {
+        entry.set_completed(self.time_provider.now(), completion.success);
+
+        let (Some(threshold), Some(elapsed)) =
+            (self.slow_query_threshold, entry.query_completed_duration())
+        else {
+            return;
+        };
+        if elapsed > threshold {
+            warn!(
+                namespace_name = %entry.namespace_name,
+                query_type = %entry.query_type,
+                query_text = %redacted_query_text(&entry.query_type, &entry.query_text),
+                ?elapsed,
+                rows = ?completion.rows,
+                success = completion.success,
+                "slow query",
+            );
+        }
+    }
+}
+
+/// Returns the query text of a [`QueryLogEntry`] with the literals redacted, or a placeholder if there is no redaction
+/// for the query type (e.g. the JSON encoded storage gRPC requests).
+fn redacted_query_text(query_type: &str, query_text: &QueryText) -> String {
+    match query_type {
+        "sql" | "flightsql" => redact_query(&query_text.to_string()),
+        "influxql" => redact_influxql(&query_text.to_string()),
+        _ => String::from("<not logged>"),
     }
 }
 
 #[cfg(test)]
 mod test_super {
     use iox_time::MockProvider;
+    use test_helpers::tracing::TracingCapture;
 
     use super::*;
 
@@ -179,6 +227,7 @@ mod test_super {
 
         let entry = Arc::new(QueryLogEntry::new(
             NamespaceId::new(1),
+            "ns".into(),
             "sql".into(),
             Box::new("SELECT 1"),
             None,
@@ -205,4 +254,90 @@ mod test_super {
         );
         assert!(!entry.success());
     }
+
+    #[test]
+    fn test_slow_query_log() {
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_millis(100).unwrap()));
+        let query_log = QueryLog::new(10, Arc::clone(&time_provider) as _)
+            .with_slow_query_threshold(Some(Duration::from_secs(1)));
+
+        let capture = TracingCapture::new();
+
+        // fast query
+        let entry = query_log.push(
+            NamespaceId::new(1),
+            "ns1".into(),
+            "sql",
+            Box::new("SELECT 1"),
+            None,
+        );
+        time_provider.inc(Duration::from_secs(1));
+        query_log.set_completed(
+            entry,
+            QueryCompletion {
+                success: true,
+                rows: Some(1),
+            },
+        );
+        assert_eq!(capture.to_string(), "");
+
+        // slow query, literals are redacted
+        let entry = query_log.push(
+            NamespaceId::new(2),
+            "ns2".into(),
+            "sql",
+            Box::new("SELECT 2 FROM t WHERE tag = 'secret'"),
+            None,
+        );
+        time_provider.inc(Duration::from_millis(1500));
+        query_log.set_completed(
+            entry,
+            QueryCompletion {
+                success: true,
+                rows: Some(10),
+            },
+        );
+        assert_eq!(
+            capture.to_string(),
+            "level = WARN; message = slow query; namespace_name = ns2; query_type = sql; \
+            query_text = SELECT 2 FROM t WHERE tag = '***'; elapsed = 1.5s; rows = Some(10); success = true; "
+        );
+    }
+
+    #[test]
+    fn test_redacted_query_text() {
+        let text = |s: &'static str| -> QueryText { Box::new(s) };
+
+        assert_eq!(
+            redacted_query_text("sql", &text("SELECT * FROM t WHERE a = 'x'")),
+            "SELECT * FROM t WHERE a = '***'"
+        );
+        assert_eq!(
+            redacted_query_text("influxql", &text("SELECT * FROM t WHERE a =~ /x/")),
+            "SELECT * FROM t WHERE a =~ /***/"
+        );
+        assert_eq!(
+            redacted_query_text("read_filter", &text(r#"{"tag": "x"}"#)),
+            "<not logged>"
+        );
+    }
+
+    #[test]
+    fn test_slow_query_log_disabled() {
+        let time_provider = Arc::new(MockProvider::new(Time::from_timestamp_millis(100).unwrap()));
+        let query_log = QueryLog::new(10, Arc::clone(&time_provider) as _);
+
+        let capture = TracingCapture::new();
+
+        let entry = query_log.push(
+            NamespaceId::new(1),
+            "ns1".into(),
+            "sql",
+            Box::new("SELECT 1"),
+            None,
+        );
+        time_provider.inc(Duration::from_secs(3600));
+        query_log.set_completed(entry, QueryCompletion::default());
+        assert_eq!(capture.to_string(), "");
+    }
 }
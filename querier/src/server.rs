@@ -108,6 +108,7 @@ mod tests {
                     Some(create_ingester_connection_for_testing()),
                     QuerierDatabase::MAX_CONCURRENT_QUERIES_MAX,
                     Arc::new(HashMap::default()),
+                    None,
                 )
                 .await
                 .unwrap(),
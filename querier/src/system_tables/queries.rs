@@ -196,11 +196,24 @@ mod tests {
             10,
             Arc::clone(&time_provider) as Arc<dyn TimeProvider>,
         ));
-        query_log.push(id1, "sql", Box::new("select * from foo"), None);
+        query_log.push(
+            id1,
+            "ns1".into(),
+            "sql",
+            Box::new("select * from foo"),
+            None,
+        );
         time_provider.inc(std::time::Duration::from_secs(24 * 60 * 60));
-        let sql2_entry = query_log.push(id1, "sql", Box::new("select * from bar"), None);
+        let sql2_entry = query_log.push(
+            id1,
+            "ns1".into(),
+            "sql",
+            Box::new("select * from bar"),
+            None,
+        );
         let read_filter_entry = query_log.push(
             id2,
+            "ns2".into(),
             "read_filter",
             Box::new("json goop"),
             Some(TraceId::new(0x45fe).unwrap()),
@@ -0,0 +1,317 @@
+//! Client-supplied request deadlines.
+//!
+//! Clients can bound how long the server works on a request, either via the standard gRPC deadline (transmitted as
+//! `grpc-timeout` header) or via the IOx specific `iox-timeout` header (a human-readable duration like `30s` or
+//! `1m 30s`). If both are set, the shorter one wins. Requests without a deadline are not bounded.
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::{ready, Stream, StreamExt};
+use tokio::time::{Instant, Sleep};
+use tonic::{metadata::MetadataMap, Status};
+
+/// Header that carries the standard gRPC deadline.
+const GRPC_TIMEOUT_HEADER: &str = "grpc-timeout";
+
+/// IOx specific header that carries a human-readable timeout.
+const IOX_TIMEOUT_HEADER: &str = "iox-timeout";
+
+/// Point in time after which the server stops working on a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deadline {
+    /// When the deadline passes.
+    pub at: Instant,
+
+    /// The timeout requested by the client, used for error messages.
+    pub timeout: Duration,
+}
+
+impl Deadline {
+    /// Determine the deadline of a request that arrived just now from its metadata, if the client requested one.
+    ///
+    /// Malformed timeout headers are rejected as `INVALID_ARGUMENT`.
+    pub fn from_metadata(metadata: &MetadataMap) -> Result<Option<Self>, Status> {
+        let grpc_timeout = header_timeout(metadata, GRPC_TIMEOUT_HEADER, parse_grpc_timeout)?;
+        let iox_timeout = header_timeout(metadata, IOX_TIMEOUT_HEADER, |s| {
+            humantime::parse_duration(s).ok()
+        })?;
+
+        let timeout = match (grpc_timeout, iox_timeout) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+
+        Ok(timeout.map(|timeout| Self {
+            at: Instant::now() + timeout,
+            timeout,
+        }))
+    }
+
+    /// Error that is returned to the client when the deadline passed.
+    pub fn exceeded(&self) -> Status {
+        Status::deadline_exceeded(format!("Query exceeded the deadline of {:?}", self.timeout))
+    }
+}
+
+/// Fails `fut` with `DEADLINE_EXCEEDED` if it does not finish before `deadline`.
+///
+/// This is meant for the planning part of a request, use [`DeadlineStream`] for the response stream.
+pub async fn with_deadline<F, T>(deadline: Option<Deadline>, fut: F) -> Result<T, Status>
+where
+    F: Future<Output = Result<T, Status>> + Send,
+{
+    match deadline {
+        Some(deadline) => tokio::time::timeout_at(deadline.at, fut)
+            .await
+            .unwrap_or_else(|_| Err(deadline.exceeded())),
+        None => fut.await,
+    }
+}
+
+fn header_timeout(
+    metadata: &MetadataMap,
+    header: &'static str,
+    parse: impl FnOnce(&str) -> Option<Duration>,
+) -> Result<Option<Duration>, Status> {
+    let Some(value) = metadata.get(header) else {
+        return Ok(None);
+    };
+
+    let value = value.to_str().unwrap_or_default();
+    let timeout = parse(value)
+        .ok_or_else(|| Status::invalid_argument(format!("Invalid '{header}' header: {value}")))?;
+    Ok(Some(timeout))
+}
+
+/// Parse the value of a `grpc-timeout` header.
+///
+/// See <https://github.com/grpc/grpc/blob/master/doc/PROTOCOL-HTTP2.md#requests>: up to 8 digits followed by a unit.
+fn parse_grpc_timeout(s: &str) -> Option<Duration> {
+    if !s.is_ascii() || s.len() < 2 || s.len() > 9 {
+        return None;
+    }
+
+    let (value, unit) = s.split_at(s.len() - 1);
+    if !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let value = value.parse::<u64>().ok()?;
+
+    let timeout = match unit {
+        "H" => Duration::from_secs(value * 60 * 60),
+        "M" => Duration::from_secs(value * 60),
+        "S" => Duration::from_secs(value),
+        "m" => Duration::from_millis(value),
+        "u" => Duration::from_micros(value),
+        "n" => Duration::from_nanos(value),
+        _ => return None,
+    };
+    Some(timeout)
+}
+
+/// Ends the wrapped response stream with a `DEADLINE_EXCEEDED` error once the deadline has passed.
+///
+/// The inner stream is dropped at that point, which cancels the query execution that feeds it. Without a deadline,
+/// the inner stream is passed on as is.
+#[derive(Debug)]
+pub struct DeadlineStream<S> {
+    inner: Option<S>,
+    deadline: Option<(Deadline, Pin<Box<Sleep>>)>,
+}
+
+impl<S> DeadlineStream<S> {
+    pub fn new(inner: S, deadline: Option<Deadline>) -> Self {
+        Self {
+            inner: Some(inner),
+            deadline: deadline
+                .map(|deadline| (deadline, Box::pin(tokio::time::sleep_until(deadline.at)))),
+        }
+    }
+}
+
+impl<S, T> Stream for DeadlineStream<S>
+where
+    S: Stream<Item = Result<T, tonic::Status>> + Unpin,
+{
+    type Item = Result<T, tonic::Status>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        let Some(inner) = this.inner.as_mut() else {
+            return Poll::Ready(None);
+        };
+
+        if let Some((deadline, sleep)) = this.deadline.as_mut() {
+            if sleep.as_mut().poll(cx).is_ready() {
+                this.inner = None;
+                return Poll::Ready(Some(Err(deadline.exceeded())));
+            }
+        }
+
+        let res = ready!(inner.poll_next_unpin(cx));
+        if res.is_none() {
+            this.inner = None;
+        }
+        Poll::Ready(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    use futures::stream::BoxStream;
+    use tonic::metadata::MetadataValue;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_grpc_timeout() {
+        assert_eq!(parse_grpc_timeout("1H"), Some(Duration::from_secs(3600)));
+        assert_eq!(parse_grpc_timeout("2M"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_grpc_timeout("3S"), Some(Duration::from_secs(3)));
+        assert_eq!(parse_grpc_timeout("100m"), Some(Duration::from_millis(100)));
+        assert_eq!(parse_grpc_timeout("5u"), Some(Duration::from_micros(5)));
+        assert_eq!(
+            parse_grpc_timeout("99999999n"),
+            Some(Duration::from_nanos(99_999_999))
+        );
+
+        assert_eq!(parse_grpc_timeout(""), None);
+        assert_eq!(parse_grpc_timeout("m"), None);
+        assert_eq!(parse_grpc_timeout("100"), None);
+        assert_eq!(parse_grpc_timeout("100x"), None);
+        assert_eq!(parse_grpc_timeout("-1m"), None);
+        assert_eq!(parse_grpc_timeout("123456789m"), None);
+        assert_eq!(parse_grpc_timeout("1ü"), None);
+    }
+
+    #[tokio::test]
+    async fn test_from_metadata() {
+        let timeout = |headers: &[(&'static str, &'static str)]| {
+            let mut metadata = MetadataMap::new();
+            for (k, v) in headers {
+                metadata.insert(*k, MetadataValue::from_static(*v));
+            }
+            Deadline::from_metadata(&metadata).map(|d| d.map(|d| d.timeout))
+        };
+
+        assert_eq!(timeout(&[]).unwrap(), None);
+        assert_eq!(
+            timeout(&[("grpc-timeout", "100m")]).unwrap(),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(
+            timeout(&[("iox-timeout", "1m 30s")]).unwrap(),
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(
+            timeout(&[("grpc-timeout", "10S"), ("iox-timeout", "5s")]).unwrap(),
+            Some(Duration::from_secs(5))
+        );
+
+        let err = timeout(&[("iox-timeout", "soon")]).unwrap_err();
+        assert_eq!(err.message(), "Invalid 'iox-timeout' header: soon");
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_with_deadline() {
+        let timeout = Duration::from_millis(50);
+        let deadline = Deadline {
+            at: Instant::now() + timeout,
+            timeout,
+        };
+
+        let res = with_deadline(Some(deadline), async { Ok(1) }).await;
+        assert_eq!(res.unwrap(), 1);
+
+        let err = with_deadline(Some(deadline), futures::future::pending::<Result<(), _>>())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::DeadlineExceeded);
+        assert_eq!(err.message(), "Query exceeded the deadline of 50ms");
+
+        let res = with_deadline(None, async { Ok(2) }).await;
+        assert_eq!(res.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_stream_cancelled_at_deadline() {
+        // a query plan that never finishes
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let guard = DropGuard(Arc::clone(&cancelled));
+        let plan: BoxStream<'static, Result<(), tonic::Status>> =
+            futures::stream::once(async { Ok(()) })
+                .chain(futures::stream::pending())
+                .map(move |res| {
+                    let _guard = &guard;
+                    res
+                })
+                .boxed();
+
+        let timeout = Duration::from_millis(50);
+        let start = Instant::now();
+        let deadline = Deadline {
+            at: start + timeout,
+            timeout,
+        };
+        let mut stream = DeadlineStream::new(plan, Some(deadline));
+
+        // data produced before the deadline is passed on
+        stream.next().await.unwrap().unwrap();
+        assert!(!cancelled.load(Ordering::SeqCst));
+
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert!(start.elapsed() >= timeout);
+        assert_eq!(err.code(), tonic::Code::DeadlineExceeded);
+        assert_eq!(err.message(), "Query exceeded the deadline of 50ms");
+        assert!(cancelled.load(Ordering::SeqCst));
+
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stream_finishes_before_deadline() {
+        let timeout = Duration::from_secs(60);
+        let deadline = Deadline {
+            at: Instant::now() + timeout,
+            timeout,
+        };
+        let stream = DeadlineStream::new(
+            futures::stream::iter([Ok::<_, tonic::Status>(1), Ok(2)]),
+            Some(deadline),
+        );
+
+        let res = stream.map(|res| res.unwrap()).collect::<Vec<_>>().await;
+        assert_eq!(res, [1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_stream_without_deadline() {
+        let stream = DeadlineStream::new(
+            futures::stream::iter([Ok::<_, tonic::Status>(1), Ok(2)]),
+            None,
+        );
+
+        let res = stream.map(|res| res.unwrap()).collect::<Vec<_>>().await;
+        assert_eq!(res, [1, 2]);
+    }
+
+    /// Flags when the query plan is dropped.
+    struct DropGuard(Arc<AtomicBool>);
+
+    impl Drop for DropGuard {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+}
@@ -17,6 +17,7 @@
 // Workaround for "unused crate" lint false positives.
 use workspace_hack as _;
 
+pub mod deadline;
 mod error;
 pub mod planner;
 pub mod test_util;
@@ -47,8 +48,44 @@ pub trait QueryNamespaceProvider: std::fmt::Debug + Send + Sync + 'static {
         include_debug_info_tables: bool,
     ) -> Option<Arc<Self::Db>>;
 
+    /// Returns false if this instance is configured to not serve the given namespace.
+    ///
+    /// [`db`](Self::db) never returns such namespaces, use [`lookup_db`](Self::lookup_db) to tell them apart from
+    /// namespaces that do not exist.
+    fn serves_namespace(&self, _name: &str) -> bool {
+        true
+    }
+
+    /// Get namespace, reporting why it is not available.
+    ///
+    /// This is the lookup the RPC services use, see [`NamespaceLookupError`].
+    async fn lookup_db(
+        &self,
+        name: &str,
+        span: Option<Span>,
+        include_debug_info_tables: bool,
+    ) -> Result<Arc<Self::Db>, NamespaceLookupError> {
+        if !self.serves_namespace(name) {
+            return Err(NamespaceLookupError::NotServed);
+        }
+
+        self.db(name, span, include_debug_info_tables)
+            .await
+            .ok_or(NamespaceLookupError::NotFound)
+    }
+
     /// Acquire concurrency-limiting sempahore
     async fn acquire_semaphore(&self, span: Option<Span>) -> InstrumentedAsyncOwnedSemaphorePermit;
 }
 
+/// Reason why [`QueryNamespaceProvider::lookup_db`] did not return a namespace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NamespaceLookupError {
+    /// The namespace does not exist, reported as `NOT_FOUND`.
+    NotFound,
+
+    /// This instance is configured to not serve the namespace, reported as `PERMISSION_DENIED`.
+    NotServed,
+}
+
 pub use error::datafusion_error_to_tonic_code;
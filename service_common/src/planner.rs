@@ -5,7 +5,7 @@ use bytes::Bytes;
 use datafusion::{
     arrow::datatypes::SchemaRef, error::DataFusionError, physical_plan::ExecutionPlan,
 };
-use flightsql::{FlightSQLCommand, FlightSQLPlanner};
+use flightsql::{AuditFlightSQLPlanner, FlightSQLCommand, MetadataCache};
 use iox_query::{
     exec::IOxSessionContext,
     frontend::sql::SqlQueryPlanner,
@@ -65,12 +65,13 @@ impl Planner {
     }
 
     /// Creates a plan for a `DoGet` FlightSQL message, as described on
-    /// [`FlightSQLPlanner::do_get`], on a separate threadpool
+    /// [`FlightSQLPlanner::do_get`](flightsql::FlightSQLPlanner::do_get), on a separate threadpool
     pub async fn flight_sql_do_get<N>(
         &self,
         namespace_name: impl Into<String> + Send,
         namespace: Arc<N>,
         cmd: FlightSQLCommand,
+        metadata_cache: Arc<MetadataCache>,
     ) -> Result<Arc<dyn ExecutionPlan>>
     where
         N: QueryNamespace + 'static,
@@ -80,7 +81,7 @@ impl Planner {
 
         self.ctx
             .run(async move {
-                FlightSQLPlanner::do_get(namespace_name, namespace, cmd, &ctx)
+                AuditFlightSQLPlanner::do_get(namespace_name, namespace, cmd, &metadata_cache, &ctx)
                     .await
                     .map_err(DataFusionError::from)
             })
@@ -88,7 +89,7 @@ impl Planner {
     }
 
     /// Creates a plan for a `DoAction` FlightSQL message, as described on
-    /// [`FlightSQLPlanner::do_action`], on a separate threadpool
+    /// [`FlightSQLPlanner::do_action`](flightsql::FlightSQLPlanner::do_action), on a separate threadpool
     pub async fn flight_sql_do_action<N>(
         &self,
         namespace_name: impl Into<String> + Send,
@@ -103,7 +104,30 @@ impl Planner {
 
         self.ctx
             .run(async move {
-                FlightSQLPlanner::do_action(namespace_name, namespace, cmd, &ctx)
+                AuditFlightSQLPlanner::do_action(namespace_name, namespace, cmd, &ctx)
+                    .await
+                    .map_err(DataFusionError::from)
+            })
+            .await
+    }
+
+    /// Handles a `DoPut` FlightSQL message, as described on
+    /// [`FlightSQLPlanner::do_put`](flightsql::FlightSQLPlanner::do_put), on a separate threadpool
+    pub async fn flight_sql_do_put<N>(
+        &self,
+        namespace_name: impl Into<String> + Send,
+        namespace: Arc<N>,
+        cmd: FlightSQLCommand,
+    ) -> Result<Bytes>
+    where
+        N: QueryNamespace + 'static,
+    {
+        let namespace_name = namespace_name.into();
+        let ctx = self.ctx.child_ctx("planner flight_sql_do_put");
+
+        self.ctx
+            .run(async move {
+                AuditFlightSQLPlanner::do_put(namespace_name, namespace, cmd, &ctx)
                     .await
                     .map_err(DataFusionError::from)
             })
@@ -112,7 +136,7 @@ impl Planner {
 
     /// Returns the [`SchemaRef`] to be included in the response to a
     /// `GetFlightInfo` FlightSQL message as described on
-    /// [`FlightSQLPlanner::get_schema`], on a separate threadpool.
+    /// [`FlightSQLPlanner::get_schema`](flightsql::FlightSQLPlanner::get_schema), on a separate threadpool.
     pub async fn flight_sql_get_flight_info_schema(
         &self,
         namespace_name: impl Into<String> + Send,
@@ -123,7 +147,7 @@ impl Planner {
 
         self.ctx
             .run(async move {
-                FlightSQLPlanner::get_schema(namespace_name, cmd, &ctx)
+                AuditFlightSQLPlanner::get_schema(namespace_name, cmd, &ctx)
                     .await
                     .map_err(DataFusionError::from)
             })
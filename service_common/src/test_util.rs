@@ -1,4 +1,7 @@
-use std::{collections::BTreeMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
 
 use async_trait::async_trait;
 use iox_query::{exec::Executor, test::TestDatabase};
@@ -13,6 +16,7 @@ use crate::QueryNamespaceProvider;
 #[derive(Debug)]
 pub struct TestDatabaseStore {
     databases: Mutex<BTreeMap<String, Arc<TestDatabase>>>,
+    not_served: Mutex<BTreeSet<String>>,
     executor: Arc<Executor>,
     pub metric_registry: Arc<metric::Registry>,
     pub query_semaphore: Arc<InstrumentedAsyncSemaphore>,
@@ -31,6 +35,7 @@ impl TestDatabaseStore {
         ));
         Self {
             databases: Mutex::new(BTreeMap::new()),
+            not_served: Mutex::new(BTreeSet::new()),
             executor: Arc::new(Executor::new_testing()),
             metric_registry,
             query_semaphore: Arc::new(semaphore_metrics.new_semaphore(semaphore_size)),
@@ -48,6 +53,11 @@ impl TestDatabaseStore {
             new_db
         }
     }
+
+    /// Stop serving the given namespace, even if it exists.
+    pub fn stop_serving(&self, name: &str) {
+        self.not_served.lock().insert(name.to_string());
+    }
 }
 
 impl Default for TestDatabaseStore {
@@ -67,11 +77,19 @@ impl QueryNamespaceProvider for TestDatabaseStore {
         _span: Option<Span>,
         _include_debug_info_tables: bool,
     ) -> Option<Arc<Self::Db>> {
+        if !self.serves_namespace(name) {
+            return None;
+        }
+
         let databases = self.databases.lock();
 
         databases.get(name).cloned()
     }
 
+    fn serves_namespace(&self, name: &str) -> bool {
+        !self.not_served.lock().contains(name)
+    }
+
     async fn acquire_semaphore(&self, span: Option<Span>) -> InstrumentedAsyncOwnedSemaphorePermit {
         Arc::clone(&self.query_semaphore)
             .acquire_owned(span)
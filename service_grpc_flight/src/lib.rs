@@ -34,22 +34,31 @@ use arrow_flight::{
 use authz::{extract_token, Authorizer};
 use data_types::NamespaceNameError;
 use datafusion::{error::DataFusionError, physical_plan::ExecutionPlan};
-use flightsql::FlightSQLCommand;
+use flightsql::{redact_command, FlightSQLCommand, MetadataCache};
 use futures::{ready, Stream, StreamExt, TryStreamExt};
 use generated_types::influxdata::iox::querier::v1 as proto;
 use iox_query::{
     exec::{ExecutionContextProvider, IOxSessionContext},
     QueryCompletedToken, QueryNamespace,
 };
+use iox_time::SystemProvider;
 use observability_deps::tracing::{debug, info, warn};
 use prost::Message;
 use request::{IoxGetRequest, RunQuery};
-use service_common::{datafusion_error_to_tonic_code, planner::Planner, QueryNamespaceProvider};
+use service_common::{
+    datafusion_error_to_tonic_code,
+    deadline::{with_deadline, Deadline, DeadlineStream},
+    planner::Planner,
+    NamespaceLookupError, QueryNamespaceProvider,
+};
 use snafu::{OptionExt, ResultExt, Snafu};
 use std::{
     fmt::Debug,
     pin::Pin,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
     task::Poll,
     time::{Duration, Instant},
 };
@@ -57,7 +66,10 @@ use tonic::{
     metadata::{AsciiMetadataValue, MetadataMap},
     Request, Response, Streaming,
 };
-use trace::{ctx::SpanContext, span::SpanExt};
+use trace::{
+    ctx::SpanContext,
+    span::{Span, SpanExt},
+};
 use trace_http::ctx::{RequestLogContext, RequestLogContextExt};
 use tracker::InstrumentedAsyncOwnedSemaphorePermit;
 
@@ -88,9 +100,15 @@ pub enum Error {
     #[snafu(display("Invalid handshake. No payload provided"))]
     InvalidHandshake {},
 
+    #[snafu(display("Invalid DoPut request. No flight descriptor provided"))]
+    InvalidDoPut {},
+
     #[snafu(display("Database '{}' not found", namespace_name))]
     DatabaseNotFound { namespace_name: String },
 
+    #[snafu(display("Database '{}' is not served by this querier", namespace_name))]
+    DatabaseNotServed { namespace_name: String },
+
     #[snafu(display(
         "Internal error reading points from namespace {}: {}",
         namespace_name,
@@ -164,8 +182,10 @@ impl From<Error> for tonic::Status {
         let query = err.query();
         match err {
             Error::DatabaseNotFound { .. }
+            | Error::DatabaseNotServed { .. }
             | Error::InvalidTicket { .. }
             | Error::InvalidHandshake { .. }
+            | Error::InvalidDoPut { .. }
             | Error::Unauthenticated { .. }
             | Error::PermissionDenied { .. }
             | Error::InvalidDatabaseName { .. }
@@ -196,8 +216,10 @@ impl Error {
 
         let code = match self {
             Self::DatabaseNotFound { .. } => tonic::Code::NotFound,
+            Self::DatabaseNotServed { .. } => tonic::Code::PermissionDenied,
             Self::InvalidTicket { .. }
             | Self::InvalidHandshake { .. }
+            | Self::InvalidDoPut { .. }
             | Self::Deserialization { .. }
             | Self::TooManyFlightSQLDatabases { .. }
             | Self::NoFlightSQLDatabase
@@ -209,9 +231,17 @@ impl Error {
             Self::UnsupportedMessageType { .. } => tonic::Code::Unimplemented,
             Self::FlightSQL { source } => match source {
                 flightsql::Error::InvalidHandle { .. }
+                | flightsql::Error::MalformedHandle { .. }
                 | flightsql::Error::Decode { .. }
-                | flightsql::Error::Protocol { .. }
-                | flightsql::Error::UnsupportedMessageType { .. } => tonic::Code::InvalidArgument,
+                | flightsql::Error::UnsupportedForMethod { .. }
+                | flightsql::Error::UnsupportedMessageType { .. }
+                | flightsql::Error::UnsupportedStatement { .. }
+                | flightsql::Error::InvalidTransaction { .. }
+                | flightsql::Error::InvalidNamespace { .. } => tonic::Code::InvalidArgument,
+                flightsql::Error::TableNotFound { .. } => tonic::Code::NotFound,
+                flightsql::Error::PreparedStatementSchemaChanged { .. } => {
+                    tonic::Code::FailedPrecondition
+                }
                 flightsql::Error::Flight { source: e } => return tonic::Status::from(e),
                 fs_err @ flightsql::Error::Arrow { .. } => {
                     // wrap in Datafusion error to walk source stacks
@@ -237,6 +267,7 @@ impl Error {
             Error::InvalidTicket { .. }
             | Error::InternalCreatingTicket { .. }
             | Error::InvalidHandshake {}
+            | Error::InvalidDoPut {}
             | Error::TooManyFlightSQLDatabases { .. }
             | Error::NoFlightSQLDatabase
             | Error::InvalidDatabaseHeader { .. }
@@ -249,7 +280,8 @@ impl Error {
             | Error::Unauthenticated
             | Error::PermissionDenied
             | Error::Authz { .. } => "<unknown>",
-            Error::DatabaseNotFound { namespace_name } => namespace_name,
+            Error::DatabaseNotFound { namespace_name }
+            | Error::DatabaseNotServed { namespace_name } => namespace_name,
             Error::Query { namespace_name, .. } => namespace_name,
             Error::Planning { namespace_name, .. } => namespace_name,
         }
@@ -261,6 +293,7 @@ impl Error {
             Error::InvalidTicket { .. }
             | Error::InternalCreatingTicket { .. }
             | Error::InvalidHandshake {}
+            | Error::InvalidDoPut {}
             | Error::TooManyFlightSQLDatabases { .. }
             | Error::NoFlightSQLDatabase
             | Error::InvalidDatabaseHeader { .. }
@@ -273,7 +306,8 @@ impl Error {
             | Error::Unauthenticated
             | Error::PermissionDenied
             | Error::Authz { .. }
-            | Error::DatabaseNotFound { .. } => "NONE",
+            | Error::DatabaseNotFound { .. }
+            | Error::DatabaseNotServed { .. } => "NONE",
             Error::Query { query, .. } => query,
             Error::Planning { query, .. } => query,
         }
@@ -473,22 +507,62 @@ where
 {
     server: Arc<S>,
     authz: Option<Arc<dyn Authorizer>>,
+    metadata_cache: Arc<MetadataCache>,
 }
 
+/// Create the Flight service.
+///
+/// FlightSQL catalog and db-schema metadata is cached for `metadata_cache_ttl`, see [`MetadataCache`].
 pub fn make_server<S>(
     server: Arc<S>,
     authz: Option<Arc<dyn Authorizer>>,
+    metadata_cache_ttl: Duration,
 ) -> FlightServer<impl Flight>
 where
     S: QueryNamespaceProvider,
 {
-    FlightServer::new(FlightService { server, authz })
+    FlightServer::new(FlightService::new(server, authz, metadata_cache_ttl))
 }
 
 impl<S> FlightService<S>
 where
     S: QueryNamespaceProvider,
 {
+    fn new(
+        server: Arc<S>,
+        authz: Option<Arc<dyn Authorizer>>,
+        metadata_cache_ttl: Duration,
+    ) -> Self {
+        Self {
+            server,
+            authz,
+            metadata_cache: Arc::new(MetadataCache::new(
+                Arc::new(SystemProvider::new()),
+                metadata_cache_ttl,
+            )),
+        }
+    }
+
+    /// Get the namespace a request refers to, see [`QueryNamespaceProvider::lookup_db`].
+    async fn lookup_db(
+        &self,
+        namespace_name: &str,
+        span: Option<Span>,
+        is_debug: bool,
+    ) -> Result<Arc<S::Db>, Error> {
+        self.server
+            .lookup_db(namespace_name, span, is_debug)
+            .await
+            .map_err(|e| match e {
+                NamespaceLookupError::NotFound => Error::DatabaseNotFound {
+                    namespace_name: namespace_name.to_string(),
+                },
+                NamespaceLookupError::NotServed => Error::DatabaseNotServed {
+                    namespace_name: namespace_name.to_string(),
+                },
+            })
+    }
+
     /// Implementation of the `DoGet` method
     async fn run_do_get(
         &self,
@@ -498,18 +572,15 @@ where
         query: RunQuery,
         namespace_name: String,
         is_debug: bool,
+        deadline: Option<Deadline>,
     ) -> Result<Response<TonicStream<FlightData>>, tonic::Status> {
         let db = self
-            .server
-            .db(
+            .lookup_db(
                 &namespace_name,
                 span_ctx.child_span("get namespace"),
                 is_debug,
             )
-            .await
-            .context(DatabaseNotFoundSnafu {
-                namespace_name: &namespace_name,
-            })?;
+            .await?;
 
         let ctx = db.new_query_context(span_ctx);
         let (query_completed_token, physical_plan) = match &query {
@@ -520,7 +591,7 @@ where
                     .await
                     .context(PlanningSnafu {
                         namespace_name: &namespace_name,
-                        query: query.to_string(),
+                        query: query.redacted(),
                     })?;
                 (token, plan)
             }
@@ -531,18 +602,23 @@ where
                     .await
                     .context(PlanningSnafu {
                         namespace_name: &namespace_name,
-                        query: query.to_string(),
+                        query: query.redacted(),
                     })?;
                 (token, plan)
             }
             RunQuery::FlightSQL(msg) => {
                 let token = db.record_query(&ctx, "flightsql", Box::new(msg.to_string()));
                 let plan = Planner::new(&ctx)
-                    .flight_sql_do_get(&namespace_name, db, msg.clone())
+                    .flight_sql_do_get(
+                        &namespace_name,
+                        db,
+                        msg.clone(),
+                        Arc::clone(&self.metadata_cache),
+                    )
                     .await
                     .context(PlanningSnafu {
                         namespace_name: &namespace_name,
-                        query: query.to_string(),
+                        query: query.redacted(),
                     })?;
                 (token, plan)
             }
@@ -560,6 +636,7 @@ where
 
         // Log any error that happens *during* execution (other error
         // handling in this file happen during planning)
+        let query = query.redacted();
         let output = output.map(move |res| {
             if let Err(e) = &res {
                 info!(%namespace_name, %query, %trace, %e, "Error executing query via DoGet");
@@ -567,7 +644,9 @@ where
             res
         });
 
-        Ok(Response::new(Box::pin(output) as TonicStream<FlightData>))
+        let output = Box::pin(DeadlineStream::new(output, deadline)) as TonicStream<FlightData>;
+
+        Ok(Response::new(output))
     }
 }
 
@@ -602,6 +681,7 @@ where
         let span_ctx: Option<SpanContext> = request.extensions().get().cloned();
         let authz_token = get_flight_authz(request.metadata());
         let mut is_debug = has_debug_header(request.metadata());
+        let deadline = Deadline::from_metadata(request.metadata())?;
         let ticket = request.into_inner();
 
         // attempt to decode ticket
@@ -614,6 +694,7 @@ where
         let request = request?;
         let namespace_name = request.database();
         let query = request.query();
+        let redacted_query = query.redacted();
         is_debug |= request.is_debug();
 
         let perms = match query {
@@ -637,28 +718,29 @@ where
         let start = Instant::now();
         info!(
             %namespace_name,
-            %query,
+            query = %redacted_query,
             %trace,
             variant=query.variant(),
             "DoGet request",
         );
 
-        let response = self
-            .run_do_get(
-                span_ctx,
-                trace.clone(),
-                permit,
-                query.clone(),
-                namespace_name.to_string(),
-                is_debug,
-            )
-            .await;
+        let response = self.run_do_get(
+            span_ctx,
+            trace.clone(),
+            permit,
+            query.clone(),
+            namespace_name.to_string(),
+            is_debug,
+            deadline,
+        );
+        // planning is bounded by the deadline as well
+        let response = with_deadline(deadline, response).await;
 
         if let Err(e) = &response {
-            info!(%namespace_name, %query, %trace, %e, "Error running DoGet");
+            info!(%namespace_name, query = %redacted_query, %trace, %e, "Error running DoGet");
         } else {
             let elapsed = Instant::now() - start;
-            debug!(%namespace_name, %query, %trace, ?elapsed, "Completed DoGet request");
+            debug!(%namespace_name, query = %redacted_query, %trace, ?elapsed, "Completed DoGet request");
         }
         response
     }
@@ -732,7 +814,8 @@ where
 
         // extract the FlightSQL message
         let cmd = cmd_from_descriptor(flight_descriptor.clone())?;
-        info!(%namespace_name, %cmd, %trace, "GetFlightInfo request");
+        let redacted_cmd = redact_command(&cmd);
+        info!(%namespace_name, cmd = %redacted_cmd, %trace, "GetFlightInfo request");
 
         let perms = flightsql_permissions(&namespace_name, &cmd);
         self.authz
@@ -741,16 +824,12 @@ where
             .map_err(Error::from)?;
 
         let db = self
-            .server
-            .db(
+            .lookup_db(
                 &namespace_name,
                 span_ctx.child_span("get namespace"),
                 is_debug,
             )
-            .await
-            .context(DatabaseNotFoundSnafu {
-                namespace_name: &namespace_name,
-            })?;
+            .await?;
 
         let ctx = db.new_query_context(span_ctx);
         let schema = Planner::new(&ctx)
@@ -758,13 +837,13 @@ where
             .await
             .context(PlanningSnafu {
                 namespace_name: &namespace_name,
-                query: format!("{cmd:?}"),
+                query: redacted_cmd.clone(),
             });
 
         if let Err(e) = &schema {
-            info!(%namespace_name, %cmd, %trace, %e, "Error running GetFlightInfo");
+            info!(%namespace_name, cmd = %redacted_cmd, %trace, %e, "Error running GetFlightInfo");
         } else {
-            debug!(%namespace_name, %cmd, %trace, "Completed GetFlightInfo request");
+            debug!(%namespace_name, cmd = %redacted_cmd, %trace, "Completed GetFlightInfo request");
         };
         let schema = schema?;
 
@@ -787,11 +866,55 @@ where
 
     async fn do_put(
         &self,
-        _request: Request<Streaming<FlightData>>,
+        request: Request<Streaming<FlightData>>,
     ) -> Result<Response<Self::DoPutStream>, tonic::Status> {
-        info!("Handling flightsql do_put body");
+        let external_span_ctx: Option<RequestLogContext> = request.extensions().get().cloned();
+        let span_ctx: Option<SpanContext> = request.extensions().get().cloned();
+        let trace = external_span_ctx.format_jaeger();
+        let is_debug = has_debug_header(request.metadata());
+
+        let namespace_name = get_flightsql_namespace(request.metadata())?;
+        let authz_token = get_flight_authz(request.metadata());
+
+        // extract the FlightSQL message from the descriptor of the first message
+        let flight_descriptor = request
+            .into_inner()
+            .message()
+            .await?
+            .and_then(|flight_data| flight_data.flight_descriptor)
+            .context(InvalidDoPutSnafu)?;
+        let cmd = cmd_from_descriptor(flight_descriptor)?;
+        let redacted_cmd = redact_command(&cmd);
+
+        info!(%namespace_name, cmd = %redacted_cmd, %trace, "DoPut request");
+
+        let perms = flightsql_permissions(&namespace_name, &cmd);
+        self.authz
+            .permissions(authz_token, &perms)
+            .await
+            .map_err(Error::from)?;
+
+        let db = self
+            .lookup_db(
+                &namespace_name,
+                span_ctx.child_span("get namespace"),
+                is_debug,
+            )
+            .await?;
+
+        let ctx = db.new_query_context(span_ctx);
+        let app_metadata = Planner::new(&ctx)
+            .flight_sql_do_put(&namespace_name, db, cmd.clone())
+            .await
+            .context(PlanningSnafu {
+                namespace_name: &namespace_name,
+                query: redacted_cmd,
+            })?;
 
-        Err(tonic::Status::unimplemented("Not yet implemented: do_put"))
+        let result = PutResult { app_metadata };
+        let stream = futures::stream::iter([Ok(result)]);
+
+        Ok(Response::new(stream.boxed()))
     }
 
     async fn do_action(
@@ -812,8 +935,9 @@ where
 
         // extract the FlightSQL message
         let cmd = FlightSQLCommand::try_decode(body).context(FlightSQLSnafu)?;
+        let redacted_cmd = redact_command(&cmd);
 
-        info!(%namespace_name, %action_type, %cmd, %trace, "DoAction request");
+        info!(%namespace_name, %action_type, cmd = %redacted_cmd, %trace, "DoAction request");
 
         let perms = flightsql_permissions(&namespace_name, &cmd);
         self.authz
@@ -822,16 +946,12 @@ where
             .map_err(Error::from)?;
 
         let db = self
-            .server
-            .db(
+            .lookup_db(
                 &namespace_name,
                 span_ctx.child_span("get namespace"),
                 is_debug,
             )
-            .await
-            .context(DatabaseNotFoundSnafu {
-                namespace_name: &namespace_name,
-            })?;
+            .await?;
 
         let ctx = db.new_query_context(span_ctx);
         let body = Planner::new(&ctx)
@@ -839,7 +959,7 @@ where
             .await
             .context(PlanningSnafu {
                 namespace_name: &namespace_name,
-                query: format!("{cmd:?}"),
+                query: redacted_cmd,
             })?;
 
         let result = arrow_flight::Result { body };
@@ -929,7 +1049,9 @@ fn flightsql_permissions(namespace_name: &str, cmd: &FlightSQLCommand) -> Vec<au
     let resource = authz::Resource::Database(namespace_name.to_string());
     let action = match cmd {
         FlightSQLCommand::CommandStatementQuery(_) => authz::Action::Read,
+        FlightSQLCommand::CommandInfluxQLStatementQuery(_) => authz::Action::Read,
         FlightSQLCommand::CommandPreparedStatementQuery(_) => authz::Action::Read,
+        FlightSQLCommand::CommandStatementUpdate(_) => authz::Action::Write,
         FlightSQLCommand::CommandGetSqlInfo(_) => authz::Action::ReadSchema,
         FlightSQLCommand::CommandGetCatalogs(_) => authz::Action::ReadSchema,
         FlightSQLCommand::CommandGetCrossReference(_) => authz::Action::ReadSchema,
@@ -942,6 +1064,8 @@ fn flightsql_permissions(namespace_name: &str, cmd: &FlightSQLCommand) -> Vec<au
         FlightSQLCommand::CommandGetXdbcTypeInfo(_) => authz::Action::ReadSchema,
         FlightSQLCommand::ActionCreatePreparedStatementRequest(_) => authz::Action::Read,
         FlightSQLCommand::ActionClosePreparedStatementRequest(_) => authz::Action::Read,
+        FlightSQLCommand::ActionBeginTransactionRequest(_) => authz::Action::Read,
+        FlightSQLCommand::ActionEndTransactionRequest(_) => authz::Action::Read,
     };
     vec![authz::Permission::ResourceAction(resource, action)]
 }
@@ -963,6 +1087,8 @@ struct GetStream {
     #[allow(dead_code)]
     permit: InstrumentedAsyncOwnedSemaphorePermit,
     query_completed_token: QueryCompletedToken,
+    /// Number of rows returned so far
+    rows: Arc<AtomicU64>,
     done: bool,
 }
 
@@ -978,14 +1104,19 @@ impl GetStream {
         let app_metadata = proto::AppMetadata {};
 
         let schema = physical_plan.schema();
+        let rows = Arc::new(AtomicU64::new(0));
+        let rows_captured = Arc::clone(&rows);
 
         let query_results = ctx
             .execute_stream(Arc::clone(&physical_plan))
             .await
             .context(QuerySnafu {
                 namespace_name: namespace_name.clone(),
-                query: query.to_string(),
+                query: query.redacted(),
             })?
+            .inspect_ok(move |batch| {
+                rows_captured.fetch_add(batch.num_rows() as u64, Ordering::Relaxed);
+            })
             .map_err(|e| {
                 let code = datafusion_error_to_tonic_code(&e);
                 tonic::Status::new(code, e.to_string()).into()
@@ -1004,6 +1135,7 @@ impl GetStream {
             inner,
             permit,
             query_completed_token,
+            rows,
             done: false,
         })
     }
@@ -1026,6 +1158,8 @@ impl Stream for GetStream {
                 None => {
                     self.done = true;
                     // if we get here, all is good
+                    let rows = self.rows.load(Ordering::Relaxed);
+                    self.query_completed_token.set_rows(rows);
                     self.query_completed_token.set_success();
                 }
                 Some(Ok(data)) => {
@@ -1076,10 +1210,7 @@ mod tests {
         // add some data
         test_storage.db_or_create("my_db").await;
 
-        let service = FlightService {
-            server: Arc::clone(&test_storage),
-            authz: Option::<Arc<dyn Authorizer>>::None,
-        };
+        let service = FlightService::new(Arc::clone(&test_storage), None, Duration::from_secs(10));
         let ticket = Ticket {
             ticket: br#"{"namespace_name": "my_db", "sql_query": "SELECT 1;"}"#
                 .to_vec()
@@ -1188,6 +1319,48 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_do_get_namespace_not_served() {
+        let test_storage = Arc::new(TestDatabaseStore::default());
+        test_storage.db_or_create("my_db").await;
+
+        let service = FlightService::new(Arc::clone(&test_storage), None, Duration::from_secs(10));
+        let ticket = |namespace_name: &str| Ticket {
+            ticket: format!(
+                r#"{{"namespace_name": "{namespace_name}", "sql_query": "SELECT 1;"}}"#
+            )
+            .into_bytes()
+            .into(),
+        };
+
+        // existing namespaces are found
+        service
+            .do_get(tonic::Request::new(ticket("my_db")))
+            .await
+            .unwrap();
+
+        // unknown namespaces are not found
+        let status = service
+            .do_get(tonic::Request::new(ticket("other_db")))
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+
+        // namespaces that are not served are rejected even though they exist
+        test_storage.stop_serving("my_db");
+        let status = service
+            .do_get(tonic::Request::new(ticket("my_db")))
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+        assert_eq!(
+            status.message(),
+            "Database 'my_db' is not served by this querier"
+        );
+    }
+
     /// Assert that given future is pending.
     ///
     /// This will try to poll the future a bit to ensure that it is not stuck in tokios task preemption.
@@ -1239,10 +1412,11 @@ mod tests {
         let test_storage = Arc::new(TestDatabaseStore::default());
         test_storage.db_or_create("bananas").await;
 
-        let svc = FlightService {
-            server: Arc::clone(&test_storage),
-            authz: Some(Arc::new(MockAuthorizer {})),
-        };
+        let svc = FlightService::new(
+            Arc::clone(&test_storage),
+            Some(Arc::new(MockAuthorizer {})),
+            Duration::from_secs(10),
+        );
 
         async fn assert_code(
             svc: &FlightService<TestDatabaseStore>,
@@ -1347,10 +1521,11 @@ mod tests {
         let test_storage = Arc::new(TestDatabaseStore::default());
         test_storage.db_or_create("bananas").await;
 
-        let svc = FlightService {
-            server: Arc::clone(&test_storage),
-            authz: Some(Arc::new(MockAuthorizer {})),
-        };
+        let svc = FlightService::new(
+            Arc::clone(&test_storage),
+            Some(Arc::new(MockAuthorizer {})),
+            Duration::from_secs(10),
+        );
 
         async fn assert_code(
             svc: &FlightService<TestDatabaseStore>,
@@ -1386,4 +1561,60 @@ mod tests {
         assert_code(&svc, tonic::Code::PermissionDenied, request("Bearer BAD")).await;
         assert_code(&svc, tonic::Code::Internal, request("Bearer UGLY")).await;
     }
+
+    #[tokio::test]
+    async fn do_get_authz_single_tenant() {
+        let test_storage = Arc::new(TestDatabaseStore::default());
+        test_storage.db_or_create("bananas").await;
+
+        let authz = Arc::new(
+            authz::mock::MockAuthorizer::new()
+                .with_allowed_token("GOOD")
+                .with_denied_token("BAD"),
+        );
+        let svc = FlightService::new(
+            Arc::clone(&test_storage),
+            Some(Arc::clone(&authz) as _),
+            Duration::from_secs(10),
+        );
+
+        let request = |token: &'static str| {
+            let mut req = tonic::Request::new(
+                IoxGetRequest::new(
+                    "bananas".to_string(),
+                    RunQuery::Sql("SELECT 1".to_string()),
+                    false,
+                )
+                .try_encode()
+                .unwrap(),
+            );
+            req.metadata_mut().insert(
+                MetadataKey::from_static("authorization"),
+                MetadataValue::from_static(token),
+            );
+            req
+        };
+
+        svc.do_get(request("Bearer GOOD")).await.unwrap();
+        let status = svc.do_get(request("Bearer BAD")).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+
+        let perms = vec![Permission::ResourceAction(
+            authz::Resource::Database("bananas".to_string()),
+            authz::Action::Read,
+        )];
+        assert_eq!(
+            authz.calls(),
+            [
+                authz::mock::MockAuthorizerCall {
+                    token: Some(b"GOOD".to_vec()),
+                    perms: perms.clone(),
+                },
+                authz::mock::MockAuthorizerCall {
+                    token: Some(b"BAD".to_vec()),
+                    perms,
+                },
+            ]
+        );
+    }
 }
@@ -2,7 +2,7 @@
 
 use arrow_flight::Ticket;
 use bytes::Bytes;
-use flightsql::FlightSQLCommand;
+use flightsql::{redact_command, redact_influxql, redact_query, FlightSQLCommand};
 use generated_types::google::protobuf::Any;
 use generated_types::influxdata::iox::querier::v1 as proto;
 use generated_types::influxdata::iox::querier::v1::read_info::QueryType;
@@ -117,6 +117,15 @@ impl RunQuery {
             Self::FlightSQL(_) => "flightsql",
         }
     }
+
+    /// The query text with all string and regular expression literals masked, so it can be logged.
+    pub fn redacted(&self) -> String {
+        match self {
+            Self::Sql(s) => redact_query(s),
+            Self::InfluxQL(s) => redact_influxql(s),
+            Self::FlightSQL(cmd) => redact_command(cmd),
+        }
+    }
 }
 
 impl Display for RunQuery {
@@ -856,6 +865,26 @@ mod tests {
         assert_eq!(request, roundtripped)
     }
 
+    #[test]
+    fn redacted_query() {
+        let query = RunQuery::Sql("select * from foo where name = 'jane'".into());
+        assert_eq!(query.redacted(), "select * from foo where name = '***'");
+
+        let query = RunQuery::InfluxQL("SELECT x FROM foo WHERE name =~ /jane/".into());
+        assert_eq!(query.redacted(), "SELECT x FROM foo WHERE name =~ /***/");
+
+        let query = RunQuery::FlightSQL(FlightSQLCommand::CommandStatementQuery(
+            CommandStatementQuery {
+                query: "select * from foo where name = 'jane'".into(),
+                transaction_id: None,
+            },
+        ));
+        assert_eq!(
+            query.redacted(),
+            "CommandStatementQueryselect * from foo where name = '***'"
+        );
+    }
+
     fn make_any_wrapped_proto_ticket(read_info: &proto::ReadInfo) -> Ticket {
         let any = Any {
             type_url: IoxGetRequest::READ_INFO_TYPE_URL.to_string(),
@@ -0,0 +1,165 @@
+use std::future::Future;
+
+use futures::{stream::BoxStream, Stream, StreamExt};
+use generated_types::{
+    google::protobuf::Empty, storage_server::Storage, CapabilitiesResponse,
+    MeasurementFieldsRequest, MeasurementFieldsResponse, MeasurementNamesRequest,
+    MeasurementTagKeysRequest, MeasurementTagValuesRequest, OffsetsResponse, ReadFilterRequest,
+    ReadGroupRequest, ReadResponse, ReadSeriesCardinalityRequest, ReadWindowAggregateRequest,
+    StringValuesResponse, TagKeysRequest, TagValuesGroupedByMeasurementAndTagKeyRequest,
+    TagValuesRequest, TagValuesResponse,
+};
+use service_common::deadline::{with_deadline, Deadline, DeadlineStream};
+use tonic::{Request, Response, Status};
+
+/// Response stream that ends with `DEADLINE_EXCEEDED` once the deadline of the request passed.
+pub type DeadlineResponseStream<T> = DeadlineStream<BoxStream<'static, Result<T, Status>>>;
+
+/// Bounds the queries of the wrapped [`Storage`] service by the deadline that the client requested, if any.
+///
+/// Planning as well as the response stream are cancelled once the deadline passes. Requests that do not run a query
+/// are passed on as is.
+#[derive(Debug)]
+pub struct DeadlineStorage<S> {
+    inner: S,
+}
+
+impl<S> DeadlineStorage<S> {
+    pub fn new(inner: S) -> Self {
+        Self { inner }
+    }
+}
+
+/// Runs a streaming request via `f`, bounded by the deadline in the request metadata.
+async fn stream_with_deadline<Req, T, S, F>(
+    req: Request<Req>,
+    f: impl FnOnce(Request<Req>) -> F,
+) -> Result<Response<DeadlineResponseStream<T>>, Status>
+where
+    F: Future<Output = Result<Response<S>, Status>> + Send,
+    S: Stream<Item = Result<T, Status>> + Send + 'static,
+{
+    let deadline = Deadline::from_metadata(req.metadata())?;
+    let response = with_deadline(deadline, f(req)).await?;
+    Ok(response.map(|stream| DeadlineStream::new(stream.boxed(), deadline)))
+}
+
+#[tonic::async_trait]
+impl<S> Storage for DeadlineStorage<S>
+where
+    S: Storage,
+{
+    type ReadFilterStream = DeadlineResponseStream<ReadResponse>;
+
+    async fn read_filter(
+        &self,
+        req: Request<ReadFilterRequest>,
+    ) -> Result<Response<Self::ReadFilterStream>, Status> {
+        stream_with_deadline(req, |req| self.inner.read_filter(req)).await
+    }
+
+    type ReadGroupStream = DeadlineResponseStream<ReadResponse>;
+
+    async fn read_group(
+        &self,
+        req: Request<ReadGroupRequest>,
+    ) -> Result<Response<Self::ReadGroupStream>, Status> {
+        stream_with_deadline(req, |req| self.inner.read_group(req)).await
+    }
+
+    type ReadWindowAggregateStream = DeadlineResponseStream<ReadResponse>;
+
+    async fn read_window_aggregate(
+        &self,
+        req: Request<ReadWindowAggregateRequest>,
+    ) -> Result<Response<Self::ReadWindowAggregateStream>, Status> {
+        stream_with_deadline(req, |req| self.inner.read_window_aggregate(req)).await
+    }
+
+    type TagKeysStream = DeadlineResponseStream<StringValuesResponse>;
+
+    async fn tag_keys(
+        &self,
+        req: Request<TagKeysRequest>,
+    ) -> Result<Response<Self::TagKeysStream>, Status> {
+        stream_with_deadline(req, |req| self.inner.tag_keys(req)).await
+    }
+
+    type TagValuesStream = DeadlineResponseStream<StringValuesResponse>;
+
+    async fn tag_values(
+        &self,
+        req: Request<TagValuesRequest>,
+    ) -> Result<Response<Self::TagValuesStream>, Status> {
+        stream_with_deadline(req, |req| self.inner.tag_values(req)).await
+    }
+
+    type TagValuesGroupedByMeasurementAndTagKeyStream = DeadlineResponseStream<TagValuesResponse>;
+
+    async fn tag_values_grouped_by_measurement_and_tag_key(
+        &self,
+        req: Request<TagValuesGroupedByMeasurementAndTagKeyRequest>,
+    ) -> Result<Response<Self::TagValuesGroupedByMeasurementAndTagKeyStream>, Status> {
+        stream_with_deadline(req, |req| {
+            self.inner
+                .tag_values_grouped_by_measurement_and_tag_key(req)
+        })
+        .await
+    }
+
+    type ReadSeriesCardinalityStream = S::ReadSeriesCardinalityStream;
+
+    async fn read_series_cardinality(
+        &self,
+        req: Request<ReadSeriesCardinalityRequest>,
+    ) -> Result<Response<Self::ReadSeriesCardinalityStream>, Status> {
+        self.inner.read_series_cardinality(req).await
+    }
+
+    async fn capabilities(
+        &self,
+        req: Request<Empty>,
+    ) -> Result<Response<CapabilitiesResponse>, Status> {
+        self.inner.capabilities(req).await
+    }
+
+    type MeasurementNamesStream = DeadlineResponseStream<StringValuesResponse>;
+
+    async fn measurement_names(
+        &self,
+        req: Request<MeasurementNamesRequest>,
+    ) -> Result<Response<Self::MeasurementNamesStream>, Status> {
+        stream_with_deadline(req, |req| self.inner.measurement_names(req)).await
+    }
+
+    type MeasurementTagKeysStream = DeadlineResponseStream<StringValuesResponse>;
+
+    async fn measurement_tag_keys(
+        &self,
+        req: Request<MeasurementTagKeysRequest>,
+    ) -> Result<Response<Self::MeasurementTagKeysStream>, Status> {
+        stream_with_deadline(req, |req| self.inner.measurement_tag_keys(req)).await
+    }
+
+    type MeasurementTagValuesStream = DeadlineResponseStream<StringValuesResponse>;
+
+    async fn measurement_tag_values(
+        &self,
+        req: Request<MeasurementTagValuesRequest>,
+    ) -> Result<Response<Self::MeasurementTagValuesStream>, Status> {
+        stream_with_deadline(req, |req| self.inner.measurement_tag_values(req)).await
+    }
+
+    type MeasurementFieldsStream = DeadlineResponseStream<MeasurementFieldsResponse>;
+
+    async fn measurement_fields(
+        &self,
+        req: Request<MeasurementFieldsRequest>,
+    ) -> Result<Response<Self::MeasurementFieldsStream>, Status> {
+        stream_with_deadline(req, |req| self.inner.measurement_fields(req)).await
+    }
+
+    async fn offsets(&self, req: Request<Empty>) -> Result<Response<OffsetsResponse>, Status> {
+        self.inner.offsets(req).await
+    }
+}
@@ -31,6 +31,7 @@ pub(crate) const TAG_KEY_MEASUREMENT: &[u8] = &[0];
 pub(crate) const TAG_KEY_FIELD: &[u8] = &[255];
 
 pub mod data;
+mod deadline;
 pub mod expr;
 pub mod id;
 pub mod input;
@@ -42,6 +43,7 @@ pub mod service;
 #[cfg(any(test, feature = "test-util"))]
 pub mod test_util;
 
+use deadline::DeadlineStorage;
 use generated_types::storage_server::{Storage, StorageServer};
 use service_common::QueryNamespaceProvider;
 use std::sync::Arc;
@@ -55,5 +57,5 @@ struct StorageService<T: QueryNamespaceProvider> {
 pub fn make_server<T: QueryNamespaceProvider + 'static>(
     db_store: Arc<T>,
 ) -> StorageServer<impl Storage> {
-    StorageServer::new(StorageService { db_store })
+    StorageServer::new(DeadlineStorage::new(StorageService { db_store }))
 }
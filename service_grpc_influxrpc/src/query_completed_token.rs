@@ -100,8 +100,8 @@ mod tests {
     fn token() -> (Arc<Mutex<Option<bool>>>, QueryCompletedToken) {
         let token = Arc::new(Mutex::new(None));
         let token_captured = Arc::clone(&token);
-        let qct = QueryCompletedToken::new(move |success| {
-            *token_captured.lock() = Some(success);
+        let qct = QueryCompletedToken::new(move |completion| {
+            *token_captured.lock() = Some(completion.success);
         });
         (token, qct)
     }
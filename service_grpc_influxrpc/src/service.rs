@@ -40,7 +40,9 @@ use iox_query::{
 };
 use observability_deps::tracing::{error, info, trace};
 use prost::{bytes::BytesMut, Message};
-use service_common::{datafusion_error_to_tonic_code, planner::Planner, QueryNamespaceProvider};
+use service_common::{
+    datafusion_error_to_tonic_code, planner::Planner, NamespaceLookupError, QueryNamespaceProvider,
+};
 use snafu::{OptionExt, ResultExt, Snafu};
 use std::{
     collections::{BTreeSet, HashMap},
@@ -49,7 +51,10 @@ use std::{
 };
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{metadata::MetadataMap, Response, Status};
-use trace::{ctx::SpanContext, span::SpanExt};
+use trace::{
+    ctx::SpanContext,
+    span::{Span, SpanExt},
+};
 use trace_http::ctx::{RequestLogContext, RequestLogContextExt};
 use tracker::InstrumentedAsyncOwnedSemaphorePermit;
 
@@ -68,6 +73,9 @@ pub enum Error {
     #[snafu(display("Namespace not found: {}", db_name))]
     NamespaceNotFound { db_name: String },
 
+    #[snafu(display("Namespace not served by this querier: {}", db_name))]
+    NamespaceNotServed { db_name: String },
+
     #[snafu(display("Error listing tables in namespace '{}': {}", db_name, source))]
     ListingTables {
         db_name: String,
@@ -212,6 +220,7 @@ impl Error {
 
         let code = match self {
             Self::NamespaceNotFound { .. } => tonic::Code::NotFound,
+            Self::NamespaceNotServed { .. } => tonic::Code::PermissionDenied,
             Self::ListingTables { source, .. }
             | Self::ListingColumns { source, .. }
             | Self::ListingFields { source, .. }
@@ -388,11 +397,12 @@ where
             "read filter",
         );
 
-        let db = self
-            .db_store
-            .db(&db_name, span_ctx.child_span("get namespace"), false)
-            .await
-            .context(NamespaceNotFoundSnafu { db_name: &db_name })?;
+        let db = get_namespace(
+            self.db_store.as_ref(),
+            &db_name,
+            span_ctx.child_span("get namespace"),
+        )
+        .await?;
 
         let ctx = db.new_query_context(span_ctx);
         let query_completed_token = db.record_query(&ctx, "read_filter", defer_json(&req));
@@ -436,11 +446,12 @@ where
             "read_group",
         );
 
-        let db = self
-            .db_store
-            .db(&db_name, span_ctx.child_span("get namespace"), false)
-            .await
-            .context(NamespaceNotFoundSnafu { db_name: &db_name })?;
+        let db = get_namespace(
+            self.db_store.as_ref(),
+            &db_name,
+            span_ctx.child_span("get namespace"),
+        )
+        .await?;
 
         let ctx = db.new_query_context(span_ctx);
         let query_completed_token = db.record_query(&ctx, "read_group", defer_json(&req));
@@ -512,11 +523,12 @@ where
             "read_window_aggregate",
         );
 
-        let db = self
-            .db_store
-            .db(&db_name, span_ctx.child_span("get namespace"), false)
-            .await
-            .context(NamespaceNotFoundSnafu { db_name: &db_name })?;
+        let db = get_namespace(
+            self.db_store.as_ref(),
+            &db_name,
+            span_ctx.child_span("get namespace"),
+        )
+        .await?;
 
         let ctx = db.new_query_context(span_ctx);
         let query_completed_token =
@@ -590,11 +602,12 @@ where
             "tag_keys",
         );
 
-        let db = self
-            .db_store
-            .db(&db_name, span_ctx.child_span("get namespace"), false)
-            .await
-            .context(NamespaceNotFoundSnafu { db_name: &db_name })?;
+        let db = get_namespace(
+            self.db_store.as_ref(),
+            &db_name,
+            span_ctx.child_span("get namespace"),
+        )
+        .await?;
 
         let ctx = db.new_query_context(span_ctx);
         let query_completed_token = db.record_query(&ctx, "tag_keys", defer_json(&req));
@@ -658,11 +671,12 @@ where
             "tag_values",
         );
 
-        let db = self
-            .db_store
-            .db(&db_name, span_ctx.child_span("get namespace"), false)
-            .await
-            .context(NamespaceNotFoundSnafu { db_name: &db_name })?;
+        let db = get_namespace(
+            self.db_store.as_ref(),
+            &db_name,
+            span_ctx.child_span("get namespace"),
+        )
+        .await?;
 
         let ctx = db.new_query_context(span_ctx);
         let query_completed_token = db.record_query(&ctx, "tag_values", defer_json(&req));
@@ -758,11 +772,12 @@ where
             "tag_values_grouped_by_measurement_and_tag_key",
         );
 
-        let db = self
-            .db_store
-            .db(&db_name, span_ctx.child_span("get namespace"), false)
-            .await
-            .context(NamespaceNotFoundSnafu { db_name: &db_name })?;
+        let db = get_namespace(
+            self.db_store.as_ref(),
+            &db_name,
+            span_ctx.child_span("get namespace"),
+        )
+        .await?;
 
         let ctx = db.new_query_context(span_ctx);
         let query_completed_token = db.record_query(
@@ -867,11 +882,12 @@ where
             "measurement_names",
         );
 
-        let db = self
-            .db_store
-            .db(&db_name, span_ctx.child_span("get namespace"), false)
-            .await
-            .context(NamespaceNotFoundSnafu { db_name: &db_name })?;
+        let db = get_namespace(
+            self.db_store.as_ref(),
+            &db_name,
+            span_ctx.child_span("get namespace"),
+        )
+        .await?;
 
         let ctx = db.new_query_context(span_ctx);
         let query_completed_token = db.record_query(&ctx, "measurement_names", defer_json(&req));
@@ -924,11 +940,12 @@ where
             "measurement_tag_keys",
         );
 
-        let db = self
-            .db_store
-            .db(&db_name, span_ctx.child_span("get namespace"), false)
-            .await
-            .context(NamespaceNotFoundSnafu { db_name: &db_name })?;
+        let db = get_namespace(
+            self.db_store.as_ref(),
+            &db_name,
+            span_ctx.child_span("get namespace"),
+        )
+        .await?;
 
         let ctx = db.new_query_context(span_ctx);
         let query_completed_token = db.record_query(&ctx, "measurement_tag_keys", defer_json(&req));
@@ -992,11 +1009,12 @@ where
             "measurement_tag_values",
         );
 
-        let db = self
-            .db_store
-            .db(&db_name, span_ctx.child_span("get namespace"), false)
-            .await
-            .context(NamespaceNotFoundSnafu { db_name: &db_name })?;
+        let db = get_namespace(
+            self.db_store.as_ref(),
+            &db_name,
+            span_ctx.child_span("get namespace"),
+        )
+        .await?;
 
         let ctx = db.new_query_context(span_ctx);
         let query_completed_token =
@@ -1062,11 +1080,12 @@ where
             "measurement_fields",
         );
 
-        let db = self
-            .db_store
-            .db(&db_name, span_ctx.child_span("get namespace"), false)
-            .await
-            .context(NamespaceNotFoundSnafu { db_name: &db_name })?;
+        let db = get_namespace(
+            self.db_store.as_ref(),
+            &db_name,
+            span_ctx.child_span("get namespace"),
+        )
+        .await?;
 
         let ctx = db.new_query_context(span_ctx);
         let query_completed_token = db.record_query(&ctx, "measurement_fields", defer_json(&req));
@@ -1121,6 +1140,28 @@ fn get_namespace_name(input: &impl GrpcInputs) -> Result<NamespaceName<'static>,
         .map_err(|e| Status::internal(e.to_string()))
 }
 
+/// Get the namespace a request refers to, see [`QueryNamespaceProvider::lookup_db`].
+async fn get_namespace<T>(
+    db_store: &T,
+    db_name: &NamespaceName<'static>,
+    span: Option<Span>,
+) -> Result<Arc<T::Db>, Error>
+where
+    T: QueryNamespaceProvider,
+{
+    db_store
+        .lookup_db(db_name, span, false)
+        .await
+        .map_err(|e| match e {
+            NamespaceLookupError::NotFound => Error::NamespaceNotFound {
+                db_name: db_name.to_string(),
+            },
+            NamespaceLookupError::NotServed => Error::NamespaceNotServed {
+                db_name: db_name.to_string(),
+            },
+        })
+}
+
 // The following code implements the business logic of the requests as
 // methods that return Results with module specific Errors (and thus
 // can use ?, etc). The trait implementations then handle mapping
@@ -3517,6 +3558,117 @@ mod tests {
         assert_eq!(response.metadata().get("storage-type").unwrap(), "iox");
     }
 
+    #[tokio::test]
+    async fn test_namespace_not_served() {
+        test_helpers::maybe_start_logging();
+        // Start a test gRPC server on a randomally allocated port
+        let fixture = Fixture::new().await.expect("Connecting to test server");
+
+        let db_info = org_and_bucket();
+        fixture.test_storage.db_or_create(db_info.db_name()).await;
+
+        let mut storage_client = storage_client::StorageClient::new(
+            fixture.client_connection.clone().into_grpc_connection(),
+        );
+        let request = |db_info: &OrgAndBucket| ReadFilterRequest {
+            read_source: Some(StorageClient::read_source(db_info, 1)),
+            range: None,
+            predicate: None,
+            ..Default::default()
+        };
+
+        // existing namespaces are found
+        storage_client.read_filter(request(&db_info)).await.unwrap();
+
+        // unknown namespaces are not found
+        let unknown =
+            OrgAndBucket::new(NonZeroU64::new(123).unwrap(), NonZeroU64::new(789).unwrap());
+        let status = storage_client
+            .read_filter(request(&unknown))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+
+        // namespaces that are not served are rejected even though they exist
+        fixture.test_storage.stop_serving(db_info.db_name());
+        let status = storage_client
+            .read_filter(request(&db_info))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+        assert_eq!(
+            status.message(),
+            format!(
+                "Namespace not served by this querier: {}",
+                db_info.db_name()
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_deadline() {
+        test_helpers::maybe_start_logging();
+        // Start a test gRPC server on a randomally allocated port
+        let fixture = Fixture::new().await.expect("Connecting to test server");
+
+        let db_info = org_and_bucket();
+        let chunk = TestChunk::new("h2o")
+            .with_time_column()
+            .with_tag_column("state")
+            .with_one_row_of_data();
+        fixture
+            .test_storage
+            .db_or_create(db_info.db_name())
+            .await
+            .add_chunk("my_partition_key", Arc::new(chunk));
+
+        let mut storage_client = storage_client::StorageClient::new(
+            fixture.client_connection.clone().into_grpc_connection(),
+        );
+        let request = |timeout: &'static str| {
+            let mut req = tonic::Request::new(ReadFilterRequest {
+                read_source: Some(StorageClient::read_source(&db_info, 1)),
+                range: None,
+                predicate: None,
+                ..Default::default()
+            });
+            req.metadata_mut()
+                .insert("iox-timeout", timeout.parse().unwrap());
+            req
+        };
+
+        // a generous deadline does not get in the way
+        let mut stream = storage_client
+            .read_filter(request("1m"))
+            .await
+            .unwrap()
+            .into_inner();
+        while let Some(res) = stream.next().await {
+            res.unwrap();
+        }
+
+        // an expired deadline fails the request
+        let status = match storage_client.read_filter(request("1ns")).await {
+            Ok(response) => response
+                .into_inner()
+                .next()
+                .await
+                .expect("stream ends with an error")
+                .unwrap_err(),
+            Err(status) => status,
+        };
+        assert_eq!(status.code(), tonic::Code::DeadlineExceeded);
+        assert_eq!(status.message(), "Query exceeded the deadline of 1ns");
+
+        // malformed headers are rejected
+        let status = storage_client
+            .read_filter(request("soon"))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert_eq!(status.message(), "Invalid 'iox-timeout' header: soon");
+    }
+
     #[tokio::test]
     async fn test_marshal_errors() {
         test_helpers::maybe_start_logging();